@@ -0,0 +1,108 @@
+use std::{collections::BTreeMap, env, fmt::Write, fs, path::PathBuf};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use prost_build::Config;
+use prost_reflect::{DescriptorPool, MessageDescriptor};
+use protocheck_build::{
+  compile_protos_with_validators, get_proto_paths_recursive, vendored_protovalidate_include_path,
+};
+
+const CASES_PACKAGE: &str = "buf.validate.conformance.cases";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  println!("cargo:rerun-if-changed=proto/");
+
+  let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+  let descriptor_path = out_dir.join("file_descriptor_set.bin");
+
+  let include_paths = [
+    PathBuf::from("proto"),
+    vendored_protovalidate_include_path(),
+  ];
+  let files = get_proto_paths_recursive("proto/buf/validate/conformance")?;
+
+  let mut config = Config::new();
+  config
+    .file_descriptor_set_path(&descriptor_path)
+    .enable_type_names()
+    .type_name_domain(["."], "type.googleapis.com")
+    .include_file("protos.rs")
+    .out_dir(&out_dir);
+
+  // The cases that use unsupported rules still compile, and they are reported as skipped at runtime
+  let report = compile_protos_with_validators(
+    &mut config,
+    &files,
+    &include_paths,
+    &[&format!("{}*", CASES_PACKAGE)],
+  )?;
+
+  config.compile_protos(&files, &include_paths)?;
+
+  println!(
+    "cargo:rustc-env=PROTO_DESCRIPTOR_SET={}",
+    descriptor_path.display()
+  );
+
+  let pool = DescriptorPool::decode(fs::read(&descriptor_path)?.as_slice())?;
+
+  let mut unsupported_rules: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+  for rule in &report.unsupported_rules {
+    unsupported_rules
+      .entry(&rule.message)
+      .or_default()
+      .push(rule.to_string());
+  }
+
+  // The table used to dispatch each case to the validators of its type, by its full name
+  let mut cases = String::from("pub(crate) static CASES: &[(&str, Validate)] = &[\n");
+
+  for message in &report.messages {
+    let message_desc = pool
+      .get_message_by_name(&message.name)
+      .ok_or_else(|| format!("The message {} is not in the descriptor", message.name))?;
+
+    writeln!(
+      cases,
+      "  (\"{}\", validate::<{}>),",
+      message.name,
+      rust_path(&message_desc)
+    )?;
+  }
+
+  cases.push_str("];\n\npub(crate) static UNSUPPORTED_RULES: &[(&str, &str)] = &[\n");
+
+  for (message, rules) in unsupported_rules {
+    writeln!(cases, "  ({:?}, {:?}),", message, rules.join(", "))?;
+  }
+
+  cases.push_str("];\n");
+
+  fs::write(out_dir.join("cases.rs"), cases)?;
+
+  Ok(())
+}
+
+// The path of the type generated by prost, inside of the module tree from protos.rs.
+// Prost also escapes the names that are Rust keywords, which are not used by the conformance cases.
+fn rust_path(message_desc: &MessageDescriptor) -> String {
+  let mut path: Vec<String> = message_desc
+    .package_name()
+    .split('.')
+    .map(ToSnakeCase::to_snake_case)
+    .collect();
+
+  let mut parent = message_desc.parent_message();
+  let mut parents = Vec::new();
+
+  while let Some(parent_desc) = parent {
+    parents.push(parent_desc.name().to_snake_case());
+    parent = parent_desc.parent_message();
+  }
+
+  path.extend(parents.into_iter().rev());
+  path.push(message_desc.name().to_upper_camel_case());
+
+  format!("crate::protos::{}", path.join("::"))
+}
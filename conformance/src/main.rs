@@ -0,0 +1,244 @@
+//! An executor for the protovalidate conformance tests.
+//!
+//! It reads a `TestConformanceRequest` from stdin, validates each case with the validators generated by protocheck for its type, and writes a `TestConformanceResponse` to stdout. A summary of the results is printed to stderr.
+//!
+//! The cases are dispatched by the type URL of their `Any`, to the types compiled from the protos in the `proto` directory. The file descriptor set in the request is not used. The cases of the types with rules that are not supported by protocheck are skipped: they are left out of the response, rather than being returned as errors which would fail the run, and they are only counted in the summary.
+use std::{
+  collections::HashMap,
+  io::{self, Read, Write},
+  process::ExitCode,
+};
+
+use prost::Message;
+use protocheck::{
+  types::{protovalidate::Violations, Any},
+  ProtoValidator,
+};
+
+use crate::protos::buf::validate::conformance::harness::{
+  test_result, TestConformanceRequest, TestConformanceResponse, TestResult,
+};
+
+#[allow(dead_code, clippy::all)]
+mod protos {
+  include!(concat!(env!("OUT_DIR"), "/protos.rs"));
+}
+
+type Validate = fn(&[u8]) -> Result<Result<(), Violations>, prost::DecodeError>;
+
+fn validate<M: Message + Default + ProtoValidator>(
+  bytes: &[u8],
+) -> Result<Result<(), Violations>, prost::DecodeError> {
+  Ok(M::decode(bytes)?.validate())
+}
+
+include!(concat!(env!("OUT_DIR"), "/cases.rs"));
+
+/// The verdict of a single case, as printed in the summary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Outcome {
+  Pass,
+  Fail,
+  Skip,
+  Error,
+}
+
+// The skipped cases have no result
+fn run_case(case: &Any) -> (Outcome, Option<test_result::Result>) {
+  let type_name = case
+    .type_url
+    .rsplit_once('/')
+    .map_or(case.type_url.as_str(), |(_, name)| name);
+
+  if UNSUPPORTED_RULES
+    .iter()
+    .any(|(message, _)| *message == type_name)
+  {
+    return (Outcome::Skip, None);
+  }
+
+  let Some((_, validate)) = CASES.iter().find(|(message, _)| *message == type_name) else {
+    return (
+      Outcome::Error,
+      Some(test_result::Result::UnexpectedError(format!(
+        "unknown type {}",
+        type_name
+      ))),
+    );
+  };
+
+  let (outcome, result) = match validate(&case.value) {
+    Ok(Ok(())) => (Outcome::Pass, test_result::Result::Success(true)),
+    Ok(Err(violations)) => (
+      Outcome::Fail,
+      test_result::Result::ValidationError(violations),
+    ),
+    Err(e) => (
+      Outcome::Error,
+      test_result::Result::RuntimeError(e.to_string()),
+    ),
+  };
+
+  (outcome, Some(result))
+}
+
+fn run(request: TestConformanceRequest) -> (TestConformanceResponse, Vec<(String, Outcome)>) {
+  let mut results = HashMap::new();
+  let mut outcomes = Vec::new();
+
+  for (name, case) in request.cases {
+    let (outcome, result) = run_case(&case);
+
+    if let Some(result) = result {
+      results.insert(
+        name.clone(),
+        TestResult {
+          result: Some(result),
+        },
+      );
+    }
+    outcomes.push((name, outcome));
+  }
+
+  outcomes.sort();
+
+  (TestConformanceResponse { results }, outcomes)
+}
+
+fn main() -> ExitCode {
+  let mut input = Vec::new();
+
+  if let Err(e) = io::stdin().read_to_end(&mut input) {
+    eprintln!("Could not read the request: {}", e);
+    return ExitCode::FAILURE;
+  }
+
+  let request = match TestConformanceRequest::decode(input.as_slice()) {
+    Ok(request) => request,
+    Err(e) => {
+      eprintln!("Could not decode the request: {}", e);
+      return ExitCode::FAILURE;
+    }
+  };
+
+  let (response, outcomes) = run(request);
+
+  let mut counts = HashMap::new();
+
+  for (name, outcome) in &outcomes {
+    eprintln!("{:?}: {}", outcome, name);
+    *counts.entry(*outcome).or_insert(0) += 1;
+  }
+
+  eprintln!(
+    "{} passed, {} failed, {} skipped, {} errors",
+    counts.get(&Outcome::Pass).unwrap_or(&0),
+    counts.get(&Outcome::Fail).unwrap_or(&0),
+    counts.get(&Outcome::Skip).unwrap_or(&0),
+    counts.get(&Outcome::Error).unwrap_or(&0)
+  );
+
+  if let Err(e) = io::stdout().write_all(&response.encode_to_vec()) {
+    eprintln!("Could not write the response: {}", e);
+    return ExitCode::FAILURE;
+  }
+
+  ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::protos::buf::validate::conformance::cases::{
+    other_package::embed::DoubleEmbed, BoolConstTrue, MessageNested, StringUlid,
+  };
+
+  fn case<M: prost::Name>(message: &M) -> Any {
+    Any::from_msg(message).unwrap()
+  }
+
+  fn outcome(case: Any) -> Outcome {
+    run_case(&case).0
+  }
+
+  #[test]
+  fn dispatch() {
+    assert_eq!(outcome(case(&BoolConstTrue { val: true })), Outcome::Pass);
+
+    let (invalid, result) = run_case(&case(&BoolConstTrue { val: false }));
+    assert_eq!(invalid, Outcome::Fail);
+
+    let Some(test_result::Result::ValidationError(Violations { violations })) = result else {
+      panic!("the violations are returned");
+    };
+    assert_eq!(violations[0].rule_id(), "bool.const");
+
+    // The nested messages and the ones in the other packages have the validators too
+    assert_eq!(outcome(case(&DoubleEmbed { val: 0 })), Outcome::Fail);
+    assert_eq!(
+      outcome(case(&MessageNested {
+        inner: Some(Default::default()),
+      })),
+      Outcome::Fail
+    );
+  }
+
+  #[test]
+  fn skipped_and_unknown_cases() {
+    assert_eq!(
+      run_case(&case(&StringUlid {
+        val: "abc".to_string(),
+      })),
+      (Outcome::Skip, None)
+    );
+
+    assert_eq!(
+      outcome(Any {
+        type_url: "type.googleapis.com/buf.validate.conformance.cases.Missing".to_string(),
+        value: Vec::new(),
+      }),
+      Outcome::Error
+    );
+
+    let (malformed, result) = run_case(&Any {
+      type_url: "type.googleapis.com/buf.validate.conformance.cases.BoolConstTrue".to_string(),
+      value: vec![0xff],
+    });
+    assert_eq!(malformed, Outcome::Error);
+    assert!(matches!(result, Some(test_result::Result::RuntimeError(_))));
+  }
+
+  #[test]
+  fn response() {
+    let request = TestConformanceRequest {
+      fdset: None,
+      cases: HashMap::from([
+        ("valid".to_string(), case(&BoolConstTrue { val: true })),
+        ("invalid".to_string(), case(&BoolConstTrue { val: false })),
+        (
+          "skipped".to_string(),
+          case(&StringUlid {
+            val: "abc".to_string(),
+          }),
+        ),
+      ]),
+    };
+
+    let (response, outcomes) = run(request);
+
+    assert_eq!(
+      outcomes,
+      [
+        ("invalid".to_string(), Outcome::Fail),
+        ("skipped".to_string(), Outcome::Skip),
+        ("valid".to_string(), Outcome::Pass)
+      ]
+    );
+    // The skipped cases are only counted in the summary, so that they don't fail the run
+    assert!(!response.results.contains_key("skipped"));
+    assert_eq!(
+      response.results["valid"].result,
+      Some(test_result::Result::Success(true))
+    );
+  }
+}
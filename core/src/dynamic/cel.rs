@@ -0,0 +1,353 @@
+use std::{collections::HashMap, sync::Arc};
+
+use ::cel::{objects::Key as CelKey, Program, Value as CelValue};
+use prost_reflect::{DynamicMessage, MapKey, ReflectMessage, Value};
+use proto_types::{
+  cel::CelConversionError,
+  protovalidate::{Rule, Violation},
+  Any, Duration, Empty, FieldMask, ListValue, Struct, Timestamp, Value as JsonValue,
+};
+
+use super::rules::{duration_value, push, timestamp_value};
+use crate::{
+  field_data::{rust_field_name, FieldContext, ParentMessages},
+  validators::cel::{
+    message_to_cel_value, validate_cel_field_conversion, validate_cel_message_with_val, CelRule,
+  },
+};
+
+// Like the structs that derive the conversion, the messages nested more deeply are converted to null
+const MAX_RECURSION_DEPTH: usize = 10;
+
+const WRAPPERS: &[&str] = &[
+  "google.protobuf.BoolValue",
+  "google.protobuf.StringValue",
+  "google.protobuf.BytesValue",
+  "google.protobuf.Int32Value",
+  "google.protobuf.Int64Value",
+  "google.protobuf.UInt32Value",
+  "google.protobuf.UInt64Value",
+  "google.protobuf.FloatValue",
+  "google.protobuf.DoubleValue",
+];
+
+/// Converts the value of a rule (such as the `StringRules` of a field) into the Cel value available as `rules` in the expressions.
+///
+/// The fields of the rules are present with their default values when they are not set, except for the members of the oneofs, which are only present when they are set. This is used by the macros for the rules of the generated validators, and by the [`DynamicValidator`](super::DynamicValidator), so that the expressions see the same values.
+pub fn rules_to_cel_value(value: &Value) -> CelValue {
+  rules_to_cel_value_recursive(value, 0)
+}
+
+fn rules_to_cel_value_recursive(value: &Value, depth: usize) -> CelValue {
+  match value {
+    Value::List(items) => CelValue::List(Arc::new(items.iter().map(rules_to_cel_value).collect())),
+    Value::Map(entries) => {
+      let entries: HashMap<CelKey, CelValue> = entries
+        .iter()
+        .map(|(key, value)| {
+          (
+            map_key_to_cel(key),
+            rules_to_cel_value_recursive(value, depth + 1),
+          )
+        })
+        .collect();
+
+      CelValue::Map(entries.into())
+    }
+    Value::Message(message) => {
+      let message_desc = message.descriptor();
+
+      match message_desc.full_name() {
+        "google.protobuf.Timestamp" => CelValue::Timestamp(
+          timestamp_value(value)
+            .unwrap_or_default()
+            .try_into()
+            .unwrap_or_default(),
+        ),
+        "google.protobuf.Duration" => CelValue::Duration(
+          duration_value(value)
+            .unwrap_or_default()
+            .try_into()
+            .unwrap_or_default(),
+        ),
+        "google.protobuf.Empty" => Empty {}.into(),
+        "google.protobuf.FieldMask" => FieldMask::new(vec![]).into(),
+        "google.protobuf.Struct" => CelValue::Map(HashMap::<CelKey, CelValue>::new().into()),
+        "google.protobuf.ListValue" => CelValue::List(Vec::<CelValue>::new().into()),
+        "google.protobuf.Value" => CelValue::Null,
+        _ if depth >= MAX_RECURSION_DEPTH => {
+          CelValue::Map(HashMap::<CelKey, CelValue>::new().into())
+        }
+        _ => {
+          let mut fields: HashMap<CelKey, CelValue> = HashMap::new();
+
+          for field in message_desc.fields() {
+            // Like the rules with a oneof (such as string.email), the fields of a oneof are only present when they are set
+            if field.containing_oneof().is_some() && !message.has_field(&field) {
+              continue;
+            }
+
+            fields.insert(
+              CelKey::String(Arc::new(rust_field_name(field.name()).into_owned())),
+              rules_to_cel_value_recursive(&message.get_field(&field), depth + 1),
+            );
+          }
+
+          CelValue::Map(fields.into())
+        }
+      }
+    }
+    scalar => scalar_to_cel(scalar),
+  }
+}
+
+fn scalar_to_cel(value: &Value) -> CelValue {
+  match value {
+    Value::Bool(v) => CelValue::Bool(*v),
+    Value::I32(v) => CelValue::Int(i64::from(*v)),
+    Value::I64(v) => CelValue::Int(*v),
+    Value::U32(v) => CelValue::UInt(u64::from(*v)),
+    Value::U64(v) => CelValue::UInt(*v),
+    Value::F32(v) => CelValue::Float(f64::from(*v)),
+    Value::F64(v) => CelValue::Float(*v),
+    Value::String(v) => CelValue::String(Arc::new(v.clone())),
+    Value::Bytes(v) => CelValue::Bytes(Arc::new(v.to_vec())),
+    Value::EnumNumber(v) => CelValue::Int(i64::from(*v)),
+    Value::Message(_) | Value::List(_) | Value::Map(_) => CelValue::Null,
+  }
+}
+
+fn map_key_to_cel(key: &MapKey) -> CelKey {
+  match key {
+    MapKey::Bool(v) => CelKey::Bool(*v),
+    MapKey::I32(v) => CelKey::Int(i64::from(*v)),
+    MapKey::I64(v) => CelKey::Int(*v),
+    MapKey::U32(v) => CelKey::Uint(u64::from(*v)),
+    MapKey::U64(v) => CelKey::Uint(*v),
+    MapKey::String(v) => CelKey::String(Arc::new(v.clone())),
+  }
+}
+
+// The value of a field, converted to the Cel value available as `this` like the values of the generated structs
+struct CelField<'a>(&'a Value);
+
+impl TryFrom<CelField<'_>> for CelValue {
+  type Error = CelConversionError;
+
+  fn try_from(value: CelField<'_>) -> Result<Self, Self::Error> {
+    value_to_cel(value.0, 0)
+  }
+}
+
+// Like CelField, for the rules of the messages
+struct CelMessage<'a>(&'a DynamicMessage);
+
+impl TryFrom<CelMessage<'_>> for CelValue {
+  type Error = CelConversionError;
+
+  fn try_from(value: CelMessage<'_>) -> Result<Self, Self::Error> {
+    message_to_cel(value.0, 0)
+  }
+}
+
+fn value_to_cel(value: &Value, depth: usize) -> Result<CelValue, CelConversionError> {
+  match value {
+    Value::Message(message) => message_to_cel(message, depth),
+    Value::List(items) => {
+      let items = items
+        .iter()
+        .map(|item| value_to_cel(item, depth))
+        .collect::<Result<Vec<CelValue>, CelConversionError>>()?;
+
+      Ok(CelValue::List(Arc::new(items)))
+    }
+    Value::Map(entries) => {
+      let entries = entries
+        .iter()
+        .map(|(key, value)| Ok((map_key_to_cel(key), value_to_cel(value, depth)?)))
+        .collect::<Result<HashMap<CelKey, CelValue>, CelConversionError>>()?;
+
+      Ok(CelValue::Map(entries.into()))
+    }
+    scalar => Ok(scalar_to_cel(scalar)),
+  }
+}
+
+fn message_to_cel(message: &DynamicMessage, depth: usize) -> Result<CelValue, CelConversionError> {
+  let message_desc = message.descriptor();
+  let full_name = message_desc.full_name();
+
+  // The well known types have the same conversions as their structs in protocheck's types
+  match full_name {
+    "google.protobuf.Duration" => {
+      let (seconds, nanos) = seconds_and_nanos(message);
+
+      return Duration { seconds, nanos }.try_into();
+    }
+    "google.protobuf.Timestamp" => {
+      let (seconds, nanos) = seconds_and_nanos(message);
+
+      return Timestamp { seconds, nanos }.try_into();
+    }
+    "google.protobuf.Any" => return Ok(transcoded::<Any>(message).into()),
+    "google.protobuf.FieldMask" => return Ok(transcoded::<FieldMask>(message).into()),
+    "google.protobuf.Empty" => return Ok(Empty {}.into()),
+    "google.protobuf.Struct" => return transcoded::<Struct>(message).try_into(),
+    "google.protobuf.ListValue" => return transcoded::<ListValue>(message).try_into(),
+    "google.protobuf.Value" => return transcoded::<JsonValue>(message).try_into(),
+    _ if WRAPPERS.contains(&full_name) => {
+      return message
+        .get_field_by_number(1)
+        .map_or(Ok(CelValue::Null), |value| value_to_cel(&value, depth));
+    }
+    _ => {}
+  }
+
+  if depth >= MAX_RECURSION_DEPTH {
+    return Ok(CelValue::Null);
+  }
+
+  let mut fields: HashMap<CelKey, CelValue> = HashMap::new();
+
+  for field in message_desc.fields() {
+    let is_in_oneof = field
+      .containing_oneof()
+      .is_some_and(|oneof| !oneof.is_synthetic());
+
+    // The oneofs only have the field that is set, and the other fields with presence are null when they are not set
+    let value = if message.has_field(&field) || !field.supports_presence() {
+      value_to_cel(&message.get_field(&field), depth + 1)?
+    } else if is_in_oneof {
+      continue;
+    } else {
+      CelValue::Null
+    };
+
+    fields.insert(
+      CelKey::String(Arc::new(rust_field_name(field.name()).into_owned())),
+      value,
+    );
+  }
+
+  Ok(CelValue::Map(fields.into()))
+}
+
+fn seconds_and_nanos(message: &DynamicMessage) -> (i64, i32) {
+  let field = |number| message.get_field_by_number(number);
+
+  (
+    field(1)
+      .and_then(|value| value.as_i64())
+      .unwrap_or_default(),
+    field(2)
+      .and_then(|value| value.as_i32())
+      .unwrap_or_default(),
+  )
+}
+
+// The well known types of the pool are the same messages as the ones of protocheck's types
+fn transcoded<T: prost_reflect::prost::Message + Default>(message: &DynamicMessage) -> T {
+  message.transcode_to().unwrap_or_default()
+}
+
+struct CelProgram {
+  id: String,
+  error_message: String,
+  program: Program,
+  uses_now: bool,
+}
+
+/// The Cel rules of a field (or of a message), which are compiled once when the rules are read from the descriptor.
+pub(super) struct CelRules {
+  rules: CelValue,
+  item_full_name: String,
+  programs: Vec<CelProgram>,
+}
+
+impl CelRules {
+  /// The `target` is either `field` or `message`, and it is only used in the errors. The `rules` are the ones available in the expressions, which are an empty map for the fields without rules for their type.
+  pub(super) fn new(
+    target: &str,
+    full_name: &str,
+    cel: &[Rule],
+    rules: Option<&Value>,
+  ) -> Result<Option<Self>, String> {
+    if cel.is_empty() {
+      return Ok(None);
+    }
+
+    let rules = rules.map_or_else(
+      || CelValue::Map(HashMap::<CelKey, CelValue>::new().into()),
+      rules_to_cel_value,
+    );
+
+    let item_full_name = full_name.replace('.', "_");
+
+    let programs = cel
+      .iter()
+      .map(|rule| {
+        Program::compile(rule.expression())
+          .map(|program| CelProgram {
+            id: rule.id().to_string(),
+            error_message: rule.message().to_string(),
+            uses_now: program.references().has_variable("now"),
+            program,
+          })
+          .map_err(|e| {
+            format!("Cel program error for {target} {item_full_name}: failed to compile: {e}")
+          })
+      })
+      .collect::<Result<Vec<CelProgram>, String>>()?;
+
+    Ok(Some(Self {
+      rules,
+      item_full_name,
+      programs,
+    }))
+  }
+
+  fn rules(&self) -> impl Iterator<Item = CelRule<'_>> {
+    self.programs.iter().map(|program| CelRule {
+      id: &program.id,
+      error_message: &program.error_message,
+      program: &program.program,
+      uses_now: program.uses_now,
+      item_full_name: &self.item_full_name,
+      rules: &self.rules,
+    })
+  }
+
+  pub(super) fn check_field(
+    &self,
+    field_context: &FieldContext,
+    value: &Value,
+    violations: &mut Vec<Violation>,
+  ) {
+    for rule in self.rules() {
+      push(
+        violations,
+        validate_cel_field_conversion(field_context, rule, CelValue::try_from(CelField(value))),
+      );
+    }
+  }
+
+  pub(super) fn check_message(
+    &self,
+    parent_messages: &ParentMessages,
+    message: &DynamicMessage,
+    violations: &mut Vec<Violation>,
+  ) {
+    // The message is converted once for all of its rules
+    match message_to_cel_value(parent_messages, &self.item_full_name, CelMessage(message)) {
+      Ok(cel_message) => {
+        for rule in self.rules() {
+          push(
+            violations,
+            validate_cel_message_with_val(parent_messages, rule, &cel_message),
+          );
+        }
+      }
+      Err(violation) => violations.push(violation),
+    }
+  }
+}
@@ -0,0 +1,1140 @@
+//! Validates the [`DynamicMessage`]s of prost-reflect, with the rules read from the `buf.validate` options of their descriptors at runtime.
+//!
+//! The rules are checked with the same validators as the ones generated by the macros, so the violations are the same as the ones of the structs. They are read (and the Cel programs are compiled) the first time that a message of each type is validated.
+//!
+//! ```rust,ignore
+//! let pool = DescriptorPool::decode(FILE_DESCRIPTOR_SET)?;
+//! let validator = DynamicValidator::new(pool.clone());
+//!
+//! let message = DynamicMessage::decode(pool.get_message_by_name("myapp.v1.GreetRequest").unwrap(), body)?;
+//!
+//! validator.validate(&message)?;
+//! ```
+//!
+//! Unlike the generated validators, the rules defined with `buf.validate.predefined` are not checked, and the fields excluded by protocheck-build are validated like the others.
+
+use std::{
+  borrow::Cow,
+  collections::HashMap,
+  fmt,
+  sync::{Arc, PoisonError, RwLock},
+};
+
+use prost_reflect::{
+  DescriptorPool, DynamicMessage, ExtensionDescriptor, FieldDescriptor, Kind, MapKey,
+  MessageDescriptor, OneofDescriptor, ReflectMessage, Value,
+};
+use proto_types::{
+  protovalidate::{
+    field_rules::Type as RulesType, FieldRules, Ignore, MessageRules, OneofRules, Violation,
+    Violations,
+  },
+  Duration, FieldType, Timestamp,
+};
+
+#[cfg(feature = "cel")]
+pub use self::cel::rules_to_cel_value;
+use self::{
+  cel::CelRules,
+  rules::{duration_value, first_duplicate, timestamp_value, Lengths, TypeChecks},
+};
+use crate::{
+  field_data::{FieldContext, FieldKind, ParentMessages, PathField, SubscriptRef},
+  validators::{
+    fail_fast::{should_stop, with_fail_fast},
+    maps, oneofs,
+    recursion::recursion_limit,
+    repeated, required,
+    timestamps::with_lazy_now,
+  },
+  ProtoType,
+};
+
+#[cfg(feature = "cel")]
+mod cel;
+mod rules;
+
+// The messages with Cel rules cannot be validated without the cel feature
+#[cfg(not(feature = "cel"))]
+mod cel {
+  use prost_reflect::{DynamicMessage, Value};
+  use proto_types::protovalidate::{Rule, Violation};
+
+  use crate::field_data::{FieldContext, ParentMessages};
+
+  pub(super) enum CelRules {}
+
+  impl CelRules {
+    pub(super) fn new(
+      _target: &str,
+      _full_name: &str,
+      cel: &[Rule],
+      _rules: Option<&Value>,
+    ) -> Result<Option<Self>, String> {
+      if cel.is_empty() {
+        Ok(None)
+      } else {
+        Err("Cannot use Cel validators without the 'cel' feature".to_string())
+      }
+    }
+
+    pub(super) fn check_field(
+      &self,
+      _field_context: &FieldContext,
+      _value: &Value,
+      _violations: &mut Vec<Violation>,
+    ) {
+      match *self {}
+    }
+
+    pub(super) fn check_message(
+      &self,
+      _parent_messages: &ParentMessages,
+      _message: &DynamicMessage,
+      _violations: &mut Vec<Violation>,
+    ) {
+      match *self {}
+    }
+  }
+}
+
+/// Validates the [`DynamicMessage`]s of the messages of a [`DescriptorPool`], which must contain the `buf/validate/validate.proto` file for the rules to be found.
+///
+/// The rules of each message are read once and then cached, so the validator should be reused (it can be shared between threads).
+pub struct DynamicValidator {
+  pool: DescriptorPool,
+  field_rules: Option<ExtensionDescriptor>,
+  oneof_rules: Option<ExtensionDescriptor>,
+  message_rules: Option<ExtensionDescriptor>,
+  plans: RwLock<HashMap<String, Arc<Result<MessagePlan, String>>>>,
+}
+
+impl fmt::Debug for DynamicValidator {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("DynamicValidator")
+      .field("pool", &self.pool)
+      .finish_non_exhaustive()
+  }
+}
+
+impl DynamicValidator {
+  /// Creates the validator for the messages of the pool. A pool without the rules of protovalidate is accepted, and its messages are always valid.
+  pub fn new(pool: DescriptorPool) -> Self {
+    Self {
+      field_rules: pool.get_extension_by_name("buf.validate.field"),
+      oneof_rules: pool.get_extension_by_name("buf.validate.oneof"),
+      message_rules: pool.get_extension_by_name("buf.validate.message"),
+      plans: RwLock::default(),
+      pool,
+    }
+  }
+
+  /// Returns the pool of the messages.
+  pub fn pool(&self) -> &DescriptorPool {
+    &self.pool
+  }
+
+  /// Validates the message and its nested messages, like the `validate` method of the structs.
+  ///
+  /// The rules that cannot be used (such as a rule for a different type than the one of its field, or a Cel expression that does not compile) are reported as an `internal_server_error` violation for the message, as they are only found at runtime.
+  pub fn validate(&self, message: &DynamicMessage) -> Result<(), Violations> {
+    let mut violations: Vec<Violation> = Vec::new();
+    let parent_messages = ParentMessages::default();
+
+    // The current time is read on its first use, and it is then the same for all of the rules checked by this call
+    with_lazy_now(|| {
+      self.validate_nested(message, &parent_messages, &mut violations);
+    });
+
+    let violations = Violations { violations };
+
+    if violations.is_empty() {
+      Ok(())
+    } else {
+      Err(violations)
+    }
+  }
+
+  /// Like [`validate`](Self::validate), but stops at the first violation and returns only that one.
+  pub fn validate_fail_fast(&self, message: &DynamicMessage) -> Result<(), Violations> {
+    with_fail_fast(|| self.validate(message)).map_err(|mut violations| {
+      violations.violations.truncate(1);
+      violations
+    })
+  }
+
+  fn validate_nested(
+    &self,
+    message: &DynamicMessage,
+    parent_messages: &ParentMessages,
+    violations: &mut Vec<Violation>,
+  ) {
+    if let Err(violation) = recursion_limit(parent_messages) {
+      violations.push(violation);
+      return;
+    }
+
+    match self.plan(&message.descriptor()).as_ref() {
+      Ok(plan) => self.check_message(plan, message, parent_messages, violations),
+      Err(_) => violations.push(Violation::new(
+        parent_messages.to_elements(),
+        "internal_server_error",
+        "internal server error",
+      )),
+    }
+  }
+
+  fn plan(&self, message_desc: &MessageDescriptor) -> Arc<Result<MessagePlan, String>> {
+    if let Some(plan) = self
+      .plans
+      .read()
+      .unwrap_or_else(PoisonError::into_inner)
+      .get(message_desc.full_name())
+    {
+      return plan.clone();
+    }
+
+    let plan = self.message_plan(message_desc).inspect_err(|e| {
+      eprintln!(
+        "Could not read the rules of the message {}: {e}",
+        message_desc.full_name()
+      )
+    });
+
+    self
+      .plans
+      .write()
+      .unwrap_or_else(PoisonError::into_inner)
+      .entry(message_desc.full_name().to_string())
+      .or_insert_with(|| Arc::new(plan))
+      .clone()
+  }
+
+  // Same order as the generated validators: the rules of the message, then the oneofs, then the other fields
+  fn check_message(
+    &self,
+    plan: &MessagePlan,
+    message: &DynamicMessage,
+    parent_messages: &ParentMessages,
+    violations: &mut Vec<Violation>,
+  ) {
+    if let Some(cel) = &plan.cel {
+      cel.check_message(parent_messages, message, violations);
+
+      if should_stop(violations) {
+        return;
+      }
+    }
+
+    for oneof in &plan.oneofs {
+      let set_field = oneof.oneof.fields().find(|field| message.has_field(field));
+
+      match &set_field {
+        Some(set_field) => {
+          if let Some(member) = oneof
+            .members
+            .iter()
+            .find(|member| member.field.number() == set_field.number())
+          {
+            self.check_oneof_member(member, message, parent_messages, violations);
+          }
+        }
+        None => {
+          if oneof.required {
+            violations.push(oneofs::required(oneof.oneof.name(), parent_messages));
+          }
+        }
+      }
+
+      // The required fields of a oneof are reported unless the oneof is set to them
+      for member in oneof.members.iter().filter(|member| member.required) {
+        if set_field
+          .as_ref()
+          .is_none_or(|set_field| set_field.number() != member.field.number())
+        {
+          violations.push(required::required(&member.context(parent_messages)));
+        }
+      }
+
+      if should_stop(violations) {
+        return;
+      }
+    }
+
+    for field in &plan.fields {
+      self.check_field(field, message, parent_messages, violations);
+
+      if should_stop(violations) {
+        return;
+      }
+    }
+  }
+
+  fn check_oneof_member(
+    &self,
+    member: &FieldPlan,
+    message: &DynamicMessage,
+    parent_messages: &ParentMessages,
+    violations: &mut Vec<Violation>,
+  ) {
+    let FieldPlanRules::Single(rules) = &member.rules else {
+      return;
+    };
+
+    let value = message.get_field(&member.field);
+    let unwrapped = member.unwrapped(&value);
+    let field_context = member.context(parent_messages);
+
+    // The members of the oneofs check their Cel rules first, and their nested message even when the other rules are ignored
+    if !member.ignores_zero_value() || member.is_populated(&unwrapped) {
+      if let Some(cel) = &rules.cel {
+        cel.check_field(&field_context, &unwrapped, violations);
+      }
+
+      if let Some(checks) = &rules.checks {
+        checks.check(&field_context, &unwrapped, violations);
+      }
+    }
+
+    if rules.nested {
+      self.check_nested(
+        &value,
+        nested_path(&member.field, None),
+        parent_messages,
+        violations,
+      );
+    }
+  }
+
+  fn check_field(
+    &self,
+    field: &FieldPlan,
+    message: &DynamicMessage,
+    parent_messages: &ParentMessages,
+    violations: &mut Vec<Violation>,
+  ) {
+    match &field.rules {
+      FieldPlanRules::Single(rules) => {
+        // The fields with presence are None when they are not set
+        let value = (!field.is_optional || message.has_field(&field.field))
+          .then(|| message.get_field(&field.field));
+
+        let Some(value) = value else {
+          if field.required {
+            violations.push(required::required(&field.context(parent_messages)));
+          }
+          return;
+        };
+
+        let unwrapped = field.unwrapped(&value);
+        let is_populated = field.is_populated(&unwrapped);
+
+        // `required` takes precedence over the ignore modes, except for IGNORE_ALWAYS
+        if field.required && !field.is_optional && !is_populated {
+          violations.push(required::required(&field.context(parent_messages)));
+          return;
+        }
+
+        if field.ignores_zero_value() && !is_populated {
+          return;
+        }
+
+        let field_context = field.context(parent_messages);
+
+        if let Some(checks) = &rules.checks {
+          checks.check(&field_context, &unwrapped, violations);
+        }
+
+        if let Some(cel) = &rules.cel {
+          cel.check_field(&field_context, &unwrapped, violations);
+        }
+
+        if rules.nested {
+          self.check_nested(
+            &value,
+            nested_path(&field.field, None),
+            parent_messages,
+            violations,
+          );
+        }
+      }
+      FieldPlanRules::Repeated(rules) => {
+        let value = message.get_field(&field.field);
+        let items = value.as_list().unwrap_or_default();
+
+        if items.is_empty() {
+          if field.required {
+            violations.push(required::required(&field.context(parent_messages)));
+          }
+
+          if field.required || field.ignores_zero_value() {
+            return;
+          }
+        }
+
+        self.check_list(field, rules, &value, items, parent_messages, violations);
+      }
+      FieldPlanRules::Map(rules) => {
+        let value = message.get_field(&field.field);
+        let Some(entries) = value.as_map() else {
+          return;
+        };
+
+        if entries.is_empty() {
+          if field.required {
+            violations.push(required::required(&field.context(parent_messages)));
+          }
+
+          if field.required || field.ignores_zero_value() {
+            return;
+          }
+        }
+
+        self.check_map(field, rules, &value, entries, parent_messages, violations);
+      }
+    }
+  }
+
+  fn check_list(
+    &self,
+    field: &FieldPlan,
+    rules: &RepeatedPlan,
+    value: &Value,
+    items: &[Value],
+    parent_messages: &ParentMessages,
+    violations: &mut Vec<Violation>,
+  ) {
+    let field_context = field.context(parent_messages);
+
+    if let Some(cel) = &rules.cel {
+      cel.check_field(&field_context, value, violations);
+    }
+
+    rules.lengths.check(&field_context, items, violations);
+
+    if !rules.unique && !rules.items.has_rules() {
+      return;
+    }
+
+    let item_context = |index: usize| FieldContext {
+      subscript: Some(SubscriptRef::Index(index as u64)),
+      field_kind: FieldKind::RepeatedItem(field.field_type),
+      ..field_context.clone()
+    };
+
+    let unwrapped: Vec<Cow<'_, Value>> = items.iter().map(|item| field.unwrapped(item)).collect();
+
+    // Only the first duplicate is reported (for the list, so the index of the context does not matter), before the other rules of its item
+    let mut duplicate = if rules.unique {
+      first_duplicate(&item_context(0), &unwrapped, field.field_type)
+    } else {
+      None
+    };
+
+    for (index, (item, unwrapped)) in items.iter().zip(&unwrapped).enumerate() {
+      if let Some((_, violation)) =
+        duplicate.take_if(|(duplicate_index, _)| *duplicate_index == index)
+      {
+        violations.push(violation);
+      }
+
+      rules
+        .items
+        .check(&item_context(index), unwrapped, violations);
+
+      if rules.items.nested {
+        let path = nested_path(&field.field, Some(SubscriptRef::Index(index as u64)));
+        self.check_nested(item, path, parent_messages, violations);
+      }
+
+      if should_stop(violations) {
+        break;
+      }
+    }
+  }
+
+  fn check_map(
+    &self,
+    field: &FieldPlan,
+    rules: &MapPlan,
+    value: &Value,
+    entries: &HashMap<MapKey, Value>,
+    parent_messages: &ParentMessages,
+    violations: &mut Vec<Violation>,
+  ) {
+    let field_context = FieldContext {
+      key_type: Some(rules.key_proto_type),
+      value_type: Some(rules.value_proto_type),
+      ..field.context(parent_messages)
+    };
+
+    if let Some(cel) = &rules.cel {
+      cel.check_field(&field_context, value, violations);
+    }
+
+    rules.lengths.check(&field_context, entries, violations);
+
+    if !rules.keys.has_rules() && !rules.values.has_rules() {
+      return;
+    }
+
+    for (key, value) in entries {
+      let subscript = map_key_subscript(key);
+
+      if rules.keys.has_rules() {
+        let key_context = FieldContext {
+          subscript: Some(subscript),
+          field_kind: FieldKind::MapKey(rules.key_type),
+          ..field_context.clone()
+        };
+
+        rules
+          .keys
+          .check(&key_context, &map_key_value(key), violations);
+      }
+
+      if rules.values.has_rules() {
+        let value_context = FieldContext {
+          subscript: Some(subscript),
+          field_kind: FieldKind::MapValue(rules.value_type),
+          ..field_context.clone()
+        };
+
+        let unwrapped = if rules.values_wrapper {
+          wrapped_value(value)
+        } else {
+          Cow::Borrowed(value)
+        };
+
+        rules.values.check(&value_context, &unwrapped, violations);
+
+        if rules.values.nested {
+          let path = PathField {
+            key_type: Some(rules.key_proto_type),
+            value_type: Some(rules.value_proto_type),
+            ..nested_path(&field.field, Some(subscript))
+          };
+          self.check_nested(value, path, parent_messages, violations);
+        }
+      }
+
+      if should_stop(violations) {
+        break;
+      }
+    }
+  }
+
+  fn check_nested(
+    &self,
+    value: &Value,
+    path: PathField<'_>,
+    parent_messages: &ParentMessages,
+    violations: &mut Vec<Violation>,
+  ) {
+    if let Some(message) = value.as_message() {
+      self.validate_nested(message, &parent_messages.nested(path), violations);
+    }
+  }
+
+  fn message_plan(&self, message_desc: &MessageDescriptor) -> Result<MessagePlan, String> {
+    let mut plan = MessagePlan::default();
+
+    if let Some(rules) = extension_rules(&message_desc.options(), self.message_rules.as_ref()) {
+      let message_rules: MessageRules = transcode(&rules)?;
+
+      plan.cel = CelRules::new(
+        "message",
+        message_desc.full_name(),
+        &message_rules.cel,
+        Some(&Value::Message(rules)),
+      )?;
+    }
+
+    for oneof in message_desc.oneofs().filter(|oneof| !oneof.is_synthetic()) {
+      let required = extension_rules(&oneof.options(), self.oneof_rules.as_ref())
+        .map(|rules| transcode::<OneofRules>(&rules))
+        .transpose()?
+        .is_some_and(|rules| rules.required());
+
+      let members = oneof
+        .fields()
+        .map(|field| self.field_plan(field, true))
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<FieldPlan>, String>>()?;
+
+      plan.oneofs.push(OneofPlan {
+        oneof,
+        required,
+        members,
+      });
+    }
+
+    for field in message_desc.fields() {
+      if field
+        .containing_oneof()
+        .is_some_and(|oneof| !oneof.is_synthetic())
+      {
+        continue;
+      }
+
+      if let Some(field_plan) = self.field_plan(field, false)? {
+        plan.fields.push(field_plan);
+      }
+    }
+
+    Ok(plan)
+  }
+
+  // Returns None for the fields that are always ignored
+  fn field_plan(
+    &self,
+    field: FieldDescriptor,
+    in_oneof: bool,
+  ) -> Result<Option<FieldPlan>, String> {
+    let error = |e: String| format!("{}: {e}", field.full_name());
+
+    let dynamic_rules = extension_rules(&field.options(), self.field_rules.as_ref());
+    let field_rules: FieldRules = dynamic_rules
+      .as_ref()
+      .map(transcode)
+      .transpose()
+      .map_err(error)?
+      .unwrap_or_default();
+
+    let ignore = dynamic_rules
+      .as_ref()
+      .map_or(IgnoreMode::Unspecified, ignore_mode);
+
+    if ignore == IgnoreMode::Always {
+      return Ok(None);
+    }
+
+    let field_type = get_field_type(&field);
+    let kind = field.kind();
+
+    let rules = if field.is_list() {
+      FieldPlanRules::Repeated(Box::new(
+        repeated_plan(&field, field_type, &field_rules, dynamic_rules.as_ref()).map_err(error)?,
+      ))
+    } else if field.is_map() {
+      FieldPlanRules::Map(Box::new(
+        map_plan(&field, &field_rules, dynamic_rules.as_ref()).map_err(error)?,
+      ))
+    } else {
+      FieldPlanRules::Single(Box::new(ValuePlan {
+        checks: type_checks(field_rules.r#type.as_ref(), field_type, &kind).map_err(error)?,
+        cel: CelRules::new(
+          "field",
+          field.full_name(),
+          &field_rules.cel,
+          dynamic_rules.as_ref().and_then(type_rules).as_ref(),
+        )
+        .map_err(error)?,
+        nested: is_validated_message(&kind),
+      }))
+    };
+
+    Ok(Some(FieldPlan {
+      // Only the fields with presence can be required in a oneof
+      required: field_rules.required() && (!in_oneof || field.supports_presence()),
+      is_optional: in_oneof || field.supports_presence(),
+      is_wrapper: is_wrapper(&kind),
+      field_type,
+      ignore,
+      rules,
+      field,
+    }))
+  }
+}
+
+#[derive(Default)]
+struct MessagePlan {
+  cel: Option<CelRules>,
+  oneofs: Vec<OneofPlan>,
+  fields: Vec<FieldPlan>,
+}
+
+struct OneofPlan {
+  oneof: OneofDescriptor,
+  required: bool,
+  members: Vec<FieldPlan>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IgnoreMode {
+  Unspecified,
+  IfZeroValue,
+  IfDefaultValue,
+  Always,
+}
+
+struct FieldPlan {
+  field: FieldDescriptor,
+  field_type: FieldType,
+  ignore: IgnoreMode,
+  required: bool,
+  is_optional: bool,
+  // For the wrappers of the well known types, like google.protobuf.BoolValue
+  is_wrapper: bool,
+  rules: FieldPlanRules,
+}
+
+enum FieldPlanRules {
+  Single(Box<ValuePlan>),
+  Repeated(Box<RepeatedPlan>),
+  Map(Box<MapPlan>),
+}
+
+// The rules of a field, of the items of a list or of the keys or values of a map
+#[derive(Default)]
+struct ValuePlan {
+  checks: Option<TypeChecks>,
+  cel: Option<CelRules>,
+  nested: bool,
+}
+
+impl ValuePlan {
+  fn has_rules(&self) -> bool {
+    self.checks.is_some() || self.cel.is_some() || self.nested
+  }
+
+  fn check(&self, field_context: &FieldContext, value: &Value, violations: &mut Vec<Violation>) {
+    if let Some(checks) = &self.checks {
+      checks.check(field_context, value, violations);
+    }
+
+    if let Some(cel) = &self.cel {
+      cel.check_field(field_context, value, violations);
+    }
+  }
+}
+
+struct RepeatedPlan {
+  cel: Option<CelRules>,
+  unique: bool,
+  lengths: Lengths<[Value]>,
+  items: ValuePlan,
+}
+
+struct MapPlan {
+  cel: Option<CelRules>,
+  lengths: Lengths<HashMap<MapKey, Value>>,
+  key_type: FieldType,
+  value_type: FieldType,
+  key_proto_type: ProtoType,
+  value_proto_type: ProtoType,
+  keys: ValuePlan,
+  values: ValuePlan,
+  values_wrapper: bool,
+}
+
+impl FieldPlan {
+  fn context<'a>(&'a self, parent_messages: &'a ParentMessages<'a>) -> FieldContext<'a> {
+    let field_kind = if self.field.is_list() {
+      FieldKind::Repeated(self.field_type)
+    } else if self.field.is_map() {
+      FieldKind::Map(self.field_type)
+    } else {
+      FieldKind::Single(self.field_type)
+    };
+
+    FieldContext {
+      proto_name: self.field.name(),
+      tag: self.field.number() as i32,
+      parent_messages,
+      subscript: None,
+      key_type: None,
+      value_type: None,
+      field_kind,
+    }
+  }
+
+  fn unwrapped<'a>(&self, value: &'a Value) -> Cow<'a, Value> {
+    if self.is_wrapper {
+      wrapped_value(value)
+    } else {
+      Cow::Borrowed(value)
+    }
+  }
+
+  // Whether the rules are skipped for the zero value. For the fields with presence,
+  // this only happens with IGNORE_IF_DEFAULT_VALUE
+  fn ignores_zero_value(&self) -> bool {
+    let has_zero_value = !matches!(self.rules, FieldPlanRules::Single(_))
+      || !matches!(
+        self.field_type,
+        FieldType::Message | FieldType::Any | FieldType::Group
+      );
+
+    has_zero_value
+      && match self.ignore {
+        IgnoreMode::IfZeroValue => !self.is_optional,
+        IgnoreMode::IfDefaultValue => true,
+        IgnoreMode::Unspecified | IgnoreMode::Always => false,
+      }
+  }
+
+  // Whether the (unwrapped) value is not the zero value of its type
+  fn is_populated(&self, value: &Value) -> bool {
+    match self.field_type {
+      FieldType::Duration => duration_value(value).is_some_and(|v| v != Duration::default()),
+      FieldType::Timestamp => timestamp_value(value).is_some_and(|v| v != Timestamp::default()),
+      _ => match value {
+        Value::Bool(v) => *v,
+        Value::I32(v) | Value::EnumNumber(v) => *v != 0,
+        Value::I64(v) => *v != 0,
+        Value::U32(v) => *v != 0,
+        Value::U64(v) => *v != 0,
+        Value::F32(v) => *v != 0.0,
+        Value::F64(v) => *v != 0.0,
+        Value::String(v) => !v.is_empty(),
+        Value::Bytes(v) => !v.is_empty(),
+        Value::Message(_) | Value::List(_) | Value::Map(_) => true,
+      },
+    }
+  }
+}
+
+// The path of a nested message, which is the field of the parent message (with the subscript of the items of the lists and maps)
+fn nested_path<'a>(
+  field: &'a FieldDescriptor,
+  subscript: Option<SubscriptRef<'a>>,
+) -> PathField<'a> {
+  PathField {
+    proto_name: field.name(),
+    tag: field.number() as i32,
+    field_type: ProtoType::Message,
+    key_type: None,
+    value_type: None,
+    subscript,
+  }
+}
+
+fn repeated_plan(
+  field: &FieldDescriptor,
+  field_type: FieldType,
+  field_rules: &FieldRules,
+  dynamic_rules: Option<&DynamicMessage>,
+) -> Result<RepeatedPlan, String> {
+  let kind = field.kind();
+  let item_is_message = is_validated_message(&kind);
+
+  let mut plan = RepeatedPlan {
+    cel: CelRules::new(
+      "field",
+      field.full_name(),
+      &field_rules.cel,
+      dynamic_rules.and_then(type_rules).as_ref(),
+    )?,
+    unique: false,
+    lengths: Lengths::new(
+      None,
+      ("min_items", None, repeated::min_items),
+      ("max_items", None, repeated::max_items),
+      "item",
+    )?,
+    items: ValuePlan {
+      nested: item_is_message,
+      ..Default::default()
+    },
+  };
+
+  let Some(RulesType::Repeated(repeated_rules)) = &field_rules.r#type else {
+    return Ok(plan);
+  };
+
+  if repeated_rules.unique() {
+    match field_type {
+      FieldType::Message | FieldType::Any => {
+        return Err("repeated.unique only works for scalar fields".to_string())
+      }
+      FieldType::Bool => {
+        return Err("repeated.unique is not supported for bool fields".to_string())
+      }
+      _ => plan.unique = true,
+    }
+  }
+
+  plan.lengths = Lengths::new(
+    None,
+    ("min_items", repeated_rules.min_items, repeated::min_items),
+    ("max_items", repeated_rules.max_items, repeated::max_items),
+    "item",
+  )?;
+
+  if let Some(items_rules) = &repeated_rules.items {
+    if items_rules.ignore() == Ignore::Always {
+      plan.items.nested = false;
+    } else {
+      let dynamic_items = dynamic_rules
+        .and_then(|rules| rules_field(rules, "repeated"))
+        .and_then(|rules| rules_field(&rules, "items"));
+
+      if !item_is_message {
+        plan.items.checks = type_checks(items_rules.r#type.as_ref(), field_type, &kind)?;
+      }
+
+      plan.items.cel = CelRules::new(
+        "field",
+        field.full_name(),
+        &items_rules.cel,
+        dynamic_items.as_ref().and_then(type_rules).as_ref(),
+      )?;
+    }
+  }
+
+  Ok(plan)
+}
+
+fn map_plan(
+  field: &FieldDescriptor,
+  field_rules: &FieldRules,
+  dynamic_rules: Option<&DynamicMessage>,
+) -> Result<MapPlan, String> {
+  let Kind::Message(entry_desc) = field.kind() else {
+    return Err("map field has no associated map entry message descriptor".to_string());
+  };
+
+  let key_desc = entry_desc.map_entry_key_field();
+  let value_desc = entry_desc.map_entry_value_field();
+  let value_is_message = is_validated_message(&value_desc.kind());
+
+  let mut plan = MapPlan {
+    cel: CelRules::new(
+      "field",
+      field.full_name(),
+      &field_rules.cel,
+      dynamic_rules.and_then(type_rules).as_ref(),
+    )?,
+    lengths: Lengths::new(
+      None,
+      ("min_pairs", None, maps::min_pairs),
+      ("max_pairs", None, maps::max_pairs),
+      "key-value pair",
+    )?,
+    key_type: get_field_type(&key_desc),
+    value_type: get_field_type(&value_desc),
+    key_proto_type: proto_type(&key_desc.kind()),
+    value_proto_type: proto_type(&value_desc.kind()),
+    keys: ValuePlan::default(),
+    values: ValuePlan {
+      nested: value_is_message,
+      ..Default::default()
+    },
+    values_wrapper: is_wrapper(&value_desc.kind()),
+  };
+
+  let Some(RulesType::Map(map_rules)) = &field_rules.r#type else {
+    return Ok(plan);
+  };
+
+  plan.lengths = Lengths::new(
+    None,
+    ("min_pairs", map_rules.min_pairs, maps::min_pairs),
+    ("max_pairs", map_rules.max_pairs, maps::max_pairs),
+    "key-value pair",
+  )?;
+
+  let dynamic_map = dynamic_rules.and_then(|rules| rules_field(rules, "map"));
+
+  if let Some(keys_rules) = &map_rules.keys
+    && keys_rules.ignore() != Ignore::Always
+  {
+    let dynamic_keys = dynamic_map
+      .as_ref()
+      .and_then(|rules| rules_field(rules, "keys"));
+
+    plan.keys.checks = type_checks(keys_rules.r#type.as_ref(), plan.key_type, &key_desc.kind())?;
+    plan.keys.cel = CelRules::new(
+      "field",
+      key_desc.full_name(),
+      &keys_rules.cel,
+      dynamic_keys.as_ref().and_then(type_rules).as_ref(),
+    )?;
+  }
+
+  if let Some(values_rules) = &map_rules.values {
+    if values_rules.ignore() == Ignore::Always {
+      plan.values.nested = false;
+    } else {
+      let dynamic_values = dynamic_map
+        .as_ref()
+        .and_then(|rules| rules_field(rules, "values"));
+
+      if !value_is_message {
+        plan.values.checks = type_checks(
+          values_rules.r#type.as_ref(),
+          plan.value_type,
+          &value_desc.kind(),
+        )?;
+      }
+
+      plan.values.cel = CelRules::new(
+        "field",
+        value_desc.full_name(),
+        &values_rules.cel,
+        dynamic_values.as_ref().and_then(type_rules).as_ref(),
+      )?;
+    }
+  }
+
+  Ok(plan)
+}
+
+fn type_checks(
+  rules: Option<&RulesType>,
+  field_type: FieldType,
+  kind: &Kind,
+) -> Result<Option<TypeChecks>, String> {
+  rules.map_or(Ok(None), |rules| TypeChecks::new(rules, field_type, kind))
+}
+
+// The rules of a descriptor, which are None if the option is not set (or if the pool does not have the rules of protovalidate)
+fn extension_rules(
+  options: &DynamicMessage,
+  extension: Option<&ExtensionDescriptor>,
+) -> Option<DynamicMessage> {
+  let extension = extension?;
+
+  options
+    .has_extension(extension)
+    .then(|| options.get_extension(extension).as_message().cloned())
+    .flatten()
+}
+
+fn rules_field(rules: &DynamicMessage, name: &str) -> Option<DynamicMessage> {
+  rules
+    .has_field_by_name(name)
+    .then(|| rules.get_field_by_name(name)?.as_message().cloned())
+    .flatten()
+}
+
+// The rules of the type of the field (such as StringRules), which are available as `rules` in the Cel expressions
+fn type_rules(rules: &DynamicMessage) -> Option<Value> {
+  let oneof = rules
+    .descriptor()
+    .oneofs()
+    .find(|oneof| oneof.name() == "type")?;
+
+  oneof
+    .fields()
+    .find(|field| rules.has_field(field))
+    .map(|field| rules.get_field(&field).into_owned())
+}
+
+fn transcode<T: prost_reflect::prost::Message + Default>(
+  rules: &DynamicMessage,
+) -> Result<T, String> {
+  rules
+    .transcode_to()
+    .map_err(|e| format!("Could not decode the rules: {e}"))
+}
+
+// Like protocheck-build, the older names of the same modes are accepted too
+fn ignore_mode(rules: &DynamicMessage) -> IgnoreMode {
+  let Some(ignore_field) = rules.descriptor().get_field_by_name("ignore") else {
+    return IgnoreMode::Unspecified;
+  };
+
+  let name = rules
+    .get_field(&ignore_field)
+    .as_enum_number()
+    .and_then(|number| ignore_field.kind().as_enum()?.get_value(number));
+
+  match name.as_ref().map(|value| value.name()) {
+    Some("IGNORE_ALWAYS") => IgnoreMode::Always,
+    Some("IGNORE_IF_UNPOPULATED" | "IGNORE_EMPTY" | "IGNORE_IF_ZERO_VALUE") => {
+      IgnoreMode::IfZeroValue
+    }
+    Some("IGNORE_IF_DEFAULT_VALUE" | "IGNORE_DEFAULT") => IgnoreMode::IfDefaultValue,
+    _ => IgnoreMode::Unspecified,
+  }
+}
+
+// The messages that are validated, which excludes the well known types
+fn is_validated_message(kind: &Kind) -> bool {
+  matches!(kind, Kind::Message(message_desc) if !message_desc.full_name().starts_with("google."))
+}
+
+// The wrappers of the well known types, which are validated with the rules of the wrapped type
+fn wrapped_type(message_desc: &MessageDescriptor) -> Option<FieldType> {
+  match message_desc.full_name() {
+    "google.protobuf.BoolValue" => Some(FieldType::Bool),
+    "google.protobuf.StringValue" => Some(FieldType::String),
+    "google.protobuf.BytesValue" => Some(FieldType::Bytes),
+    "google.protobuf.Int32Value" => Some(FieldType::Int32),
+    "google.protobuf.Int64Value" => Some(FieldType::Int64),
+    "google.protobuf.UInt32Value" => Some(FieldType::Uint32),
+    "google.protobuf.UInt64Value" => Some(FieldType::Uint64),
+    "google.protobuf.FloatValue" => Some(FieldType::Float),
+    "google.protobuf.DoubleValue" => Some(FieldType::Double),
+    _ => None,
+  }
+}
+
+fn is_wrapper(kind: &Kind) -> bool {
+  matches!(kind, Kind::Message(message_desc) if wrapped_type(message_desc).is_some())
+}
+
+fn wrapped_value(value: &Value) -> Cow<'_, Value> {
+  value
+    .as_message()
+    .and_then(|message| message.get_field_by_number(1))
+    .unwrap_or(Cow::Borrowed(value))
+}
+
+fn get_field_type(field: &FieldDescriptor) -> FieldType {
+  match field.kind() {
+    Kind::Message(message_desc) => match message_desc.full_name() {
+      "google.protobuf.Duration" => FieldType::Duration,
+      "google.protobuf.Timestamp" => FieldType::Timestamp,
+      "google.protobuf.Any" => FieldType::Any,
+      _ => wrapped_type(&message_desc).unwrap_or(FieldType::Message),
+    },
+    kind => proto_type(&kind).into(),
+  }
+}
+
+fn proto_type(kind: &Kind) -> ProtoType {
+  match kind {
+    Kind::Double => ProtoType::Double,
+    Kind::Float => ProtoType::Float,
+    Kind::Int32 => ProtoType::Int32,
+    Kind::Int64 => ProtoType::Int64,
+    Kind::Uint32 => ProtoType::Uint32,
+    Kind::Uint64 => ProtoType::Uint64,
+    Kind::Sint32 => ProtoType::Sint32,
+    Kind::Sint64 => ProtoType::Sint64,
+    Kind::Fixed32 => ProtoType::Fixed32,
+    Kind::Fixed64 => ProtoType::Fixed64,
+    Kind::Sfixed32 => ProtoType::Sfixed32,
+    Kind::Sfixed64 => ProtoType::Sfixed64,
+    Kind::Bool => ProtoType::Bool,
+    Kind::String => ProtoType::String,
+    Kind::Bytes => ProtoType::Bytes,
+    Kind::Message(_) => ProtoType::Message,
+    Kind::Enum(_) => ProtoType::Enum,
+  }
+}
+
+fn map_key_subscript(key: &MapKey) -> SubscriptRef<'_> {
+  match key {
+    MapKey::Bool(key) => SubscriptRef::BoolKey(*key),
+    MapKey::I32(key) => SubscriptRef::IntKey(i64::from(*key)),
+    MapKey::I64(key) => SubscriptRef::IntKey(*key),
+    MapKey::U32(key) => SubscriptRef::UintKey(u64::from(*key)),
+    MapKey::U64(key) => SubscriptRef::UintKey(*key),
+    MapKey::String(key) => SubscriptRef::StringKey(key),
+  }
+}
+
+fn map_key_value(key: &MapKey) -> Value {
+  match key {
+    MapKey::Bool(key) => Value::Bool(*key),
+    MapKey::I32(key) => Value::I32(*key),
+    MapKey::I64(key) => Value::I64(*key),
+    MapKey::U32(key) => Value::U32(*key),
+    MapKey::U64(key) => Value::U64(*key),
+    MapKey::String(key) => Value::String(key.clone()),
+  }
+}
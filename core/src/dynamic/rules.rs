@@ -0,0 +1,1438 @@
+use std::borrow::Cow;
+
+#[cfg(feature = "ordered-float")]
+use ordered_float::OrderedFloat;
+use prost_reflect::{EnumDescriptor, Kind, Value};
+use proto_types::{
+  protovalidate::{
+    bytes_rules, double_rules, duration_rules, field_rules::Type as RulesType, fixed32_rules,
+    fixed64_rules, float_rules, int32_rules, int64_rules, s_fixed32_rules, s_fixed64_rules,
+    s_int32_rules, s_int64_rules, string_rules, timestamp_rules, u_int32_rules, u_int64_rules,
+    AnyRules, BytesRules, DurationRules, EnumRules, StringRules, TimestampRules, Violation,
+  },
+  Any, Duration, FieldType, Timestamp,
+};
+
+use crate::{
+  field_data::FieldContext,
+  validators::{
+    bytes, comparables,
+    comparables::Comparable,
+    constants,
+    constants::ConstRule,
+    containing,
+    containing::{HashLookup, HashSet},
+    enums, floats,
+    repeated::{self, UniqueItem, UniqueLookup, INLINE_UNIQUE_ITEMS},
+    rule_messages::{self, Bound},
+    string, timestamps,
+  },
+  wrappers::*,
+};
+
+const LIST_PREFIXES: [&str; 2] = [
+  rule_messages::IN_LIST_PREFIX,
+  rule_messages::NOT_IN_LIST_PREFIX,
+];
+
+pub(super) fn push(violations: &mut Vec<Violation>, result: Result<(), Violation>) {
+  if let Err(violation) = result {
+    violations.push(violation);
+  }
+}
+
+/// The rules of a value for its type (the `type` oneof of the `FieldRules`), read once from the descriptor.
+pub(super) enum TypeChecks {
+  Float(NumericChecks<f32>),
+  Double(NumericChecks<f64>),
+  Int32(NumericChecks<i32>),
+  Int64(NumericChecks<i64>),
+  Uint32(NumericChecks<u32>),
+  Uint64(NumericChecks<u64>),
+  Sint32(NumericChecks<Sint32>),
+  Sint64(NumericChecks<Sint64>),
+  Fixed32(NumericChecks<Fixed32>),
+  Fixed64(NumericChecks<Fixed64>),
+  Sfixed32(NumericChecks<Sfixed32>),
+  Sfixed64(NumericChecks<Sfixed64>),
+  Bool(Option<(bool, String)>),
+  String(Box<StringChecks>),
+  Bytes(Box<BytesChecks>),
+  Enum(EnumChecks),
+  Duration(NumericChecks<Duration>),
+  Timestamp(TimestampChecks),
+  Any(AnyChecks),
+}
+
+macro_rules! numeric_checks {
+  ($rules:expr, $module:ident, $finite:expr) => {{
+    let rules = $rules;
+
+    NumericChecks::new(
+      rules.r#const,
+      rules.less_than.map(|less_than| match less_than {
+        $module::LessThan::Lt(value) => (value, false),
+        $module::LessThan::Lte(value) => (value, true),
+      }),
+      rules.greater_than.map(|greater_than| match greater_than {
+        $module::GreaterThan::Gt(value) => (value, false),
+        $module::GreaterThan::Gte(value) => (value, true),
+      }),
+      &rules.r#in,
+      &rules.not_in,
+      $finite,
+    )
+  }};
+}
+
+impl TypeChecks {
+  /// Returns None for the rules of the lists and of the maps, which are ignored on the other fields like in the generated validators.
+  pub(super) fn new(
+    rules: &RulesType,
+    field_type: FieldType,
+    kind: &Kind,
+  ) -> Result<Option<Self>, String> {
+    let matching_type = match rules {
+      RulesType::Float(_) => FieldType::Float,
+      RulesType::Double(_) => FieldType::Double,
+      RulesType::Int32(_) => FieldType::Int32,
+      RulesType::Int64(_) => FieldType::Int64,
+      RulesType::Uint32(_) => FieldType::Uint32,
+      RulesType::Uint64(_) => FieldType::Uint64,
+      RulesType::Sint32(_) => FieldType::Sint32,
+      RulesType::Sint64(_) => FieldType::Sint64,
+      RulesType::Fixed32(_) => FieldType::Fixed32,
+      RulesType::Fixed64(_) => FieldType::Fixed64,
+      RulesType::Sfixed32(_) => FieldType::Sfixed32,
+      RulesType::Sfixed64(_) => FieldType::Sfixed64,
+      RulesType::Bool(_) => FieldType::Bool,
+      RulesType::String(_) => FieldType::String,
+      RulesType::Bytes(_) => FieldType::Bytes,
+      RulesType::Enum(_) => FieldType::Enum,
+      RulesType::Any(_) => FieldType::Any,
+      RulesType::Duration(_) => FieldType::Duration,
+      RulesType::Timestamp(_) => FieldType::Timestamp,
+      RulesType::Repeated(_) | RulesType::Map(_) => return Ok(None),
+    };
+
+    if matching_type != field_type {
+      return Err(format!(
+        "wrong rule type. Expected {matching_type:?}, found {field_type:?}"
+      ));
+    }
+
+    let checks = match rules {
+      RulesType::Float(rules) => Self::Float(numeric_checks!(rules, float_rules, rules.finite())?),
+      RulesType::Double(rules) => {
+        Self::Double(numeric_checks!(rules, double_rules, rules.finite())?)
+      }
+      RulesType::Int32(rules) => Self::Int32(numeric_checks!(rules, int32_rules, false)?),
+      RulesType::Int64(rules) => Self::Int64(numeric_checks!(rules, int64_rules, false)?),
+      RulesType::Uint32(rules) => Self::Uint32(numeric_checks!(rules, u_int32_rules, false)?),
+      RulesType::Uint64(rules) => Self::Uint64(numeric_checks!(rules, u_int64_rules, false)?),
+      RulesType::Sint32(rules) => Self::Sint32(numeric_checks!(rules, s_int32_rules, false)?),
+      RulesType::Sint64(rules) => Self::Sint64(numeric_checks!(rules, s_int64_rules, false)?),
+      RulesType::Fixed32(rules) => Self::Fixed32(numeric_checks!(rules, fixed32_rules, false)?),
+      RulesType::Fixed64(rules) => Self::Fixed64(numeric_checks!(rules, fixed64_rules, false)?),
+      RulesType::Sfixed32(rules) => Self::Sfixed32(numeric_checks!(rules, s_fixed32_rules, false)?),
+      RulesType::Sfixed64(rules) => Self::Sfixed64(numeric_checks!(rules, s_fixed64_rules, false)?),
+      RulesType::Bool(rules) => Self::Bool(
+        rules
+          .r#const
+          .map(|value| (value, rule_messages::const_message(value))),
+      ),
+      RulesType::String(rules) => Self::String(Box::new(StringChecks::new(rules)?)),
+      RulesType::Bytes(rules) => Self::Bytes(Box::new(BytesChecks::new(rules)?)),
+      RulesType::Enum(rules) => {
+        let Kind::Enum(enum_desc) = kind else {
+          return Err(format!(
+            "wrong rule type. Expected {matching_type:?}, found {field_type:?}"
+          ));
+        };
+
+        Self::Enum(EnumChecks::new(rules, enum_desc)?)
+      }
+      RulesType::Duration(rules) => Self::Duration(duration_checks(rules)?),
+      RulesType::Timestamp(rules) => Self::Timestamp(TimestampChecks::new(rules)?),
+      RulesType::Any(rules) => Self::Any(AnyChecks::new(rules)?),
+      RulesType::Repeated(_) | RulesType::Map(_) => return Ok(None),
+    };
+
+    Ok(Some(checks))
+  }
+
+  /// Checks the value, after the wrappers of the well known types have been unwrapped. The values of another type are ignored, as they cannot come from a message of the pool.
+  pub(super) fn check(
+    &self,
+    field_context: &FieldContext,
+    value: &Value,
+    violations: &mut Vec<Violation>,
+  ) {
+    match self {
+      Self::Float(checks) => checks.check(field_context, value, violations),
+      Self::Double(checks) => checks.check(field_context, value, violations),
+      Self::Int32(checks) => checks.check(field_context, value, violations),
+      Self::Int64(checks) => checks.check(field_context, value, violations),
+      Self::Uint32(checks) => checks.check(field_context, value, violations),
+      Self::Uint64(checks) => checks.check(field_context, value, violations),
+      Self::Sint32(checks) => checks.check(field_context, value, violations),
+      Self::Sint64(checks) => checks.check(field_context, value, violations),
+      Self::Fixed32(checks) => checks.check(field_context, value, violations),
+      Self::Fixed64(checks) => checks.check(field_context, value, violations),
+      Self::Sfixed32(checks) => checks.check(field_context, value, violations),
+      Self::Sfixed64(checks) => checks.check(field_context, value, violations),
+      Self::Bool(constant) => {
+        if let (Some((target, message)), Some(value)) = (constant, value.as_bool()) {
+          push(
+            violations,
+            constants::constant(field_context, value, *target, message),
+          );
+        }
+      }
+      Self::String(checks) => {
+        if let Some(value) = value.as_str() {
+          checks.check(field_context, value, violations);
+        }
+      }
+      Self::Bytes(checks) => {
+        if let Some(value) = value.as_bytes() {
+          checks.check(field_context, value, violations);
+        }
+      }
+      Self::Enum(checks) => {
+        if let Some(value) = value.as_enum_number() {
+          checks.check(field_context, value, violations);
+        }
+      }
+      Self::Duration(checks) => checks.check(field_context, value, violations),
+      Self::Timestamp(checks) => {
+        if let Some(value) = timestamp_value(value) {
+          checks.check(field_context, value, violations);
+        }
+      }
+      Self::Any(checks) => {
+        if let Some(value) = any_value(value) {
+          checks.check(field_context, &value, violations);
+        }
+      }
+    }
+  }
+}
+
+// The seconds and the nanos of a Duration or of a Timestamp
+fn seconds_and_nanos(value: &Value) -> Option<(i64, i32)> {
+  let message = value.as_message()?;
+  let seconds = message.get_field_by_number(1)?.as_i64()?;
+  let nanos = message.get_field_by_number(2)?.as_i32()?;
+
+  Some((seconds, nanos))
+}
+
+// The durations are normalized before being compared, like in the generated validators
+pub(super) fn duration_value(value: &Value) -> Option<Duration> {
+  let (seconds, nanos) = seconds_and_nanos(value)?;
+
+  Some(Duration { seconds, nanos }.normalized())
+}
+
+pub(super) fn timestamp_value(value: &Value) -> Option<Timestamp> {
+  let (seconds, nanos) = seconds_and_nanos(value)?;
+
+  Some(Timestamp { seconds, nanos })
+}
+
+// Only the type URL is used by the rules
+fn any_value(value: &Value) -> Option<Any> {
+  let message = value.as_message()?;
+  let type_url = message.get_field_by_number(1)?.as_str()?.to_string();
+
+  Some(Any {
+    type_url,
+    ..Default::default()
+  })
+}
+
+/// The `lt`, `lte`, `gt` and `gte` rules, which become a single range when both bounds are set.
+pub(super) enum Comparison<R> {
+  Lt(R, String),
+  Lte(R, String),
+  Gt(R, String),
+  Gte(R, String),
+  Range {
+    lower: R,
+    lower_inclusive: bool,
+    upper: R,
+    upper_inclusive: bool,
+    message: String,
+  },
+}
+
+impl<R: Bound> Comparison<R> {
+  /// The bounds come with a flag that is true for the inclusive ones.
+  pub(super) fn new(
+    less_than: Option<(R, bool)>,
+    greater_than: Option<(R, bool)>,
+  ) -> Result<Option<Self>, String> {
+    rule_messages::check_bounds(less_than, greater_than)?;
+
+    let comparison = match (less_than, greater_than) {
+      (Some((upper, upper_inclusive)), Some((lower, lower_inclusive))) => Self::Range {
+        lower,
+        lower_inclusive,
+        upper,
+        upper_inclusive,
+        message: rule_messages::range_message(lower, lower_inclusive, upper, upper_inclusive),
+      },
+      (Some((upper, true)), None) => {
+        Self::Lte(upper, rule_messages::less_than_message(upper, true))
+      }
+      (Some((upper, false)), None) => {
+        Self::Lt(upper, rule_messages::less_than_message(upper, false))
+      }
+      (None, Some((lower, true))) => {
+        Self::Gte(lower, rule_messages::greater_than_message(lower, true))
+      }
+      (None, Some((lower, false))) => {
+        Self::Gt(lower, rule_messages::greater_than_message(lower, false))
+      }
+      (None, None) => return Ok(None),
+    };
+
+    Ok(Some(comparison))
+  }
+
+  pub(super) fn check<T>(&self, field_context: &FieldContext, value: T) -> Result<(), Violation>
+  where
+    T: Comparable + PartialOrd<R>,
+  {
+    match self {
+      Self::Lt(target, message) => comparables::lt(field_context, value, *target, message),
+      Self::Lte(target, message) => comparables::lte(field_context, value, *target, message),
+      Self::Gt(target, message) => comparables::gt(field_context, value, *target, message),
+      Self::Gte(target, message) => comparables::gte(field_context, value, *target, message),
+      Self::Range {
+        lower,
+        lower_inclusive,
+        upper,
+        upper_inclusive,
+        message,
+      } => comparables::range(
+        field_context,
+        value,
+        *lower,
+        *lower_inclusive,
+        *upper,
+        *upper_inclusive,
+        message,
+      ),
+    }
+  }
+}
+
+/// The items of an `in` or `not_in` rule, which are hashed when the list is long, like the static lists of the generated validators.
+pub(super) struct ListRule<T> {
+  items: Vec<T>,
+  set: Option<HashSet<T>>,
+  message: String,
+}
+
+impl<T> ListRule<T> {
+  fn scanned(items: Vec<T>, message: String) -> Self {
+    Self {
+      items,
+      set: None,
+      message,
+    }
+  }
+
+  fn lookup(&self) -> HashLookup<'_, T> {
+    match &self.set {
+      Some(set) => HashLookup::Set(set),
+      None => HashLookup::Slice(&self.items),
+    }
+  }
+}
+
+impl<T: Clone + Eq + std::hash::Hash> ListRule<T> {
+  fn hashed(items: Vec<T>, message: String) -> Self {
+    let set = (items.len() >= 16).then(|| items.iter().cloned().collect());
+
+    Self {
+      items,
+      set,
+      message,
+    }
+  }
+}
+
+// The lists of strings and of bytes own their items, which are borrowed by the lookups of the validators
+impl<T> ListRule<T> {
+  fn borrowed<B: ?Sized>(&self) -> Vec<&B>
+  where
+    T: AsRef<B>,
+  {
+    self.items.iter().map(AsRef::as_ref).collect()
+  }
+}
+
+// Checks that no item is both allowed and forbidden, and creates the messages of the lists that are not empty
+fn list_messages<T: PartialEq>(
+  in_list: &[T],
+  not_in_list: &[T],
+  display: impl Fn(&T) -> String,
+  prefixes: [&str; 2],
+) -> Result<[Option<String>; 2], String> {
+  rule_messages::check_lists(in_list, not_in_list, &display)?;
+
+  let message = |prefix: &str, list: &[T]| {
+    (!list.is_empty()).then(|| rule_messages::list_message(prefix, list, &display))
+  };
+
+  Ok([
+    message(prefixes[0], in_list),
+    message(prefixes[1], not_in_list),
+  ])
+}
+
+/// The numeric types (and the durations), which share the const, comparison and list rules.
+pub(super) trait Numeric:
+  Copy + Comparable + ConstRule + PartialEq<Self::Rule> + PartialOrd<Self::Rule>
+{
+  /// The type of the values in the rules.
+  type Rule: Bound;
+  /// The type of the items in the lookups of the lists.
+  type Item;
+
+  fn from_value(value: &Value) -> Option<Self>;
+
+  fn list(items: &[Self::Rule], message: String) -> ListRule<Self::Item>;
+
+  fn in_list(
+    field_context: &FieldContext,
+    value: Self,
+    list: &ListRule<Self::Item>,
+  ) -> Result<(), Violation>;
+
+  fn not_in_list(
+    field_context: &FieldContext,
+    value: Self,
+    list: &ListRule<Self::Item>,
+  ) -> Result<(), Violation>;
+
+  fn display(item: &Self::Rule) -> String;
+
+  fn finite(_field_context: &FieldContext, _value: Self) -> Result<(), Violation> {
+    Ok(())
+  }
+}
+
+macro_rules! impl_numeric {
+  ($typ:ty, $rule:ty, |$value:ident| $from_value:expr) => {
+    impl Numeric for $typ {
+      type Rule = $rule;
+      type Item = $rule;
+
+      fn from_value($value: &Value) -> Option<Self> {
+        $from_value
+      }
+
+      fn list(items: &[$rule], message: String) -> ListRule<$rule> {
+        ListRule::hashed(items.to_vec(), message)
+      }
+
+      fn in_list(
+        field_context: &FieldContext,
+        value: Self,
+        list: &ListRule<$rule>,
+      ) -> Result<(), Violation> {
+        containing::in_list(field_context, value, &list.lookup(), &list.message)
+      }
+
+      fn not_in_list(
+        field_context: &FieldContext,
+        value: Self,
+        list: &ListRule<$rule>,
+      ) -> Result<(), Violation> {
+        containing::not_in_list(field_context, value, &list.lookup(), &list.message)
+      }
+
+      fn display(item: &$rule) -> String {
+        item.to_string()
+      }
+    }
+  };
+}
+
+impl_numeric!(i32, i32, |value| value.as_i32());
+impl_numeric!(i64, i64, |value| value.as_i64());
+impl_numeric!(u32, u32, |value| value.as_u32());
+impl_numeric!(u64, u64, |value| value.as_u64());
+impl_numeric!(Sint32, i32, |value| value.as_i32().map(Sint32));
+impl_numeric!(Sint64, i64, |value| value.as_i64().map(Sint64));
+impl_numeric!(Fixed32, u32, |value| value.as_u32().map(Fixed32));
+impl_numeric!(Fixed64, u64, |value| value.as_u64().map(Fixed64));
+impl_numeric!(Sfixed32, i32, |value| value.as_i32().map(Sfixed32));
+impl_numeric!(Sfixed64, i64, |value| value.as_i64().map(Sfixed64));
+impl_numeric!(Duration, Duration, |value| duration_value(value));
+
+macro_rules! impl_float {
+  ($typ:ident, |$value:ident| $from_value:expr, $finite:path, |$item:ident| $display:expr) => {
+    impl Numeric for $typ {
+      type Rule = $typ;
+      #[cfg(feature = "ordered-float")]
+      type Item = OrderedFloat<$typ>;
+      #[cfg(not(feature = "ordered-float"))]
+      type Item = $typ;
+
+      fn from_value($value: &Value) -> Option<Self> {
+        $from_value
+      }
+
+      #[cfg(feature = "ordered-float")]
+      fn list(items: &[$typ], message: String) -> ListRule<Self::Item> {
+        ListRule::hashed(items.iter().copied().map(OrderedFloat).collect(), message)
+      }
+
+      // The floats can only be hashed with ordered-float
+      #[cfg(not(feature = "ordered-float"))]
+      fn list(items: &[$typ], message: String) -> ListRule<Self::Item> {
+        ListRule::scanned(items.to_vec(), message)
+      }
+
+      fn in_list(
+        field_context: &FieldContext,
+        value: Self,
+        list: &ListRule<Self::Item>,
+      ) -> Result<(), Violation> {
+        containing::in_list(field_context, value, &list.lookup(), &list.message)
+      }
+
+      fn not_in_list(
+        field_context: &FieldContext,
+        value: Self,
+        list: &ListRule<Self::Item>,
+      ) -> Result<(), Violation> {
+        containing::not_in_list(field_context, value, &list.lookup(), &list.message)
+      }
+
+      fn display($item: &$typ) -> String {
+        $display
+      }
+
+      fn finite(field_context: &FieldContext, value: Self) -> Result<(), Violation> {
+        $finite(field_context, value)
+      }
+    }
+  };
+}
+
+// The floats are shown as f64 in the lists, like in the generated validators
+impl_float!(
+  f32,
+  |value| value.as_f32(),
+  floats::float_is_finite,
+  |item| f64::from(*item).to_string()
+);
+impl_float!(
+  f64,
+  |value| value.as_f64(),
+  floats::double_is_finite,
+  |item| item.to_string()
+);
+
+pub(super) struct NumericChecks<T: Numeric> {
+  constant: Option<(T::Rule, String)>,
+  comparison: Option<Comparison<T::Rule>>,
+  in_list: Option<ListRule<T::Item>>,
+  not_in_list: Option<ListRule<T::Item>>,
+  finite: bool,
+}
+
+impl<T: Numeric> NumericChecks<T> {
+  fn new(
+    constant: Option<T::Rule>,
+    less_than: Option<(T::Rule, bool)>,
+    greater_than: Option<(T::Rule, bool)>,
+    in_list: &[T::Rule],
+    not_in_list: &[T::Rule],
+    finite: bool,
+  ) -> Result<Self, String> {
+    let constant = constant.map(|value| {
+      let message = rule_messages::const_message(value.representation());
+
+      (value, message)
+    });
+
+    // The other rules are ignored when const is set
+    if constant.is_some() {
+      return Ok(Self {
+        constant,
+        comparison: None,
+        in_list: None,
+        not_in_list: None,
+        finite: false,
+      });
+    }
+
+    let comparison = Comparison::new(less_than, greater_than)?;
+
+    let [in_message, not_in_message] =
+      list_messages(in_list, not_in_list, T::display, LIST_PREFIXES)?;
+
+    Ok(Self {
+      constant,
+      comparison,
+      in_list: in_message.map(|message| T::list(in_list, message)),
+      not_in_list: not_in_message.map(|message| T::list(not_in_list, message)),
+      finite,
+    })
+  }
+
+  fn check(&self, field_context: &FieldContext, value: &Value, violations: &mut Vec<Violation>) {
+    let Some(value) = T::from_value(value) else {
+      return;
+    };
+
+    if let Some((target, message)) = &self.constant {
+      push(
+        violations,
+        constants::constant(field_context, value, *target, message),
+      );
+
+      return;
+    }
+
+    if let Some(comparison) = &self.comparison {
+      push(violations, comparison.check(field_context, value));
+    }
+
+    if let Some(list) = &self.in_list {
+      push(violations, T::in_list(field_context, value, list));
+    }
+
+    if let Some(list) = &self.not_in_list {
+      push(violations, T::not_in_list(field_context, value, list));
+    }
+
+    if self.finite {
+      push(violations, T::finite(field_context, value));
+    }
+  }
+}
+
+// The values of the rules are normalized, like the values of the fields
+fn duration_checks(rules: &DurationRules) -> Result<NumericChecks<Duration>, String> {
+  let normalized =
+    |values: &[Duration]| -> Vec<Duration> { values.iter().map(Duration::normalized).collect() };
+
+  NumericChecks::new(
+    rules.r#const.map(|value| value.normalized()),
+    rules.less_than.map(|less_than| match less_than {
+      duration_rules::LessThan::Lt(value) => (value.normalized(), false),
+      duration_rules::LessThan::Lte(value) => (value.normalized(), true),
+    }),
+    rules.greater_than.map(|greater_than| match greater_than {
+      duration_rules::GreaterThan::Gt(value) => (value.normalized(), false),
+      duration_rules::GreaterThan::Gte(value) => (value.normalized(), true),
+    }),
+    &normalized(&rules.r#in),
+    &normalized(&rules.not_in),
+    false,
+  )
+}
+
+/// A validator of the length rules.
+pub(super) type LengthValidator<V> = fn(&FieldContext, &V, u64, &str) -> Result<(), Violation>;
+
+/// A length rule, with its name (for the errors), its value and its validator.
+pub(super) type LengthRule<V> = (&'static str, Option<u64>, LengthValidator<V>);
+
+/// The exact, min and max length rules of a value.
+pub(super) struct Lengths<V: ?Sized> {
+  checks: Vec<(LengthValidator<V>, u64, String)>,
+}
+
+impl<V: ?Sized> Lengths<V> {
+  /// Some kinds of values (such as the lists) have no rule for the exact length.
+  pub(super) fn new(
+    len: Option<LengthRule<V>>,
+    min: LengthRule<V>,
+    max: LengthRule<V>,
+    unit: &str,
+  ) -> Result<Self, String> {
+    let (min_name, min_value, min_validator) = min;
+    let (max_name, max_value, max_validator) = max;
+
+    rule_messages::check_lengths(
+      len
+        .as_ref()
+        .and_then(|(len_name, len_value, _)| len_value.map(|value| (*len_name, value))),
+      (min_name, min_value),
+      (max_name, max_value),
+    )?;
+
+    let mut checks = Vec::new();
+
+    if let Some((_, Some(len_value), len_validator)) = len {
+      checks.push((
+        len_validator,
+        len_value,
+        rule_messages::len_message(len_value, unit),
+      ));
+    }
+
+    if let Some(min_value) = min_value {
+      checks.push((
+        min_validator,
+        min_value,
+        rule_messages::min_len_message(min_value, unit),
+      ));
+    }
+
+    if let Some(max_value) = max_value {
+      checks.push((
+        max_validator,
+        max_value,
+        rule_messages::max_len_message(max_value, unit),
+      ));
+    }
+
+    Ok(Self { checks })
+  }
+
+  pub(super) fn check(
+    &self,
+    field_context: &FieldContext,
+    value: &V,
+    violations: &mut Vec<Violation>,
+  ) {
+    for (validator, target, message) in &self.checks {
+      push(
+        violations,
+        validator(field_context, value, *target, message),
+      );
+    }
+  }
+}
+
+type SubstringValidator<V> = fn(&FieldContext, &V, &V, &str) -> Result<(), Violation>;
+
+enum WellKnownString {
+  Format(fn(&FieldContext, &str) -> Result<(), Violation>),
+  Header(fn(&FieldContext, &str, bool) -> Result<(), Violation>, bool),
+}
+
+pub(super) struct StringChecks {
+  constant: Option<(String, String)>,
+  in_list: Option<ListRule<String>>,
+  not_in_list: Option<ListRule<String>>,
+  chars: Lengths<str>,
+  bytes: Lengths<str>,
+  substrings: Vec<(SubstringValidator<str>, String, String)>,
+  pattern: Option<(regex::Regex, String)>,
+  well_known: Option<WellKnownString>,
+}
+
+impl StringChecks {
+  fn new(rules: &StringRules) -> Result<Self, String> {
+    if let Some(value) = &rules.r#const {
+      return Ok(Self {
+        constant: Some((value.clone(), rule_messages::const_message(value))),
+        in_list: None,
+        not_in_list: None,
+        chars: Lengths { checks: Vec::new() },
+        bytes: Lengths { checks: Vec::new() },
+        substrings: Vec::new(),
+        pattern: None,
+        well_known: None,
+      });
+    }
+
+    let [in_message, not_in_message] =
+      list_messages(&rules.r#in, &rules.not_in, String::clone, LIST_PREFIXES)?;
+
+    let chars = Lengths::new(
+      Some(("len", rules.len, string::len)),
+      ("min_len", rules.min_len, string::min_len),
+      ("max_len", rules.max_len, string::max_len),
+      "character",
+    )?;
+
+    let bytes = Lengths::new(
+      Some(("len_bytes", rules.len_bytes, string::len_bytes)),
+      ("min_bytes", rules.min_bytes, string::min_bytes),
+      ("max_bytes", rules.max_bytes, string::max_bytes),
+      "character",
+    )?;
+
+    let substring_rules: [(&Option<String>, SubstringValidator<str>, &str, &str); 4] = [
+      (
+        &rules.contains,
+        string::contains,
+        "must contain the substring '",
+        "'",
+      ),
+      (
+        &rules.not_contains,
+        string::not_contains,
+        "must not contain the substring '",
+        "'",
+      ),
+      (&rules.prefix, string::prefix, "must start with '", "'"),
+      (&rules.suffix, string::suffix, "must end with '", "'"),
+    ];
+
+    let substrings = substring_rules
+      .into_iter()
+      .filter_map(|(target, validator, before, after)| {
+        target.as_ref().map(|target| {
+          (
+            validator,
+            target.clone(),
+            format!("{before}{target}{after}"),
+          )
+        })
+      })
+      .collect();
+
+    let pattern = rules
+      .pattern
+      .as_ref()
+      .map(|pattern| {
+        regex::Regex::new(pattern)
+          .map(|regex| {
+            (
+              regex,
+              format!("must match the following regex: `{pattern}`"),
+            )
+          })
+          .map_err(|e| format!("invalid regex pattern: {e}"))
+      })
+      .transpose()?;
+
+    let well_known = rules.well_known.and_then(|well_known| {
+      use string_rules::WellKnown;
+      use WellKnownString::Format;
+
+      match well_known {
+        WellKnown::Email(enabled) => enabled.then_some(Format(string::email)),
+        WellKnown::Hostname(enabled) => enabled.then_some(Format(string::hostname)),
+        WellKnown::Ip(enabled) => enabled.then_some(Format(string::ip)),
+        WellKnown::Ipv4(enabled) => enabled.then_some(Format(string::ipv4)),
+        WellKnown::Ipv6(enabled) => enabled.then_some(Format(string::ipv6)),
+        WellKnown::Uri(enabled) => enabled.then_some(Format(string::uri)),
+        WellKnown::UriRef(enabled) => enabled.then_some(Format(string::uri_ref)),
+        WellKnown::Address(enabled) => enabled.then_some(Format(string::address)),
+        WellKnown::Uuid(enabled) => enabled.then_some(Format(string::uuid)),
+        WellKnown::Tuuid(enabled) => enabled.then_some(Format(string::tuuid)),
+        WellKnown::IpWithPrefixlen(enabled) => enabled.then_some(Format(string::ip_with_prefixlen)),
+        WellKnown::Ipv4WithPrefixlen(enabled) => {
+          enabled.then_some(Format(string::ipv4_with_prefixlen))
+        }
+        WellKnown::Ipv6WithPrefixlen(enabled) => {
+          enabled.then_some(Format(string::ipv6_with_prefixlen))
+        }
+        WellKnown::IpPrefix(enabled) => enabled.then_some(Format(string::ip_prefix)),
+        WellKnown::Ipv4Prefix(enabled) => enabled.then_some(Format(string::ipv4_prefix)),
+        WellKnown::Ipv6Prefix(enabled) => enabled.then_some(Format(string::ipv6_prefix)),
+        WellKnown::HostAndPort(enabled) => enabled.then_some(Format(string::host_and_port)),
+        WellKnown::WellKnownRegex(well_known_regex) => {
+          let strict = rules.strict.unwrap_or(true);
+
+          match well_known_regex {
+            1 => Some(WellKnownString::Header(string::header_name, strict)),
+            2 => Some(WellKnownString::Header(string::header_value, strict)),
+            _ => None,
+          }
+        }
+      }
+    });
+
+    Ok(Self {
+      constant: None,
+      in_list: in_message.map(|message| ListRule::scanned(rules.r#in.clone(), message)),
+      not_in_list: not_in_message.map(|message| ListRule::scanned(rules.not_in.clone(), message)),
+      chars,
+      bytes,
+      substrings,
+      pattern,
+      well_known,
+    })
+  }
+
+  fn check(&self, field_context: &FieldContext, value: &str, violations: &mut Vec<Violation>) {
+    if let Some((target, message)) = &self.constant {
+      push(
+        violations,
+        constants::constant(field_context, value, target.as_str(), message),
+      );
+
+      return;
+    }
+
+    if let Some(list) = &self.in_list {
+      let items = list.borrowed::<str>();
+
+      push(
+        violations,
+        containing::in_list(
+          field_context,
+          value,
+          &HashLookup::Slice(&items),
+          &list.message,
+        ),
+      );
+    }
+
+    if let Some(list) = &self.not_in_list {
+      let items = list.borrowed::<str>();
+
+      push(
+        violations,
+        containing::not_in_list(
+          field_context,
+          value,
+          &HashLookup::Slice(&items),
+          &list.message,
+        ),
+      );
+    }
+
+    self.chars.check(field_context, value, violations);
+    self.bytes.check(field_context, value, violations);
+
+    for (validator, target, message) in &self.substrings {
+      push(violations, validator(field_context, value, target, message));
+    }
+
+    if let Some((regex, message)) = &self.pattern {
+      push(
+        violations,
+        string::pattern(field_context, value, regex, message),
+      );
+    }
+
+    match &self.well_known {
+      Some(WellKnownString::Format(validator)) => push(violations, validator(field_context, value)),
+      Some(WellKnownString::Header(validator, strict)) => {
+        push(violations, validator(field_context, value, *strict))
+      }
+      None => {}
+    }
+  }
+}
+
+type BytesValidator = fn(&FieldContext, &[u8]) -> Result<(), Violation>;
+
+pub(super) struct BytesChecks {
+  constant: Option<(Vec<u8>, String)>,
+  in_list: Option<ListRule<Vec<u8>>>,
+  not_in_list: Option<ListRule<Vec<u8>>>,
+  lengths: Lengths<[u8]>,
+  substrings: Vec<(SubstringValidator<[u8]>, Vec<u8>, String)>,
+  pattern: Option<(regex::bytes::Regex, String)>,
+  well_known: Option<BytesValidator>,
+}
+
+impl BytesChecks {
+  fn new(rules: &BytesRules) -> Result<Self, String> {
+    if let Some(value) = &rules.r#const {
+      return Ok(Self {
+        constant: Some((
+          value.to_vec(),
+          rule_messages::const_message(value.escape_ascii()),
+        )),
+        in_list: None,
+        not_in_list: None,
+        lengths: Lengths { checks: Vec::new() },
+        substrings: Vec::new(),
+        pattern: None,
+        well_known: None,
+      });
+    }
+
+    let [in_message, not_in_message] = list_messages(
+      &rules.r#in,
+      &rules.not_in,
+      |item| item.escape_ascii().to_string(),
+      LIST_PREFIXES,
+    )?;
+
+    let lengths = Lengths::new(
+      Some(("len", rules.len, bytes::len)),
+      ("min_len", rules.min_len, bytes::min_len),
+      ("max_len", rules.max_len, bytes::max_len),
+      "byte",
+    )?;
+
+    let substring_rules: [(_, SubstringValidator<[u8]>, &str); 3] = [
+      (&rules.contains, bytes::contains, "must contain"),
+      (&rules.prefix, bytes::prefix, "must start with"),
+      (&rules.suffix, bytes::suffix, "must end with"),
+    ];
+
+    let substrings = substring_rules
+      .into_iter()
+      .filter_map(|(target, validator, prefix)| {
+        target.as_ref().map(|target| {
+          (
+            validator,
+            target.to_vec(),
+            format!("{prefix} {}", target.escape_ascii()),
+          )
+        })
+      })
+      .collect();
+
+    let pattern = rules
+      .pattern
+      .as_ref()
+      .map(|pattern| {
+        regex::bytes::Regex::new(pattern)
+          .map(|regex| {
+            (
+              regex,
+              format!("must match the following regex: `{pattern}`"),
+            )
+          })
+          .map_err(|e| format!("invalid regex pattern: {e}"))
+      })
+      .transpose()?;
+
+    let well_known = rules.well_known.and_then(|well_known| match well_known {
+      bytes_rules::WellKnown::Ip(enabled) => enabled.then_some(bytes::ip as BytesValidator),
+      bytes_rules::WellKnown::Ipv4(enabled) => enabled.then_some(bytes::ipv4 as BytesValidator),
+      bytes_rules::WellKnown::Ipv6(enabled) => enabled.then_some(bytes::ipv6 as BytesValidator),
+    });
+
+    let owned = |items: &[::bytes::Bytes]| items.iter().map(|item| item.to_vec()).collect();
+
+    Ok(Self {
+      constant: None,
+      in_list: in_message.map(|message| ListRule::scanned(owned(&rules.r#in), message)),
+      not_in_list: not_in_message.map(|message| ListRule::scanned(owned(&rules.not_in), message)),
+      lengths,
+      substrings,
+      pattern,
+      well_known,
+    })
+  }
+
+  fn check(&self, field_context: &FieldContext, value: &[u8], violations: &mut Vec<Violation>) {
+    if let Some((target, message)) = &self.constant {
+      push(
+        violations,
+        constants::constant(field_context, value, target.as_slice(), message),
+      );
+
+      return;
+    }
+
+    if let Some(list) = &self.in_list {
+      let items = list.borrowed::<[u8]>();
+
+      push(
+        violations,
+        containing::in_list(
+          field_context,
+          value,
+          &HashLookup::Slice(&items),
+          &list.message,
+        ),
+      );
+    }
+
+    if let Some(list) = &self.not_in_list {
+      let items = list.borrowed::<[u8]>();
+
+      push(
+        violations,
+        containing::not_in_list(
+          field_context,
+          value,
+          &HashLookup::Slice(&items),
+          &list.message,
+        ),
+      );
+    }
+
+    self.lengths.check(field_context, value, violations);
+
+    for (validator, target, message) in &self.substrings {
+      push(violations, validator(field_context, value, target, message));
+    }
+
+    if let Some((regex, message)) = &self.pattern {
+      push(
+        violations,
+        bytes::pattern(field_context, value, regex, message),
+      );
+    }
+
+    if let Some(validator) = self.well_known {
+      push(violations, validator(field_context, value));
+    }
+  }
+}
+
+pub(super) struct EnumChecks {
+  constant: Option<(i32, String)>,
+  defined_only: Option<EnumDescriptor>,
+  in_list: Option<ListRule<i32>>,
+  not_in_list: Option<ListRule<i32>>,
+}
+
+impl EnumChecks {
+  fn new(rules: &EnumRules, enum_desc: &EnumDescriptor) -> Result<Self, String> {
+    if let Some(value) = rules.r#const {
+      return Ok(Self {
+        constant: Some((value, rule_messages::const_message(value))),
+        defined_only: None,
+        in_list: None,
+        not_in_list: None,
+      });
+    }
+
+    let invalid_numbers: Vec<i32> = rules
+      .r#in
+      .iter()
+      .copied()
+      .filter(|number| enum_desc.get_value(*number).is_none())
+      .collect();
+
+    if !invalid_numbers.is_empty() {
+      return Err(format!(
+        "enum_rules.in contains values that are not in the {} enum: {:?}",
+        enum_desc.name(),
+        invalid_numbers
+      ));
+    }
+
+    let [in_message, not_in_message] =
+      list_messages(&rules.r#in, &rules.not_in, i32::to_string, LIST_PREFIXES)?;
+
+    Ok(Self {
+      constant: None,
+      defined_only: rules.defined_only().then(|| enum_desc.clone()),
+      in_list: in_message.map(|message| ListRule::hashed(rules.r#in.clone(), message)),
+      not_in_list: not_in_message.map(|message| ListRule::hashed(rules.not_in.clone(), message)),
+    })
+  }
+
+  fn check(&self, field_context: &FieldContext, value: i32, violations: &mut Vec<Violation>) {
+    if let Some((target, message)) = &self.constant {
+      push(
+        violations,
+        constants::constant(field_context, EnumVariant(value), *target, message),
+      );
+
+      return;
+    }
+
+    if let Some(enum_desc) = &self.defined_only
+      && enum_desc.get_value(value).is_none()
+    {
+      violations.push(enums::defined_only(field_context, enum_desc.name(), value));
+    }
+
+    if let Some(list) = &self.in_list {
+      push(
+        violations,
+        containing::in_list(
+          field_context,
+          EnumVariant(value),
+          &list.lookup(),
+          &list.message,
+        ),
+      );
+    }
+
+    if let Some(list) = &self.not_in_list {
+      push(
+        violations,
+        containing::not_in_list(
+          field_context,
+          EnumVariant(value),
+          &list.lookup(),
+          &list.message,
+        ),
+      );
+    }
+  }
+}
+
+pub(super) struct TimestampChecks {
+  constant: Option<(Timestamp, String)>,
+  within: Option<(Duration, String)>,
+  comparison: Option<Comparison<Timestamp>>,
+  lt_now: bool,
+  gt_now: bool,
+}
+
+impl TimestampChecks {
+  fn new(rules: &TimestampRules) -> Result<Self, String> {
+    if let Some(value) = rules.r#const {
+      return Ok(Self {
+        constant: Some((value, rule_messages::const_message(value))),
+        within: None,
+        comparison: None,
+        lt_now: false,
+        gt_now: false,
+      });
+    }
+
+    let less_than = match rules.less_than {
+      Some(timestamp_rules::LessThan::Lt(value)) => Some((value, false)),
+      Some(timestamp_rules::LessThan::Lte(value)) => Some((value, true)),
+      _ => None,
+    };
+
+    let greater_than = match rules.greater_than {
+      Some(timestamp_rules::GreaterThan::Gt(value)) => Some((value, false)),
+      Some(timestamp_rules::GreaterThan::Gte(value)) => Some((value, true)),
+      _ => None,
+    };
+
+    Ok(Self {
+      constant: None,
+      within: rules
+        .within
+        .map(|within| (within, format!("must be within {within} from now"))),
+      comparison: Comparison::new(less_than, greater_than)?,
+      lt_now: rules.less_than == Some(timestamp_rules::LessThan::LtNow(true)),
+      gt_now: rules.greater_than == Some(timestamp_rules::GreaterThan::GtNow(true)),
+    })
+  }
+
+  fn check(&self, field_context: &FieldContext, value: Timestamp, violations: &mut Vec<Violation>) {
+    // The other rules are only evaluated for the timestamps with valid nanos, like in protovalidate
+    if let Err(violation) = timestamps::valid(field_context, value) {
+      violations.push(violation);
+
+      return;
+    }
+
+    if let Some((target, message)) = &self.constant {
+      push(
+        violations,
+        constants::constant(field_context, value, *target, message),
+      );
+
+      return;
+    }
+
+    if let Some((within, message)) = &self.within {
+      push(
+        violations,
+        timestamps::within(field_context, value, *within, message),
+      );
+    }
+
+    if let Some(comparison) = &self.comparison {
+      push(violations, comparison.check(field_context, value));
+    }
+
+    if self.lt_now {
+      push(violations, timestamps::lt_now(field_context, value));
+    }
+
+    if self.gt_now {
+      push(violations, timestamps::gt_now(field_context, value));
+    }
+  }
+}
+
+pub(super) struct AnyChecks {
+  in_list: Option<ListRule<String>>,
+  not_in_list: Option<ListRule<String>>,
+}
+
+impl AnyChecks {
+  fn new(rules: &AnyRules) -> Result<Self, String> {
+    let [in_message, not_in_message] = list_messages(
+      &rules.r#in,
+      &rules.not_in,
+      String::clone,
+      [
+        rule_messages::TYPE_URL_IN_LIST_PREFIX,
+        rule_messages::TYPE_URL_NOT_IN_LIST_PREFIX,
+      ],
+    )?;
+
+    Ok(Self {
+      in_list: in_message.map(|message| ListRule::scanned(rules.r#in.clone(), message)),
+      not_in_list: not_in_message.map(|message| ListRule::scanned(rules.not_in.clone(), message)),
+    })
+  }
+
+  fn check(&self, field_context: &FieldContext, value: &Any, violations: &mut Vec<Violation>) {
+    if let Some(list) = &self.in_list {
+      let items = list.borrowed::<str>();
+
+      push(
+        violations,
+        containing::in_list(
+          field_context,
+          value,
+          &HashLookup::Slice(&items),
+          &list.message,
+        ),
+      );
+    }
+
+    if let Some(list) = &self.not_in_list {
+      let items = list.borrowed::<str>();
+
+      push(
+        violations,
+        containing::not_in_list(
+          field_context,
+          value,
+          &HashLookup::Slice(&items),
+          &list.message,
+        ),
+      );
+    }
+  }
+}
+
+/// Finds the first item that duplicates a previous one, with the violation of the `repeated.unique` rule. Like in the generated validators, it is reported along with the other rules of that item.
+pub(super) fn first_duplicate(
+  field_context: &FieldContext,
+  items: &[Cow<'_, Value>],
+  item_type: FieldType,
+) -> Option<(usize, Violation)> {
+  let len = items.len();
+
+  match item_type {
+    FieldType::Float => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_f32()),
+      float_lookup(len),
+    ),
+    FieldType::Double => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_f64()),
+      float_lookup(len),
+    ),
+    FieldType::Int32 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_i32()),
+      hashed_lookup(len),
+    ),
+    FieldType::Int64 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_i64()),
+      hashed_lookup(len),
+    ),
+    FieldType::Uint32 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_u32()),
+      hashed_lookup(len),
+    ),
+    FieldType::Uint64 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_u64()),
+      hashed_lookup(len),
+    ),
+    FieldType::Sint32 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_i32().map(Sint32)),
+      hashed_lookup(len),
+    ),
+    FieldType::Sint64 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_i64().map(Sint64)),
+      hashed_lookup(len),
+    ),
+    FieldType::Fixed32 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_u32().map(Fixed32)),
+      hashed_lookup(len),
+    ),
+    FieldType::Fixed64 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_u64().map(Fixed64)),
+      hashed_lookup(len),
+    ),
+    FieldType::Sfixed32 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_i32().map(Sfixed32)),
+      hashed_lookup(len),
+    ),
+    FieldType::Sfixed64 => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_i64().map(Sfixed64)),
+      hashed_lookup(len),
+    ),
+    FieldType::Enum => find_duplicate(
+      field_context,
+      items
+        .iter()
+        .map(|item| item.as_enum_number().map(EnumVariant)),
+      hashed_lookup(len),
+    ),
+    FieldType::String => find_duplicate(
+      field_context,
+      items.iter().map(|item| item.as_str()),
+      hashed_lookup(len),
+    ),
+    FieldType::Bytes => find_duplicate(
+      field_context,
+      items
+        .iter()
+        .map(|item| item.as_bytes().map(|bytes| bytes.as_ref())),
+      hashed_lookup(len),
+    ),
+    FieldType::Duration => find_duplicate(
+      field_context,
+      items.iter().map(|item| duration_value(item)),
+      hashed_lookup(len),
+    ),
+    FieldType::Timestamp => find_duplicate(
+      field_context,
+      items.iter().map(|item| timestamp_value(item)),
+      hashed_lookup(len),
+    ),
+    // The other types are rejected when the rules are read
+    FieldType::Bool | FieldType::Message | FieldType::Group | FieldType::Any => None,
+  }
+}
+
+fn find_duplicate<T, I>(
+  field_context: &FieldContext,
+  items: impl Iterator<Item = Option<T>>,
+  mut lookup: T::Container,
+) -> Option<(usize, Violation)>
+where
+  T: UniqueItem<I>,
+{
+  items.enumerate().find_map(|(index, item)| {
+    repeated::unique(field_context, item?, &mut lookup)
+      .err()
+      .map(|violation| (index, violation))
+  })
+}
+
+fn hashed_lookup<T: Copy + PartialEq>(len: usize) -> UniqueLookup<T> {
+  if len < INLINE_UNIQUE_ITEMS {
+    UniqueLookup::inline()
+  } else {
+    UniqueLookup::Set(HashSet::default())
+  }
+}
+
+#[cfg(feature = "ordered-float")]
+fn float_lookup<T: Copy + PartialEq>(len: usize) -> UniqueLookup<T> {
+  hashed_lookup(len)
+}
+
+// The floats can only be hashed with ordered-float
+#[cfg(not(feature = "ordered-float"))]
+fn float_lookup<T: Copy + PartialEq>(len: usize) -> UniqueLookup<T> {
+  if len <= INLINE_UNIQUE_ITEMS {
+    UniqueLookup::inline()
+  } else {
+    UniqueLookup::Vec(Vec::with_capacity(len))
+  }
+}
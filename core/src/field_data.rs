@@ -1,3 +1,5 @@
+use alloc::{borrow::Cow, format, string::ToString, vec::Vec};
+
 use proto_types::FieldType;
 
 use crate::{
@@ -10,13 +12,139 @@ use crate::{
 pub struct FieldContext<'a> {
   pub proto_name: &'a str,
   pub tag: i32,
-  pub parent_elements: &'a [FieldPathElement],
-  pub subscript: Option<Subscript>,
+  pub parent_messages: &'a ParentMessages<'a>,
+  pub subscript: Option<SubscriptRef<'a>>,
   pub key_type: Option<ProtoType>,
   pub value_type: Option<ProtoType>,
   pub field_kind: FieldKind,
 }
 
+impl FieldContext<'_> {
+  /// Returns the path of the field, which is only created when a violation is reported.
+  pub fn field_path_elements(&self) -> Vec<FieldPathElement> {
+    let mut elements = self.parent_messages.to_elements();
+
+    elements.push(FieldPathElement {
+      field_type: Some(self.field_kind.inner_type().into()),
+      field_name: Some(self.proto_name.to_string()),
+      key_type: self.key_type.map(|t| t as i32),
+      value_type: self.value_type.map(|t| t as i32),
+      field_number: Some(self.tag),
+      subscript: self.subscript.map(Subscript::from),
+    });
+
+    elements
+  }
+}
+
+/// The path of the message being validated, made of the fields of its parent messages.
+///
+/// It is a linked list that lives on the stack, where each nested message points to the path of its parent, so that the validation of a valid message does not allocate. The path is converted into the [`FieldPathElement`]s only when a violation is created.
+///
+/// # Examples
+/// ```rust
+/// use protocheck_core::field_data::{ParentMessages, PathField, SubscriptRef};
+/// use proto_types::field_descriptor_proto::Type;
+///
+/// let root = ParentMessages::default();
+/// let items = root.nested(PathField {
+///   proto_name: "items",
+///   tag: 2,
+///   field_type: Type::Message,
+///   key_type: None,
+///   value_type: None,
+///   subscript: Some(SubscriptRef::Index(3)),
+/// });
+///
+/// assert_eq!(items.len(), 1);
+/// assert_eq!(items.to_elements()[0].field_name(), "items");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ParentMessages<'a> {
+  last: Option<(PathField<'a>, &'a ParentMessages<'a>)>,
+  len: usize,
+}
+
+impl<'a> ParentMessages<'a> {
+  /// Returns the path of a message nested in the given field of the current one.
+  pub fn nested(&'a self, field: PathField<'a>) -> Self {
+    Self {
+      last: Some((field, self)),
+      len: self.len + 1,
+    }
+  }
+
+  /// Returns the number of parent messages.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns true for the top level message.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Creates the elements of the path, from the top level message. One more element is reserved, for the field that is usually added next.
+  pub fn to_elements(&self) -> Vec<FieldPathElement> {
+    let mut elements = Vec::with_capacity(self.len + 1);
+    let mut current = self;
+
+    while let Some((field, parent)) = &current.last {
+      elements.push(field.to_element());
+      current = parent;
+    }
+
+    elements.reverse();
+    elements
+  }
+}
+
+/// A field in the path of a nested message. It only borrows its data, and it is converted into a [`FieldPathElement`] when a violation is created.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PathField<'a> {
+  pub proto_name: &'a str,
+  pub tag: i32,
+  pub field_type: ProtoType,
+  pub key_type: Option<ProtoType>,
+  pub value_type: Option<ProtoType>,
+  pub subscript: Option<SubscriptRef<'a>>,
+}
+
+impl PathField<'_> {
+  pub fn to_element(&self) -> FieldPathElement {
+    FieldPathElement {
+      field_name: Some(self.proto_name.to_string()),
+      field_number: Some(self.tag),
+      field_type: Some(self.field_type as i32),
+      key_type: self.key_type.map(|t| t as i32),
+      value_type: self.value_type.map(|t| t as i32),
+      subscript: self.subscript.map(Subscript::from),
+    }
+  }
+}
+
+/// A borrowed [`Subscript`], so that the keys of the maps are not cloned unless there is a violation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptRef<'a> {
+  Index(u64),
+  BoolKey(bool),
+  IntKey(i64),
+  UintKey(u64),
+  StringKey(&'a str),
+}
+
+impl From<SubscriptRef<'_>> for Subscript {
+  fn from(subscript: SubscriptRef<'_>) -> Self {
+    match subscript {
+      SubscriptRef::Index(index) => Subscript::Index(index),
+      SubscriptRef::BoolKey(key) => Subscript::BoolKey(key),
+      SubscriptRef::IntKey(key) => Subscript::IntKey(key),
+      SubscriptRef::UintKey(key) => Subscript::UintKey(key),
+      SubscriptRef::StringKey(key) => Subscript::StringKey(key.to_string()),
+    }
+  }
+}
+
 /// The kind of field being validated. This extra context helps generating more precise violation reports.
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum FieldKind {
@@ -61,6 +189,28 @@ impl FieldKind {
   }
 }
 
+// The keywords that prost escapes as raw identifiers, and the ones that cannot be raw identifiers, which get a trailing underscore
+const PROST_KEYWORDS_RENAMED_WITH_R: &[&str] = &[
+  "as", "break", "const", "continue", "else", "enum", "false", "fn", "for", "if", "impl", "in",
+  "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct",
+  "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+  "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "async",
+  "await",
+];
+
+const PROST_KEYWORDS_RENAMED_WITH_UNDERSCORE: &[&str] = &["crate", "extern", "super", "self"];
+
+/// Returns the name of the field in the structs generated by prost (such as `r#type` for `type`), which is also its key in the Cel values of the messages.
+pub fn rust_field_name(proto_name: &str) -> Cow<'_, str> {
+  if PROST_KEYWORDS_RENAMED_WITH_UNDERSCORE.contains(&proto_name) {
+    Cow::Owned(format!("{proto_name}_"))
+  } else if PROST_KEYWORDS_RENAMED_WITH_R.contains(&proto_name) {
+    Cow::Owned(format!("r#{proto_name}"))
+  } else {
+    Cow::Borrowed(proto_name)
+  }
+}
+
 #[cfg(feature = "totokens")]
 use proc_macro2::TokenStream;
 #[cfg(feature = "totokens")]
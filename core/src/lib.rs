@@ -1,6 +1,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+extern crate alloc;
+
 use proto_types::protovalidate::{FieldPath, FieldPathElement};
 pub(crate) use proto_types::{field_descriptor_proto::Type as ProtoType, protovalidate};
 
@@ -11,7 +14,13 @@ pub mod field_data;
 #[cfg(feature = "validators")]
 pub mod validators;
 
+/// The validator of the `DynamicMessage`s of prost-reflect, which reads the rules from the descriptors at runtime and checks them with the same validators as the generated code.
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
+
 #[cfg(feature = "cel")]
 pub use cel;
+#[cfg(feature = "dynamic")]
+pub use prost_reflect;
 
 pub mod wrappers;
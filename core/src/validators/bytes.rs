@@ -1,22 +1,28 @@
-use ::bytes::Bytes;
-
-use super::{
-  well_known_strings::{is_valid_ip, is_valid_ipv4, is_valid_ipv6},
-  *,
-};
+use super::*;
 use crate::protovalidate::violations_data::bytes_violations::*;
 
+// The values are taken as slices, so that both bytes::Bytes and Vec<u8> can be validated.
+// The ip rules only check the length of the raw address
 macro_rules! well_known_rule {
   (
     $name:ident,
-    $definition:literal
+    $definition:literal,
+    $validation_expression:expr
   ) => {
     paste::paste! {
-      pub fn $name(field_context: &FieldContext, value: &Bytes) -> Result<(), Violation> {
-        let is_valid = [<is_valid _ $name>](String::from_utf8_lossy(value.as_ref()).as_ref());
+      pub fn $name(field_context: &FieldContext, value: &[u8]) -> Result<(), Violation> {
+        let is_valid = ($validation_expression)(value.len());
 
         if is_valid {
           Ok(())
+        } else if value.is_empty() {
+          // Like in protovalidate, the empty values have a separate rule id
+          Err(create_violation_with_custom_id(
+            concat!("bytes.", stringify!($name), "_empty"),
+            field_context,
+            &[< BYTES _ $name:upper _ VIOLATION >],
+            concat!("is empty, which is not a valid ", $definition)
+          ))
         } else {
           Err(create_violation(
             field_context,
@@ -38,9 +44,9 @@ macro_rules! bytes_validator {
   ) => {
     pub fn $name(
       field_context: &FieldContext,
-      value: &Bytes,
+      value: &[u8],
       target: $target_type,
-      error_message: &'static str,
+      error_message: &str,
     ) -> Result<(), Violation> {
       let is_valid = $validation_expression(target, value);
 
@@ -57,20 +63,20 @@ macro_rules! bytes_validator {
   };
 }
 
-well_known_rule!(ip, "ip address");
+well_known_rule!(ip, "IP address", |len: usize| len == 4 || len == 16);
 
-well_known_rule!(ipv4, "ipv4 address");
+well_known_rule!(ipv4, "IPv4 address", |len: usize| len == 4);
 
-well_known_rule!(ipv6, "ipv6 address");
+well_known_rule!(ipv6, "IPv6 address", |len: usize| len == 16);
 
 #[cfg(feature = "regex")]
 pub fn pattern(
   field_context: &FieldContext,
-  value: &Bytes,
+  value: &[u8],
   regex: &regex::bytes::Regex,
   error_message: &str,
 ) -> Result<(), Violation> {
-  let is_valid = regex.is_match(value.as_ref());
+  let is_valid = regex.is_match(value);
 
   if is_valid {
     Ok(())
@@ -83,20 +89,20 @@ pub fn pattern(
   }
 }
 
-bytes_validator!(bytes_arg, min_len, u64, |t: u64, v: &Bytes| v.len() as u64
+bytes_validator!(bytes_arg, min_len, u64, |t: u64, v: &[u8]| v.len() as u64
   >= t);
 
-bytes_validator!(bytes_arg, max_len, u64, |t: u64, v: &Bytes| v.len() as u64
+bytes_validator!(bytes_arg, max_len, u64, |t: u64, v: &[u8]| v.len() as u64
   <= t);
 
-bytes_validator!(bytes_arg, len, u64, |t: u64, v: &Bytes| v.len() as u64 == t);
+bytes_validator!(bytes_arg, len, u64, |t: u64, v: &[u8]| v.len() as u64 == t);
 
-bytes_validator!(bytes_arg, contains, &[u8], |t: &[u8], v: &Bytes| v
-  .windows(t.len())
-  .any(|win| win == t));
+bytes_validator!(bytes_arg, contains, &[u8], |t: &[u8], v: &[u8]| t
+  .is_empty()
+  || v.windows(t.len()).any(|win| win == t));
 
-bytes_validator!(bytes_arg, suffix, &[u8], |t: &[u8], v: &Bytes| v
+bytes_validator!(bytes_arg, suffix, &[u8], |t: &[u8], v: &[u8]| v
   .ends_with(t));
 
-bytes_validator!(bytes_arg, prefix, &[u8], |t: &[u8], v: &Bytes| v
+bytes_validator!(bytes_arg, prefix, &[u8], |t: &[u8], v: &[u8]| v
   .starts_with(t));
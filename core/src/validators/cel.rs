@@ -1,158 +1,449 @@
-use ::cel::{Context, Program, Value as CelValue};
-use chrono::Utc;
+use std::sync::Arc;
+
+use ::cel::{
+  extractors::{Arguments, This},
+  objects::{Key, ValueType},
+  Context, ExecutionError, FunctionContext, Program, Value as CelValue,
+};
 use proto_types::cel::CelConversionError;
 
-use super::*;
+use super::{cel_format::format, timestamps::now, well_known_strings::*, *};
 use crate::protovalidate::{violations_data::CEL_VIOLATION, Violation};
 
-pub struct CelRule {
-  pub id: &'static str,
-  pub error_message: &'static str,
-  pub program: &'static Program,
-  pub item_full_name: &'static str,
+/// A Cel rule, with its compiled program. The generated validators refer to statics, while the dynamic validator borrows them from the rules that it reads from the descriptors.
+pub struct CelRule<'a> {
+  pub id: &'a str,
+  pub error_message: &'a str,
+  pub program: &'a Program,
+  /// Whether the expression uses `now`, as the current time is only read for the expressions that use it.
+  pub uses_now: bool,
+  pub item_full_name: &'a str,
+  /// The rules containing the expression (such as the `StringRules` of a field, or the `MessageRules` of a message), available as `rules`.
+  pub rules: &'a CelValue,
+}
+
+// The functions are only registered once, and each evaluation adds its variables in a child scope of this context
+static CEL_CONTEXT: LazyLock<Context<'static>> = LazyLock::new(cel_context);
+
+// The current time, available as `now`, which is the same for the whole validation of a message.
+// Only the timestamps pinned with `with_now` (or returned by a custom clock) can be out of the range of the Cel timestamps,
+// and the rules that use it then fail to evaluate, rather than running against a different time
+fn cel_now() -> Result<CelValue, CelConversionError> {
+  now().try_into()
+}
+
+/// Creates the Cel context used for the rules, with the functions of protovalidate registered on top of the standard ones.
+///
+/// These are `isEmail`, `isHostname`, `isIp` (with an optional version), `isUri` and `isUriRef` for strings, `isNan` and `isInf` (with an optional sign) for doubles, and `unique` for the lists of scalars. The string functions use the same checks as the corresponding string rules.
+///
+/// It also has the `format` function of the strings extension of Cel, which is used to build the error messages, as in `'must be at most %d'.format([rules.max_len])`.
+///
+/// # Examples
+/// ```rust
+/// use cel::{Program, Value};
+/// use protocheck_core::validators::cel::cel_context;
+///
+/// let program = Program::compile("'127.0.0.1'.isIp(4) && [1, 2, 3].unique()").unwrap();
+///
+/// assert_eq!(program.execute(&cel_context()).unwrap(), Value::Bool(true));
+/// ```
+pub fn cel_context() -> Context<'static> {
+  let mut context = Context::default();
+
+  #[cfg(feature = "regex")]
+  context.add_function("isEmail", |This(this): This<Arc<String>>| {
+    is_valid_email(&this)
+  });
+  context.add_function("isHostname", |This(this): This<Arc<String>>| {
+    is_valid_hostname(&this)
+  });
+  context.add_function("isIp", is_ip);
+  #[cfg(feature = "uri")]
+  context.add_function("isUri", |This(this): This<Arc<String>>| is_valid_uri(&this));
+  #[cfg(feature = "uri")]
+  context.add_function("isUriRef", |This(this): This<Arc<String>>| {
+    is_valid_uri_ref(&this)
+  });
+  context.add_function("isNan", |This(this): This<f64>| this.is_nan());
+  context.add_function("isInf", is_inf);
+  context.add_function("unique", unique);
+  context.add_function("format", format);
+
+  context
+}
+
+// `isIp()` accepts both versions, and `isIp(version)` only the given one (with 0 meaning either of them)
+fn is_ip(
+  ftx: &FunctionContext,
+  This(this): This<Arc<String>>,
+  Arguments(args): Arguments,
+) -> Result<bool, ExecutionError> {
+  match optional_int_argument(ftx, &args)? {
+    0 => Ok(is_valid_ip(&this)),
+    4 => Ok(is_valid_ipv4(&this)),
+    6 => Ok(is_valid_ipv6(&this)),
+    _ => Ok(false),
+  }
+}
+
+// `isInf()` accepts both infinities, and `isInf(sign)` only the positive one for a positive sign or the negative one for a negative sign
+fn is_inf(
+  ftx: &FunctionContext,
+  This(this): This<f64>,
+  Arguments(args): Arguments,
+) -> Result<bool, ExecutionError> {
+  let sign = optional_int_argument(ftx, &args)?;
+
+  Ok(match sign {
+    0 => this.is_infinite(),
+    s if s > 0 => this == f64::INFINITY,
+    _ => this == f64::NEG_INFINITY,
+  })
+}
+
+// The optional integer argument of `isIp` and `isInf`, which is 0 when it is missing
+fn optional_int_argument(ftx: &FunctionContext, args: &[CelValue]) -> Result<i64, ExecutionError> {
+  match args {
+    [] => Ok(0),
+    [CelValue::Int(value)] => Ok(*value),
+    [other] => Err(ftx.error(format!(
+      "expected an int argument, got {:?}",
+      other.type_of()
+    ))),
+    _ => Err(ftx.error("expected at most one argument")),
+  }
+}
+
+fn unique(
+  ftx: &FunctionContext,
+  This(this): This<Arc<Vec<CelValue>>>,
+) -> Result<bool, ExecutionError> {
+  let mut keys: HashSet<Key> = HashSet::with_capacity(this.len());
+  let mut bytes: HashSet<&[u8]> = HashSet::new();
+  let mut floats: Vec<f64> = Vec::new();
+
+  for value in this.iter() {
+    let is_new = match value {
+      CelValue::Bytes(value) => bytes.insert(value.as_slice()),
+      CelValue::Float(value) => {
+        if floats.contains(value) {
+          false
+        } else {
+          floats.push(*value);
+          true
+        }
+      }
+      CelValue::Int(_) | CelValue::UInt(_) | CelValue::Bool(_) | CelValue::String(_) => keys
+        .insert(
+          value
+            .clone()
+            .try_into()
+            .map_err(ExecutionError::UnsupportedKeyType)?,
+        ),
+      other => {
+        return Err(ftx.error(format!(
+          "unique() is not supported for lists of {:?}",
+          other.type_of()
+        )))
+      }
+    };
+
+    if !is_new {
+      return Ok(false);
+    }
+  }
+
+  Ok(true)
 }
 
 pub fn validate_cel_field_with_val(
   field_context: &FieldContext,
-  rule: CelRule,
+  rule: CelRule<'_>,
   value: CelValue,
 ) -> Result<(), Violation>
 where
 {
+  execute_cel_field_rule(field_context, rule, value, None, &CEL_VIOLATION)
+}
+
+/// A rule defined with `buf.validate.predefined`, whose expression can refer to the value of the rule with the `rule` variable.
+pub struct PredefinedCelRule<'a> {
+  pub rule: CelRule<'a>,
+  /// The value of the extension that was set in the rules of the field.
+  pub value: &'a CelValue,
+  /// The path of the extension inside of the rules of the field, such as `string.[acme.is_ticker]`.
+  pub violation_data: &'a ViolationData,
+}
+
+pub fn validate_predefined_cel_field_try_into<T>(
+  field_context: &FieldContext,
+  predefined_rule: PredefinedCelRule<'_>,
+  value: T,
+) -> Result<(), Violation>
+where
+  T: TryInto<CelValue> + Clone,
+  <T as std::convert::TryInto<::cel::Value>>::Error: std::fmt::Display,
+{
+  validate_predefined_cel_field_conversion(field_context, predefined_rule, value.try_into())
+}
+
+/// Like [`validate_cel_field_conversion`], for the predefined rules.
+pub fn validate_predefined_cel_field_conversion<E>(
+  field_context: &FieldContext,
+  predefined_rule: PredefinedCelRule<'_>,
+  conversion: Result<CelValue, E>,
+) -> Result<(), Violation>
+where
+  E: std::fmt::Display,
+{
+  let PredefinedCelRule {
+    rule,
+    value: rule_value,
+    violation_data,
+  } = predefined_rule;
+
+  let cel_val: CelValue = conversion.map_err(|e| {
+    eprintln!(
+      "Failed to convert field {} to Cel value: {}",
+      rule.item_full_name, e
+    );
+
+    create_cel_field_violation(
+      rule.id,
+      field_context,
+      violation_data,
+      "internal server error",
+    )
+  })?;
+
+  execute_cel_field_rule(
+    field_context,
+    rule,
+    cel_val,
+    Some(rule_value),
+    violation_data,
+  )
+}
+
+fn execute_cel_field_rule(
+  field_context: &FieldContext,
+  rule: CelRule<'_>,
+  value: CelValue,
+  rule_value: Option<&CelValue>,
+  violation_data: &ViolationData,
+) -> Result<(), Violation> {
   let CelRule {
     id: rule_id,
     error_message,
     program,
+    uses_now,
+    rules,
     ..
   } = rule;
 
-  let mut cel_context = Context::default();
-  cel_context.add_variable_from_value("now", CelValue::Timestamp(Utc::now().into()));
+  let mut cel_context = CEL_CONTEXT.new_inner_scope();
+  if uses_now {
+    let now = cel_now().map_err(|e| {
+      eprintln!(
+        "Error during Cel validation for field {}: {e}",
+        field_context.proto_name
+      );
+
+      create_cel_field_violation(
+        rule_id,
+        field_context,
+        violation_data,
+        &now_error_message(&e),
+      )
+    })?;
+
+    cel_context.add_variable_from_value("now", now);
+  }
+  cel_context.add_variable_from_value("rules", rules);
 
   cel_context.add_variable_from_value("this", value);
 
+  if let Some(rule_value) = rule_value {
+    cel_context.add_variable_from_value("rule", rule_value.clone());
+  }
+
   let result = program.execute(&cel_context).map_err(|e| {
     eprintln!(
       "Error during Cel validation for field {}: {e}",
       field_context.proto_name
     );
 
-    create_cel_field_violation(rule_id, field_context, "internal server error")
+    create_cel_field_violation(
+      rule_id,
+      field_context,
+      violation_data,
+      &evaluation_error_message(&e),
+    )
   })?;
 
-  if let CelValue::Bool(bool_value) = result {
-    if bool_value {
-      Ok(())
-    } else {
+  match rule_outcome(result, error_message) {
+    Ok(outcome) => outcome.map_err(|message| {
+      create_cel_field_violation(rule_id, field_context, violation_data, &message)
+    }),
+    Err(result_type) => {
+      eprintln!(
+        "Error during Cel validation for field {}: expected a boolean or a string from the expression, got `{result_type:?}`",
+        field_context.proto_name,
+      );
+
       Err(create_cel_field_violation(
         rule_id,
         field_context,
-        error_message,
+        violation_data,
+        "internal server error",
       ))
     }
-  } else {
-    eprintln!(
-      "Error during Cel validation for field {}: expected boolean result from expression, got `{:?}`",
-      field_context.proto_name,
-      result.type_of()
-    );
+  }
+}
 
-    Err(create_cel_field_violation(
-      rule_id,
-      field_context,
-      "internal server error",
-    ))
+// Like in protovalidate, the expressions return either a boolean, or a string that is the error message (usually
+// built with `format`), with an empty string meaning that the rule is satisfied.
+// Returns the type of the result if it is neither of these
+fn rule_outcome(result: CelValue, error_message: &str) -> Result<Result<(), String>, ValueType> {
+  match result {
+    CelValue::Bool(true) => Ok(Ok(())),
+    CelValue::Bool(false) => Ok(Err(error_message.to_string())),
+    CelValue::String(message) if message.is_empty() => Ok(Ok(())),
+    CelValue::String(message) => Ok(Err(message.to_string())),
+    other => Err(other.type_of()),
   }
 }
 
+// The message of the violations for the expressions that fail during their evaluation, such as with a malformed
+// format string
+fn evaluation_error_message(error: &ExecutionError) -> String {
+  format!("failed to evaluate the rule: {error}")
+}
+
+// The message of the violations for the expressions that use `now` when the current time cannot be converted
+fn now_error_message(error: &CelConversionError) -> String {
+  format!("failed to evaluate the rule: the current time {error}")
+}
+
 pub fn validate_cel_field_try_into<T>(
   field_context: &FieldContext,
-  rule: CelRule,
+  rule: CelRule<'_>,
   value: T,
 ) -> Result<(), Violation>
 where
   T: TryInto<CelValue> + Clone,
   <T as std::convert::TryInto<::cel::Value>>::Error: std::fmt::Display,
 {
-  let cel_val: CelValue = value.try_into().map_err(|e| {
+  validate_cel_field_conversion(field_context, rule, value.try_into())
+}
+
+/// Checks a Cel rule with a field that has already been converted, which is how the generated validators handle the lists and maps, whose items are converted one by one (so that the keys, the enums and the messages get the same Cel values as in the message rules). A failed conversion is reported as an internal server error.
+pub fn validate_cel_field_conversion<E>(
+  field_context: &FieldContext,
+  rule: CelRule<'_>,
+  conversion: Result<CelValue, E>,
+) -> Result<(), Violation>
+where
+  E: std::fmt::Display,
+{
+  let cel_val: CelValue = conversion.map_err(|e| {
     eprintln!(
       "Failed to convert field {} to Cel value: {}",
       rule.item_full_name, e
     );
 
-    create_cel_field_violation(rule.id, field_context, "internal server error")
+    create_cel_field_violation(
+      rule.id,
+      field_context,
+      &CEL_VIOLATION,
+      "internal server error",
+    )
   })?;
 
   validate_cel_field_with_val(field_context, rule, cel_val)
 }
 
+/// Converts a message into the Cel value used as `this` by its rules. The generated validators convert the message once, and then check each of its rules with [`validate_cel_message_with_val`].
+pub fn message_to_cel_value<T>(
+  parent_messages: &ParentMessages,
+  message_name: &str,
+  value: T,
+) -> Result<CelValue, Violation>
+where
+  T: TryInto<CelValue, Error = CelConversionError>,
+{
+  value.try_into().map_err(|e| {
+    eprintln!(
+      "Error during Cel validation for message {message_name}: could not convert message to Cel value: {e}"
+    );
+
+    create_cel_message_violation(
+      "internal_server_error",
+      "internal server error",
+      parent_messages,
+    )
+  })
+}
+
 pub fn validate_cel_message<T>(
-  parent_elements: &[FieldPathElement],
-  rule: CelRule,
+  parent_messages: &ParentMessages,
+  rule: CelRule<'_>,
   value: T,
 ) -> Result<(), Violation>
 where
   T: TryInto<CelValue, Error = CelConversionError>,
 {
+  let cel_val = message_to_cel_value(parent_messages, rule.item_full_name, value)?;
+
+  validate_cel_message_with_val(parent_messages, rule, &cel_val)
+}
+
+pub fn validate_cel_message_with_val(
+  parent_messages: &ParentMessages,
+  rule: CelRule<'_>,
+  cel_val: &CelValue,
+) -> Result<(), Violation> {
   let CelRule {
     id: rule_id,
     error_message,
     program,
+    uses_now,
     item_full_name: message_name,
+    rules,
   } = rule;
 
-  let mut cel_context = Context::default();
-  cel_context.add_variable_from_value("now", CelValue::Timestamp(Utc::now().into()));
+  let mut cel_context = CEL_CONTEXT.new_inner_scope();
+  if uses_now {
+    let now = cel_now().map_err(|e| {
+      eprintln!("Error during Cel validation for message {message_name}: {e}");
 
-  let cel_val: CelValue = value.try_into().map_err(|e| {
-    eprintln!(
-      "Error during Cel validation for message {message_name}: could not convert message to Cel value: {e}"
-    );
+      create_cel_message_violation(rule_id, &now_error_message(&e), parent_messages)
+    })?;
 
-    create_cel_message_violation(
-      "internal_server_error",
-      "internal server error",
-      parent_elements,
-    )
-  })?;
+    cel_context.add_variable_from_value("now", now);
+  }
+  cel_context.add_variable_from_value("rules", rules);
 
   cel_context.add_variable_from_value("this", cel_val);
-  let result = program.execute(&cel_context);
+  let result = program.execute(&cel_context).map_err(|e| {
+    eprintln!("Error during Cel validation for message {message_name}: {e}");
 
-  match result {
-    Ok(value) => {
-      if let CelValue::Bool(bool_value) = value {
-        if bool_value {
-          Ok(())
-        } else {
-          Err(create_cel_message_violation(
-            rule_id,
-            error_message,
-            parent_elements,
-          ))
-        }
-      } else {
-        eprintln!(
-          "Error during Cel validation for message {message_name}: expected boolean result from expression, got `{:?}`",
-          value.type_of()
-        );
-
-        Err(create_cel_message_violation(
-          "internal_server_error",
-          "internal server error",
-          parent_elements,
-        ))
-      }
+    create_cel_message_violation(rule_id, &evaluation_error_message(&e), parent_messages)
+  })?;
+
+  match rule_outcome(result, error_message) {
+    Ok(outcome) => {
+      outcome.map_err(|message| create_cel_message_violation(rule_id, &message, parent_messages))
     }
-    Err(e) => {
+    Err(result_type) => {
       eprintln!(
-        "Error during Cel validation for message {message_name}: program failed to compile: {e}",
+        "Error during Cel validation for message {message_name}: expected a boolean or a string from the expression, got `{result_type:?}`"
       );
 
       Err(create_cel_message_violation(
         "internal_server_error",
         "internal server error",
-        parent_elements,
+        parent_messages,
       ))
     }
   }
@@ -161,28 +452,22 @@ where
 fn create_cel_field_violation(
   rule_id: &str,
   field_context: &FieldContext,
+  violation_data: &ViolationData,
   error_message: &str,
 ) -> Violation {
-  create_violation_with_custom_id(rule_id, field_context, &CEL_VIOLATION, error_message)
+  create_violation_with_custom_id(rule_id, field_context, violation_data, error_message)
 }
 
 fn create_cel_message_violation(
   rule_id: &str,
   error_message: &str,
-  parent_elements: &[FieldPathElement],
+  parent_messages: &ParentMessages,
 ) -> Violation {
-  let is_nested = !parent_elements.is_empty();
-  let field_path = is_nested.then(|| FieldPath {
-    elements: parent_elements.to_vec(),
-  });
-
+  // The violations of the top level message have no field path
   Violation {
-    message: Some(error_message.to_string()),
-    rule_id: Some(rule_id.to_string()),
     rule: Some(FieldPath {
       elements: CEL_VIOLATION.elements.to_vec(),
     }),
-    field: field_path,
-    for_key: None,
+    ..Violation::new(parent_messages.to_elements(), rule_id, error_message)
   }
 }
@@ -0,0 +1,335 @@
+use std::{fmt::Write, iter::Peekable, str::Chars, sync::Arc};
+
+use ::cel::{extractors::This, ExecutionError, FunctionContext, Value as CelValue};
+use chrono::SecondsFormat;
+
+// The precision of %f and %e when it is not specified, like in the strings extension of Cel
+const DEFAULT_PRECISION: usize = 6;
+
+const SUPPORTED_CLAUSES: &str = "sdfexXob";
+
+/// The `format` function of the strings extension of Cel, which replaces the clauses of the string with the values of the list, as in `'must be between %d and %d'.format([rules.gte, rules.lte])`.
+///
+/// The supported clauses are `%s`, `%d`, `%f` and `%e` (with an optional precision, as in `%.2f`), `%x` and `%X`, `%o`, `%b`, and `%%` for a percent sign. The malformed clauses, the values of the wrong type and an argument count that does not match the clauses are evaluation errors.
+pub(crate) fn format(
+  ftx: &FunctionContext,
+  This(this): This<Arc<String>>,
+  args: Arc<Vec<CelValue>>,
+) -> Result<Arc<String>, ExecutionError> {
+  let mut output = String::with_capacity(this.len());
+  let mut args_iter = args.iter();
+  let mut chars = this.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    if c != '%' {
+      output.push(c);
+      continue;
+    }
+
+    let precision = parse_precision(ftx, &mut chars)?;
+
+    let clause = chars
+      .next()
+      .ok_or_else(|| ftx.error("unterminated formatting clause at the end of the string"))?;
+
+    if clause == '%' && precision.is_none() {
+      output.push('%');
+      continue;
+    }
+
+    if !SUPPORTED_CLAUSES.contains(clause) {
+      return Err(ftx.error(format!("unrecognized formatting clause %{clause}")));
+    }
+
+    let arg = args_iter.next().ok_or_else(|| {
+      ftx.error(format!(
+        "not enough arguments: the clause %{clause} has no value, only {} were given",
+        args.len()
+      ))
+    })?;
+
+    let formatted = match clause {
+      's' => format_string(ftx, arg)?,
+      'd' => format_decimal(ftx, arg)?,
+      'f' => format_fixed(ftx, arg, precision.unwrap_or(DEFAULT_PRECISION))?,
+      'e' => format_scientific(ftx, arg, precision.unwrap_or(DEFAULT_PRECISION))?,
+      'x' => format_hex(ftx, arg)?,
+      'X' => format_hex(ftx, arg)?.to_uppercase(),
+      'o' => format_integer(ftx, arg, 'o', |v| format!("{v:o}"))?,
+      'b' => match arg {
+        CelValue::Bool(v) => u8::from(*v).to_string(),
+        _ => format_integer(ftx, arg, 'b', |v| format!("{v:b}"))?,
+      },
+      _ => unreachable!("the clause was checked above"),
+    };
+
+    output.push_str(&formatted);
+  }
+
+  let unused = args_iter.count();
+
+  if unused > 0 {
+    return Err(ftx.error(format!(
+      "too many arguments: {} were given, but only {} were used",
+      args.len(),
+      args.len() - unused
+    )));
+  }
+
+  Ok(Arc::new(output))
+}
+
+// The precision that can follow the percent sign, as in %.2f
+fn parse_precision(
+  ftx: &FunctionContext,
+  chars: &mut Peekable<Chars>,
+) -> Result<Option<usize>, ExecutionError> {
+  if chars.next_if_eq(&'.').is_none() {
+    return Ok(None);
+  }
+
+  let mut digits = String::new();
+
+  while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+    digits.push(digit);
+  }
+
+  digits
+    .parse()
+    .map(Some)
+    .map_err(|_| ftx.error("invalid precision in the formatting clause"))
+}
+
+fn format_string(ftx: &FunctionContext, value: &CelValue) -> Result<String, ExecutionError> {
+  Ok(match value {
+    CelValue::String(v) => v.to_string(),
+    CelValue::Bytes(v) => String::from_utf8_lossy(v).into_owned(),
+    CelValue::Bool(v) => v.to_string(),
+    CelValue::Int(v) => v.to_string(),
+    CelValue::UInt(v) => v.to_string(),
+    CelValue::Float(v) => format_special_float(*v).unwrap_or_else(|| v.to_string()),
+    CelValue::Null => "null".to_string(),
+    CelValue::Timestamp(v) => v.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+    CelValue::Duration(v) => format_duration(v),
+    CelValue::List(values) => {
+      let items = values
+        .iter()
+        .map(|v| format_string(ftx, v))
+        .collect::<Result<Vec<_>, _>>()?;
+
+      format!("[{}]", items.join(", "))
+    }
+    CelValue::Map(map) => {
+      let mut entries = map
+        .map
+        .iter()
+        .map(|(k, v)| Ok(format!("{k}: {}", format_string(ftx, v)?)))
+        .collect::<Result<Vec<_>, ExecutionError>>()?;
+      // The keys are sorted so that the output does not depend on the order of the map
+      entries.sort();
+
+      format!("{{{}}}", entries.join(", "))
+    }
+    other => {
+      return Err(ftx.error(format!(
+        "the clause %s does not support the type {:?}",
+        other.type_of()
+      )));
+    }
+  })
+}
+
+// The durations are formatted in seconds, like with the string conversion of Cel (such as `1.5s`)
+fn format_duration(duration: &chrono::Duration) -> String {
+  let sign = if duration < &chrono::Duration::zero() {
+    "-"
+  } else {
+    ""
+  };
+  let duration = duration.abs();
+  let nanos = duration.subsec_nanos();
+
+  if nanos == 0 {
+    format!("{sign}{}s", duration.num_seconds())
+  } else {
+    let fraction = format!("{nanos:09}");
+
+    format!(
+      "{sign}{}.{}s",
+      duration.num_seconds(),
+      fraction.trim_end_matches('0')
+    )
+  }
+}
+
+fn format_special_float(value: f64) -> Option<String> {
+  if value.is_nan() {
+    Some("NaN".to_string())
+  } else if value.is_infinite() {
+    Some(if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string())
+  } else {
+    None
+  }
+}
+
+fn format_decimal(ftx: &FunctionContext, value: &CelValue) -> Result<String, ExecutionError> {
+  format_integer(ftx, value, 'd', |v| v.to_string())
+}
+
+// The integers are formatted with their sign followed by the absolute value, as in `-ff`
+fn format_integer(
+  ftx: &FunctionContext,
+  value: &CelValue,
+  clause: char,
+  format_abs: impl Fn(u64) -> String,
+) -> Result<String, ExecutionError> {
+  match value {
+    CelValue::Int(v) if *v < 0 => Ok(format!("-{}", format_abs(v.unsigned_abs()))),
+    CelValue::Int(v) => Ok(format_abs(v.unsigned_abs())),
+    CelValue::UInt(v) => Ok(format_abs(*v)),
+    other => Err(ftx.error(format!(
+      "the clause %{clause} only supports integers, got {:?}",
+      other.type_of()
+    ))),
+  }
+}
+
+fn float_arg(ftx: &FunctionContext, value: &CelValue, clause: char) -> Result<f64, ExecutionError> {
+  match value {
+    CelValue::Float(v) => Ok(*v),
+    CelValue::Int(v) => Ok(*v as f64),
+    CelValue::UInt(v) => Ok(*v as f64),
+    other => Err(ftx.error(format!(
+      "the clause %{clause} only supports numbers, got {:?}",
+      other.type_of()
+    ))),
+  }
+}
+
+fn format_fixed(
+  ftx: &FunctionContext,
+  value: &CelValue,
+  precision: usize,
+) -> Result<String, ExecutionError> {
+  let value = float_arg(ftx, value, 'f')?;
+
+  Ok(format_special_float(value).unwrap_or_else(|| format!("{value:.precision$}")))
+}
+
+// Like in Cel (and C), the exponent has a sign and at least two digits, as in `1.052033e+03`
+fn format_scientific(
+  ftx: &FunctionContext,
+  value: &CelValue,
+  precision: usize,
+) -> Result<String, ExecutionError> {
+  let value = float_arg(ftx, value, 'e')?;
+
+  if let Some(special) = format_special_float(value) {
+    return Ok(special);
+  }
+
+  let formatted = format!("{value:.precision$e}");
+  let (mantissa, exponent) = formatted
+    .split_once('e')
+    .expect("the scientific notation always has an exponent");
+  let exponent: i32 = exponent
+    .parse()
+    .expect("the exponent of the scientific notation is an integer");
+  let sign = if exponent < 0 { '-' } else { '+' };
+
+  Ok(format!("{mantissa}e{sign}{:02}", exponent.unsigned_abs()))
+}
+
+// The strings and bytes are formatted as the hexadecimal values of their bytes
+fn format_hex(ftx: &FunctionContext, value: &CelValue) -> Result<String, ExecutionError> {
+  let hex_bytes = |bytes: &[u8]| {
+    bytes.iter().fold(String::new(), |mut output, byte| {
+      let _ = write!(output, "{byte:02x}");
+      output
+    })
+  };
+
+  match value {
+    CelValue::String(v) => Ok(hex_bytes(v.as_bytes())),
+    CelValue::Bytes(v) => Ok(hex_bytes(v)),
+    _ => format_integer(ftx, value, 'x', |v| format!("{v:x}")),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::cel::{Program, Value as CelValue};
+
+  use crate::validators::cel::cel_context;
+
+  fn format(expression: &str) -> Result<String, String> {
+    let program = Program::compile(expression).map_err(|e| e.to_string())?;
+
+    match program.execute(&cel_context()) {
+      Ok(CelValue::String(v)) => Ok(v.to_string()),
+      Ok(other) => Err(format!("unexpected result {other:?}")),
+      Err(e) => Err(e.to_string()),
+    }
+  }
+
+  #[test]
+  fn clauses() {
+    let cases = [
+      ("'%s, %s and %s'.format(['a', 1, true])", "a, 1 and true"),
+      ("'%s'.format([b'abc'])", "abc"),
+      ("'%s'.format([1.5])", "1.5"),
+      ("'%s'.format([null])", "null"),
+      ("'%s'.format([[1, 'a', [2.5]]])", "[1, a, [2.5]]"),
+      ("'%s'.format([{'b': 2, 'a': 1}])", "{a: 1, b: 2}"),
+      ("'%s'.format([duration('90s')])", "90s"),
+      ("'%s'.format([duration('1.5s')])", "1.5s"),
+      (
+        "'%s'.format([timestamp('2023-02-03T23:31:20Z')])",
+        "2023-02-03T23:31:20Z",
+      ),
+      (
+        "'must be between %d and %d'.format([-5, uint(10)])",
+        "must be between -5 and 10",
+      ),
+      ("'%f'.format([3.14])", "3.140000"),
+      ("'%.2f'.format([3.14159])", "3.14"),
+      ("'%.0f'.format([2])", "2"),
+      (
+        "'%f %f'.format([double('NaN'), double('-Infinity')])",
+        "NaN -Infinity",
+      ),
+      ("'%e'.format([1052.032911275])", "1.052033e+03"),
+      ("'%.2e'.format([0.000123])", "1.23e-04"),
+      ("'%x %X'.format([255, uint(255)])", "ff FF"),
+      ("'%x'.format([-255])", "-ff"),
+      ("'%x'.format(['Hi'])", "4869"),
+      ("'%X'.format([b'\\xff'])", "FF"),
+      ("'%o'.format([8])", "10"),
+      ("'%b %b %b'.format([5, true, false])", "101 1 0"),
+      ("'100%%'.format([])", "100%"),
+    ];
+
+    for (expression, expected) in cases {
+      assert_eq!(format(expression).as_deref(), Ok(expected), "{expression}");
+    }
+  }
+
+  #[test]
+  fn errors() {
+    let cases = [
+      ("'%d and %d'.format([1])", "not enough arguments"),
+      ("'%d'.format([1, 2])", "too many arguments"),
+      ("'%d'.format([1.5])", "only supports integers"),
+      ("'%f'.format(['a'])", "only supports numbers"),
+      ("'%q'.format([1])", "unrecognized formatting clause %q"),
+      ("'%.f'.format([1.5])", "invalid precision"),
+      ("'50%'.format([])", "unterminated formatting clause"),
+    ];
+
+    for (expression, expected) in cases {
+      let error = format(expression).unwrap_err();
+
+      assert!(error.contains(expected), "{expression}: {error}");
+    }
+  }
+}
@@ -5,6 +5,7 @@ use crate::protovalidate::violations_data::{
   gt_violations::*, gte_violations::*, lt_violations::*, lte_violations::*,
 };
 
+// The wrappers of the integer types are compared with the plain values of the rules
 pub trait Comparable: PartialOrd {
   const LT_VIOLATION: &'static LazyLock<ViolationData>;
   const LTE_VIOLATION: &'static LazyLock<ViolationData>;
@@ -41,14 +42,14 @@ impl_comparable!(Fixed64, FIXED64);
 impl_comparable!(Duration, DURATION);
 impl_comparable!(Timestamp, TIMESTAMP);
 
-pub fn lt<T>(
+pub fn lt<T, U>(
   field_context: &FieldContext,
   value: T,
-  target: T,
+  target: U,
   error_message: &str,
 ) -> Result<(), Violation>
 where
-  T: Comparable,
+  T: Comparable + PartialOrd<U>,
 {
   let is_valid = value < target;
 
@@ -63,14 +64,14 @@ where
   }
 }
 
-pub fn lte<T>(
+pub fn lte<T, U>(
   field_context: &FieldContext,
   value: T,
-  target: T,
+  target: U,
   error_message: &str,
 ) -> Result<(), Violation>
 where
-  T: Comparable,
+  T: Comparable + PartialOrd<U>,
 {
   let is_valid = value <= target;
 
@@ -85,14 +86,14 @@ where
   }
 }
 
-pub fn gt<T>(
+pub fn gt<T, U>(
   field_context: &FieldContext,
   value: T,
-  target: T,
+  target: U,
   error_message: &str,
 ) -> Result<(), Violation>
 where
-  T: Comparable,
+  T: Comparable + PartialOrd<U>,
 {
   let is_valid = value > target;
 
@@ -107,14 +108,14 @@ where
   }
 }
 
-pub fn gte<T>(
+pub fn gte<T, U>(
   field_context: &FieldContext,
   value: T,
-  target: T,
+  target: U,
   error_message: &str,
 ) -> Result<(), Violation>
 where
-  T: Comparable,
+  T: Comparable + PartialOrd<U>,
 {
   let is_valid = value >= target;
 
@@ -128,3 +129,51 @@ where
     ))
   }
 }
+
+/// Checks a range with both a lower and an upper bound, which is reported as a single violation of the lower bound
+/// with an id made of both of them (such as `int32.gt_lt`), like in protovalidate.
+pub fn range<T, U>(
+  field_context: &FieldContext,
+  value: T,
+  lower: U,
+  lower_inclusive: bool,
+  upper: U,
+  upper_inclusive: bool,
+  error_message: &str,
+) -> Result<(), Violation>
+where
+  T: Comparable + PartialOrd<U>,
+{
+  let above_lower = if lower_inclusive {
+    value >= lower
+  } else {
+    value > lower
+  };
+  let below_upper = if upper_inclusive {
+    value <= upper
+  } else {
+    value < upper
+  };
+
+  if above_lower && below_upper {
+    return Ok(());
+  }
+
+  let violation_data = if lower_inclusive {
+    T::GTE_VIOLATION
+  } else {
+    T::GT_VIOLATION
+  };
+  let rule_id = format!(
+    "{}_{}",
+    violation_data.name,
+    if upper_inclusive { "lte" } else { "lt" }
+  );
+
+  Err(create_violation_with_custom_id(
+    &rule_id,
+    field_context,
+    violation_data,
+    error_message,
+  ))
+}
@@ -20,7 +20,7 @@ macro_rules! impl_const {
 impl_const!(&str, STRING);
 impl_const!(EnumVariant, ENUM);
 impl_const!(bool, BOOL);
-impl_const!(&::bytes::Bytes, BYTES);
+impl_const!(&[u8], BYTES);
 impl_const!(Duration, DURATION);
 impl_const!(Timestamp, TIMESTAMP);
 impl_const!(i64, INT64);
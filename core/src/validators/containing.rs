@@ -3,13 +3,21 @@ use proto_types::{Any, Duration};
 use super::*;
 use crate::protovalidate::violations_data::{in_violations::*, not_in_violations::*};
 
+/// The set used for the long `in` and `not_in` lists, which comes from `hashbrown` without the `std` feature.
+#[cfg(feature = "std")]
+pub use std::collections::HashSet;
+
+#[cfg(not(feature = "std"))]
+pub use hashbrown::HashSet;
+
 pub trait ListLookup<Item = Self>: Sized {
   const IN_VIOLATION: &'static LazyLock<ViolationData>;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData>;
 
-  type Container;
+  /// The list of the rule, which borrows the items of the static lists in the generated validators, and those of the rules read from the descriptors in the dynamic validator.
+  type Container<'a>;
 
-  fn is_in(container: &Self::Container, item: Item) -> bool;
+  fn is_in(container: &Self::Container<'_>, item: Item) -> bool;
 }
 
 pub enum HashLookup<'a, T> {
@@ -24,9 +32,9 @@ macro_rules! impl_hash_lookup {
         const IN_VIOLATION: &'static LazyLock<ViolationData> = &[< $proto_type _IN_VIOLATION >];
         const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &[< $proto_type _NOT_IN_VIOLATION >];
 
-        type Container = HashLookup<'static, $typ>;
+        type Container<'a> = HashLookup<'a, $typ>;
 
-        fn is_in(container: &Self::Container, item: $typ) -> bool {
+        fn is_in(container: &Self::Container<'_>, item: $typ) -> bool {
           match container {
             HashLookup::Slice(slice) => slice.contains(&item),
             HashLookup::Set(set) => set.contains(&item),
@@ -42,12 +50,12 @@ macro_rules! impl_hash_lookup {
         const IN_VIOLATION: &'static LazyLock<ViolationData> = &[< $proto_type _IN_VIOLATION >];
         const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &[< $proto_type _NOT_IN_VIOLATION >];
 
-        type Container = HashLookup<'static, $target>;
+        type Container<'a> = HashLookup<'a, $target>;
 
-        fn is_in(container: &Self::Container, item: $wrapper) -> bool {
+        fn is_in(container: &Self::Container<'_>, item: $wrapper) -> bool {
           match container {
             HashLookup::Slice(slice) => slice.contains(&item),
-            HashLookup::Set(set) => set.contains(&item),
+            HashLookup::Set(set) => set.contains(&*item),
           }
         }
       }
@@ -59,9 +67,9 @@ macro_rules! impl_hash_lookup {
 impl ListLookup for f32 {
   const IN_VIOLATION: &'static LazyLock<ViolationData> = &FLOAT_IN_VIOLATION;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &FLOAT_NOT_IN_VIOLATION;
-  type Container = HashLookup<'static, f32>;
+  type Container<'a> = HashLookup<'a, f32>;
 
-  fn is_in(container: &Self::Container, item: Self) -> bool {
+  fn is_in(container: &Self::Container<'_>, item: Self) -> bool {
     match container {
       HashLookup::Slice(items) => items.contains(&item),
       HashLookup::Set(_) => {
@@ -75,9 +83,9 @@ impl ListLookup for f32 {
 impl ListLookup for f64 {
   const IN_VIOLATION: &'static LazyLock<ViolationData> = &DOUBLE_IN_VIOLATION;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &DOUBLE_NOT_IN_VIOLATION;
-  type Container = HashLookup<'static, f64>;
+  type Container<'a> = HashLookup<'a, f64>;
 
-  fn is_in(container: &Self::Container, item: Self) -> bool {
+  fn is_in(container: &Self::Container<'_>, item: Self) -> bool {
     match container {
       HashLookup::Slice(items) => items.contains(&item),
       HashLookup::Set(_) => {
@@ -91,9 +99,14 @@ impl ListLookup for f64 {
 impl ListLookup for f32 {
   const IN_VIOLATION: &'static LazyLock<ViolationData> = &FLOAT_IN_VIOLATION;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &FLOAT_NOT_IN_VIOLATION;
-  type Container = HashLookup<'static, ordered_float::OrderedFloat<f32>>;
+  type Container<'a> = HashLookup<'a, ordered_float::OrderedFloat<f32>>;
+
+  fn is_in(container: &Self::Container<'_>, item: Self) -> bool {
+    // OrderedFloat considers NaN equal to itself, unlike the comparisons of protovalidate
+    if item.is_nan() {
+      return false;
+    }
 
-  fn is_in(container: &Self::Container, item: Self) -> bool {
     match container {
       HashLookup::Slice(items) => items.contains(&ordered_float::OrderedFloat(item)),
       HashLookup::Set(set) => set.contains(&ordered_float::OrderedFloat(item)),
@@ -105,9 +118,14 @@ impl ListLookup for f32 {
 impl ListLookup for f64 {
   const IN_VIOLATION: &'static LazyLock<ViolationData> = &DOUBLE_IN_VIOLATION;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &DOUBLE_NOT_IN_VIOLATION;
-  type Container = HashLookup<'static, ordered_float::OrderedFloat<f64>>;
+  type Container<'a> = HashLookup<'a, ordered_float::OrderedFloat<f64>>;
+
+  fn is_in(container: &Self::Container<'_>, item: Self) -> bool {
+    // OrderedFloat considers NaN equal to itself, unlike the comparisons of protovalidate
+    if item.is_nan() {
+      return false;
+    }
 
-  fn is_in(container: &Self::Container, item: Self) -> bool {
     match container {
       HashLookup::Slice(items) => items.contains(&ordered_float::OrderedFloat(item)),
       HashLookup::Set(set) => set.contains(&ordered_float::OrderedFloat(item)),
@@ -130,15 +148,15 @@ impl_hash_lookup!(u64, UINT64);
 impl_hash_lookup!(u32, UINT32);
 impl_hash_lookup!(Duration, DURATION);
 
-impl ListLookup<&::bytes::Bytes> for &::bytes::Bytes {
+impl ListLookup<&[u8]> for &[u8] {
   const IN_VIOLATION: &'static LazyLock<ViolationData> = &BYTES_IN_VIOLATION;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &BYTES_NOT_IN_VIOLATION;
-  type Container = HashLookup<'static, &'static [u8]>;
+  type Container<'a> = HashLookup<'a, &'a [u8]>;
 
-  fn is_in(container: &Self::Container, item: &::bytes::Bytes) -> bool {
+  fn is_in(container: &Self::Container<'_>, item: &[u8]) -> bool {
     match container {
-      HashLookup::Slice(slice) => slice.contains(&item.as_ref()),
-      HashLookup::Set(set) => set.contains(&item.as_ref()),
+      HashLookup::Slice(slice) => slice.contains(&item),
+      HashLookup::Set(set) => set.contains(item),
     }
   }
 }
@@ -146,12 +164,12 @@ impl ListLookup<&::bytes::Bytes> for &::bytes::Bytes {
 impl ListLookup<&Any> for &Any {
   const IN_VIOLATION: &'static LazyLock<ViolationData> = &ANY_IN_VIOLATION;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &ANY_NOT_IN_VIOLATION;
-  type Container = HashLookup<'static, &'static str>;
+  type Container<'a> = HashLookup<'a, &'a str>;
 
-  fn is_in(container: &Self::Container, item: &Any) -> bool {
+  fn is_in(container: &Self::Container<'_>, item: &Any) -> bool {
     match container {
-      HashLookup::Slice(slice) => slice.contains(&item.type_url.as_ref()),
-      HashLookup::Set(set) => set.contains(&item.type_url.as_ref()),
+      HashLookup::Slice(slice) => slice.iter().any(|url| *url == item.type_url),
+      HashLookup::Set(set) => set.contains(item.type_url.as_str()),
     }
   }
 }
@@ -159,12 +177,12 @@ impl ListLookup<&Any> for &Any {
 impl ListLookup<&str> for &str {
   const IN_VIOLATION: &'static LazyLock<ViolationData> = &STRING_IN_VIOLATION;
   const NOT_IN_VIOLATION: &'static LazyLock<ViolationData> = &STRING_NOT_IN_VIOLATION;
-  type Container = HashLookup<'static, &'static str>;
+  type Container<'a> = HashLookup<'a, &'a str>;
 
-  fn is_in(container: &Self::Container, item: &str) -> bool {
+  fn is_in(container: &Self::Container<'_>, item: &str) -> bool {
     match container {
       HashLookup::Slice(slice) => slice.contains(&item),
-      HashLookup::Set(set) => set.contains(&item),
+      HashLookup::Set(set) => set.contains(item),
     }
   }
 }
@@ -172,7 +190,7 @@ impl ListLookup<&str> for &str {
 pub fn in_list<T>(
   field_context: &FieldContext,
   value: T,
-  list: &T::Container,
+  list: &T::Container<'_>,
   error_message: &str,
 ) -> Result<(), Violation>
 where
@@ -194,7 +212,7 @@ where
 pub fn not_in_list<T>(
   field_context: &FieldContext,
   value: T,
-  list: &T::Container,
+  list: &T::Container<'_>,
   error_message: &str,
 ) -> Result<(), Violation>
 where
@@ -0,0 +1,44 @@
+use super::local::local_cell;
+use crate::protovalidate::Violation;
+
+local_cell! {
+  static FAIL_FAST: bool = false;
+}
+
+/// Returns true if the validation performed on this thread stops at the first violation.
+pub fn is_fail_fast() -> bool {
+  FAIL_FAST.get()
+}
+
+/// Runs the closure with the validation performed on this thread stopping at the first violation. This is what the `validate_fail_fast` method of the messages uses.
+///
+/// Without the `std` feature, the mode applies to all of the validations of the program instead, including the ones on the other cores and in the interrupt handlers.
+///
+/// # Examples
+/// ```rust
+/// use protocheck_core::validators::fail_fast::{is_fail_fast, with_fail_fast};
+///
+/// assert!(with_fail_fast(is_fail_fast));
+/// assert!(!is_fail_fast());
+/// ```
+pub fn with_fail_fast<T>(f: impl FnOnce() -> T) -> T {
+  // Restores the previous value even if the closure panics
+  struct Reset(bool);
+
+  impl Drop for Reset {
+    fn drop(&mut self) {
+      FAIL_FAST.set(self.0);
+    }
+  }
+
+  let _reset = Reset(FAIL_FAST.replace(true));
+
+  f()
+}
+
+/// Checks whether the validation should stop, which is the case when the fail-fast mode is active and a violation was found. This is called by the generated validators after each field and each item of the lists and maps.
+#[inline]
+pub fn should_stop(violations: &[Violation]) -> bool {
+  // The thread local is only read once there is a violation
+  !violations.is_empty() && is_fail_fast()
+}
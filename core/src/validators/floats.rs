@@ -2,7 +2,7 @@ use super::*;
 use crate::protovalidate::violations_data::{DOUBLE_FINITE_VIOLATION, FLOAT_FINITE_VIOLATION};
 
 pub fn float_is_finite(field_context: &FieldContext, value: f32) -> Result<(), Violation> {
-  let check = !value.is_nan();
+  let check = value.is_finite();
 
   if check {
     Ok(())
@@ -10,13 +10,13 @@ pub fn float_is_finite(field_context: &FieldContext, value: f32) -> Result<(), V
     Err(create_violation(
       field_context,
       &FLOAT_FINITE_VIOLATION,
-      "must be a finite number",
+      "must be finite",
     ))
   }
 }
 
 pub fn double_is_finite(field_context: &FieldContext, value: f64) -> Result<(), Violation> {
-  let check = !value.is_nan();
+  let check = value.is_finite();
 
   if check {
     Ok(())
@@ -24,7 +24,7 @@ pub fn double_is_finite(field_context: &FieldContext, value: f64) -> Result<(),
     Err(create_violation(
       field_context,
       &DOUBLE_FINITE_VIOLATION,
-      "must be a finite number",
+      "must be finite",
     ))
   }
 }
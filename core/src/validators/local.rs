@@ -0,0 +1,57 @@
+// The settings of the validation are local to each thread with the standard library, and global without it, as there is no notion of thread there. All of the kinds of cells have the `get`, `set` and `replace` methods.
+//
+// Without the standard library, the settings (the fail-fast mode, the maximum depth and the pinned time) are therefore shared by all of the validations of the program, so they are not reentrant: a validation performed on another core or in an interrupt handler while one of the `with_*` functions is running sees its settings. These cells are atomics, so they never block (the pinned time has its own cell, in the timestamps module).
+
+#[cfg(feature = "std")]
+macro_rules! local_cell {
+  (static $name:ident: $ty:ty = $init:expr;) => {
+    thread_local! {
+      static $name: core::cell::Cell<$ty> = const { core::cell::Cell::new($init) };
+    }
+  };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! local_cell {
+  (static $name:ident: bool = $init:expr;) => {
+    static $name: $crate::validators::local::BoolCell =
+      $crate::validators::local::BoolCell::new($init);
+  };
+  (static $name:ident: usize = $init:expr;) => {
+    static $name: $crate::validators::local::UsizeCell =
+      $crate::validators::local::UsizeCell::new($init);
+  };
+}
+
+pub(crate) use local_cell;
+
+#[cfg(not(feature = "std"))]
+macro_rules! atomic_cell {
+  ($name:ident, $atomic:ty, $ty:ty) => {
+    pub(crate) struct $name($atomic);
+
+    impl $name {
+      pub(crate) const fn new(value: $ty) -> Self {
+        Self(<$atomic>::new(value))
+      }
+
+      pub(crate) fn get(&self) -> $ty {
+        self.0.load(core::sync::atomic::Ordering::SeqCst)
+      }
+
+      pub(crate) fn set(&self, value: $ty) {
+        self.0.store(value, core::sync::atomic::Ordering::SeqCst);
+      }
+
+      pub(crate) fn replace(&self, value: $ty) -> $ty {
+        self.0.swap(value, core::sync::atomic::Ordering::SeqCst)
+      }
+    }
+  };
+}
+
+#[cfg(not(feature = "std"))]
+atomic_cell!(BoolCell, core::sync::atomic::AtomicBool, bool);
+
+#[cfg(not(feature = "std"))]
+atomic_cell!(UsizeCell, core::sync::atomic::AtomicUsize, usize);
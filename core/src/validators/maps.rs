@@ -1,13 +1,32 @@
+use alloc::collections::BTreeMap;
+
 use super::*;
 use crate::protovalidate::violations_data::{MAP_MAX_PAIRS_VIOLATION, MAP_MIN_PAIRS_VIOLATION};
 
-pub fn min_pairs<K, V>(
+/// The maps generated by prost, which are BTreeMaps when it is configured with `btree_map`.
+pub trait ProtoMap {
+  fn pairs(&self) -> usize;
+}
+
+impl<K, V, S> ProtoMap for HashMap<K, V, S> {
+  fn pairs(&self) -> usize {
+    self.len()
+  }
+}
+
+impl<K, V> ProtoMap for BTreeMap<K, V> {
+  fn pairs(&self) -> usize {
+    self.len()
+  }
+}
+
+pub fn min_pairs<M: ProtoMap>(
   field_context: &FieldContext,
-  value: &HashMap<K, V>,
+  value: &M,
   min_pairs: u64,
   error_message: &str,
 ) -> Result<(), Violation> {
-  let check = value.len() >= min_pairs as usize;
+  let check = value.pairs() >= min_pairs as usize;
 
   if check {
     Ok(())
@@ -20,13 +39,13 @@ pub fn min_pairs<K, V>(
   }
 }
 
-pub fn max_pairs<K, V>(
+pub fn max_pairs<M: ProtoMap>(
   field_context: &FieldContext,
-  value: &HashMap<K, V>,
+  value: &M,
   max_pairs: u64,
   error_message: &str,
 ) -> Result<(), Violation> {
-  let check = value.len() <= max_pairs as usize;
+  let check = value.pairs() <= max_pairs as usize;
 
   if check {
     Ok(())
@@ -1,7 +1,10 @@
-use std::{
-  collections::{HashMap, HashSet},
-  sync::LazyLock,
-};
+use alloc::{format, string::ToString, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(not(feature = "std"))]
+use hashbrown::{HashMap, HashSet};
+use proto_types::sync::LazyLock;
 
 use super::*;
 use crate::{
@@ -13,6 +16,8 @@ use crate::{
 
 pub(crate) mod create_violation;
 
+mod local;
+
 /// Validators for bytes fields.
 pub mod bytes;
 
@@ -32,6 +37,9 @@ pub mod containing;
 /// Validators for enum fields.
 pub mod enums;
 
+/// The fail-fast mode, which stops the validation at the first violation.
+pub mod fail_fast;
+
 /// Validators for floating point numbers (f32 and f64).
 pub mod floats;
 
@@ -41,16 +49,26 @@ pub mod maps;
 /// Validators for protobuf oneofs.
 pub mod oneofs;
 
+/// The limit on the depth of the nested messages.
+pub mod recursion;
+
 /// Validators for repeated fields.
 pub mod repeated;
 
 /// Validators for fields that are marked as "required" in the protovalidate annotations.
 pub mod required;
 
+/// The messages of the rules and the checks of their conflicting values, shared by the proc macro and the dynamic validator.
+#[cfg(any(feature = "totokens", feature = "dynamic"))]
+pub mod rule_messages;
+
 /// Validators for strings.
 pub mod string;
 
 /// Validators for timestamp fields.
 pub mod timestamps;
 
+#[cfg(feature = "cel")]
+mod cel_format;
+
 mod well_known_strings;
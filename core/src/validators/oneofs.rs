@@ -1,10 +1,13 @@
+use alloc::{string::ToString, vec};
+
 use crate::{
+  field_data::ParentMessages,
   protovalidate::{FieldPath, FieldPathElement, Violation},
   ProtoType,
 };
 
-pub fn required(name: &str, parent_elements: &[FieldPathElement]) -> Violation {
-  let mut elements = parent_elements.to_vec();
+pub fn required(name: &str, parent_messages: &ParentMessages) -> Violation {
+  let mut elements = parent_messages.to_elements();
 
   elements.push(FieldPathElement {
     field_name: Some(name.to_string()),
@@ -16,10 +19,6 @@ pub fn required(name: &str, parent_elements: &[FieldPathElement]) -> Violation {
   });
 
   Violation {
-    rule_id: Some("oneof.required".to_string()),
-    message: Some("at least one value is required".to_string()),
-    for_key: None,
-    field: Some(FieldPath { elements }),
     rule: Some(FieldPath {
       elements: vec![FieldPathElement {
         field_name: Some("required".to_string()),
@@ -30,5 +29,6 @@ pub fn required(name: &str, parent_elements: &[FieldPathElement]) -> Violation {
         value_type: None,
       }],
     }),
+    ..Violation::new(elements, "oneof.required", "at least one value is required")
   }
 }
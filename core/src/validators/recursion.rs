@@ -0,0 +1,57 @@
+use alloc::format;
+
+use super::local::local_cell;
+use crate::{field_data::ParentMessages, protovalidate::Violation};
+
+/// The maximum depth of the nested messages, used unless it is changed with [`with_max_depth`]. Like in protovalidate, the messages nested more deeply are not validated.
+pub const DEFAULT_MAX_DEPTH: usize = 100;
+
+local_cell! {
+  static MAX_DEPTH: usize = DEFAULT_MAX_DEPTH;
+}
+
+/// Returns the maximum depth of the nested messages that are validated on this thread.
+pub fn max_depth() -> usize {
+  MAX_DEPTH.get()
+}
+
+/// Runs the closure with the maximum depth of the nested messages set to `max_depth` for the validation performed on this thread.
+///
+/// Without the `std` feature, the depth applies to all of the validations of the program instead, including the ones on the other cores and in the interrupt handlers.
+///
+/// # Examples
+/// ```rust
+/// use protocheck_core::validators::recursion::{max_depth, with_max_depth, DEFAULT_MAX_DEPTH};
+///
+/// assert_eq!(with_max_depth(10, max_depth), 10);
+/// assert_eq!(max_depth(), DEFAULT_MAX_DEPTH);
+/// ```
+pub fn with_max_depth<T>(max_depth: usize, f: impl FnOnce() -> T) -> T {
+  // Restores the previous value even if the closure panics
+  struct Reset(usize);
+
+  impl Drop for Reset {
+    fn drop(&mut self) {
+      MAX_DEPTH.set(self.0);
+    }
+  }
+
+  let _reset = Reset(MAX_DEPTH.replace(max_depth));
+
+  f()
+}
+
+/// Checks the depth of a nested message, which is the number of elements in its path, so that the self-referential messages cannot exhaust the stack.
+pub fn recursion_limit(parent_messages: &ParentMessages) -> Result<(), Violation> {
+  let max_depth = max_depth();
+
+  if parent_messages.len() <= max_depth {
+    return Ok(());
+  }
+
+  Err(Violation::new(
+    parent_messages.to_elements(),
+    "message.recursion_limit",
+    format!("exceeds the maximum depth of {} nested messages", max_depth),
+  ))
+}
@@ -43,11 +43,55 @@ pub fn max_items<T>(
   }
 }
 
+/// The number of items that are kept on the stack by [`UniqueLookup::Inline`].
+pub const INLINE_UNIQUE_ITEMS: usize = 16;
+
+/// The items of a list that have already been checked by the `repeated.unique` rule.
 pub enum UniqueLookup<T> {
+  /// Used for the short lists, so that they are checked without allocating.
+  Inline([Option<T>; INLINE_UNIQUE_ITEMS], usize),
   Vec(Vec<T>),
   Set(HashSet<T>),
 }
 
+impl<T: Copy + PartialEq> UniqueLookup<T> {
+  pub fn inline() -> Self {
+    Self::Inline([None; INLINE_UNIQUE_ITEMS], 0)
+  }
+
+  // The lookups other than the sets compare the items one by one
+  fn insert_scanned(&mut self, item: T) -> bool {
+    match self {
+      Self::Inline(items, len) => {
+        if items[..*len].contains(&Some(item)) {
+          return false;
+        }
+
+        if *len < INLINE_UNIQUE_ITEMS {
+          items[*len] = Some(item);
+          *len += 1;
+        } else {
+          // Only happens if the list is longer than expected
+          let mut vec: Vec<T> = items.iter().flatten().copied().collect();
+          vec.push(item);
+          *self = Self::Vec(vec);
+        }
+
+        true
+      }
+      Self::Vec(vec) => {
+        if vec.contains(&item) {
+          false
+        } else {
+          vec.push(item);
+          true
+        }
+      }
+      Self::Set(_) => unreachable!("the sets are checked by the caller"),
+    }
+  }
+}
+
 pub trait UniqueItem<Item = Self> {
   type Container;
 
@@ -61,15 +105,8 @@ macro_rules! impl_unique {
 
       fn check_unique(container: &mut UniqueLookup<Self>, item: Self) -> bool {
         match container {
-          UniqueLookup::Vec(vec) => {
-            if vec.contains(&item) {
-              false
-            } else {
-              vec.push(item);
-              true
-            }
-          }
           UniqueLookup::Set(set) => set.insert(item),
+          lookup => lookup.insert_scanned(item),
         }
       }
     }
@@ -96,17 +133,10 @@ impl UniqueItem for f32 {
 
   fn check_unique(container: &mut UniqueLookup<Self>, item: Self) -> bool {
     match container {
-      UniqueLookup::Vec(vec) => {
-        if vec.contains(&item) {
-          false
-        } else {
-          vec.push(item);
-          true
-        }
-      }
       UniqueLookup::Set(_) => {
         panic!("Cannot use set lookups for floats without the ordered-float flag")
       }
+      lookup => lookup.insert_scanned(item),
     }
   }
 }
@@ -117,17 +147,10 @@ impl UniqueItem for f64 {
 
   fn check_unique(container: &mut UniqueLookup<Self>, item: Self) -> bool {
     match container {
-      UniqueLookup::Vec(vec) => {
-        if vec.contains(&item) {
-          false
-        } else {
-          vec.push(item);
-          true
-        }
-      }
       UniqueLookup::Set(_) => {
         panic!("Cannot use set lookups for floats without the ordered-float flag")
       }
+      lookup => lookup.insert_scanned(item),
     }
   }
 }
@@ -140,18 +163,16 @@ impl UniqueItem<ordered_float::OrderedFloat<f32>> for f32 {
     container: &mut UniqueLookup<ordered_float::OrderedFloat<f32>>,
     item: Self,
   ) -> bool {
+    // OrderedFloat considers NaN equal to itself, while NaN is never equal to another value in protovalidate
+    if item.is_nan() {
+      return true;
+    }
+
     let item = ordered_float::OrderedFloat(item);
 
     match container {
-      UniqueLookup::Vec(vec) => {
-        if vec.contains(&item) {
-          false
-        } else {
-          vec.push(item);
-          true
-        }
-      }
       UniqueLookup::Set(set) => set.insert(item),
+      lookup => lookup.insert_scanned(item),
     }
   }
 }
@@ -164,36 +185,27 @@ impl UniqueItem<ordered_float::OrderedFloat<f64>> for f64 {
     container: &mut UniqueLookup<ordered_float::OrderedFloat<f64>>,
     item: Self,
   ) -> bool {
+    // OrderedFloat considers NaN equal to itself, while NaN is never equal to another value in protovalidate
+    if item.is_nan() {
+      return true;
+    }
+
     let item = ordered_float::OrderedFloat(item);
 
     match container {
-      UniqueLookup::Vec(vec) => {
-        if vec.contains(&item) {
-          false
-        } else {
-          vec.push(item);
-          true
-        }
-      }
       UniqueLookup::Set(set) => set.insert(item),
+      lookup => lookup.insert_scanned(item),
     }
   }
 }
 
-impl UniqueItem for &::bytes::Bytes {
+impl UniqueItem for &[u8] {
   type Container = UniqueLookup<Self>;
 
   fn check_unique(container: &mut UniqueLookup<Self>, item: Self) -> bool {
     match container {
-      UniqueLookup::Vec(vec) => {
-        if vec.contains(&item) {
-          false
-        } else {
-          vec.push(item);
-          true
-        }
-      }
       UniqueLookup::Set(set) => set.insert(item),
+      lookup => lookup.insert_scanned(item),
     }
   }
 }
@@ -203,15 +215,8 @@ impl UniqueItem for &str {
 
   fn check_unique(container: &mut UniqueLookup<Self>, item: Self) -> bool {
     match container {
-      UniqueLookup::Vec(vec) => {
-        if vec.contains(&item) {
-          false
-        } else {
-          vec.push(item);
-          true
-        }
-      }
       UniqueLookup::Set(set) => set.insert(item),
+      lookup => lookup.insert_scanned(item),
     }
   }
 }
@@ -229,10 +234,17 @@ where
   if is_valid {
     Ok(())
   } else {
+    // The rule belongs to the list rather than to the duplicate item, like in protovalidate
+    let list_context = FieldContext {
+      subscript: None,
+      field_kind: FieldKind::Repeated(field_context.field_kind.inner_type()),
+      ..*field_context
+    };
+
     Err(create_violation(
-      field_context,
+      &list_context,
       &REPEATED_UNIQUE_VIOLATION,
-      "must contain unique values",
+      "must contain unique items",
     ))
   }
 }
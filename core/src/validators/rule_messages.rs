@@ -0,0 +1,203 @@
+use alloc::{
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
+use core::fmt::Display;
+
+use proto_types::{Duration, Timestamp};
+
+/// The prefix of the message of the `in` rules.
+pub const IN_LIST_PREFIX: &str = "must be in list";
+/// The prefix of the message of the `not_in` rules.
+pub const NOT_IN_LIST_PREFIX: &str = "must not be in list";
+/// The prefix of the message of the `in` rule of the `Any` fields.
+pub const TYPE_URL_IN_LIST_PREFIX: &str = "type URL must be in list";
+/// The prefix of the message of the `not_in` rule of the `Any` fields.
+pub const TYPE_URL_NOT_IN_LIST_PREFIX: &str = "type URL must not be in list";
+
+/// The adjectives and the representation of the values in the messages of the comparison rules.
+pub trait Bound: Copy + PartialOrd {
+  const SMALLER: &'static str = "less";
+  const GREATER: &'static str = "greater";
+
+  fn representation(&self) -> String;
+}
+
+macro_rules! impl_bound {
+  ($($typ:ty),*) => {
+    $(
+      impl Bound for $typ {
+        fn representation(&self) -> String {
+          self.to_string()
+        }
+      }
+    )*
+  };
+}
+
+impl_bound!(f32, f64, i32, i64, u32, u64);
+
+impl Bound for Duration {
+  const SMALLER: &'static str = "shorter";
+  const GREATER: &'static str = "longer";
+
+  fn representation(&self) -> String {
+    self.to_string()
+  }
+}
+
+impl Bound for Timestamp {
+  const SMALLER: &'static str = "earlier";
+  const GREATER: &'static str = "later";
+
+  fn representation(&self) -> String {
+    self
+      .format("%d %b %Y %R %Z")
+      .unwrap_or_else(|_| self.to_string())
+  }
+}
+
+fn bound_message<R: Bound>(adjective: &str, value: R, inclusive: bool) -> String {
+  let value = value.representation();
+
+  if inclusive {
+    format!("must be {adjective} than or equal to {value}")
+  } else {
+    format!("must be {adjective} than {value}")
+  }
+}
+
+/// The message of the `lt` rule, or of the `lte` rule when the bound is inclusive.
+pub fn less_than_message<R: Bound>(value: R, inclusive: bool) -> String {
+  bound_message(R::SMALLER, value, inclusive)
+}
+
+/// The message of the `gt` rule, or of the `gte` rule when the bound is inclusive.
+pub fn greater_than_message<R: Bound>(value: R, inclusive: bool) -> String {
+  bound_message(R::GREATER, value, inclusive)
+}
+
+/// The message of the range formed by a lower and an upper bound, which are checked as a single rule.
+pub fn range_message<R: Bound>(
+  lower: R,
+  lower_inclusive: bool,
+  upper: R,
+  upper_inclusive: bool,
+) -> String {
+  format!(
+    "{} and {}",
+    greater_than_message(lower, lower_inclusive),
+    less_than_message(upper, upper_inclusive).trim_start_matches("must be ")
+  )
+}
+
+/// Checks that the upper bound is above the lower one. The bounds come with a flag that is true for the inclusive ones.
+pub fn check_bounds<R: Bound>(
+  less_than: Option<(R, bool)>,
+  greater_than: Option<(R, bool)>,
+) -> Result<(), &'static str> {
+  let (Some((upper, upper_inclusive)), Some((lower, lower_inclusive))) = (less_than, greater_than)
+  else {
+    return Ok(());
+  };
+
+  let error = match (lower_inclusive, upper_inclusive) {
+    (true, true) => (upper < lower).then_some("Lte cannot be smaller than Gte"),
+    (true, false) => (upper <= lower).then_some("Lt cannot be smaller than Gte"),
+    (false, true) => (upper <= lower).then_some("Lte cannot be smaller than or equal to Gt"),
+    (false, false) => (upper <= lower).then_some("Lt cannot be smaller than or equal to Gt"),
+  };
+
+  match error {
+    Some(error) => Err(error),
+    None => Ok(()),
+  }
+}
+
+/// Checks that no item is both allowed and forbidden. The conflicting items are listed once, in the order of the `in` list.
+pub fn check_lists<T: PartialEq>(
+  in_list: &[T],
+  not_in_list: &[T],
+  display: impl Fn(&T) -> String,
+) -> Result<(), String> {
+  let mut conflicts: Vec<String> = Vec::new();
+
+  for item in in_list.iter().filter(|item| not_in_list.contains(item)) {
+    let item = display(item);
+
+    if !conflicts.contains(&item) {
+      conflicts.push(item);
+    }
+  }
+
+  if conflicts.is_empty() {
+    Ok(())
+  } else {
+    Err(format!(
+      "the following items are in the allowed and forbidden lists of items at the same time: {}",
+      conflicts.join(", ")
+    ))
+  }
+}
+
+/// The message of an `in` or `not_in` rule, such as `must be in list [a, b]`.
+pub fn list_message<T>(prefix: &str, list: &[T], display: impl Fn(&T) -> String) -> String {
+  let items: Vec<String> = list.iter().map(display).collect();
+
+  format!("{prefix} [{}]", items.join(", "))
+}
+
+/// The message of a `const` rule.
+pub fn const_message(value: impl Display) -> String {
+  format!("must be equal to {value}")
+}
+
+/// Checks that the exact length is not used with the minimum or the maximum, and that the minimum is not above the maximum. The rules come with their names, which are used in the errors.
+pub fn check_lengths(
+  len: Option<(&str, u64)>,
+  min: (&str, Option<u64>),
+  max: (&str, Option<u64>),
+) -> Result<(), String> {
+  let (min_name, min_value) = min;
+  let (max_name, max_value) = max;
+
+  if let Some((len_name, _)) = len
+    && (min_value.is_some() || max_value.is_some())
+  {
+    return Err(format!(
+      "{len_name} cannot be used with {min_name} or {max_name}"
+    ));
+  }
+
+  if let (Some(min_value), Some(max_value)) = (min_value, max_value)
+    && min_value > max_value
+  {
+    return Err(format!("{min_name} cannot be larger than {max_name}"));
+  }
+
+  Ok(())
+}
+
+fn plural(value: u64) -> &'static str {
+  if value != 1 {
+    "s"
+  } else {
+    ""
+  }
+}
+
+/// The message of the rules for the exact length, such as `len` or `len_bytes`. The unit is singular, like `byte`.
+pub fn len_message(value: u64, unit: &str) -> String {
+  format!("must be exactly {value} {unit}{} long", plural(value))
+}
+
+/// The message of the rules for the minimum length, such as `min_len` or `min_items`.
+pub fn min_len_message(value: u64, unit: &str) -> String {
+  format!("must contain at least {value} {unit}{}", plural(value))
+}
+
+/// The message of the rules for the maximum length, such as `max_len` or `max_items`.
+pub fn max_len_message(value: u64, unit: &str) -> String {
+  format!("cannot contain more than {value} {unit}{}", plural(value))
+}
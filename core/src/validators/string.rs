@@ -12,6 +12,14 @@ macro_rules! well_known_rule {
 
         if is_valid {
           Ok(())
+        } else if value.is_empty() {
+          // Like in protovalidate, the empty values have a separate rule id
+          Err(create_violation_with_custom_id(
+            concat!("string.", stringify!($name), "_empty"),
+            field_context,
+            &[< STRING _ $name:upper _ VIOLATION >],
+            concat!("is empty, which is not a valid ", $definition)
+          ))
         } else {
           Err(create_violation(
             field_context,
@@ -53,20 +61,24 @@ macro_rules! string_validator {
   };
 }
 
-// Char length
-string_validator!(
-  max_len,
-  u64,
-  |value: &str, max_len: u64| value.chars().count() as u64 <= max_len
-);
-string_validator!(
-  min_len,
-  u64,
-  |value: &str, min_len: u64| value.chars().count() as u64 >= min_len
-);
-string_validator!(len, u64, |value: &str, max_len: u64| value.chars().count()
-  as u64
-  == max_len);
+// Char length, counted in Unicode code points.
+// The counts stop as soon as the result is known, so that long strings are not scanned entirely
+fn chars_count_up_to(value: &str, limit: u64) -> u64 {
+  let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+
+  value.chars().take(limit).count() as u64
+}
+
+string_validator!(max_len, u64, |value: &str, max_len: u64| {
+  // Each code point takes at least one byte
+  value.len() as u64 <= max_len || chars_count_up_to(value, max_len.saturating_add(1)) <= max_len
+});
+string_validator!(min_len, u64, |value: &str, min_len: u64| {
+  value.len() as u64 >= min_len && chars_count_up_to(value, min_len) >= min_len
+});
+string_validator!(len, u64, |value: &str, len: u64| {
+  value.len() as u64 >= len && chars_count_up_to(value, len.saturating_add(1)) == len
+});
 
 // Bytes length
 string_validator!(len_bytes, u64, |value: &str, len: u64| value.len() as u64
@@ -94,33 +106,30 @@ string_validator!(prefix, &str, |value: &str, prefix: &str| value
 string_validator!(suffix, &str, |value: &str, suffix: &str| value
   .ends_with(suffix));
 
-well_known_rule!(
-  host_and_port,
-  "pair of host (hostname or IP address) and port"
-);
+well_known_rule!(host_and_port, "host (hostname or IP address) and port pair");
 
 well_known_rule!(hostname, "hostname");
 
+#[cfg(feature = "uri")]
 well_known_rule!(uri, "uri");
+#[cfg(feature = "uri")]
 well_known_rule!(uri_ref, "URI reference");
 
-well_known_rule!(address, "hostname or ip address");
-well_known_rule!(ip, "ip address");
-well_known_rule!(ipv4, "ipv4 address");
-well_known_rule!(ipv6, "ipv6 address");
-well_known_rule!(ip_prefix, "ip prefix");
-well_known_rule!(ipv4_prefix, "ipv4 prefix");
-well_known_rule!(ipv6_prefix, "ipv6 prefix");
-well_known_rule!(ip_with_prefixlen, "ip address with prefix length");
-well_known_rule!(ipv4_with_prefixlen, "ipv4 address with prefix length");
-well_known_rule!(ipv6_with_prefixlen, "ipv6 address with prefix length");
+well_known_rule!(address, "hostname, or ip address");
+well_known_rule!(ip, "IP address");
+well_known_rule!(ipv4, "IPv4 address");
+well_known_rule!(ipv6, "IPv6 address");
+well_known_rule!(ip_prefix, "IP prefix");
+well_known_rule!(ipv4_prefix, "IPv4 prefix");
+well_known_rule!(ipv6_prefix, "IPv6 prefix");
+well_known_rule!(ip_with_prefixlen, "IP address with prefix length");
+well_known_rule!(ipv4_with_prefixlen, "IPv4 address with prefix length");
+well_known_rule!(ipv6_with_prefixlen, "IPv6 address with prefix length");
 
 #[cfg(feature = "regex")]
 well_known_rule!(email, "email address");
-#[cfg(feature = "regex")]
-well_known_rule!(uuid, "uuid");
-#[cfg(feature = "regex")]
-well_known_rule!(tuuid, "trimmed uuid");
+well_known_rule!(uuid, "UUID");
+well_known_rule!(tuuid, "trimmed UUID");
 
 #[cfg(feature = "regex")]
 pub fn header_name(
@@ -128,6 +137,16 @@ pub fn header_name(
   value: &str,
   strict: bool,
 ) -> Result<(), Violation> {
+  // Like in protovalidate, the empty names have a separate rule
+  if value.is_empty() {
+    return Err(create_violation_with_custom_id(
+      "string.well_known_regex.header_name_empty",
+      field_context,
+      &STRING_WELL_KNOWN_REGEX_VIOLATION,
+      "is empty, which is not a valid HTTP header name",
+    ));
+  }
+
   let check = is_valid_http_header_name(value, strict);
 
   if check {
@@ -161,3 +180,18 @@ pub fn header_value(
     ))
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::chars_count_up_to;
+
+  #[test]
+  fn chars_counts() {
+    assert_eq!(chars_count_up_to("héllo", 10), 5);
+    assert_eq!(chars_count_up_to("héllo", 3), 3);
+    assert_eq!(chars_count_up_to("e\u{301}", 10), 2);
+    assert_eq!(chars_count_up_to("🦀🦀", 10), 2);
+    assert_eq!(chars_count_up_to("", 10), 0);
+    assert_eq!(chars_count_up_to("abc", u64::MAX), 3);
+  }
+}
@@ -1,14 +1,267 @@
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicU8, Ordering};
+use core::{error::Error, fmt};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
 use proto_types::{protovalidate::violations_data::timestamp_violations::*, Duration, Timestamp};
 
+#[cfg(feature = "std")]
+use super::local::local_cell;
 use super::*;
 
+// Where the rules take the current time from
+#[derive(Clone, Copy)]
+enum Now {
+  Clock,
+  // Inside of `with_lazy_now`, before the time is read for the first time
+  FirstUse,
+  Pinned(Timestamp),
+}
+
+#[cfg(feature = "std")]
+local_cell! {
+  static NOW_OVERRIDE: Now = Now::Clock;
+}
+
+#[cfg(not(feature = "std"))]
+static NOW_OVERRIDE: NowCell = NowCell::new();
+
+// The global cell of the time without the standard library. Its kind is an atomic, so the spin lock is only taken when the time is pinned, and not by the validations that do not use it.
+#[cfg(not(feature = "std"))]
+struct NowCell {
+  kind: AtomicU8,
+  pinned: spin::Mutex<Timestamp>,
+}
+
+#[cfg(not(feature = "std"))]
+impl NowCell {
+  const CLOCK: u8 = 0;
+  const FIRST_USE: u8 = 1;
+  const PINNED: u8 = 2;
+
+  const fn new() -> Self {
+    Self {
+      kind: AtomicU8::new(Self::CLOCK),
+      pinned: spin::Mutex::new(Timestamp {
+        seconds: 0,
+        nanos: 0,
+      }),
+    }
+  }
+
+  fn get(&self) -> Now {
+    match self.kind.load(Ordering::SeqCst) {
+      Self::PINNED => Now::Pinned(*self.pinned.lock()),
+      Self::FIRST_USE => Now::FirstUse,
+      _ => Now::Clock,
+    }
+  }
+
+  fn set(&self, now: Now) {
+    let kind = match now {
+      Now::Clock => Self::CLOCK,
+      Now::FirstUse => Self::FIRST_USE,
+      Now::Pinned(now) => {
+        *self.pinned.lock() = now;
+        Self::PINNED
+      }
+    };
+
+    self.kind.store(kind, Ordering::SeqCst);
+  }
+
+  fn replace(&self, now: Now) -> Now {
+    let previous = self.get();
+    self.set(now);
+    previous
+  }
+}
+
+#[cfg(feature = "std")]
+static CLOCK: OnceLock<Box<dyn Clock>> = OnceLock::new();
+
+#[cfg(not(feature = "std"))]
+static CLOCK: spin::Once<Box<dyn Clock>> = spin::Once::new();
+
+/// The source of the current time for the `lt_now`, `gt_now` and `within` rules, and for the `now` variable of the Cel rules.
+///
+/// It is implemented for the closures that return a [`Timestamp`], and it can be replaced once with [`set_clock`], before the first validation that uses it. Without the `std` feature there is no default clock, so it must be set before validating the messages with these rules.
+pub trait Clock: Send + Sync + 'static {
+  /// Returns the current time.
+  fn now(&self) -> Timestamp;
+}
+
+impl<F> Clock for F
+where
+  F: Fn() -> Timestamp + Send + Sync + 'static,
+{
+  fn now(&self) -> Timestamp {
+    self()
+  }
+}
+
+/// The default [`Clock`] with the `std` feature, which uses [`Timestamp::now`].
+///
+/// This is the system time, or `Date.now()` of JavaScript on `wasm32-unknown-unknown` with the `wasm` feature (as the system time is not available there, and reading it panics).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+  fn now(&self) -> Timestamp {
+    Timestamp::now()
+  }
+}
+
+/// The error returned by [`set_clock`] when the clock was already set, or already used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetClockError;
+
+impl fmt::Display for SetClockError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("the clock was already set")
+  }
+}
+
+impl Error for SetClockError {}
+
+/// Replaces the default clock (the `SystemClock`) with another [`Clock`] for the whole program.
+///
+/// This can only be done once, before the current time is read for the first time, like for the loggers of the `log` crate. To pin the time in the tests, use [`with_now`] instead.
+///
+/// Without the `std` feature, this is the only source of the current time, and the rules that depend on it panic if the clock was not set.
+///
+/// # Examples
+/// ```rust
+/// use protocheck_core::validators::timestamps::{now, set_clock};
+/// use proto_types::Timestamp;
+///
+/// set_clock(|| Timestamp::new(1_000, 0)).unwrap();
+///
+/// assert_eq!(now(), Timestamp::new(1_000, 0));
+/// assert!(set_clock(|| Timestamp::new(2_000, 0)).is_err());
+/// ```
+pub fn set_clock(clock: impl Clock) -> Result<(), SetClockError> {
+  #[cfg(feature = "std")]
+  return CLOCK.set(Box::new(clock)).map_err(|_| SetClockError);
+
+  #[cfg(not(feature = "std"))]
+  {
+    let mut clock = Some(Box::new(clock) as Box<dyn Clock>);
+    CLOCK.call_once(|| clock.take().unwrap());
+
+    // The clock is only taken if it was the first one
+    clock.map_or(Ok(()), |_| Err(SetClockError))
+  }
+}
+
+fn clock() -> &'static dyn Clock {
+  #[cfg(feature = "std")]
+  return CLOCK.get_or_init(|| Box::new(SystemClock)).as_ref();
+
+  #[cfg(not(feature = "std"))]
+  CLOCK
+    .get()
+    .expect("the rules that depend on the current time need a clock set with `set_clock` without the std feature")
+    .as_ref()
+}
+
+/// Returns the current time used by the `lt_now`, `gt_now` and `within` rules, which is the time of the [`Clock`] unless it is pinned with [`with_now`] (or read once by [`with_lazy_now`]).
+///
+/// # Panics
+///
+/// Without the `std` feature, if the time is not pinned and the clock was not set with [`set_clock`].
+pub fn now() -> Timestamp {
+  match NOW_OVERRIDE.get() {
+    Now::Pinned(now) => now,
+    Now::Clock => clock().now(),
+    Now::FirstUse => {
+      let now = clock().now();
+      NOW_OVERRIDE.set(Now::Pinned(now));
+      now
+    }
+  }
+}
+
+/// Runs the closure with the current time pinned to `now` for the timestamp rules evaluated on this thread, which is useful in tests.
+///
+/// Without the `std` feature, the time is pinned for all of the validations of the program instead, including the ones on the other cores and in the interrupt handlers.
+///
+/// # Examples
+/// ```rust
+/// use protocheck_core::validators::timestamps::{now, with_now};
+/// use proto_types::Timestamp;
+///
+/// let pinned = Timestamp::new(1_000, 0);
+///
+/// assert_eq!(with_now(pinned, now), pinned);
+/// ```
+pub fn with_now<T>(now: Timestamp, f: impl FnOnce() -> T) -> T {
+  let _reset = ResetNow(NOW_OVERRIDE.replace(Now::Pinned(now)));
+
+  f()
+}
+
+/// Runs the closure with the current time read from the [`Clock`] only once, the first time that a rule needs it, so that all of the rules checked by the closure (including the Cel rules) use the same time. This is what the `validate` method of the messages uses, so the validation of the messages without these rules never reads the clock.
+///
+/// The time pinned with [`with_now`] is used instead, if there is one.
+///
+/// # Examples
+/// ```rust
+/// use protocheck_core::validators::timestamps::{now, with_lazy_now};
+///
+/// let (first, second) = with_lazy_now(|| (now(), now()));
+///
+/// assert_eq!(first, second);
+/// ```
+pub fn with_lazy_now<T>(f: impl FnOnce() -> T) -> T {
+  // The time of an outer call (or the pinned one) is kept
+  if !matches!(NOW_OVERRIDE.get(), Now::Clock) {
+    return f();
+  }
+
+  let _reset = ResetNow(NOW_OVERRIDE.replace(Now::FirstUse));
+
+  f()
+}
+
+// Restores the previous time even if the closure panics
+struct ResetNow(Now);
+
+impl Drop for ResetNow {
+  fn drop(&mut self) {
+    NOW_OVERRIDE.set(self.0);
+  }
+}
+
+pub fn valid(field_context: &FieldContext, value: Timestamp) -> Result<(), Violation> {
+  let check = (0..1_000_000_000).contains(&value.nanos);
+
+  if check {
+    Ok(())
+  } else {
+    Err(create_violation(
+      field_context,
+      &TIMESTAMP_VALID_VIOLATION,
+      "must be a valid timestamp",
+    ))
+  }
+}
+
 pub fn within(
   field_context: &FieldContext,
   value: Timestamp,
   time_range: Duration,
   error_message: &str,
 ) -> Result<(), Violation> {
-  let check = value.is_within_range_from_now(time_range);
+  let now = now();
+
+  // A bound that falls outside of the range of the valid timestamps does not exclude anything
+  let check = now.checked_sub(&time_range).is_none_or(|min| min <= value)
+    && now.checked_add(&time_range).is_none_or(|max| value <= max);
 
   if check {
     Ok(())
@@ -22,7 +275,7 @@ pub fn within(
 }
 
 pub fn lt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), Violation> {
-  let check = value.is_past();
+  let check = value < now();
 
   if check {
     Ok(())
@@ -36,7 +289,7 @@ pub fn lt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), Viol
 }
 
 pub fn gt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), Violation> {
-  let check = value.is_future();
+  let check = value > now();
 
   if check {
     Ok(())
@@ -48,3 +301,40 @@ pub fn gt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), Viol
     ))
   }
 }
+
+#[cfg(all(test, feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+mod test {
+  use proto_types::FieldType;
+  use wasm_bindgen_test::wasm_bindgen_test;
+
+  use super::*;
+
+  #[wasm_bindgen_test]
+  fn now_rules() {
+    let parent_messages = ParentMessages::default();
+    let field_context = FieldContext {
+      proto_name: "created_at",
+      tag: 1,
+      parent_messages: &parent_messages,
+      subscript: None,
+      key_type: None,
+      value_type: None,
+      field_kind: FieldKind::Single(FieldType::Timestamp),
+    };
+
+    // The current time comes from `Date.now()`, as the system time is not available
+    let date_now = (js_sys::Date::now() / 1000.0) as i64;
+    assert!((now().seconds - date_now).abs() <= 1);
+
+    let past = Timestamp::new(date_now - 60, 0);
+    let future = Timestamp::new(date_now + 60, 0);
+
+    assert!(lt_now(&field_context, past).is_ok());
+    assert!(lt_now(&field_context, future).is_err());
+    assert!(gt_now(&field_context, future).is_ok());
+    assert!(gt_now(&field_context, past).is_err());
+
+    let range = Duration::new(120, 0);
+    assert!(within(&field_context, past, range, "must be within 2m from now").is_ok());
+  }
+}
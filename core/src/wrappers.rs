@@ -1,21 +1,29 @@
+use alloc::{string::String, vec::Vec};
+
 macro_rules! impl_wrapper {
   ($name:ident, $target:ty) => {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct $name(pub $target);
 
-    impl std::ops::Deref for $name {
+    impl core::ops::Deref for $name {
       type Target = $target;
       fn deref(&self) -> &Self::Target {
         &self.0
       }
     }
 
-    impl std::cmp::PartialEq<$target> for $name {
+    impl core::cmp::PartialEq<$target> for $name {
       fn eq(&self, other: &$target) -> bool {
         self.0 == *other
       }
     }
 
+    impl core::cmp::PartialOrd<$target> for $name {
+      fn partial_cmp(&self, other: &$target) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other)
+      }
+    }
+
     impl From<$name> for $target {
       fn from(value: $name) -> $target {
         value.0
@@ -31,3 +39,50 @@ impl_wrapper!(Sfixed32, i32);
 impl_wrapper!(Fixed64, u64);
 impl_wrapper!(Fixed32, u32);
 impl_wrapper!(EnumVariant, i32);
+
+/// The value of a field that uses one of the wrappers of the well known types, such as `google.protobuf.BoolValue`.
+/// prost turns these fields into the wrapped type, unless the well known types are compiled to a crate like the types of protocheck.
+pub trait WrapperValue {
+  type Value: ?Sized;
+
+  fn wrapped_value(&self) -> &Self::Value;
+}
+
+macro_rules! impl_wrapper_value {
+  ($wrapper:ty, $target:ty) => {
+    impl WrapperValue for $wrapper {
+      type Value = $target;
+
+      fn wrapped_value(&self) -> &Self::Value {
+        &self.value
+      }
+    }
+
+    impl WrapperValue for $target {
+      type Value = $target;
+
+      fn wrapped_value(&self) -> &Self::Value {
+        self
+      }
+    }
+  };
+}
+
+impl_wrapper_value!(proto_types::BoolValue, bool);
+impl_wrapper_value!(proto_types::StringValue, String);
+impl_wrapper_value!(proto_types::BytesValue, bytes::Bytes);
+impl_wrapper_value!(proto_types::Int32Value, i32);
+impl_wrapper_value!(proto_types::Int64Value, i64);
+impl_wrapper_value!(proto_types::UInt32Value, u32);
+impl_wrapper_value!(proto_types::UInt64Value, u64);
+impl_wrapper_value!(proto_types::FloatValue, f32);
+impl_wrapper_value!(proto_types::DoubleValue, f64);
+
+// The bytes of prost-types, which are always compiled to Vec<u8>
+impl WrapperValue for Vec<u8> {
+  type Value = Vec<u8>;
+
+  fn wrapped_value(&self) -> &Self::Value {
+    self
+  }
+}
@@ -0,0 +1,38 @@
+use std::{env, path::PathBuf};
+
+use prost_build::Config;
+use protocheck_build::{
+  compile_protos_with_validators, get_proto_paths_recursive, vendored_protovalidate_include_path,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  println!("cargo:rerun-if-changed=proto/");
+
+  let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+  let descriptor_path = out_dir.join("file_descriptor_set.bin");
+
+  let include_paths = [
+    PathBuf::from("proto"),
+    vendored_protovalidate_include_path(),
+  ];
+  let files = get_proto_paths_recursive("proto")?;
+
+  let mut config = Config::new();
+  // The maps of prost use the HashMap of the standard library by default
+  config
+    .file_descriptor_set_path(&descriptor_path)
+    .btree_map(["."])
+    .include_file("protos.rs")
+    .out_dir(&out_dir);
+
+  compile_protos_with_validators(&mut config, &files, &include_paths, &["example.v1"])?;
+
+  config.compile_protos(&files, &include_paths)?;
+
+  println!(
+    "cargo:rustc-env=PROTO_DESCRIPTOR_SET={}",
+    descriptor_path.display()
+  );
+
+  Ok(())
+}
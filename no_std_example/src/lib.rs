@@ -0,0 +1,116 @@
+//! Messages with validators that are compiled without the standard library, to check that protocheck supports `no_std` targets (with `alloc`).
+//!
+//! It is built for a target without `std` with `just test-no-std`.
+// The crate only drops `std` on the targets without an operating system, because the builds of the whole workspace enable the `std` features of protocheck needed by the other crates (such as `cel`), and their validators need `std`
+#![cfg_attr(target_os = "none", no_std)]
+
+extern crate alloc;
+
+#[allow(clippy::all)]
+mod protos {
+  include!(concat!(env!("OUT_DIR"), "/protos.rs"));
+}
+
+pub use protos::example::v1::*;
+
+#[cfg(test)]
+mod test {
+  use alloc::{string::ToString, vec, vec::Vec};
+
+  use protocheck::{types::Timestamp, validators::timestamps::with_now};
+
+  use super::*;
+
+  fn reading() -> Reading {
+    Reading {
+      sensor_id: "t1".to_string(),
+      value: 21,
+      channel: 4,
+      taken_at: Some(Timestamp {
+        seconds: 1_000,
+        nanos: 0,
+      }),
+    }
+  }
+
+  #[test]
+  fn valid_messages() {
+    let report = Report {
+      device: "dev-1".to_string(),
+      mode: Mode::Active as i32,
+      readings: vec![reading()],
+      target: Some(report::Target::Port(8080)),
+      ..Default::default()
+    };
+
+    with_now(
+      Timestamp {
+        seconds: 2_000,
+        nanos: 0,
+      },
+      || {
+        assert!(report.validate().is_ok());
+      },
+    );
+  }
+
+  // Without the standard library there is no default clock, and it is only needed by the rules that use the current time
+  #[test]
+  fn no_clock() {
+    let report = Report {
+      device: "dev-1".to_string(),
+      mode: Mode::Active as i32,
+      readings: vec![Reading {
+        taken_at: None,
+        ..reading()
+      }],
+      target: Some(report::Target::Port(8080)),
+      ..Default::default()
+    };
+
+    assert!(report.validate().is_ok());
+
+    let invalid = Report {
+      device: "1".to_string(),
+      ..report
+    };
+    let violations = invalid.validate().unwrap_err();
+
+    assert_eq!(violations.violations.len(), 1);
+    assert_eq!(violations.violations[0].rule_id(), "string.prefix");
+  }
+
+  #[test]
+  fn invalid_messages() {
+    let report = Report {
+      device: "1".to_string(),
+      mode: Mode::Unspecified as i32,
+      readings: vec![Reading {
+        channel: 3,
+        ..reading()
+      }],
+      ..Default::default()
+    };
+
+    let violations = with_now(
+      Timestamp {
+        seconds: 500,
+        nanos: 0,
+      },
+      || report.validate(),
+    )
+    .unwrap_err();
+    let rule_ids: Vec<_> = violations.violations.iter().map(|v| v.rule_id()).collect();
+
+    assert_eq!(
+      rule_ids,
+      [
+        "oneof.required",
+        "string.prefix",
+        "enum.not_in",
+        "uint32.in",
+        "timestamp.lt_now",
+      ]
+    );
+  }
+}
@@ -1,11 +1,14 @@
 mod oneof_attrs;
+mod predefined_attrs;
 
 pub use oneof_attrs::*;
+pub use predefined_attrs::*;
 
 use crate::*;
 
 pub struct ProstAttrData {
   pub enum_path: Option<String>,
+  pub oneof_path: Option<String>,
 }
 
 static MAP_ENUM_REGEX: LazyLock<Regex> = LazyLock::new(|| {
@@ -17,6 +20,7 @@ impl syn::parse::Parse for ProstAttrData {
     let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
 
     let mut enum_path: Option<String> = None;
+    let mut oneof_path: Option<String> = None;
 
     for meta in metas {
       if let Meta::NameValue(nv) = meta {
@@ -28,6 +32,11 @@ impl syn::parse::Parse for ProstAttrData {
 
             enum_path = Some(lit_str.value());
           }
+          "oneof" => {
+            let lit_str: LitStr = syn::parse2(nv.value.into_token_stream())?;
+
+            oneof_path = Some(lit_str.value());
+          }
           "map" => {
             let lit_str: LitStr = syn::parse2(nv.value.into_token_stream())?;
             let map_attr = lit_str.value();
@@ -42,7 +51,10 @@ impl syn::parse::Parse for ProstAttrData {
       }
     }
 
-    Ok(ProstAttrData { enum_path })
+    Ok(ProstAttrData {
+      enum_path,
+      oneof_path,
+    })
   }
 }
 
@@ -53,3 +65,81 @@ pub fn extract_string_lit(expr: &Expr) -> Result<String, Error> {
     Err(error!(expr, "Expected a string literal"))
   }
 }
+
+// Checks for the #[protocheck(skip_validation)] attribute, which protocheck-build adds to the excluded fields
+pub fn has_skip_validation_attr(attributes: &[Attribute]) -> Result<bool, Error> {
+  has_protocheck_flag(attributes, "skip_validation")
+}
+
+// Checks for the #[protocheck(skip_nested_validation)] attribute, which protocheck-build adds to the fields
+// whose message does not get the validators (because it is excluded, disabled or outside of the selected packages)
+pub fn has_skip_nested_validation_attr(attributes: &[Attribute]) -> Result<bool, Error> {
+  has_protocheck_flag(attributes, "skip_nested_validation")
+}
+
+fn has_protocheck_flag(attributes: &[Attribute], flag: &str) -> Result<bool, Error> {
+  for attr in attributes
+    .iter()
+    .filter(|attr| attr.path().is_ident("protocheck"))
+  {
+    let args = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+    if args
+      .iter()
+      .any(|arg| matches!(arg, Meta::Path(path) if path.is_ident(flag)))
+    {
+      return Ok(true);
+    }
+  }
+
+  Ok(false)
+}
+
+// The ignore mode of a field. protocheck-build resolves it by name and adds it as an attribute,
+// as the numbers of the modes differ between versions of protovalidate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IgnoreMode {
+  Unspecified,
+  // Skips the rules of the fields without presence when they are set to the zero value,
+  // like IGNORE_IF_ZERO_VALUE and the older IGNORE_IF_UNPOPULATED
+  IfZeroValue,
+  // Also skips the rules of the fields with presence when they are set to the zero value
+  IfDefaultValue,
+  Always,
+}
+
+impl IgnoreMode {
+  // Used when the attribute is missing, which only happens if the macros are not used with protocheck-build
+  pub fn from_rules(rules: &FieldRules) -> Self {
+    match rules.ignore() {
+      Ignore::Unspecified => Self::Unspecified,
+      Ignore::IfZeroValue => Self::IfZeroValue,
+      Ignore::Always => Self::Always,
+    }
+  }
+}
+
+// Checks for the #[protocheck(ignore_field)], #[protocheck(ignore_if_unpopulated)] and #[protocheck(ignore_if_default)]
+// attributes, which protocheck-build adds to the fields with an ignore mode
+pub fn extract_ignore_attr(attributes: &[Attribute]) -> Result<Option<IgnoreMode>, Error> {
+  for attr in attributes
+    .iter()
+    .filter(|attr| attr.path().is_ident("protocheck"))
+  {
+    let args = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+    for arg in args {
+      if let Meta::Path(path) = arg {
+        if path.is_ident("ignore_field") {
+          return Ok(Some(IgnoreMode::Always));
+        } else if path.is_ident("ignore_if_unpopulated") {
+          return Ok(Some(IgnoreMode::IfZeroValue));
+        } else if path.is_ident("ignore_if_default") {
+          return Ok(Some(IgnoreMode::IfDefaultValue));
+        }
+      }
+    }
+  }
+
+  Ok(None)
+}
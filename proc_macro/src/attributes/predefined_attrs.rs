@@ -0,0 +1,73 @@
+use crate::*;
+
+// The data of a predefined rule, which is added by protocheck-build as
+// #[protocheck(predefined(rule = "string.[acme.is_ticker]", value = "true", id = "...", message = "...", expression = "..."))]
+#[derive(Clone, Debug)]
+pub struct PredefinedRuleAttr {
+  // The path of the extension inside of the field rules
+  pub rule_path: String,
+  // The value of the extension, as a CEL literal
+  pub value: String,
+  pub id: String,
+  pub message: String,
+  pub expression: String,
+}
+
+pub fn extract_predefined_rules(
+  attributes: &[Attribute],
+) -> Result<Vec<PredefinedRuleAttr>, Error> {
+  let mut rules: Vec<PredefinedRuleAttr> = Vec::new();
+
+  for attr in attributes
+    .iter()
+    .filter(|attr| attr.path().is_ident("protocheck"))
+  {
+    let args = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+    for arg in args {
+      if let Meta::List(list) = arg
+        && list.path.is_ident("predefined")
+      {
+        let metas = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+        let mut rule_path: Option<String> = None;
+        let mut value: Option<String> = None;
+        let mut id = String::new();
+        let mut message = String::new();
+        let mut expression: Option<String> = None;
+
+        for meta in metas {
+          let nv = meta.require_name_value()?;
+          let ident = nv.path.require_ident()?.to_string();
+
+          match ident.as_str() {
+            "rule" => rule_path = Some(extract_string_lit(&nv.value)?),
+            "value" => value = Some(extract_string_lit(&nv.value)?),
+            "id" => id = extract_string_lit(&nv.value)?,
+            "message" => message = extract_string_lit(&nv.value)?,
+            "expression" => expression = Some(extract_string_lit(&nv.value)?),
+            _ => {
+              return Err(error!(
+                &nv.path,
+                format!("Unknown predefined rule attribute `{ident}`")
+              ))
+            }
+          };
+        }
+
+        let missing =
+          |name: &str| error!(&list, format!("Missing `{name}` in the predefined rule"));
+
+        rules.push(PredefinedRuleAttr {
+          rule_path: rule_path.ok_or_else(|| missing("rule"))?,
+          value: value.ok_or_else(|| missing("value"))?,
+          id,
+          message,
+          expression: expression.ok_or_else(|| missing("expression"))?,
+        });
+      }
+    }
+  }
+
+  Ok(rules)
+}
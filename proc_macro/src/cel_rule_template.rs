@@ -4,12 +4,15 @@ use crate::*;
 pub enum CelRuleTemplateTarget<'a> {
   Message {
     message_desc: &'a MessageDescriptor,
+    message_rules: &'a MessageRules,
     parent_messages_ident: Ident,
     violations_ident: Ident,
     struct_span: Span,
   },
   Field {
     field_desc: &'a FieldDescriptor,
+    // The rules containing the Cel rules, which can be the rules of the items of a list or of the keys and values of a map
+    field_rules: &'a FieldRules,
     validation_data: &'a ValidationData<'a>,
     field_span: Span,
   },
@@ -23,6 +26,13 @@ impl CelRuleTemplateTarget<'_> {
     }
   }
 
+  pub fn get_rules(&self) -> &[Rule] {
+    match self {
+      CelRuleTemplateTarget::Message { message_rules, .. } => &message_rules.cel,
+      CelRuleTemplateTarget::Field { field_rules, .. } => &field_rules.cel,
+    }
+  }
+
   pub fn get_validation_type(&self) -> &str {
     match self {
       CelRuleTemplateTarget::Field { .. } => "field",
@@ -1,11 +1,59 @@
+use syn::{GenericArgument, PathArguments, Type};
 use syn_utils::{Float, Int, RustType, TypeInfo, Uint};
 
 use crate::*;
 
+// The bytes fields are Vec<u8> unless prost is configured to use bytes::Bytes for them
+fn is_byte_vec(type_info: &TypeInfo) -> bool {
+  let RustType::Vec(inner) = type_info.type_.as_ref() else {
+    return false;
+  };
+
+  match inner.type_.as_ref() {
+    RustType::Uint(Uint::U8) => true,
+    RustType::Other(path) => path.path.is_ident("u8"),
+    _ => false,
+  }
+}
+
+// The maps are BTreeMaps when prost is configured with `btree_map`, and they are converted like the HashMaps
+fn btree_map_as_hash_map(type_info: &TypeInfo) -> Result<Option<RustType>, Error> {
+  let RustType::Other(path) = type_info.type_.as_ref() else {
+    return Ok(None);
+  };
+
+  let Some(segment) = path.path.segments.last().filter(|segment| segment.ident == "BTreeMap") else {
+    return Ok(None);
+  };
+
+  let PathArguments::AngleBracketed(args) = &segment.arguments else {
+    return Ok(None);
+  };
+
+  let types: Vec<&Type> = args
+    .args
+    .iter()
+    .filter_map(|arg| match arg {
+      GenericArgument::Type(ty) => Some(ty),
+      _ => None,
+    })
+    .collect();
+
+  let [key_type, value_type] = types.as_slice() else {
+    return Ok(None);
+  };
+
+  Ok(Some(RustType::HashMap((
+    TypeInfo::from_type(key_type)?.into(),
+    TypeInfo::from_type(value_type)?.into(),
+  ))))
+}
+
 pub fn get_conversion_tokens(type_info: &TypeInfo, val_tokens: &TokenStream2) -> TokenStream2 {
   match type_info.type_.as_ref() {
     RustType::Box(_) => quote! { (*#val_tokens).try_into_cel_value_recursive(depth + 1)? },
     RustType::Bytes => quote! { #val_tokens.to_vec().into() },
+    _ if is_byte_vec(type_info) => quote! { #val_tokens.to_vec().into() },
     RustType::Float(Float::F32) => quote! { (*#val_tokens as f64).into() },
     RustType::Uint(Uint::U32) => quote! { (*#val_tokens as u64).into() },
     RustType::Int(Int::I32) => quote! { (*#val_tokens as i64).into() },
@@ -15,6 +63,44 @@ pub fn get_conversion_tokens(type_info: &TypeInfo, val_tokens: &TokenStream2) ->
   }
 }
 
+// Rebuilds the value as the equivalent protocheck type for the fields marked with #[protocheck(timestamp)]
+// or #[protocheck(duration)], which are added by protocheck-build when the well known types come from another crate
+fn get_well_known_conversion_tokens(
+  well_known_type: Option<&Ident>,
+  type_info: &TypeInfo,
+  val_tokens: &TokenStream2,
+) -> TokenStream2 {
+  match well_known_type {
+    Some(type_ident) => {
+      quote! {
+        ::protocheck::types::#type_ident { seconds: #val_tokens.seconds, nanos: #val_tokens.nanos }
+          .try_into()
+          .map_err(::protocheck::types::cel::CelConversionError::from)?
+      }
+    }
+    None => get_conversion_tokens(type_info, val_tokens),
+  }
+}
+
+fn extract_well_known_type(attrs: &[Attribute]) -> Option<Ident> {
+  let mut well_known_type: Option<Ident> = None;
+
+  for attr in attrs {
+    if attr.path().is_ident("protocheck") {
+      let _ = attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("timestamp") {
+          well_known_type = Some(new_ident("Timestamp"));
+        } else if meta.path.is_ident("duration") {
+          well_known_type = Some(new_ident("Duration"));
+        }
+        Ok(())
+      });
+    }
+  }
+
+  well_known_type
+}
+
 pub fn derive_cel_value_oneof(item: ItemEnum) -> Result<TokenStream2, Error> {
   let enum_name = &item.ident;
 
@@ -35,7 +121,13 @@ pub fn derive_cel_value_oneof(item: ItemEnum) -> Result<TokenStream2, Error> {
 
         let type_info = TypeInfo::from_type(type_ident)?;
 
-        let into_expression = get_conversion_tokens(&type_info, &quote! { #val_ident });
+        let well_known_type = extract_well_known_type(&variant.attrs);
+
+        let into_expression = get_well_known_conversion_tokens(
+          well_known_type.as_ref(),
+          &type_info,
+          &quote! { #val_ident },
+        );
 
         let arm = quote! {
           #enum_name::#variant_ident(#val_ident) => {
@@ -127,6 +219,8 @@ pub(crate) fn derive_cel_value_struct(item: ItemStruct) -> Result<TokenStream2,
         continue
     }
 
+    let well_known_type = extract_well_known_type(&field.attrs);
+
     if is_oneof {
       tokens.extend(quote! {
         if let Some(oneof) = &value.#field_ident {
@@ -135,14 +229,18 @@ pub(crate) fn derive_cel_value_struct(item: ItemStruct) -> Result<TokenStream2,
         }
       });
     } else {
-      let outer_type = TypeInfo::from_type(field_type)?;
+      let mut outer_type = TypeInfo::from_type(field_type)?;
+
+      if let Some(map_type) = btree_map_as_hash_map(&outer_type)? {
+        outer_type.type_ = map_type.into();
+      }
 
       let val_ident = new_ident("v");
       let val_tokens = quote! { #val_ident };
 
       match outer_type.type_.as_ref() {
         RustType::Option(inner) => {
-          let conversion_tokens = get_conversion_tokens(inner, &val_tokens);
+          let conversion_tokens = get_well_known_conversion_tokens(well_known_type.as_ref(), inner, &val_tokens);
 
           tokens.extend(quote! {
             if let Some(#val_ident) = &value.#field_ident {
@@ -152,8 +250,8 @@ pub(crate) fn derive_cel_value_struct(item: ItemStruct) -> Result<TokenStream2,
             }
           });
         }
-        RustType::Vec(inner) => {
-          let conversion_tokens = get_conversion_tokens(inner, &val_tokens);
+        RustType::Vec(inner) if !is_byte_vec(&outer_type) => {
+          let conversion_tokens = get_well_known_conversion_tokens(well_known_type.as_ref(), inner, &val_tokens);
 
           tokens.extend(quote! {
             let mut converted: Vec<::protocheck::cel::Value> = Vec::new();
@@ -168,7 +266,8 @@ pub(crate) fn derive_cel_value_struct(item: ItemStruct) -> Result<TokenStream2,
         RustType::HashMap((k, v)) => {
           let keys_ident = new_ident("key");
           let keys_conversion_tokens = get_conversion_tokens(k, &quote! { #keys_ident });
-          let values_conversion_tokens = get_conversion_tokens(v, &val_tokens);
+          let values_conversion_tokens =
+            get_well_known_conversion_tokens(well_known_type.as_ref(), v, &val_tokens);
           tokens.extend(quote! {
             let mut field_map: ::std::collections::HashMap<::protocheck::cel::objects::Key, ::protocheck::cel::Value> = ::std::collections::HashMap::new();
 
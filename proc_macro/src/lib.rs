@@ -1,6 +1,8 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 #![allow(clippy::collapsible_if)]
+// Some of the imports and helpers are only used by the Cel validators
+#![cfg_attr(not(feature = "cel"), allow(unused))]
 
 use std::{
   borrow::Cow,
@@ -8,7 +10,7 @@ use std::{
   collections::{HashMap, HashSet},
   fmt::{Debug, Display},
   hash::Hash,
-  sync::{Arc, LazyLock},
+  sync::LazyLock,
 };
 
 use bytes::Bytes;
@@ -24,9 +26,12 @@ use prost_reflect::{
 use proto_types::{
   field_descriptor_proto::Type as ProtoType,
   protovalidate::{field_rules::Type as RulesType, *},
-  Duration, Empty, FieldMask, FieldType, Timestamp,
+  Duration, FieldType, Timestamp,
+};
+use protocheck_core::{
+  field_data::FieldKind,
+  validators::rule_messages::{self, Bound},
 };
-use protocheck_core::field_data::FieldKind;
 use quote::{format_ident, quote, ToTokens};
 use regex::Regex;
 use syn::{
@@ -36,8 +41,8 @@ use syn::{
 };
 
 use crate::{
-  attributes::*, cel_rule_template::*, message_validator::*, oneof_validator::*, pool_loader::*,
-  rules::*, special_field_names::*, utils::*, validation_data::*,
+  attributes::*, cel_rule_template::*, mask_fields::*, message_validator::*, oneof_validator::*,
+  pool_loader::*, rules::*, special_field_names::*, utils::*, validation_data::*,
 };
 
 #[macro_use]
@@ -46,6 +51,7 @@ mod attributes;
 mod cel_rule_template;
 #[cfg(feature = "cel")]
 mod cel_try_into;
+mod mask_fields;
 mod message_validator;
 mod oneof_validator;
 mod pool_loader;
@@ -83,6 +89,18 @@ pub fn try_into_cel_value_derive(input: TokenStream) -> TokenStream {
   }
 }
 
+/// Implements the `ValidatedRequest` marker trait for the request messages of the gRPC methods. This is added by protocheck-build along with the validators.
+#[proc_macro_derive(ValidatedRequest)]
+pub fn validated_request_derive(input: TokenStream) -> TokenStream {
+  let item = parse_macro_input!(input as ItemStruct);
+  let struct_ident = &item.ident;
+
+  quote! {
+    impl ::protocheck::ValidatedRequest for #struct_ident {}
+  }
+  .into()
+}
+
 /// Adds the validation methods to the generated protobuf message structs.
 #[proc_macro_attribute]
 pub fn protobuf_validate(attrs: TokenStream, input: TokenStream) -> TokenStream {
@@ -112,6 +130,7 @@ pub fn protobuf_validate(attrs: TokenStream, input: TokenStream) -> TokenStream
   };
 
   let struct_ident = &item.ident;
+  let field_mask_target = impl_field_mask_target(struct_ident, &message_desc);
 
   if cfg!(feature = "cel") {
     let cel_attr: Attribute = parse_quote!(#[derive(::protocheck::macros::TryIntoCelValue)]);
@@ -124,22 +143,41 @@ pub fn protobuf_validate(attrs: TokenStream, input: TokenStream) -> TokenStream
 
     impl #struct_ident {
       pub fn validate(&self) -> Result<(), ::protocheck::types::protovalidate::Violations> {
-        let mut violations: Vec<::protocheck::types::protovalidate::Violation> = Vec::new();
-        let mut parent_messages: Vec<::protocheck::types::protovalidate::FieldPathElement> = Vec::new();
+        // Neither of these allocates unless there is a violation
+        let mut violations: ::protocheck::__private::Vec<::protocheck::types::protovalidate::Violation> = ::protocheck::__private::Vec::new();
+        let parent_messages = ::protocheck::field_data::ParentMessages::default();
+
+        // The current time is read on its first use, and it is then the same for all of the rules (including the Cel rules) checked by this call
+        ::protocheck::validators::timestamps::with_lazy_now(|| {
+          self.nested_validate(&parent_messages, &mut violations);
+        });
 
-        self.nested_validate(&mut parent_messages, &mut violations);
+        let violations = ::protocheck::types::protovalidate::Violations { violations };
 
-        if violations.len() > 0 {
-          return Err(::protocheck::types::protovalidate::Violations { violations });
+        if violations.is_empty() {
+          Ok(())
+        } else {
+          Err(violations)
         }
-        Ok(())
+      }
+
+      pub fn validate_fail_fast(&self) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        ::protocheck::validators::fail_fast::with_fail_fast(|| self.validate()).map_err(|mut violations| {
+          // The rules of the same field are checked together, so they can report more than one violation
+          violations.violations.truncate(1);
+          violations
+        })
       }
 
       pub fn nested_validate(
         &self,
-        parent_messages: &mut Vec<::protocheck::types::protovalidate::FieldPathElement>,
-        violations: &mut Vec<::protocheck::types::protovalidate::Violation>
+        parent_messages: &::protocheck::field_data::ParentMessages,
+        violations: &mut ::protocheck::__private::Vec<::protocheck::types::protovalidate::Violation>
       ) {
+        if let Err(violation) = ::protocheck::validators::recursion::recursion_limit(parent_messages) {
+          violations.push(violation);
+          return;
+        }
 
         #validators
 
@@ -150,7 +188,13 @@ pub fn protobuf_validate(attrs: TokenStream, input: TokenStream) -> TokenStream
       fn validate(&self) -> Result<(), ::protocheck::types::protovalidate::Violations> {
         self.validate()
       }
+
+      fn validate_fail_fast(&self) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        self.validate_fail_fast()
+      }
     }
+
+    #field_mask_target
   };
 
   output.into()
@@ -195,14 +239,17 @@ pub fn protobuf_validate_oneof(attrs: TokenStream, input: TokenStream) -> TokenS
   };
 
   let mut validators: HashMap<Ident, TokenStream2> = HashMap::new();
+  let mut required_checks = TokenStream2::new();
 
   for oneof in message_desc.oneofs() {
     if oneof.name() == oneof_name {
       match extract_oneof_validators(&item, &oneof) {
         Ok(OneofValidatorsOutput {
           validators: validators_data,
+          required_checks: required_checks_data,
         }) => {
           validators = validators_data;
+          required_checks = required_checks_data;
         }
         Err(e) => return e.to_compile_error().into(),
       };
@@ -211,6 +258,7 @@ pub fn protobuf_validate_oneof(attrs: TokenStream, input: TokenStream) -> TokenS
   }
 
   let mut validators_tokens = TokenStream2::new();
+  let validators_count = validators.len();
 
   for (ident, validator) in validators {
     validators_tokens.extend(quote! {
@@ -220,6 +268,11 @@ pub fn protobuf_validate_oneof(attrs: TokenStream, input: TokenStream) -> TokenS
     });
   }
 
+  // The ignored and the excluded fields have no validators
+  if validators_count < item.variants.len() {
+    validators_tokens.extend(quote! { _ => {} });
+  }
+
   let oneof_rust_ident = &item.ident;
 
   if cfg!(feature = "cel") {
@@ -233,15 +286,26 @@ pub fn protobuf_validate_oneof(attrs: TokenStream, input: TokenStream) -> TokenS
     #item
 
     impl #oneof_rust_ident {
+      #[allow(clippy::ptr_arg)]
       pub fn validate(
         &self,
-        parent_messages: &mut Vec<::protocheck::types::protovalidate::FieldPathElement>,
-        violations: &mut Vec<::protocheck::types::protovalidate::Violation>,
+        parent_messages: &::protocheck::field_data::ParentMessages,
+        violations: &mut ::protocheck::__private::Vec<::protocheck::types::protovalidate::Violation>,
       ) {
         match self {
           #validators_tokens
         };
       }
+
+      // Called by the parent message, as the required fields must also be reported when the oneof is unset
+      #[allow(unused_variables, clippy::ptr_arg)]
+      pub fn validate_required(
+        oneof: Option<&Self>,
+        parent_messages: &::protocheck::field_data::ParentMessages,
+        violations: &mut ::protocheck::__private::Vec<::protocheck::types::protovalidate::Violation>,
+      ) {
+        #required_checks
+      }
     }
   };
 
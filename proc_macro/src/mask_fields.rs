@@ -0,0 +1,80 @@
+use crate::*;
+
+// The message type of the fields that a field mask path can go through, which excludes the lists and maps
+fn singular_message(field: &FieldDescriptor) -> Option<MessageDescriptor> {
+  match field.kind() {
+    ProstReflectKind::Message(message) if !field.is_list() && !field.is_map() => Some(message),
+    _ => None,
+  }
+}
+
+/// Implements `FieldMaskTarget` with the fields of the message and of all the messages that can be reached through its singular message fields.
+///
+/// Each message becomes a static, so that the recursive messages can refer to each other.
+pub fn impl_field_mask_target(
+  struct_ident: &Ident,
+  message_desc: &MessageDescriptor,
+) -> TokenStream2 {
+  let mut messages = vec![message_desc.clone()];
+  let mut i = 0;
+
+  while i < messages.len() {
+    let message = messages[i].clone();
+
+    for field in message.fields() {
+      if let Some(nested) = singular_message(&field) {
+        if !messages.contains(&nested) {
+          messages.push(nested);
+        }
+      }
+    }
+
+    i += 1;
+  }
+
+  let static_ident = |message: &MessageDescriptor| {
+    let index = messages
+      .iter()
+      .position(|m| m == message)
+      .unwrap_or_default();
+
+    format_ident!("MESSAGE_{}", index)
+  };
+
+  let statics = messages.iter().map(|message| {
+    let ident = static_ident(message);
+    let full_name = message.full_name();
+
+    let fields = message.fields().map(|field| {
+      let field_name = field.name();
+      let nested = match singular_message(&field) {
+        Some(nested) => {
+          let nested_ident = static_ident(&nested);
+          quote! { Some(&#nested_ident) }
+        }
+        None => quote! { None },
+      };
+
+      quote! {
+        ::protocheck::types::MessageField { name: #field_name, message: #nested }
+      }
+    });
+
+    quote! {
+      static #ident: ::protocheck::types::MessageFields = ::protocheck::types::MessageFields {
+        name: #full_name,
+        fields: &[#(#fields),*],
+      };
+    }
+  });
+
+  quote! {
+    impl ::protocheck::types::FieldMaskTarget for #struct_ident {
+      fn message_fields() -> &'static ::protocheck::types::MessageFields {
+        #(#statics)*
+
+        &MESSAGE_0
+      }
+    }
+  }
+}
@@ -11,6 +11,15 @@ pub fn extract_message_validators(
   let mut rust_field_spans: HashMap<String, Span> = HashMap::new();
   // <Field name, Enum name>
   let mut enum_fields: HashMap<String, String> = HashMap::new();
+  // <Field name, Oneof enum path>
+  let mut oneof_fields: HashMap<String, String> = HashMap::new();
+  // <Field name, Predefined rules added by protocheck-build>
+  let mut predefined_rules: HashMap<String, Vec<PredefinedRuleAttr>> = HashMap::new();
+  // The fields excluded by protocheck-build
+  let mut skipped_fields: HashSet<String> = HashSet::new();
+  let mut skipped_nested_fields: HashSet<String> = HashSet::new();
+  // <Field name, Ignore mode resolved by protocheck-build>
+  let mut ignore_modes: HashMap<String, IgnoreMode> = HashMap::new();
 
   for field in fields {
     if let Some(ident) = &field.ident {
@@ -19,9 +28,36 @@ pub fn extract_message_validators(
         .iter()
         .filter(|attr| attr.path().is_ident("prost"))
       {
-        if let Some(enum_path) = attr.parse_args::<ProstAttrData>()?.enum_path {
+        let ProstAttrData {
+          enum_path,
+          oneof_path,
+        } = attr.parse_args::<ProstAttrData>()?;
+
+        if let Some(enum_path) = enum_path {
           enum_fields.insert(ident.to_string(), enum_path);
         }
+
+        if let Some(oneof_path) = oneof_path {
+          oneof_fields.insert(ident.to_string(), oneof_path);
+        }
+      }
+
+      let field_predefined_rules = extract_predefined_rules(&field.attrs)?;
+
+      if !field_predefined_rules.is_empty() {
+        predefined_rules.insert(ident.to_string(), field_predefined_rules);
+      }
+
+      if has_skip_validation_attr(&field.attrs)? {
+        skipped_fields.insert(ident.to_string());
+      }
+
+      if has_skip_nested_validation_attr(&field.attrs)? {
+        skipped_nested_fields.insert(ident.to_string());
+      }
+
+      if let Some(ignore) = extract_ignore_attr(&field.attrs)? {
+        ignore_modes.insert(ident.to_string(), ignore);
       }
 
       rust_field_spans.insert(ident.to_string(), field.span());
@@ -33,6 +69,7 @@ pub fn extract_message_validators(
 
   let violations_ident = new_ident("violations");
   let parent_messages_ident = new_ident("parent_messages");
+  let fail_fast_check = fail_fast_check(&violations_ident, quote! { return });
 
   // Message Rules
   if let ProstValue::Message(message_rules_msg) = message_rules_descriptor.as_ref() {
@@ -43,12 +80,13 @@ pub fn extract_message_validators(
       validators.extend(get_cel_rules_checked(
         &CelRuleTemplateTarget::Message {
           message_desc,
+          message_rules: &message_rules,
           parent_messages_ident: parent_messages_ident.clone(),
           violations_ident: violations_ident.clone(),
           struct_span: item.span(),
         },
-        &message_rules.cel,
       )?);
+      validators.extend(fail_fast_check.clone());
     }
   }
 
@@ -70,7 +108,7 @@ pub fn extract_message_validators(
       let oneof_rust_ident = proto_name_to_rust_ident(oneof_proto_name);
 
       let required_check = oneof_rules.required().then_some(quote! {
-        #violations_ident.push(::protocheck::validators::oneofs::required(#oneof_proto_name, #parent_messages_ident.as_slice()));
+        #violations_ident.push(::protocheck::validators::oneofs::required(#oneof_proto_name, #parent_messages_ident));
       });
 
       validators.extend(quote! {
@@ -79,6 +117,31 @@ pub fn extract_message_validators(
           None => { #required_check }
         };
       });
+
+      if oneof_has_required_fields(item, &oneof)? {
+        let oneof_path_str = oneof_fields
+          .get(&oneof_rust_ident.to_string())
+          .ok_or_else(|| {
+            error!(
+              item,
+              format!("Could not find the type of the oneof {oneof_proto_name}")
+            )
+          })?;
+        let oneof_path: Path = syn::parse_str(oneof_path_str).map_err(|e| {
+          error!(
+            item,
+            format!(
+              "Failed to parse the path `{oneof_path_str}` of the oneof {oneof_proto_name}: {e}"
+            )
+          )
+        })?;
+
+        validators.extend(quote! {
+          #oneof_path::validate_required(self.#oneof_rust_ident.as_ref(), #parent_messages_ident, #violations_ident);
+        });
+      }
+
+      validators.extend(fail_fast_check.clone());
     }
   }
 
@@ -92,6 +155,10 @@ pub fn extract_message_validators(
     let field_proto_name = field.name();
     let field_rust_name = proto_name_to_rust_name(field_proto_name);
 
+    if skipped_fields.contains(field_rust_name.as_ref()) {
+      continue;
+    }
+
     let mut field_validators = TokenStream2::new();
 
     let item_rust_ident = proto_name_to_rust_ident(field_proto_name);
@@ -123,10 +190,13 @@ pub fn extract_message_validators(
       let field_rules = FieldRules::decode(field_rules_msg.encode_to_vec().as_slice())
         .map_err(|e| Error::new_spanned(item, format!("Could not decode field rules: {}", e)))?;
 
-      let ignore = field_rules.ignore();
-      let is_required = field_rules.required() && field.supports_presence();
+      let ignore = ignore_modes
+        .get(field_rust_name.as_ref())
+        .copied()
+        .unwrap_or_else(|| IgnoreMode::from_rules(&field_rules));
+      let is_required = field_rules.required();
 
-      if matches!(ignore, Ignore::Always) {
+      if ignore == IgnoreMode::Always {
         continue;
       }
 
@@ -139,6 +209,8 @@ pub fn extract_message_validators(
         is_in_oneof: false,
         is_optional,
         is_boxed: field_is_boxed(&field, message_desc),
+        is_wrapper: is_wrapper(&field),
+        skip_nested_validation: skipped_nested_fields.contains(field_rust_name.as_ref()),
         field_span,
         map_keys_type: None,
         map_values_type: None,
@@ -166,6 +238,12 @@ pub fn extract_message_validators(
           &field,
           &field_rules,
         )?;
+
+        if is_required {
+          field_validators = validation_data.wrap_with_required_check(field_validators);
+        } else if validation_data.ignores_zero_value() && !field_validators.is_empty() {
+          field_validators = validation_data.wrap_with_default_value_check(field_validators);
+        }
       } else if is_map {
         get_map_rules(
           &mut validation_data,
@@ -174,6 +252,12 @@ pub fn extract_message_validators(
           &field,
           &field_rules,
         )?;
+
+        if is_required {
+          field_validators = validation_data.wrap_with_required_check(field_validators);
+        } else if validation_data.ignores_zero_value() && !field_validators.is_empty() {
+          field_validators = validation_data.wrap_with_default_value_check(field_validators);
+        }
       } else {
         if let Some(rules_type) = field_rules_type {
           let rules = get_field_rules(field_rust_enum, &field, &validation_data, rules_type)?;
@@ -185,10 +269,19 @@ pub fn extract_message_validators(
           field_validators.extend(get_cel_rules_checked(
             &CelRuleTemplateTarget::Field {
               field_desc: &field,
+              field_rules: &field_rules,
               validation_data: &validation_data,
               field_span,
             },
-            &field_rules.cel,
+          )?);
+        }
+
+        if let Some(field_predefined_rules) = predefined_rules.get(field_rust_name.as_ref()) {
+          field_validators.extend(get_predefined_rules_checked(
+            &field,
+            &field_rules,
+            &validation_data,
+            field_predefined_rules,
           )?);
         }
 
@@ -206,13 +299,41 @@ pub fn extract_message_validators(
         }
       }
 
-      validators.extend(field_validators);
+      if !field_validators.is_empty() {
+        validators.extend(field_validators);
+        validators.extend(fail_fast_check.clone());
+      }
     }
   }
 
   Ok(validators)
 }
 
+// The required fields of a oneof are reported unless the oneof is set to them, so they are checked by the parent message too.
+// The ignore modes resolved by protocheck-build are attributes of the variants of the oneof, which this macro cannot read,
+// so the ignored fields are left out by `validate_required` itself rather than by their numeric mode here
+fn oneof_has_required_fields(
+  item: &ItemStruct,
+  oneof_desc: &OneofDescriptor,
+) -> Result<bool, Error> {
+  for field in oneof_desc.fields() {
+    if let ProstValue::Message(field_rules_msg) = field
+      .options()
+      .get_extension(&FIELD_RULES_EXT_DESCRIPTOR)
+      .as_ref()
+    {
+      let field_rules = FieldRules::decode(field_rules_msg.encode_to_vec().as_slice())
+        .map_err(|e| error!(item, format!("Could not decode field rules: {}", e)))?;
+
+      if field_rules.required() {
+        return Ok(true);
+      }
+    }
+  }
+
+  Ok(false)
+}
+
 pub fn field_is_boxed(field_desc: &FieldDescriptor, message_desc: &MessageDescriptor) -> bool {
   if let ProstReflectKind::Message(field_message_desc) = field_desc.kind() {
     return !field_desc.is_list() && field_message_desc.full_name() == message_desc.full_name();
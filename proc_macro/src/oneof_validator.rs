@@ -5,11 +5,17 @@ struct OneofField {
   pub ident: Ident,
   pub enum_path: Option<String>,
   pub span: Span,
+  pub predefined_rules: Vec<PredefinedRuleAttr>,
+  pub skip_validation: bool,
+  pub skip_nested_validation: bool,
+  pub ignore: Option<IgnoreMode>,
 }
 
 #[derive(Debug)]
 pub struct OneofValidatorsOutput {
   pub validators: HashMap<Ident, TokenStream2>,
+  // The checks for the required fields, which fail unless the oneof is set to that field
+  pub required_checks: TokenStream2,
 }
 
 pub fn extract_oneof_validators(
@@ -17,6 +23,7 @@ pub fn extract_oneof_validators(
   oneof_desc: &OneofDescriptor,
 ) -> Result<OneofValidatorsOutput, Error> {
   let mut validators: HashMap<Ident, TokenStream2> = HashMap::new();
+  let mut required_checks = TokenStream2::new();
   let mut oneof_variants: HashMap<String, OneofField> = HashMap::new();
 
   let oneof_proto_name = &oneof_desc.name();
@@ -31,6 +38,10 @@ pub fn extract_oneof_validators(
         ident: variant.ident.clone(),
         enum_path,
         span: variant.span(),
+        predefined_rules: extract_predefined_rules(&variant.attrs)?,
+        skip_validation: has_skip_validation_attr(&variant.attrs)?,
+        skip_nested_validation: has_skip_nested_validation_attr(&variant.attrs)?,
+        ignore: extract_ignore_attr(&variant.attrs)?,
       },
     );
   }
@@ -40,7 +51,10 @@ pub fn extract_oneof_validators(
       enum_path: enum_ident,
       ident: field_ident,
       span: field_span,
-      ..
+      predefined_rules,
+      skip_validation,
+      skip_nested_validation,
+      ignore,
     } = oneof_variants
       .remove(field.name())
       .ok_or(Error::new(
@@ -52,6 +66,10 @@ pub fn extract_oneof_validators(
         ),
       ))?;
 
+    if skip_validation {
+      continue;
+    }
+
     let mut field_validators = TokenStream2::new();
 
     let field_options = field.options();
@@ -61,10 +79,10 @@ pub fn extract_oneof_validators(
       let field_rules = FieldRules::decode(field_rules_message.encode_to_vec().as_slice())
         .map_err(|e| Error::new_spanned(item, format!("Could not decode field rules: {}", e)))?;
 
-      let ignore = field_rules.ignore();
+      let ignore = ignore.unwrap_or_else(|| IgnoreMode::from_rules(&field_rules));
       let is_required = field_rules.required() && field.supports_presence();
 
-      if matches!(ignore, Ignore::Always) {
+      if ignore == IgnoreMode::Always {
         continue;
       }
 
@@ -88,6 +106,8 @@ pub fn extract_oneof_validators(
         is_in_oneof: true,
         is_optional: true,
         is_boxed: field_is_boxed(&field, oneof_desc.parent_message()),
+        is_wrapper: is_wrapper(&field),
+        skip_nested_validation,
         field_span,
         proto_name: field_name,
         tag: field.number() as i32,
@@ -109,14 +129,31 @@ pub fn extract_oneof_validators(
         value_ident: OnceCell::new(),
       };
 
+      if let Some(required_tokens) = validation_data.get_required_validation_tokens() {
+        required_checks.extend(quote! {
+          if !matches!(oneof, Some(Self::#field_ident(_))) {
+            #required_tokens
+          }
+        });
+      }
+
       if !field_rules.cel.is_empty() {
         field_validators.extend(get_cel_rules_checked(
           &CelRuleTemplateTarget::Field {
             field_desc: &field,
+            field_rules: &field_rules,
             validation_data: &validation_data,
             field_span,
           },
-          &field_rules.cel,
+        )?);
+      }
+
+      if !predefined_rules.is_empty() {
+        field_validators.extend(get_predefined_rules_checked(
+          &field,
+          &field_rules,
+          &validation_data,
+          &predefined_rules,
         )?);
       }
 
@@ -140,5 +177,8 @@ pub fn extract_oneof_validators(
     }
   }
 
-  Ok(OneofValidatorsOutput { validators })
+  Ok(OneofValidatorsOutput {
+    validators,
+    required_checks,
+  })
 }
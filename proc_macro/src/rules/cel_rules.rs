@@ -1,38 +1,75 @@
 use crate::*;
 
-pub fn get_cel_rules_checked(
-  rule_target: &CelRuleTemplateTarget,
-  rules: &[Rule],
+pub fn get_cel_rules_checked(rule_target: &CelRuleTemplateTarget) -> Result<TokenStream2, Error> {
+  #[cfg(feature = "cel")]
+  return cel_validator::get_cel_rules(rule_target);
+
+  #[cfg(not(feature = "cel"))]
+  {
+    let _ = rule_target;
+    Err(Error::new(
+      Span::call_site(),
+      "Cannot use Cel validators without the 'cel' feature",
+    ))
+  }
+}
+
+pub fn get_predefined_rules_checked(
+  field_desc: &FieldDescriptor,
+  field_rules: &FieldRules,
+  validation_data: &ValidationData,
+  rules: &[PredefinedRuleAttr],
 ) -> Result<TokenStream2, Error> {
-  if cfg!(feature = "cel") {
-    cel_validator::get_cel_rules(rule_target, rules)
-  } else {
-    unimplemented!("Cannot use Cel validators without the 'cel' feature")
+  #[cfg(feature = "cel")]
+  return cel_validator::get_predefined_rules(field_desc, field_rules, validation_data, rules);
+
+  #[cfg(not(feature = "cel"))]
+  {
+    let _ = (field_desc, field_rules, validation_data, rules);
+    Err(Error::new(
+      Span::call_site(),
+      "Cannot use predefined rules without the 'cel' feature",
+    ))
   }
 }
 
 #[cfg(feature = "cel")]
 mod cel_validator {
   use cel::{objects::Key as CelKey, Context, Program, Value as CelValue};
+  use protocheck_core::{dynamic::rules_to_cel_value, validators::cel::cel_context};
 
   use crate::*;
 
-  pub fn get_cel_rules(
-    rule_target: &CelRuleTemplateTarget,
-    rules: &[Rule],
-  ) -> Result<TokenStream2, Error> {
+  pub fn get_cel_rules(rule_target: &CelRuleTemplateTarget) -> Result<TokenStream2, Error> {
     let mut tokens = TokenStream2::new();
+    let mut message_validators = TokenStream2::new();
 
-    let cel_value: CelValue = match rule_target {
-      CelRuleTemplateTarget::Message { message_desc, .. } => {
+    let (cel_value, rules_value, has_json_values): (CelValue, CelValue, bool) = match rule_target {
+      CelRuleTemplateTarget::Message {
+        message_desc,
+        message_rules,
+        ..
+      } => {
         let dyn_message = DynamicMessage::new((*message_desc).clone());
-        convert_prost_value_to_cel_value(&ProstValue::Message(dyn_message))?
+        (
+          convert_prost_value_to_cel_value(&ProstValue::Message(dyn_message))?,
+          convert_rules_to_cel_value("buf.validate.MessageRules", *message_rules)?,
+          contains_json_values(message_desc, 0),
+        )
       }
       CelRuleTemplateTarget::Field {
         field_desc,
+        field_rules,
         validation_data,
         ..
-      } => get_default_field_prost_value(validation_data, field_desc)?,
+      } => (
+        get_default_field_prost_value(validation_data, field_desc)?,
+        get_field_rules_cel_value(field_rules)?,
+        match field_desc.kind() {
+          ProstReflectKind::Message(field_message) => contains_json_values(&field_message, 0),
+          _ => false,
+        },
+      ),
     };
 
     let validation_type = rule_target.get_validation_type();
@@ -43,7 +80,17 @@ mod cel_validator {
 
     let (parent_messages_ident, violations_ident) = rule_target.get_idents();
 
-    for (index, rule) in rules.iter().enumerate() {
+    let static_rules_ident = new_ident(&format!(
+      "CEL_RULES_{}",
+      target_name.to_case(Case::UpperSnake)
+    ));
+    let rules_value_tokens = cel_value_tokens(&rules_value);
+
+    tokens.extend(quote! {
+      static #static_rules_ident: std::sync::LazyLock<protocheck::cel::Value> = std::sync::LazyLock::new(|| #rules_value_tokens);
+    });
+
+    for (index, rule) in rule_target.get_rules().iter().enumerate() {
       let program = match Program::compile(rule.expression()) {
         Ok(prog) => prog,
         Err(e) => {
@@ -54,101 +101,26 @@ mod cel_validator {
         }
       };
 
-      let mut context = Context::default();
+      let mut context = cel_context();
 
       context.add_variable_from_value("this", &cel_value);
+      context.add_variable_from_value("rules", &rules_value);
+      context.add_variable_from_value("now", now_placeholder());
 
+      // The expressions over Json values can access keys that are not in the default values
+      // (such as `this.metadata.labels['env']`), so only their compilation is checked
       match program.execute(&context) {
+        Ok(CelValue::Bool(_) | CelValue::String(_)) => {}
         Ok(result) => {
-          if let CelValue::Bool(_) = result {
-            let expression = rule.expression().to_string();
-            let error_message = rule.message().to_string();
-            let rule_id = rule.id().to_string();
-
-            let static_program_ident = new_ident(&format!(
-              "CEL_PROGRAM_{}_{}",
-              target_name.to_case(Case::UpperSnake),
-              index
-            ));
-
-            tokens.extend(quote! {
-              static #static_program_ident: std::sync::LazyLock<protocheck::cel::Program> = std::sync::LazyLock::new(|| {
-                protocheck::cel::Program::compile(#expression).expect(#compilation_error_msg)
-              });
-            });
-
-            let rule_tokens = quote! {
-              ::protocheck::validators::cel::CelRule {
-                id: #rule_id,
-                error_message: #error_message,
-                program: &#static_program_ident,
-                item_full_name: #target_name
-              }
-            };
-
-            match rule_target {
-              CelRuleTemplateTarget::Field {
-                validation_data, ..
-              } => {
-                let field_context_ident = &validation_data.field_context_ident();
-                let value_ident = validation_data.value_ident();
-
-                let validation_expression = match validation_data.field_kind.inner_type() {
-                  FieldType::Message => {
-                    quote! { validate_cel_field_try_into(&#field_context_ident, rule, #value_ident.clone()) }
-                  }
-                  FieldType::Bytes => {
-                    quote! { validate_cel_field_try_into(&#field_context_ident, rule, #value_ident.to_vec()) }
-                  }
-                  FieldType::Float => {
-                    quote! { validate_cel_field_try_into(&#field_context_ident, rule, (#value_ident as f64)) }
-                  }
-                  FieldType::Int32 | FieldType::Sint32 | FieldType::Sfixed32 => {
-                    quote! { validate_cel_field_try_into(&#field_context_ident, rule, (#value_ident as i64)) }
-                  }
-                  FieldType::Uint32 | FieldType::Fixed32 => {
-                    quote! { validate_cel_field_try_into(&#field_context_ident, rule, (#value_ident as u64)) }
-                  }
-                  _ => {
-                    quote! { validate_cel_field_try_into(&#field_context_ident, rule, #value_ident) }
-                  }
-                };
-
-                let validator_tokens = quote! {
-                  let rule = #rule_tokens;
-
-                  match ::protocheck::validators::cel::#validation_expression {
-                    Ok(_) => {}
-                    Err(v) => #violations_ident.push(v)
-                  };
-                };
-
-                tokens.extend(validator_tokens);
-              }
-
-              CelRuleTemplateTarget::Message { .. } => {
-                let validator_tokens = quote! {
-                  let rule = #rule_tokens;
-
-                  match ::protocheck::validators::cel::validate_cel_message(#parent_messages_ident, rule, self.clone()) {
-                    Ok(_) => {}
-                    Err(v) => #violations_ident.push(v)
-                  };
-                };
-
-                tokens.extend(validator_tokens);
-              }
-            };
-          } else {
-            bail_spanned!(
-              rule_target.span(),
-              format!(
-                "{compilation_error_msg}: expected boolean, got {}",
-                result.type_of()
-              )
-            );
-          }
+          bail_spanned!(
+            rule_target.span(),
+            format!(
+              "{compilation_error_msg}: expected a boolean or a string, got {}",
+              result.type_of()
+            )
+          );
         }
+        Err(_) if has_json_values => {}
         Err(e) => {
           bail_spanned!(
             rule_target.span(),
@@ -156,115 +128,516 @@ mod cel_validator {
           );
         }
       };
+
+      let expression = rule.expression().to_string();
+      let error_message = rule.message().to_string();
+      let rule_id = rule.id().to_string();
+      let uses_now = program.references().has_variable("now");
+
+      let static_program_ident = new_ident(&format!(
+        "CEL_PROGRAM_{}_{}",
+        target_name.to_case(Case::UpperSnake),
+        index
+      ));
+
+      tokens.extend(quote! {
+        static #static_program_ident: std::sync::LazyLock<protocheck::cel::Program> = std::sync::LazyLock::new(|| {
+          protocheck::cel::Program::compile(#expression).expect(#compilation_error_msg)
+        });
+      });
+
+      let rule_tokens = quote! {
+        ::protocheck::validators::cel::CelRule {
+          id: #rule_id,
+          error_message: #error_message,
+          program: &#static_program_ident,
+          uses_now: #uses_now,
+          item_full_name: #target_name,
+          rules: &#static_rules_ident,
+        }
+      };
+
+      match rule_target {
+        CelRuleTemplateTarget::Field {
+          validation_data,
+          field_desc,
+          ..
+        } => {
+          let field_context_ident = &validation_data.field_context_ident();
+
+          let cel_value_tokens = get_cel_value_tokens(validation_data, field_desc);
+          let validation_expression = quote! { validate_cel_field_conversion(&#field_context_ident, rule, #cel_value_tokens) };
+
+          let validator_tokens = quote! {
+            let rule = #rule_tokens;
+
+            match ::protocheck::validators::cel::#validation_expression {
+              Ok(_) => {}
+              Err(v) => #violations_ident.push(v)
+            };
+          };
+
+          tokens.extend(validator_tokens);
+        }
+
+        CelRuleTemplateTarget::Message { .. } => {
+          let validator_tokens = quote! {
+            let rule = #rule_tokens;
+
+            match ::protocheck::validators::cel::validate_cel_message_with_val(#parent_messages_ident, rule, &cel_message) {
+              Ok(_) => {}
+              Err(v) => #violations_ident.push(v)
+            };
+          };
+
+          message_validators.extend(validator_tokens);
+        }
+      };
+    }
+
+    // The message is converted once for all of its rules
+    if !message_validators.is_empty() {
+      tokens.extend(quote! {
+        match ::protocheck::validators::cel::message_to_cel_value(#parent_messages_ident, #target_name, self.clone()) {
+          Ok(cel_message) => { #message_validators }
+          Err(v) => #violations_ident.push(v)
+        };
+      });
     }
 
     Ok(tokens)
   }
 
-  fn get_default_field_prost_value(
-    validation_data: &ValidationData,
+  // The predefined rules are evaluated like the other Cel rules, except that the value
+  // of the rule is available as `rule`, and that the violations use the path of the rule's extension
+  pub fn get_predefined_rules(
     field_desc: &FieldDescriptor,
-  ) -> Result<CelValue, Error> {
-    let default_val = if validation_data.field_kind.is_repeated_item() {
-      ProstValue::default_value(&field_desc.kind())
-    } else {
-      ProstValue::default_value_for_field(field_desc)
-    };
+    field_rules: &FieldRules,
+    validation_data: &ValidationData,
+    rules: &[PredefinedRuleAttr],
+  ) -> Result<TokenStream2, Error> {
+    let mut tokens = TokenStream2::new();
 
-    convert_prost_value_to_cel_value(&default_val)
-  }
+    let field_span = validation_data.field_span;
+    let target_name = field_desc.full_name().replace(".", "_");
+    let this_value = get_default_field_prost_value(validation_data, field_desc)?;
+    let rules_value = get_field_rules_cel_value(field_rules)?;
 
-  fn convert_prost_value_to_cel_value(prost_value: &ProstValue) -> Result<CelValue, Error> {
-    convert_prost_value_to_cel_value_recursive(prost_value, 0)
+    let static_rules_ident = new_ident(&format!(
+      "CEL_PREDEFINED_RULES_{}",
+      target_name.to_case(Case::UpperSnake)
+    ));
+    let rules_value_tokens = cel_value_tokens(&rules_value);
+
+    tokens.extend(quote! {
+      static #static_rules_ident: std::sync::LazyLock<protocheck::cel::Value> = std::sync::LazyLock::new(|| #rules_value_tokens);
+    });
+
+    let violations_ident = validation_data.violations_ident;
+    let field_context_ident = &validation_data.field_context_ident();
+    let cel_value_tokens = get_cel_value_tokens(validation_data, field_desc);
+
+    for (index, rule) in rules.iter().enumerate() {
+      let PredefinedRuleAttr {
+        rule_path,
+        value,
+        id,
+        message,
+        expression,
+      } = rule;
+
+      let compilation_error_msg = format!(
+        "Cel program error for the predefined rule {} of the field {}",
+        rule_path,
+        field_desc.full_name()
+      );
+
+      let rule_value = Program::compile(value)
+        .map_err(|e| e.to_string())
+        .and_then(|program| {
+          program
+            .execute(&Context::default())
+            .map_err(|e| e.to_string())
+        })
+        .map_err(|e| {
+          error_spanned!(
+            field_span,
+            format!("{compilation_error_msg}: invalid rule value `{value}`: {e}")
+          )
+        })?;
+
+      let program = Program::compile(expression).map_err(|e| {
+        error_spanned!(
+          field_span,
+          format!("{compilation_error_msg}: failed to compile: {e}")
+        )
+      })?;
+
+      let mut context = cel_context();
+      context.add_variable_from_value("this", &this_value);
+      context.add_variable_from_value("rule", rule_value);
+      context.add_variable_from_value("rules", &rules_value);
+      context.add_variable_from_value("now", now_placeholder());
+
+      match program.execute(&context) {
+        Ok(CelValue::Bool(_) | CelValue::String(_)) => {}
+        Ok(result) => bail_spanned!(
+          field_span,
+          format!(
+            "{compilation_error_msg}: expected a boolean or a string, got {}",
+            result.type_of()
+          )
+        ),
+        Err(e) => bail_spanned!(
+          field_span,
+          format!("{compilation_error_msg}: failed execution: {e}")
+        ),
+      };
+
+      let rule_elements = get_predefined_rule_elements(rule_path).ok_or_else(|| {
+        error_spanned!(
+          field_span,
+          format!("{compilation_error_msg}: the rule was not found in the descriptor pool")
+        )
+      })?;
+
+      let uses_now = program.references().has_variable("now");
+
+      let suffix = format!("{}_{}", target_name.to_case(Case::UpperSnake), index);
+      let static_program_ident = new_ident(&format!("CEL_PREDEFINED_PROGRAM_{suffix}"));
+      let static_value_ident = new_ident(&format!("CEL_PREDEFINED_VALUE_{suffix}"));
+      let static_violation_ident = new_ident(&format!("CEL_PREDEFINED_VIOLATION_{suffix}"));
+
+      let element_tokens = rule_elements
+        .iter()
+        .map(|(field_name, field_number, field_type)| {
+          quote! {
+            ::protocheck::types::protovalidate::FieldPathElement {
+              field_name: Some(#field_name.to_string()),
+              field_number: Some(#field_number),
+              field_type: Some(#field_type),
+              ..Default::default()
+            }
+          }
+        });
+
+      tokens.extend(quote! {
+        static #static_program_ident: std::sync::LazyLock<protocheck::cel::Program> = std::sync::LazyLock::new(|| {
+          protocheck::cel::Program::compile(#expression).expect(#compilation_error_msg)
+        });
+
+        static #static_value_ident: std::sync::LazyLock<protocheck::cel::Value> = std::sync::LazyLock::new(|| {
+          protocheck::cel::Program::compile(#value)
+            .expect(#compilation_error_msg)
+            .execute(&protocheck::cel::Context::default())
+            .expect(#compilation_error_msg)
+        });
+
+        static #static_violation_ident: std::sync::LazyLock<::protocheck::types::protovalidate::ViolationData> = std::sync::LazyLock::new(|| {
+          ::protocheck::types::protovalidate::ViolationData {
+            name: #rule_path,
+            elements: Box::leak(vec![#(#element_tokens),*].into_boxed_slice()),
+          }
+        });
+
+        let rule = ::protocheck::validators::cel::PredefinedCelRule {
+          rule: ::protocheck::validators::cel::CelRule {
+            id: #id,
+            error_message: #message,
+            program: &#static_program_ident,
+            uses_now: #uses_now,
+            item_full_name: #target_name,
+            rules: &#static_rules_ident,
+          },
+          value: &#static_value_ident,
+          violation_data: &#static_violation_ident,
+        };
+
+        match ::protocheck::validators::cel::validate_predefined_cel_field_conversion(&#field_context_ident, rule, #cel_value_tokens) {
+          Ok(_) => {}
+          Err(v) => #violations_ident.push(v)
+        };
+      });
+    }
+
+    Ok(tokens)
   }
 
-  const MAX_RECURSION_DEPTH: usize = 10;
+  // Resolves the path elements of a rule such as `string.[acme.is_ticker]`, made of the field of the
+  // rules type inside of buf.validate.FieldRules, and of the extension
+  fn get_predefined_rule_elements(rule_path: &str) -> Option<Vec<(String, i32, i32)>> {
+    let (rules_type, extension_name) = rule_path.split_once(".[")?;
+    let extension_name = extension_name.strip_suffix(']')?;
+
+    let rules_type_field = DESCRIPTOR_POOL
+      .get_message_by_name("buf.validate.FieldRules")?
+      .get_field_by_name(rules_type)?;
+    let extension = DESCRIPTOR_POOL.get_extension_by_name(extension_name)?;
+
+    Some(vec![
+      (
+        rules_type_field.name().to_string(),
+        rules_type_field.number() as i32,
+        convert_kind_to_proto_type(rules_type_field.kind()) as i32,
+      ),
+      (
+        format!("[{}]", extension.full_name()),
+        extension.number() as i32,
+        convert_kind_to_proto_type(extension.kind()) as i32,
+      ),
+    ])
+  }
 
-  fn convert_prost_value_to_cel_value_recursive(
-    prost_value: &ProstValue,
-    depth: usize,
-  ) -> Result<CelValue, Error> {
-    match prost_value {
-      ProstValue::F64(v) => Ok(CelValue::Float(*v)),
-      ProstValue::F32(v) => Ok(CelValue::Float(*v as f64)),
-      ProstValue::I32(v) => Ok(CelValue::Int(*v as i64)),
-      ProstValue::I64(v) => Ok(CelValue::Int(*v)),
-      ProstValue::U32(v) => Ok(CelValue::UInt(*v as u64)),
-      ProstValue::U64(v) => Ok(CelValue::UInt(*v)),
-      ProstValue::Bool(v) => Ok(CelValue::Bool(*v)),
-      ProstValue::String(v) => Ok(CelValue::String(Arc::new(v.to_string()))),
-      ProstValue::Bytes(v) => Ok(CelValue::Bytes(Arc::new(v.to_vec()))),
-      ProstValue::EnumNumber(v) => Ok(CelValue::Int(*v as i64)),
-      ProstValue::List(list_values) => {
-        let cel_elements: Result<Vec<CelValue>, Error> = list_values
-          .iter()
-          .map(convert_prost_value_to_cel_value)
-          .collect();
-
-        Ok(CelValue::List(Arc::new(cel_elements?)))
+  // The conversion of the field into the Cel value that is passed to the validators, as a
+  // Result<Value, CelConversionError>. The lists and maps are converted item by item, as the
+  // value is a reference and some of the items (such as the messages, the enums or the uint32 keys)
+  // have no direct conversion into a Cel value
+  fn get_cel_value_tokens(
+    validation_data: &ValidationData,
+    field_desc: &FieldDescriptor,
+  ) -> TokenStream2 {
+    let value_ident = validation_data.value_ident();
+
+    match (validation_data.field_kind, field_desc.kind()) {
+      (FieldKind::Repeated(_), item_kind) => {
+        let item_tokens = get_cel_collection_item_tokens(&quote! { item }, &item_kind);
+
+        quote! {
+          #value_ident
+            .iter()
+            .cloned()
+            .map(|item| -> Result<::protocheck::cel::Value, ::protocheck::types::cel::CelConversionError> {
+              Ok(#item_tokens)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(::protocheck::cel::Value::from)
+        }
       }
-      ProstValue::Map(map_values) => {
-        let mut cel_map = HashMap::new();
-
-        for (key, val) in map_values.iter() {
-          let cel_key = match key {
-            prost_reflect::MapKey::String(s) => CelKey::String(Arc::new(s.clone())),
-            prost_reflect::MapKey::I32(v) => CelKey::Int(*v as i64),
-            prost_reflect::MapKey::I64(v) => CelKey::Int(*v),
-            prost_reflect::MapKey::U32(v) => CelKey::Uint(*v as u64),
-            prost_reflect::MapKey::U64(v) => CelKey::Uint(*v),
-            prost_reflect::MapKey::Bool(v) => CelKey::Bool(*v),
-          };
-          let cel_val = convert_prost_value_to_cel_value_recursive(val, depth + 1)?;
-
-          cel_map.insert(cel_key, cel_val);
+      (FieldKind::Map(_), ProstReflectKind::Message(entry_desc)) => {
+        let key_kind = entry_desc.map_entry_key_field().kind();
+        let value_kind = entry_desc.map_entry_value_field().kind();
+        let key_tokens = get_cel_key_tokens(&quote! { key }, &key_kind);
+        let item_tokens = get_cel_collection_item_tokens(&quote! { item }, &value_kind);
+
+        quote! {
+          #value_ident
+            .iter()
+            .map(|(key, item)| (key.clone(), item.clone()))
+            .map(|(key, item)| -> Result<(::protocheck::cel::objects::Key, ::protocheck::cel::Value), ::protocheck::types::cel::CelConversionError> {
+              Ok((#key_tokens, #item_tokens))
+            })
+            .collect::<Result<::std::collections::HashMap<_, _>, _>>()
+            .map(|map| ::protocheck::cel::Value::Map(map.into()))
+        }
+      }
+      _ => {
+        let value_tokens = get_cel_item_tokens(
+          &quote! { #value_ident },
+          validation_data.field_kind.inner_type(),
+        );
+
+        quote! {
+          ::protocheck::cel::Value::try_from(#value_tokens).map_err(::protocheck::types::cel::CelConversionError::from)
         }
+      }
+    }
+  }
 
-        Ok(CelValue::Map(cel_map.into()))
+  // The Cel value of an item of a list or of a map, like in the conversions of the messages
+  fn get_cel_collection_item_tokens(value: &TokenStream2, kind: &ProstReflectKind) -> TokenStream2 {
+    match kind {
+      ProstReflectKind::Message(_) => quote! {
+        ::protocheck::cel::Value::try_from(#value).map_err(::protocheck::types::cel::CelConversionError::from)?
+      },
+      ProstReflectKind::Bytes => quote! { ::protocheck::cel::Value::from(#value.to_vec()) },
+      ProstReflectKind::Float => quote! { ::protocheck::cel::Value::from(f64::from(#value)) },
+      ProstReflectKind::Int32
+      | ProstReflectKind::Sint32
+      | ProstReflectKind::Sfixed32
+      | ProstReflectKind::Enum(_) => quote! { ::protocheck::cel::Value::from(i64::from(#value)) },
+      ProstReflectKind::Uint32 | ProstReflectKind::Fixed32 => {
+        quote! { ::protocheck::cel::Value::from(u64::from(#value)) }
       }
-      ProstValue::Message(dynamic_msg) => {
-        let msg_desc = dynamic_msg.descriptor();
-        let full_name = msg_desc.full_name();
-
-        match full_name {
-          "google.protobuf.Timestamp" => Ok(CelValue::Timestamp(
-            Timestamp::default().try_into().unwrap(),
-          )),
-          "google.protobuf.Empty" => Ok(Empty {}.into()),
-          "google.protobuf.FieldMask" => Ok(FieldMask::new(vec![]).into()),
-          "google.protobuf.Duration" => {
-            Ok(CelValue::Duration(Duration::default().try_into().unwrap()))
-          }
-          _ => {
-            if depth >= MAX_RECURSION_DEPTH {
-              return Ok(CelValue::Map(HashMap::<CelKey, CelValue>::new().into()));
-            }
+      _ => quote! { ::protocheck::cel::Value::from(#value) },
+    }
+  }
 
-            let mut cel_map = HashMap::new();
+  // The keys of the maps, which can be strings, integers or booleans
+  fn get_cel_key_tokens(key: &TokenStream2, kind: &ProstReflectKind) -> TokenStream2 {
+    match kind {
+      ProstReflectKind::Int32 | ProstReflectKind::Sint32 | ProstReflectKind::Sfixed32 => {
+        quote! { ::protocheck::cel::objects::Key::from(i64::from(#key)) }
+      }
+      ProstReflectKind::Uint32 | ProstReflectKind::Fixed32 => {
+        quote! { ::protocheck::cel::objects::Key::from(u64::from(#key)) }
+      }
+      _ => quote! { ::protocheck::cel::objects::Key::from(#key) },
+    }
+  }
+
+  fn get_cel_item_tokens(value: &TokenStream2, field_type: FieldType) -> TokenStream2 {
+    match field_type {
+      FieldType::Message | FieldType::Any => quote! { #value.clone() },
+      FieldType::Bytes => quote! { #value.to_vec() },
+      FieldType::Float => quote! { (#value as f64) },
+      FieldType::Int32 | FieldType::Sint32 | FieldType::Sfixed32 => {
+        quote! { (#value as i64) }
+      }
+      FieldType::Uint32 | FieldType::Fixed32 => quote! { (#value as u64) },
+      _ => quote! { #value },
+    }
+  }
 
-            for field in msg_desc.fields() {
-              if field.containing_oneof().is_some() {
-                continue;
-              }
+  // Any timestamp, as `now` is only used to check the Cel programs at build time
+  fn now_placeholder() -> CelValue {
+    CelValue::Timestamp(Timestamp::default().try_into().unwrap())
+  }
 
-              let actual_field_name_with_potential_escaping =
-                proto_name_to_rust_name(field.name()).to_string();
+  // The rules of the type of the field (such as StringRules), which contain the Cel rules and the predefined rules
+  fn get_field_rules_cel_value(field_rules: &FieldRules) -> Result<CelValue, Error> {
+    let field_rules_msg = decode_rules("buf.validate.FieldRules", field_rules)?;
+
+    let type_rules = field_rules_msg
+      .descriptor()
+      .oneofs()
+      .find(|oneof| oneof.name() == "type")
+      .and_then(|oneof| {
+        oneof
+          .fields()
+          .find(|field| field_rules_msg.has_field(field))
+      });
+
+    match type_rules {
+      Some(field) => convert_prost_value_to_cel_value(&field_rules_msg.get_field(&field)),
+      None => Ok(CelValue::Map(HashMap::<CelKey, CelValue>::new().into())),
+    }
+  }
 
-              let cel_field_name =
-                CelKey::String(Arc::new(actual_field_name_with_potential_escaping));
+  fn convert_rules_to_cel_value(name: &str, rules: &impl Message) -> Result<CelValue, Error> {
+    convert_prost_value_to_cel_value(&ProstValue::Message(decode_rules(name, rules)?))
+  }
 
-              let cel_field_value = convert_prost_value_to_cel_value_recursive(
-                &ProstValue::default_value(&field.kind()),
-                depth + 1,
-              )?;
+  fn decode_rules(name: &str, rules: &impl Message) -> Result<DynamicMessage, Error> {
+    let descriptor = DESCRIPTOR_POOL.get_message_by_name(name).ok_or_else(|| {
+      error_spanned!(
+        Span::call_site(),
+        format!("Message {name} not found in the descriptor pool")
+      )
+    })?;
+
+    DynamicMessage::decode(descriptor, rules.encode_to_vec().as_slice()).map_err(|e| {
+      error_spanned!(
+        Span::call_site(),
+        format!("Could not decode the rules {name}: {e}")
+      )
+    })
+  }
 
-              cel_map.insert(cel_field_name, cel_field_value);
+  // The tokens building a Cel value, which is used to add the rules of a field to the generated code
+  fn cel_value_tokens(value: &CelValue) -> TokenStream2 {
+    let cel_value = quote! { ::protocheck::cel::Value };
+
+    match value {
+      CelValue::Int(v) => quote! { #cel_value::Int(#v) },
+      CelValue::UInt(v) => quote! { #cel_value::UInt(#v) },
+      CelValue::Float(v) => {
+        // The non finite values have no literal
+        let bits = v.to_bits();
+        quote! { #cel_value::Float(f64::from_bits(#bits)) }
+      }
+      CelValue::Bool(v) => quote! { #cel_value::Bool(#v) },
+      CelValue::String(v) => {
+        let v = v.as_str();
+        quote! { #cel_value::String(::std::sync::Arc::new(#v.to_string())) }
+      }
+      CelValue::Bytes(v) => {
+        let v = LitByteStr::new(v, Span::call_site());
+        quote! { #cel_value::Bytes(::std::sync::Arc::new(#v.to_vec())) }
+      }
+      CelValue::List(items) => {
+        let items = items.iter().map(cel_value_tokens);
+        quote! { #cel_value::List(::std::sync::Arc::new(vec![#(#items),*])) }
+      }
+      CelValue::Map(map) => {
+        let entries = map.map.iter().map(|(key, val)| {
+          let key = match key {
+            CelKey::Int(k) => quote! { ::protocheck::cel::objects::Key::Int(#k) },
+            CelKey::Uint(k) => quote! { ::protocheck::cel::objects::Key::Uint(#k) },
+            CelKey::Bool(k) => quote! { ::protocheck::cel::objects::Key::Bool(#k) },
+            CelKey::String(k) => {
+              let k = k.as_str();
+              quote! { ::protocheck::cel::objects::Key::String(::std::sync::Arc::new(#k.to_string())) }
             }
+          };
+          let val = cel_value_tokens(val);
 
-            Ok(CelValue::Map(cel_map.into()))
-          }
+          quote! { (#key, #val) }
+        });
+
+        quote! {
+          #cel_value::Map(
+            ::std::collections::HashMap::<::protocheck::cel::objects::Key, #cel_value>::from([#(#entries),*]).into()
+          )
+        }
+      }
+      CelValue::Timestamp(v) => {
+        let seconds = v.timestamp();
+        let nanos = v.timestamp_subsec_nanos() as i32;
+
+        quote! {
+          #cel_value::try_from(::protocheck::types::Timestamp { seconds: #seconds, nanos: #nanos })
+            .expect("Invalid timestamp in the Cel rules")
+        }
+      }
+      CelValue::Duration(v) => {
+        let seconds = v.num_seconds();
+        let nanos = v.subsec_nanos();
+
+        quote! {
+          #cel_value::try_from(::protocheck::types::Duration { seconds: #seconds, nanos: #nanos })
+            .expect("Invalid duration in the Cel rules")
         }
       }
+      CelValue::Null | CelValue::Function(..) => quote! { #cel_value::Null },
     }
   }
+
+  fn get_default_field_prost_value(
+    validation_data: &ValidationData,
+    field_desc: &FieldDescriptor,
+  ) -> Result<CelValue, Error> {
+    let default_val = if validation_data.field_kind.is_repeated_item() {
+      ProstValue::default_value(&field_desc.kind())
+    } else {
+      ProstValue::default_value_for_field(field_desc)
+    };
+
+    convert_prost_value_to_cel_value(&default_val)
+  }
+
+  // The same conversion as the one of the dynamic validator, so that the rules have the same values in both
+  fn convert_prost_value_to_cel_value(prost_value: &ProstValue) -> Result<CelValue, Error> {
+    Ok(rules_to_cel_value(prost_value))
+  }
+
+  const MAX_RECURSION_DEPTH: usize = 10;
+
+  // The Json values, whose content (and so the keys of a Struct) is only known during the validation
+  const JSON_VALUE_TYPES: &[&str] = &[
+    "google.protobuf.Struct",
+    "google.protobuf.Value",
+    "google.protobuf.ListValue",
+  ];
+
+  // Whether the message is a Json value or has one in its fields, including the maps and the nested messages
+  fn contains_json_values(message_desc: &MessageDescriptor, depth: usize) -> bool {
+    JSON_VALUE_TYPES.contains(&message_desc.full_name())
+      || (depth < MAX_RECURSION_DEPTH
+        && message_desc.fields().any(|field| match field.kind() {
+          ProstReflectKind::Message(field_message) => {
+            contains_json_values(&field_message, depth + 1)
+          }
+          _ => false,
+        }))
+  }
 }
@@ -1,14 +1,11 @@
 use crate::*;
 
-pub enum ComparableLessThan<T: ComparableError> {
+pub enum ComparableLessThan<T: Bound> {
   Lt(T),
   Lte(T),
 }
 
-impl<T> ComparableLessThan<T>
-where
-  T: Copy + ComparableError,
-{
+impl<T: Bound> ComparableLessThan<T> {
   pub fn value(&self) -> T {
     match self {
       Self::Lte(val) => *val,
@@ -16,23 +13,16 @@ where
     }
   }
 
-  pub fn error_message(&self) -> String {
-    let value_str = self.value().string_representation();
-    let smaller_adjective = T::smaller_adjective();
+  pub fn is_inclusive(&self) -> bool {
+    matches!(self, Self::Lte(_))
+  }
 
-    match self {
-      ComparableLessThan::Lt(_) => format!("must be {smaller_adjective} than {value_str}"),
-      ComparableLessThan::Lte(_) => {
-        format!("must be {smaller_adjective} than or equal to {value_str}")
-      }
-    }
+  pub fn error_message(&self) -> String {
+    rule_messages::less_than_message(self.value(), self.is_inclusive())
   }
 }
 
-impl<T> ComparableGreaterThan<T>
-where
-  T: Copy + ComparableError,
-{
+impl<T: Bound> ComparableGreaterThan<T> {
   pub fn value(&self) -> T {
     match self {
       Self::Gte(val) => *val,
@@ -40,120 +30,45 @@ where
     }
   }
 
-  pub fn error_message(&self) -> String {
-    let value_str = self.value().string_representation();
-    let greater_adjective = T::greater_adjective();
+  pub fn is_inclusive(&self) -> bool {
+    matches!(self, Self::Gte(_))
+  }
 
-    match self {
-      ComparableGreaterThan::Gt(_) => format!("must be {greater_adjective} than {value_str}"),
-      ComparableGreaterThan::Gte(_) => {
-        format!("must be {greater_adjective} than or equal to {value_str}")
-      }
-    }
+  pub fn error_message(&self) -> String {
+    rule_messages::greater_than_message(self.value(), self.is_inclusive())
   }
 }
 
-pub enum ComparableGreaterThan<T: ComparableError> {
+pub enum ComparableGreaterThan<T: Bound> {
   Gt(T),
   Gte(T),
 }
 
-impl ComparableError for Timestamp {
-  fn greater_adjective() -> &'static str {
-    "later"
-  }
-
-  fn smaller_adjective() -> &'static str {
-    "earlier"
-  }
-
-  fn string_representation(&self) -> String {
-    self
-      .format("%d %b %Y %R %Z")
-      .unwrap_or_else(|_| self.to_string())
-  }
-}
-
-impl ComparableError for Duration {
-  fn greater_adjective() -> &'static str {
-    "longer"
-  }
-
-  fn smaller_adjective() -> &'static str {
-    "shorter"
-  }
-}
-
-pub trait ComparableError: Display {
-  fn smaller_adjective() -> &'static str {
-    "less"
-  }
-
-  fn greater_adjective() -> &'static str {
-    "greater"
-  }
-
-  fn string_representation(&self) -> String {
-    self.to_string()
-  }
-}
-
-pub struct ComparableRules<T>
-where
-  T: PartialOrd + PartialEq + ComparableError,
-{
+pub struct ComparableRules<T: Bound> {
   pub less_than: Option<ComparableLessThan<T>>,
   pub greater_than: Option<ComparableGreaterThan<T>>,
 }
 
-impl<T> ComparableRules<T>
-where
-  T: PartialOrd + PartialEq + ComparableError,
-{
+impl<T: Bound> ComparableRules<T> {
   pub fn validate(self) -> Result<Self, &'static str> {
-    if let Some(ref gt_rule) = self.greater_than
-      && let Some(ref lt_rule) = self.less_than {
-        match gt_rule {
-          ComparableGreaterThan::Gte (gte_val) => {
-            match lt_rule {
-              ComparableLessThan::Lte (lte_val) => {
-                if lte_val < gte_val {
-                  return Err("Lte cannot be smaller than Gte");
-                }
-              }
-              ComparableLessThan::Lt (lt_val) => {
-                if lt_val <= gte_val {
-                  return Err("Lt cannot be smaller than Gte");
-                }
-              }
-            };
-          }
-          ComparableGreaterThan::Gt (gt_val) => {
-            match lt_rule {
-              ComparableLessThan::Lte (lte_val) => {
-                if lte_val <= gt_val {
-                  return Err("Lte cannot be smaller than or equal to Gt");
-                }
-              }
-              ComparableLessThan::Lt (lt_val) => {
-                if lt_val <= gt_val {
-                  return Err("Lt cannot be smaller than or equal to Gt");
-                }
-              }
-            };
-          }
-        };
-      }
+    rule_messages::check_bounds(
+      self
+        .less_than
+        .as_ref()
+        .map(|lt_rule| (lt_rule.value(), lt_rule.is_inclusive())),
+      self
+        .greater_than
+        .as_ref()
+        .map(|gt_rule| (gt_rule.value(), gt_rule.is_inclusive())),
+    )?;
+
     Ok(self)
   }
 }
 
 use crate::timestamp_rules::{GreaterThan as TimestampGreaterThan, LessThan as TimestampLessThan};
 
-pub trait RulesWithComparables<T>
-where
-  T: PartialOrd + PartialEq + ComparableError,
-{
+pub trait RulesWithComparables<T: Bound> {
   fn comparable_rules(&self) -> ComparableRules<T>;
 }
 
@@ -210,13 +125,6 @@ impl RulesWithComparables<Timestamp> for TimestampRules {
   }
 }
 
-impl ComparableError for i32 {}
-impl ComparableError for i64 {}
-impl ComparableError for u32 {}
-impl ComparableError for u64 {}
-impl ComparableError for f32 {}
-impl ComparableError for f64 {}
-
 macro_rules! impl_comparable {
   ($target_type:ty, $rule_type:ident) => {
     paste::paste! {
@@ -19,7 +19,7 @@ macro_rules! const_rule {
         fn const_rule(&self) -> Option<ConstRule<$target_type>> {
           self.r#const.as_ref().map(|v| ConstRule {
             val: v.clone(),
-            error_message: format!("must be equal to {v}")
+            error_message: rule_messages::const_message(v)
           })
         }
       }
@@ -35,7 +35,7 @@ impl RuleWithConst<TokenStream2> for BytesRules {
         // Need to assert it as a slice because Bytes does not have PartialEq with arrays
         quote! { &#byte_str[..] }
       },
-      error_message: format!("must be equal to {}", v.escape_ascii()),
+      error_message: rule_messages::const_message(v.escape_ascii()),
     })
   }
 }
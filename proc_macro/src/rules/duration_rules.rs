@@ -9,6 +9,8 @@ pub fn get_duration_rules(
   let field_span = validation_data.field_span;
   let field_name = validation_data.full_name;
 
+  let rules = &normalized_rules(rules);
+
   if let Some(const_rule) = rules.const_rule() {
     validation_data.get_const_validator(&mut tokens, const_rule);
 
@@ -34,3 +36,31 @@ pub fn get_duration_rules(
 
   Ok(tokens)
 }
+
+// The values of the fields are normalized before being compared, so that
+// durations like { seconds: 1, nanos: -5 } are equal to their normalized form
+fn normalized_rules(rules: &DurationRules) -> DurationRules {
+  let mut rules = rules.clone();
+
+  if let Some(const_val) = rules.r#const.as_mut() {
+    const_val.normalize();
+  }
+
+  if let Some(duration_rules::LessThan::Lt(val) | duration_rules::LessThan::Lte(val)) =
+    rules.less_than.as_mut()
+  {
+    val.normalize();
+  }
+
+  if let Some(duration_rules::GreaterThan::Gt(val) | duration_rules::GreaterThan::Gte(val)) =
+    rules.greater_than.as_mut()
+  {
+    val.normalize();
+  }
+
+  for val in rules.r#in.iter_mut().chain(rules.not_in.iter_mut()) {
+    val.normalize();
+  }
+
+  rules
+}
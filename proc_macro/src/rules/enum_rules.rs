@@ -34,8 +34,10 @@ pub fn get_enum_rules(
     let value_ident = validation_data.value_ident();
 
     let validator_tokens = quote! {
-      if !#enum_path::try_from(i32::from(#value_ident)).is_ok() {
-        #violations_ident.push(::protocheck::validators::enums::defined_only(&#field_context_ident, #enum_name));
+      let number = i32::from(#value_ident);
+
+      if #enum_path::try_from(number).is_err() {
+        #violations_ident.push(::protocheck::validators::enums::defined_only(&#field_context_ident, #enum_name, number));
       }
     };
 
@@ -131,13 +131,36 @@ pub fn get_field_kind(field_desc: &FieldDescriptor) -> FieldKind {
   }
 }
 
+// The wrappers of the well known types, which are validated with the rules of the wrapped type
+pub fn wrapped_type(message_desc: &MessageDescriptor) -> Option<FieldType> {
+  match message_desc.full_name() {
+    "google.protobuf.BoolValue" => Some(FieldType::Bool),
+    "google.protobuf.StringValue" => Some(FieldType::String),
+    "google.protobuf.BytesValue" => Some(FieldType::Bytes),
+    "google.protobuf.Int32Value" => Some(FieldType::Int32),
+    "google.protobuf.Int64Value" => Some(FieldType::Int64),
+    "google.protobuf.UInt32Value" => Some(FieldType::Uint32),
+    "google.protobuf.UInt64Value" => Some(FieldType::Uint64),
+    "google.protobuf.FloatValue" => Some(FieldType::Float),
+    "google.protobuf.DoubleValue" => Some(FieldType::Double),
+    _ => None,
+  }
+}
+
+pub fn is_wrapper(field_desc: &FieldDescriptor) -> bool {
+  match field_desc.kind() {
+    ProstReflectKind::Message(message_desc) => wrapped_type(&message_desc).is_some(),
+    _ => false,
+  }
+}
+
 pub fn get_field_type(field_desc: &FieldDescriptor) -> FieldType {
   match field_desc.kind() {
     ProstReflectKind::Message(message_desc) => match message_desc.full_name() {
       "google.protobuf.Duration" => FieldType::Duration,
       "google.protobuf.Timestamp" => FieldType::Timestamp,
       "google.protobuf.Any" => FieldType::Any,
-      _ => FieldType::Message,
+      _ => wrapped_type(&message_desc).unwrap_or(FieldType::Message),
     },
     ProstReflectKind::Double => FieldType::Double,
     ProstReflectKind::Float => FieldType::Float,
@@ -180,14 +203,6 @@ pub fn convert_kind_to_proto_type(kind: ProstReflectKind) -> ProtoType {
   }
 }
 
-pub fn get_plural_suffix(items: u64) -> &'static str {
-  if items != 1 {
-    "s"
-  } else {
-    ""
-  }
-}
-
 pub fn get_field_error(field_name: &str, field_span: Span, error: &str) -> Error {
   Error::new(
     field_span,
@@ -72,24 +72,11 @@ impl LengthRules {
   }
 
   pub fn validate(self) -> Result<LengthRules, String> {
-    let len = self.len;
-    let min_len = self.min_len;
-    let max_len = self.max_len;
-
-    if len.is_some() && (min_len.is_some() || max_len.is_some()) {
-      return Err(format!(
-        "{} cannot be used with {} or {}",
-        self.len_name(),
-        self.min_len_name(),
-        self.max_len_name()
-      ));
-    }
-
-    if let Some(min) = min_len
-      && let Some(max) = max_len
-        && min > max {
-          return Err(format!("{} cannot be larger than {}", self.min_len_name(), self.max_len_name()));
-        }
+    rule_messages::check_lengths(
+      self.len.map(|len| (self.len_name(), len)),
+      (self.min_len_name(), self.min_len),
+      (self.max_len_name(), self.max_len),
+    )?;
 
     Ok(self)
   }
@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use ordered_float::OrderedFloat;
 
 use crate::*;
@@ -79,7 +78,7 @@ impl<'a, T: ToTokens + Clone> List<'a, T> {
       } else {
         quote! {
           protocheck::validators::containing::HashLookup::<#float_type>::Set({
-            static SET: std::sync::LazyLock<std::collections::HashSet<#float_type>> = std::sync::LazyLock::new(|| {
+            static SET: protocheck::types::sync::LazyLock<protocheck::validators::containing::HashSet<#float_type>> = protocheck::types::sync::LazyLock::new(|| {
               [ #floats_tokens ].into_iter().collect()
             });
 
@@ -98,7 +97,7 @@ impl<'a, T: ToTokens + Clone> List<'a, T> {
       quote! {
         protocheck::validators::containing::HashLookup::<#list_item_type>::Set({
 
-          static SET: std::sync::LazyLock<std::collections::HashSet<#list_item_type>> = std::sync::LazyLock::new(|| {
+          static SET: protocheck::types::sync::LazyLock<protocheck::validators::containing::HashSet<#list_item_type>> = protocheck::types::sync::LazyLock::new(|| {
             [ #(#item_tokens),* ].into_iter().collect()
           });
 
@@ -138,28 +137,10 @@ pub struct Lists<'a, T: ToTokens + Clone + PartialEq + Eq + Hash + Display> {
 
 impl<'a, T: ToTokens + Clone + PartialEq + Eq + Hash + Display> Lists<'a, T> {
   pub fn validate(self) -> Result<Self, String> {
-    if self.in_list.is_none() || self.not_in_list.is_none() {
-      return Ok(self);
-    }
-
-    let mut in_list_set: HashSet<&T> = HashSet::new();
-    if let Some(in_list) = self.in_list.as_ref() {
-      in_list_set.extend(in_list.iter());
-    }
-
-    let mut not_in_list_set: HashSet<&T> = HashSet::new();
-    if let Some(not_in_list) = self.not_in_list.as_ref() {
-      not_in_list_set.extend(not_in_list.iter());
-    }
-
-    let invalid_items: Vec<&&T> = in_list_set.intersection(&not_in_list_set).collect();
-
-    if !invalid_items.is_empty() {
-      return Err(format!(
-        "the following items are in the allowed and forbidden lists of items at the same time: {}",
-        invalid_items.iter().format(", ")
-      ));
-    }
+    if let Some(in_list) = self.in_list.as_ref()
+      && let Some(not_in_list) = self.not_in_list.as_ref() {
+        rule_messages::check_lists(in_list, not_in_list, T::to_string)?;
+      }
 
     Ok(self)
   }
@@ -180,8 +161,8 @@ impl ToTokens for BytesWrapper {
 }
 
 pub trait RuleWithLists<T: ToTokens + Display + Hash + Clone + PartialEq + Eq>: Sized {
-  const IN_ERROR_MSG: &'static str = "must be one of the following values";
-  const NOT_IN_ERROR_MSG: &'static str = "cannot be one of the following values";
+  const IN_ERROR_MSG: &'static str = rule_messages::IN_LIST_PREFIX;
+  const NOT_IN_ERROR_MSG: &'static str = rule_messages::NOT_IN_LIST_PREFIX;
   const LIST_KIND: ListKind;
 
   fn lists(&'_ self) -> Lists<'_, T>;
@@ -193,9 +174,7 @@ pub trait RuleWithLists<T: ToTokens + Display + Hash + Clone + PartialEq + Eq>:
     } = self.lists().validate()?;
 
     let in_list = in_list.map(|list| {
-      let error_prefix = Self::IN_ERROR_MSG;
-
-      let error_message = format!("{error_prefix}: {}", list.iter().format(", "));
+      let error_message = rule_messages::list_message(Self::IN_ERROR_MSG, &list, T::to_string);
 
       List {
         error_message,
@@ -205,9 +184,7 @@ pub trait RuleWithLists<T: ToTokens + Display + Hash + Clone + PartialEq + Eq>:
     });
 
     let not_in_list = not_in_list.map(|list| {
-      let error_prefix = Self::NOT_IN_ERROR_MSG;
-
-      let error_message = format!("{error_prefix}: {}", list.iter().format(", "));
+      let error_message = rule_messages::list_message(Self::NOT_IN_ERROR_MSG, &list, T::to_string);
 
       List {
         error_message,
@@ -266,8 +243,8 @@ impl_lists!(Fixed32Rules, u32, U32);
 
 impl RuleWithLists<String> for AnyRules {
   const LIST_KIND: ListKind = ListKind::Any;
-  const IN_ERROR_MSG: &'static str = "must have one of these type URLs";
-  const NOT_IN_ERROR_MSG: &'static str = "cannot have one of these type URLs";
+  const IN_ERROR_MSG: &'static str = rule_messages::TYPE_URL_IN_LIST_PREFIX;
+  const NOT_IN_ERROR_MSG: &'static str = rule_messages::TYPE_URL_NOT_IN_LIST_PREFIX;
 
   fn lists<'a>(&'_ self) -> Lists<'_, String> {
     let in_list = if !self.r#in.is_empty() {
@@ -347,7 +324,22 @@ impl ToTokens for FloatWrapper {
   fn to_tokens(&self, tokens: &mut TokenStream2) {
     let f = self.float;
 
-    let output = if self.is_f32 {
+    let float_type = if self.is_f32 {
+      quote! { f32 }
+    } else {
+      quote! { f64 }
+    };
+
+    // The literals cannot represent the non finite values
+    let output = if f.is_nan() {
+      quote! { #float_type::NAN }
+    } else if f.is_infinite() {
+      if f.is_sign_positive() {
+        quote! { #float_type::INFINITY }
+      } else {
+        quote! { #float_type::NEG_INFINITY }
+      }
+    } else if self.is_f32 {
       proc_macro2::Literal::f32_suffixed(f.into_inner() as f32).to_token_stream()
     } else {
       f.to_token_stream()
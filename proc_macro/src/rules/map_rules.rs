@@ -55,10 +55,10 @@ pub fn get_map_rules(
     map_level_rules.extend(get_cel_rules_checked(
       &CelRuleTemplateTarget::Field {
         field_desc: map_field_desc,
+        field_rules,
         validation_data: map_validation_data,
         field_span: map_validation_data.field_span,
       },
-      &field_rules.cel,
     )?);
   }
 
@@ -92,9 +92,9 @@ pub fn get_map_rules(
             &CelRuleTemplateTarget::Field {
               validation_data: &keys_validation_data,
               field_desc: &key_desc,
+              field_rules: keys_rules_descriptor,
               field_span: map_validation_data.field_span,
             },
-            &keys_rules_descriptor.cel,
           )?;
           keys_rules.extend(cel_rules);
         }
@@ -107,7 +107,8 @@ pub fn get_map_rules(
       if matches!(ignore, Ignore::Always) {
         ignore_values_validators = true;
       } else {
-        let values_validation_data = map_validation_data.to_map_value(get_field_type(&value_desc));
+        let mut values_validation_data = map_validation_data.to_map_value(get_field_type(&value_desc));
+        values_validation_data.is_wrapper = is_wrapper(&value_desc);
 
         if let Some(ref rules) = values_rules_descriptor.r#type
           && !value_is_message {
@@ -125,9 +126,9 @@ pub fn get_map_rules(
             &CelRuleTemplateTarget::Field {
               validation_data: &values_validation_data,
               field_desc: &value_desc,
+              field_rules: values_rules_descriptor,
               field_span: map_validation_data.field_span,
             },
-            &values_rules_descriptor.cel,
           )?;
           values_rules.extend(cel_rules);
         }
@@ -10,7 +10,7 @@ where
     + RuleWithConst<T::Unit>
     + RulesWithComparables<T::Unit>
     + RuleWithLists<HashableType>,
-  <T as NumericRules<HashableType>>::Unit: ComparableError + Copy,
+  <T as NumericRules<HashableType>>::Unit: Bound,
 {
   let mut tokens = TokenStream2::new();
 
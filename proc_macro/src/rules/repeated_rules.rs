@@ -22,10 +22,10 @@ pub fn get_repeated_rules(
     vec_level_rules.extend(get_cel_rules_checked(
       &CelRuleTemplateTarget::Field {
         field_desc,
+        field_rules,
         validation_data,
         field_span: validation_data.field_span,
       },
-      &field_rules.cel,
     )?);
   }
 
@@ -55,14 +55,21 @@ pub fn get_repeated_rules(
 
       let ordered_floats_enabled = cfg!(feature = "ordered-float");
 
+      // The short lists are checked on the stack
       let lookup_tokens = if is_float && !ordered_floats_enabled {
-        quote! { ::protocheck::validators::repeated::UniqueLookup::Vec(vec![]) }
+        quote! {
+          if #vec_ident.len() <= ::protocheck::validators::repeated::INLINE_UNIQUE_ITEMS {
+            ::protocheck::validators::repeated::UniqueLookup::inline()
+          } else {
+            ::protocheck::validators::repeated::UniqueLookup::Vec(Default::default())
+          }
+        }
       } else {
         quote! {
           if #vec_ident.len() < 16 {
-            ::protocheck::validators::repeated::UniqueLookup::Vec(vec![])
+            ::protocheck::validators::repeated::UniqueLookup::inline()
           } else {
-            ::protocheck::validators::repeated::UniqueLookup::Set(::std::collections::HashSet::new())
+            ::protocheck::validators::repeated::UniqueLookup::Set(Default::default())
           }
         }
       };
@@ -118,10 +125,10 @@ pub fn get_repeated_rules(
           let cel_rules = get_cel_rules_checked(
             &CelRuleTemplateTarget::Field {
               field_desc,
+              field_rules: items_rules_descriptor,
               validation_data: repeated_items_validation_data,
               field_span: validation_data.field_span,
             },
-            &items_rules_descriptor.cel,
           )?;
           items_rules.extend(cel_rules);
         }
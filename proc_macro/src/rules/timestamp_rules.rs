@@ -3,6 +3,25 @@ use crate::*;
 pub fn get_timestamp_rules(
   validation_data: &ValidationData,
   rules: &TimestampRules,
+) -> Result<TokenStream2, Error> {
+  let rules_tokens = get_timestamp_value_rules(validation_data, rules)?;
+
+  let field_context_ident = &validation_data.field_context_ident();
+  let value_ident = validation_data.value_ident();
+  let violations_ident = validation_data.violations_ident;
+
+  // The other rules are only evaluated for the timestamps with valid nanos, like in protovalidate
+  Ok(quote! {
+    match ::protocheck::validators::timestamps::valid(&#field_context_ident, #value_ident) {
+      Ok(_) => { #rules_tokens }
+      Err(v) => #violations_ident.push(v)
+    };
+  })
+}
+
+fn get_timestamp_value_rules(
+  validation_data: &ValidationData,
+  rules: &TimestampRules,
 ) -> Result<TokenStream2, Error> {
   let mut tokens = TokenStream2::new();
 
@@ -3,3 +3,12 @@ use crate::*;
 pub fn new_ident(name: &str) -> Ident {
   Ident::new(name, Span::call_site())
 }
+
+// Exits the validation of a message (with `return`) or of a list or map (with `break`) in fail-fast mode, once a violation was found
+pub fn fail_fast_check(violations_ident: &Ident, exit: TokenStream2) -> TokenStream2 {
+  quote! {
+    if ::protocheck::validators::fail_fast::should_stop(#violations_ident.as_slice()) {
+      #exit;
+    }
+  }
+}
@@ -4,12 +4,16 @@ use crate::*;
 pub(crate) struct ValidationData<'a> {
   pub full_name: &'a str,
   pub proto_name: &'a str,
-  pub ignore: Ignore,
+  pub ignore: IgnoreMode,
   pub tag: i32,
   pub is_required: bool,
   pub is_optional: bool,
   pub is_in_oneof: bool,
   pub is_boxed: bool,
+  // For the wrappers of the well known types, like google.protobuf.BoolValue
+  pub is_wrapper: bool,
+  // For the message fields whose message does not have the validators
+  pub skip_nested_validation: bool,
   pub field_span: Span,
 
   // These two are always needed to populate
@@ -105,12 +109,7 @@ impl ValidationData<'_> {
     let unit = rules.unit();
 
     if let Some(len) = rules.len {
-      let error_message = format!(
-        "must be exactly {} {}{} long",
-        len,
-        unit,
-        get_plural_suffix(len)
-      );
+      let error_message = rule_messages::len_message(len, unit);
       let func_name = new_ident(rules.len_name());
 
       let expr = quote! {
@@ -121,12 +120,7 @@ impl ValidationData<'_> {
     }
 
     if let Some(min_len) = rules.min_len {
-      let error_message = format!(
-        "must contain at least {} {}{}",
-        min_len,
-        unit,
-        get_plural_suffix(min_len)
-      );
+      let error_message = rule_messages::min_len_message(min_len, unit);
       let func_name = new_ident(rules.min_len_name());
 
       let expr = quote! {
@@ -137,12 +131,7 @@ impl ValidationData<'_> {
     }
 
     if let Some(max_len) = rules.max_len {
-      let error_message = format!(
-        "cannot contain more than {} {}{}",
-        max_len,
-        unit,
-        get_plural_suffix(max_len)
-      );
+      let error_message = rule_messages::max_len_message(max_len, unit);
       let func_name = new_ident(rules.max_len_name());
 
       let expr = quote! {
@@ -183,12 +172,30 @@ impl ValidationData<'_> {
     tokens: &mut TokenStream2,
     comparable_rules: &ComparableRules<T>,
   ) where
-    T: ToTokens + PartialEq + PartialOrd + ComparableError + Copy,
+    T: ToTokens + Bound,
   {
     let module_path = quote! { ::protocheck::validators::comparables };
     let field_context_ident = self.field_context_ident();
     let value_ident = self.value_ident();
 
+    // Like in protovalidate, the ranges are checked as a single rule
+    if let Some(less_than) = comparable_rules.less_than.as_ref()
+      && let Some(greater_than) = comparable_rules.greater_than.as_ref() {
+        let lower = greater_than.value();
+        let lower_inclusive = greater_than.is_inclusive();
+        let upper = less_than.value();
+        let upper_inclusive = less_than.is_inclusive();
+        let error_message =
+          rule_messages::range_message(lower, lower_inclusive, upper, upper_inclusive);
+
+        let expr = quote! {
+          #module_path::range(&#field_context_ident, #value_ident, #lower, #lower_inclusive, #upper, #upper_inclusive, #error_message)
+        };
+        self.get_validator_tokens(tokens, &expr);
+
+        return;
+      }
+
     if let Some(less_than) = comparable_rules.less_than.as_ref() {
       let error_message = less_than.error_message();
 
@@ -297,7 +304,7 @@ impl ValidationData<'_> {
 
     quote! {
       let #field_context_ident = ::protocheck::field_data::FieldContext {
-        parent_elements: #parent_messages_ident.as_slice(),
+        parent_messages: #parent_messages_ident,
         subscript: #subscript_tokens,
         key_type: #key_type_tokens,
         value_type: #value_type_tokens,
@@ -361,6 +368,7 @@ impl ValidationData<'_> {
       let field_ident = &self.item_rust_ident;
       let key_ident = &self.map_key_ident;
       let map_value_ident = &self.map_value_ident;
+      let fail_fast_check = fail_fast_check(self.violations_ident, quote! { break });
 
       quote! {
         for (#key_ident, #map_value_ident) in self.#field_ident.iter() {
@@ -369,6 +377,8 @@ impl ValidationData<'_> {
 
           #values_context_tokens
           #values_rules
+
+          #fail_fast_check
         }
       }
     });
@@ -442,10 +452,14 @@ impl ValidationData<'_> {
         self.vec_item_context_ident,
       );
 
+      let fail_fast_check = fail_fast_check(self.violations_ident, quote! { break });
+
       quote! {
         for (#index_ident, #item_ident) in self.#field_ident.iter().enumerate() {
           #items_context_tokens
           #items_rules
+
+          #fail_fast_check
         }
       }
     });
@@ -477,6 +491,10 @@ impl ValidationData<'_> {
     tokens: &mut TokenStream2,
     field_kind: FieldKind,
   ) {
+    if self.skip_nested_validation {
+      return;
+    }
+
     let Self {
       parent_messages_ident,
       violations_ident,
@@ -496,36 +514,30 @@ impl ValidationData<'_> {
       _ => &format_ident!("val"),
     };
 
-    // These are not part of the FieldContext but of FieldPathElement
-    // so they need to be cast as integers
     let field_key_type = self
       .map_keys_type
-      .map_or(quote! { None }, |k| quote! { Some(#k as i32) });
+      .map_or(quote! { None }, |k| quote! { Some(#k) });
 
     let field_value_type = self
       .map_values_type
-      .map_or(quote! { None }, |v| quote! { Some(#v as i32) });
+      .map_or(quote! { None }, |v| quote! { Some(#v) });
 
     let subscript_tokens = self.subscript_tokens(field_kind);
 
-    let field_path_element_tokens = quote! {
-      ::protocheck::types::protovalidate::FieldPathElement {
-        field_name: Some(#field_proto_name.to_string()),
-        field_number: Some(#field_tag as i32),
-        field_type: Some(#field_proto_type as i32),
+    let path_field_tokens = quote! {
+      ::protocheck::field_data::PathField {
+        proto_name: #field_proto_name,
+        tag: #field_tag,
+        field_type: #field_proto_type,
         key_type: #field_key_type,
         value_type: #field_value_type,
         subscript: #subscript_tokens,
-      };
+      }
     };
 
-    // We build the nested context and delegate validation to the struct of the field
+    // We extend the path of the parent messages (on the stack) and delegate validation to the struct of the field
     tokens.extend(quote! {
-      let current_nested_field_element = #field_path_element_tokens;
-
-      #parent_messages_ident.push(current_nested_field_element);
-      #value_ident.nested_validate(#parent_messages_ident, #violations_ident);
-      #parent_messages_ident.pop();
+      #value_ident.nested_validate(&#parent_messages_ident.nested(#path_field_tokens), #violations_ident);
     });
   }
 
@@ -547,11 +559,42 @@ impl ValidationData<'_> {
     let item_rust_ident = &self.item_rust_ident;
     let required_validation_tokens = self.get_required_validation_tokens();
 
-    tokens.extend(quote! {
-      if self.#item_rust_ident.is_none() {
-        #required_validation_tokens
+    if self.is_option() {
+      tokens.extend(quote! {
+        if self.#item_rust_ident.is_none() {
+          #required_validation_tokens
+        }
+      });
+    } else {
+      let non_zero_check = self.non_zero_check();
+
+      tokens.extend(quote! {
+        if !(#non_zero_check) {
+          #required_validation_tokens
+        }
+      });
+    }
+  }
+
+  // For the fields without presence, `required` means that the value must not be the zero value
+  // (or an empty list or map), and the other rules are only applied when it is not
+  pub fn wrap_with_required_check(&self, validators: TokenStream2) -> TokenStream2 {
+    if validators.is_empty() {
+      let mut tokens = TokenStream2::new();
+      self.get_required_only_validator(&mut tokens);
+      return tokens;
+    }
+
+    let non_zero_check = self.non_zero_check();
+    let required_check = self.get_required_validation_tokens();
+
+    quote! {
+      if #non_zero_check {
+        #validators
+      } else {
+        #required_check
       }
-    });
+    }
   }
 
   pub fn get_aggregated_validator_tokens(&self, validators: TokenStream2) -> TokenStream2 {
@@ -560,6 +603,13 @@ impl ValidationData<'_> {
     let field_ident = self.item_rust_ident;
 
     if self.is_option() {
+      // IGNORE_IF_DEFAULT_VALUE also skips the values that are set to the zero value
+      let validators = if self.ignores_zero_value() {
+        self.wrap_with_default_value_check(validators)
+      } else {
+        validators
+      };
+
       quote! {
         match self.#field_ident.as_ref() {
           Some(val) => {
@@ -575,7 +625,10 @@ impl ValidationData<'_> {
         #validators
       };
 
-      if matches!(self.ignore, Ignore::IfZeroValue) && !self.is_in_oneof {
+      // `required` takes precedence over the ignore modes, except for IGNORE_ALWAYS
+      if self.is_required && !self.is_in_oneof {
+        self.wrap_with_required_check(validation_tokens)
+      } else if self.ignores_zero_value() {
         self.wrap_with_default_value_check(validation_tokens)
       } else {
         validation_tokens
@@ -584,19 +637,47 @@ impl ValidationData<'_> {
   }
 
   pub fn wrap_with_default_value_check(&self, validators: TokenStream2) -> TokenStream2 {
+    let default_check = self.non_zero_check();
+
+    quote! {
+      if #default_check {
+        #validators
+      }
+    }
+  }
+
+  // Whether the rules are skipped for the zero value. For the fields with presence,
+  // this only happens with IGNORE_IF_DEFAULT_VALUE
+  pub fn ignores_zero_value(&self) -> bool {
+    let has_zero_value = matches!(self.field_kind, FieldKind::Map(_) | FieldKind::Repeated(_))
+      || !matches!(
+        self.field_kind.inner_type(),
+        FieldType::Message | FieldType::Any | FieldType::Group
+      );
+
+    has_zero_value
+      && match self.ignore {
+        IgnoreMode::IfZeroValue => !self.is_optional,
+        IgnoreMode::IfDefaultValue => true,
+        IgnoreMode::Unspecified | IgnoreMode::Always => false,
+      }
+  }
+
+  fn non_zero_check(&self) -> TokenStream2 {
     let value_ident = self.value_ident();
 
-    let default_check = match self.field_kind.inner_type() {
+    if matches!(self.field_kind, FieldKind::Map(_) | FieldKind::Repeated(_)) {
+      return quote! { !#value_ident.is_empty() };
+    }
+
+    match self.field_kind.inner_type() {
       FieldType::Bytes | FieldType::String => quote! { !#value_ident.is_empty() },
       FieldType::Bool => quote! { #value_ident },
       FieldType::Float | FieldType::Double => quote! { #value_ident != 0.0 },
-      _ => quote! { #value_ident != 0 },
-    };
-
-    quote! {
-      if #default_check {
-        #validators
+      FieldType::Duration | FieldType::Timestamp => {
+        quote! { (#value_ident) != ::core::default::Default::default() }
       }
+      _ => quote! { #value_ident != 0 },
     }
   }
 
@@ -623,7 +704,7 @@ impl ValidationData<'_> {
     match field_kind {
       FieldKind::RepeatedItem(_) => {
         let index_ident = self.index_ident;
-        quote! { Some(::protocheck::types::protovalidate::field_path_element::Subscript::Index(#index_ident as u64)) }
+        quote! { Some(::protocheck::field_data::SubscriptRef::Index(#index_ident as u64)) }
       }
 
       FieldKind::MapKey(_) | FieldKind::MapValue(_) => {
@@ -685,6 +766,11 @@ impl ValidationData<'_> {
         FieldKind::Single(_) => self.is_optional || self.is_in_oneof,
       };
 
+      // The wrappers are message fields, so they are always behind a ref like the wrapped value
+      if self.is_wrapper {
+        base_ident = quote! { ::protocheck::wrappers::WrapperValue::wrapped_value(#base_ident) };
+      }
+
       if ident_is_ref && self.field_kind.is_copy() {
         base_ident = quote! { (*#base_ident) }
       }
@@ -699,10 +785,19 @@ impl ValidationData<'_> {
         FieldType::Uint32 => base_ident,
         FieldType::Group => base_ident,
         FieldType::Message => base_ident,
-        FieldType::Duration => base_ident,
-        FieldType::Timestamp => base_ident,
+        // Rebuilt from their fields, so that structurally identical types (like the ones from prost-types) can be validated too
+        FieldType::Duration => quote! {
+          ::protocheck::types::Duration { seconds: #base_ident.seconds, nanos: #base_ident.nanos }.normalized()
+        },
+        FieldType::Timestamp => quote! {
+          ::protocheck::types::Timestamp { seconds: #base_ident.seconds, nanos: #base_ident.nanos }
+        },
         FieldType::Any => base_ident,
-        FieldType::Bytes => quote! { &#base_ident },
+        // Either bytes::Bytes or Vec<u8>, depending on the prost config
+        FieldType::Bytes if ident_is_ref => {
+          quote! { ::core::convert::AsRef::<[u8]>::as_ref(#base_ident) }
+        }
+        FieldType::Bytes => quote! { ::core::convert::AsRef::<[u8]>::as_ref(&#base_ident) },
         FieldType::String => quote! { #base_ident.as_str() },
         FieldType::Fixed64 => quote! { protocheck::wrappers::Fixed64(#base_ident) },
         FieldType::Fixed32 => quote! { protocheck::wrappers::Fixed32(#base_ident) },
@@ -717,10 +812,10 @@ impl ValidationData<'_> {
 }
 
 fn generate_key_subscript(key_proto_type: &ProtoType, key_ident: &Ident) -> TokenStream2 {
-  let subscript_path = quote! { ::protocheck::types::protovalidate::field_path_element::Subscript };
+  let subscript_path = quote! { ::protocheck::field_data::SubscriptRef };
 
   match key_proto_type {
-    ProtoType::String => quote! { #subscript_path::StringKey(#key_ident.clone().into()) },
+    ProtoType::String => quote! { #subscript_path::StringKey(#key_ident.as_str()) },
 
     ProtoType::Uint64 | ProtoType::Uint32 | ProtoType::Fixed64 | ProtoType::Fixed32 => quote! { #subscript_path::UintKey(#key_ident.clone().into()) },
 
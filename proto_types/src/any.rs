@@ -1,8 +1,91 @@
 // From (prost-types)[https://github.com/tokio-rs/prost/blob/master/prost-types/src/any.rs]
+use thiserror::Error;
+
 use super::*;
 use crate::constants::PACKAGE_PREFIX;
 
+/// The prefix of the type URLs created by [`Any::pack`].
+pub const DEFAULT_TYPE_URL_PREFIX: &str = "type.googleapis.com";
+
+/// The errors that can occur when unpacking an [`Any`].
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum UnpackError {
+  /// The type URL of the `Any` is not the one of the requested message.
+  #[error("expected a message of type {expected}, found the type URL \"{type_url}\"")]
+  TypeMismatch {
+    /// The full name of the requested message, like `google.protobuf.Duration`.
+    expected: String,
+    /// The type URL of the `Any`.
+    type_url: String,
+  },
+
+  /// The payload could not be decoded as the requested message.
+  #[error("{0}")]
+  Decode(#[cfg_attr(feature = "std", source)] DecodeError),
+}
+
+// The DecodeError only implements Error with the std feature of prost
+impl From<DecodeError> for UnpackError {
+  fn from(error: DecodeError) -> Self {
+    Self::Decode(error)
+  }
+}
+
 impl Any {
+  /// Packs the message, with a type URL made of [`DEFAULT_TYPE_URL_PREFIX`] and its full name, like `type.googleapis.com/google.protobuf.Duration`.
+  ///
+  /// # Examples
+  /// ```rust
+  /// use proto_types::{Any, Duration, Timestamp};
+  ///
+  /// let any = Any::pack(&Duration::new(5, 0));
+  ///
+  /// assert_eq!(any.type_url, "type.googleapis.com/google.protobuf.Duration");
+  /// assert!(any.is::<Duration>());
+  /// assert_eq!(any.unpack::<Duration>(), Ok(Duration::new(5, 0)));
+  /// assert!(any.unpack::<Timestamp>().is_err());
+  /// ```
+  pub fn pack<M>(msg: &M) -> Self
+  where
+    M: Message + Name,
+  {
+    Self::pack_with_prefix(msg, DEFAULT_TYPE_URL_PREFIX)
+  }
+
+  /// Packs the message with a custom prefix for the type URL, like `type.example.com`. A trailing slash in the prefix is ignored.
+  pub fn pack_with_prefix<M>(msg: &M, prefix: &str) -> Self
+  where
+    M: Message + Name,
+  {
+    Any {
+      type_url: format!("{}/{}", prefix.trim_end_matches('/'), M::full_name()),
+      value: msg.encode_to_vec(),
+    }
+  }
+
+  /// Returns true if the last segment of the type URL is the full name of the message, regardless of the prefix.
+  pub fn is<M>(&self) -> bool
+  where
+    M: Name,
+  {
+    TypeUrl::new(&self.type_url).is_some_and(|type_url| type_url.full_name == M::full_name())
+  }
+
+  /// Decodes the message, after checking that the type URL matches it with [`is`](Any::is).
+  pub fn unpack<M>(&self) -> Result<M, UnpackError>
+  where
+    M: Message + Default + Name,
+  {
+    if !self.is::<M>() {
+      return Err(UnpackError::TypeMismatch {
+        expected: M::full_name(),
+        type_url: self.type_url.clone(),
+      });
+    }
+
+    Ok(M::decode(self.value.as_slice())?)
+  }
+
   /// Serialize the given message type `M` as [`Any`].
   pub fn from_msg<M>(msg: &M) -> Result<Self, EncodeError>
   where
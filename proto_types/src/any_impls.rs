@@ -1,6 +1,6 @@
 #[cfg(feature = "serde")]
 mod serde {
-  use std::fmt;
+  use core::fmt;
 
   use base64::{prelude::BASE64_STANDARD, Engine};
   use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
@@ -3,7 +3,11 @@ use std::{collections::HashMap, convert::Infallible};
 use cel::{objects::Key as CelKey, Value as CelValue};
 use thiserror::Error;
 
-use crate::{duration::DurationError, timestamp::TimestampError, Any, Empty, FieldMask};
+use crate::{
+  duration::DurationError, timestamp::TimestampError, value::Kind, Any, BoolValue, BytesValue,
+  DoubleValue, Duration, Empty, FieldMask, FloatValue, Int32Value, Int64Value, ListValue,
+  StringValue, Struct, Timestamp, UInt32Value, UInt64Value, Value,
+};
 
 #[derive(Debug, Error, PartialEq, Eq, Clone)]
 pub enum CelConversionError {
@@ -12,6 +16,18 @@ pub enum CelConversionError {
 
   #[error("{0}")]
   TimestampError(#[from] TimestampError),
+
+  /// The timestamp is outside of the range of the Cel timestamps, which go from 0001-01-01T00:00:00Z to 9999-12-31T23:59:59.999999999Z.
+  #[error("{0} is out of the range of the Cel timestamps")]
+  TimestampOutOfRange(Timestamp),
+
+  /// The duration is longer than the Cel durations, which go up to 315,576,000,000 seconds (about 10,000 years) in both directions.
+  #[error("{0} is out of the range of the Cel durations")]
+  DurationOutOfRange(Duration),
+
+  /// The google.protobuf.Value has no kind, so it does not hold any of the Json values.
+  #[error("the google.protobuf.Value has no kind")]
+  MissingValueKind,
 }
 
 impl From<Infallible> for CelConversionError {
@@ -30,18 +46,75 @@ impl From<Any> for CelValue {
   }
 }
 
+// The Json values are converted to the corresponding dynamic Cel values, so that a Struct can be used like a map
+// with string keys, as in `this.metadata.labels['env'] == 'prod'`
+impl TryFrom<Value> for CelValue {
+  type Error = CelConversionError;
+
+  fn try_from(value: Value) -> Result<Self, Self::Error> {
+    match value.kind.ok_or(CelConversionError::MissingValueKind)? {
+      Kind::NullValue(_) => Ok(CelValue::Null),
+      Kind::NumberValue(v) => Ok(CelValue::Float(v)),
+      Kind::StringValue(v) => Ok(CelValue::String(v.into())),
+      Kind::BoolValue(v) => Ok(CelValue::Bool(v)),
+      Kind::StructValue(v) => v.try_into(),
+      Kind::ListValue(v) => v.try_into(),
+    }
+  }
+}
+
+impl TryFrom<Struct> for CelValue {
+  type Error = CelConversionError;
+
+  fn try_from(value: Struct) -> Result<Self, Self::Error> {
+    let mut cel_map: HashMap<CelKey, CelValue> = HashMap::new();
+
+    for (key, val) in value.fields {
+      cel_map.insert(key.into(), val.try_into()?);
+    }
+
+    Ok(CelValue::Map(cel_map.into()))
+  }
+}
+
+impl TryFrom<ListValue> for CelValue {
+  type Error = CelConversionError;
+
+  fn try_from(value: ListValue) -> Result<Self, Self::Error> {
+    let cel_vals = value
+      .values
+      .into_iter()
+      .map(CelValue::try_from)
+      .collect::<Result<Vec<CelValue>, CelConversionError>>()?;
+
+    Ok(CelValue::List(cel_vals.into()))
+  }
+}
+
 #[cfg(feature = "chrono")]
 mod chrono {
   use cel::Value as CelValue;
   use chrono::{DateTime, FixedOffset};
 
-  use crate::{cel::CelConversionError, Duration, Timestamp};
+  // The ranges of the Cel timestamps and durations are the same as the ones of protobuf
+  use crate::{
+    cel::CelConversionError,
+    constants::{MAX_DURATION_SECONDS, MAX_TIMESTAMP_SECONDS, MIN_TIMESTAMP_SECONDS},
+    Duration, Timestamp,
+  };
 
+  // The nanos are normalized before the range is checked, so that `{ seconds: 10, nanos: 1_500_000_000 }` is 11.5 seconds
   impl TryFrom<Duration> for CelValue {
     type Error = CelConversionError;
 
     fn try_from(value: Duration) -> Result<Self, Self::Error> {
-      let chrono_dur: chrono::Duration = value.try_into().map_err(CelConversionError::from)?;
+      let normalized = value.normalized();
+
+      if normalized.seconds.unsigned_abs() > MAX_DURATION_SECONDS {
+        return Err(CelConversionError::DurationOutOfRange(value));
+      }
+
+      let chrono_dur: chrono::Duration = normalized.try_into().map_err(CelConversionError::from)?;
 
       Ok(CelValue::Duration(chrono_dur))
     }
@@ -51,13 +124,45 @@ mod chrono {
     type Error = CelConversionError;
 
     fn try_from(value: Timestamp) -> Result<Self, Self::Error> {
+      let normalized = value.normalized();
+
+      if !(MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&normalized.seconds) {
+        return Err(CelConversionError::TimestampOutOfRange(value));
+      }
+
       let chrono_timestamp: DateTime<FixedOffset> =
-        value.try_into().map_err(CelConversionError::from)?;
+        normalized.try_into().map_err(CelConversionError::from)?;
       Ok(CelValue::Timestamp(chrono_timestamp))
     }
   }
 }
 
+// The wrappers are converted to the wrapped value, like in the Cel environment of protovalidate
+macro_rules! impl_wrapper_into_cel {
+  ($wrapper:ty, $variant:ident) => {
+    impl From<$wrapper> for CelValue {
+      fn from(value: $wrapper) -> Self {
+        CelValue::$variant(value.value.into())
+      }
+    }
+  };
+}
+
+impl_wrapper_into_cel!(BoolValue, Bool);
+impl_wrapper_into_cel!(StringValue, String);
+impl_wrapper_into_cel!(Int32Value, Int);
+impl_wrapper_into_cel!(Int64Value, Int);
+impl_wrapper_into_cel!(UInt32Value, UInt);
+impl_wrapper_into_cel!(UInt64Value, UInt);
+impl_wrapper_into_cel!(FloatValue, Float);
+impl_wrapper_into_cel!(DoubleValue, Float);
+
+impl From<BytesValue> for CelValue {
+  fn from(value: BytesValue) -> Self {
+    CelValue::Bytes(value.value.to_vec().into())
+  }
+}
+
 impl From<FieldMask> for CelValue {
   fn from(value: FieldMask) -> Self {
     let paths = value.paths;
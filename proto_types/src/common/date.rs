@@ -1,9 +1,9 @@
-use std::{
+use core::{
   cmp::{Ord, Ordering, PartialOrd},
   fmt::Display,
 };
 
-use ::prost::alloc::string::String;
+use ::prost::alloc::string::{String, ToString};
 use thiserror::Error;
 
 use crate::common::Date;
@@ -22,7 +22,7 @@ pub enum DateError {
 }
 
 impl Display for Date {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     match self.kind() {
       DateKind::Full => write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day),
       DateKind::YearAndMonth => write!(f, "{:04}-{:02}", self.year, self.month),
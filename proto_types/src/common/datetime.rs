@@ -1,4 +1,5 @@
-use std::fmt::{Display, Formatter};
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
 
 use thiserror::Error;
 
@@ -8,13 +9,13 @@ use crate::{
 };
 
 impl Display for TimeZone {
-  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
     write!(f, "{}", self.id)
   }
 }
 
 impl Display for DateTime {
-  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
     if self.year != 0 {
       write!(f, "{:04}-", self.year)?;
     }
@@ -87,7 +88,7 @@ pub enum DateTimeError {
 }
 
 impl PartialOrd for TimeOffset {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
     match (self, other) {
       (Self::UtcOffset(a), Self::UtcOffset(b)) => a.partial_cmp(b),
       // Can't determine order without timezone information
@@ -99,7 +100,7 @@ impl PartialOrd for TimeOffset {
 }
 
 impl PartialOrd for DateTime {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
     if !(self.is_valid() && other.is_valid()) {
       return None;
     }
@@ -118,7 +119,7 @@ impl PartialOrd for DateTime {
       .then_with(|| self.seconds.cmp(&other.seconds))
       .then_with(|| self.nanos.cmp(&other.nanos));
 
-    if ord != std::cmp::Ordering::Equal {
+    if ord != core::cmp::Ordering::Equal {
       return Some(ord);
     }
 
@@ -461,7 +462,7 @@ mod chrono {
     type Error = DateTimeError;
 
     fn try_from(value: crate::DateTime) -> Result<Self, Self::Error> {
-      use std::str::FromStr;
+      use core::str::FromStr;
 
       use chrono::{NaiveDateTime, TimeZone};
       use chrono_tz::Tz;
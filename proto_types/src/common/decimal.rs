@@ -7,7 +7,7 @@ impl Decimal {
 }
 
 impl Display for Decimal {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     write!(f, "{}", self.value)
   }
 }
@@ -1,4 +1,5 @@
-use std::cmp::Ordering;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
 
 use thiserror::Error;
 
@@ -49,6 +50,7 @@ impl Interval {
 
   /// Creates an [`Interval`] going from now to the `end_time` specified.
   /// The present moment is calculated using the SystemTime.
+  #[cfg(feature = "std")]
   pub fn from_now_to(end_time: Timestamp) -> Self {
     Self {
       start_time: Some(Timestamp::now()),
@@ -58,6 +60,7 @@ impl Interval {
 
   /// Creates a new [`Interval`] going from the specified `start_time` to the present moment.
   /// The present moment is calculated using the SystemTime.
+  #[cfg(feature = "std")]
   pub fn from_start_to_now(start_time: Timestamp) -> Self {
     Self {
       start_time: Some(start_time),
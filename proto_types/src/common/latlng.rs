@@ -43,8 +43,8 @@ impl LatLng {
   }
 }
 
-impl std::fmt::Display for LatLng {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for LatLng {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     write!(f, "{:.6},{:.6}", self.latitude, self.longitude)
   }
 }
@@ -1,7 +1,9 @@
 #![allow(clippy::doc_overindented_list_items)]
 #![allow(clippy::doc_lazy_continuation)]
 
-use std::fmt::Display;
+#[cfg(feature = "phone_number")]
+use alloc::string::String;
+use core::fmt::Display;
 
 include!("./google.type.rs");
 
@@ -253,13 +255,13 @@ impl Month {
 }
 
 impl Display for DayOfWeek {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     write!(f, "{}", self.as_title_case())
   }
 }
 
 impl Display for Month {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     write!(f, "{}", self.as_title_case())
   }
 }
@@ -1,7 +1,7 @@
 //! Implementations for the google.type.Money message.
 //! DISCLAIMER: all of the methods implemented for Money are just implemented for convenience, and they are provided as is, without warranties of any kind. By using this module, the user is relieving the authors of this library from any responsibility for any damage that may be caused by its usage.
 
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
 use thiserror::Error;
 
@@ -3,6 +3,13 @@ pub(crate) const PACKAGE_PREFIX: &str = "google.protobuf";
 pub(crate) const TIME_NANOS_MAX: i32 = NANOS_PER_SECOND - 1;
 pub(crate) const NANOS_PER_SECOND: i32 = 1_000_000_000;
 
+// The range of google.protobuf.Timestamp, from 0001-01-01T00:00:00Z to 9999-12-31T23:59:59.999999999Z
+pub(crate) const MIN_TIMESTAMP_SECONDS: i64 = -62_135_596_800;
+pub(crate) const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+
+// The range of google.protobuf.Duration, which is about 10000 years in both directions
+pub(crate) const MAX_DURATION_SECONDS: u64 = 315_576_000_000;
+
 pub(crate) const SECONDS_PER_MINUTE: u64 = 60;
 pub(crate) const SECONDS_PER_HOUR: u64 = 3600;
 pub(crate) const SECONDS_PER_DAY: u64 = 86400;
@@ -27,14 +27,14 @@ mod diesel_mysql {
   impl FromSql<SqlTimestamp, Mysql> for Timestamp {
     fn from_sql(bytes: MysqlValue<'_>) -> DeserializeResult<Self> {
       let chrono_datetime: NaiveDateTime = FromSql::<SqlTimestamp, Mysql>::from_sql(bytes)?;
-      Ok(chrono_datetime.into())
+      Ok(chrono_datetime.try_into()?)
     }
   }
 
   impl FromSql<MysqlDateTime, Mysql> for Timestamp {
     fn from_sql(bytes: MysqlValue<'_>) -> DeserializeResult<Self> {
       let chrono_datetime: NaiveDateTime = FromSql::<MysqlDateTime, Mysql>::from_sql(bytes)?;
-      Ok(chrono_datetime.into())
+      Ok(chrono_datetime.try_into()?)
     }
   }
 
@@ -130,14 +130,14 @@ mod diesel_postgres {
   impl FromSql<SqlTimestamp, Pg> for Timestamp {
     fn from_sql(bytes: PgValue<'_>) -> DeserializeResult<Self> {
       let chrono_datetime: NaiveDateTime = FromSql::<SqlTimestamp, Pg>::from_sql(bytes)?;
-      Ok(chrono_datetime.into())
+      Ok(chrono_datetime.try_into()?)
     }
   }
 
   impl FromSql<Timestamptz, Pg> for Timestamp {
     fn from_sql(bytes: PgValue<'_>) -> DeserializeResult<Self> {
       let chrono_datetime: NaiveDateTime = FromSql::<Timestamptz, Pg>::from_sql(bytes)?;
-      Ok(chrono_datetime.into())
+      Ok(chrono_datetime.try_into()?)
     }
   }
 
@@ -226,9 +226,7 @@ mod diesel_sqlite {
   const ENCODE_TIME_FORMAT: &str = "%T%.f";
 
   fn format_naive_datetime(value: NaiveDateTime) -> String {
-    value
-      .format(ENCODE_NAIVE_DATETIME_FORMAT)
-      .to_string()
+    value.format(ENCODE_NAIVE_DATETIME_FORMAT).to_string()
   }
 
   impl FromSql<Time, Sqlite> for TimeOfDay {
@@ -241,14 +239,14 @@ mod diesel_sqlite {
   impl FromSql<SqlTimestamp, Sqlite> for Timestamp {
     fn from_sql(value: <Sqlite as Backend>::RawValue<'_>) -> DeserializeResult<Self> {
       let chrono_datetime: NaiveDateTime = FromSql::<SqlTimestamp, Sqlite>::from_sql(value)?;
-      Ok(chrono_datetime.into())
+      Ok(chrono_datetime.try_into()?)
     }
   }
 
   impl FromSql<TimestamptzSqlite, Sqlite> for Timestamp {
     fn from_sql(value: <Sqlite as Backend>::RawValue<'_>) -> DeserializeResult<Self> {
       let chrono_datetime: NaiveDateTime = FromSql::<TimestamptzSqlite, Sqlite>::from_sql(value)?;
-      Ok(chrono_datetime.into())
+      Ok(chrono_datetime.try_into()?)
     }
   }
 
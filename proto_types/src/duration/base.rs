@@ -1,6 +1,6 @@
 // Partially taken from (prost-types)[https://github.com/tokio-rs/prost/blob/master/prost-types/src/duration.rs]
 use super::super::*;
-use crate::constants::{NANOS_PER_SECOND, PACKAGE_PREFIX, TIME_NANOS_MAX};
+use crate::constants::{MAX_DURATION_SECONDS, NANOS_PER_SECOND, PACKAGE_PREFIX, TIME_NANOS_MAX};
 
 impl Duration {
   /// Normalizes the duration to a canonical format.
@@ -95,9 +95,14 @@ impl Name for Duration {
 impl TryFrom<time::Duration> for Duration {
   type Error = DurationError;
 
-  /// Converts a `std::time::Duration` to a `Duration`, failing if the duration is too large.
+  /// Converts a `core::time::Duration` to a `Duration`, failing if the duration is longer than the maximum of protobuf (315,576,000,000 seconds, about 10000 years).
   fn try_from(duration: time::Duration) -> Result<Duration, DurationError> {
-    let seconds = i64::try_from(duration.as_secs()).map_err(|_| DurationError::OutOfRange)?;
+    if duration.as_secs() > MAX_DURATION_SECONDS {
+      return Err(DurationError::OutOfRange);
+    }
+
+    // Safe casting as the seconds are in the range checked above
+    let seconds = duration.as_secs() as i64;
 
     let nanos = duration.subsec_nanos() as i32;
 
@@ -110,7 +115,7 @@ impl TryFrom<time::Duration> for Duration {
 impl TryFrom<Duration> for time::Duration {
   type Error = DurationError;
 
-  /// Converts a `Duration` to a `std::time::Duration`, failing if the duration is negative.
+  /// Converts a `Duration` to a `core::time::Duration`, failing if the duration is negative.
   fn try_from(mut duration: Duration) -> Result<time::Duration, DurationError> {
     duration.normalize();
 
@@ -139,14 +144,14 @@ pub enum DurationError {
   /// [1]: https://developers.google.com/protocol-buffers/docs/proto3#json
   ParseFailure,
 
-  /// Indicates failure to convert a `prost_types::Duration` to a `std::time::Duration` because
-  /// the duration is negative. The included `std::time::Duration` matches the magnitude of the
+  /// Indicates failure to convert a `prost_types::Duration` to a `core::time::Duration` because
+  /// the duration is negative. The included `core::time::Duration` matches the magnitude of the
   /// original negative `prost_types::Duration`.
   NegativeDuration(time::Duration),
 
-  /// Indicates failure to convert a `std::time::Duration` to a `prost_types::Duration`.
+  /// Indicates failure to convert a `core::time::Duration` to a `prost_types::Duration`.
   ///
-  /// Converting a `std::time::Duration` to a `prost_types::Duration` fails if the magnitude
+  /// Converting a `core::time::Duration` to a `prost_types::Duration` fails if the magnitude
   /// exceeds that representable by `prost_types::Duration`.
   OutOfRange,
 }
@@ -167,7 +172,7 @@ impl fmt::Display for DurationError {
   }
 }
 
-impl std::error::Error for DurationError {}
+impl core::error::Error for DurationError {}
 
 impl FromStr for Duration {
   type Err = DurationError;
@@ -211,3 +216,38 @@ mod chrono {
     }
   }
 }
+
+#[cfg(feature = "time")]
+mod time_crate {
+  use crate::{constants::MAX_DURATION_SECONDS, duration::DurationError, Duration};
+
+  /// Fails if the duration is longer than the maximum of protobuf, in either direction.
+  impl TryFrom<::time::Duration> for Duration {
+    type Error = DurationError;
+
+    fn try_from(value: ::time::Duration) -> Result<Self, DurationError> {
+      if value.whole_seconds().unsigned_abs() > MAX_DURATION_SECONDS {
+        return Err(DurationError::OutOfRange);
+      }
+
+      // The seconds and the nanos of the time crate always have the same sign, like in protobuf
+      Ok(Self {
+        seconds: value.whole_seconds(),
+        nanos: value.subsec_nanoseconds(),
+      })
+    }
+  }
+
+  /// Fails if the normalized duration is too long for the time crate.
+  impl TryFrom<Duration> for ::time::Duration {
+    type Error = DurationError;
+
+    fn try_from(value: Duration) -> Result<Self, DurationError> {
+      let normalized = value.normalized();
+
+      ::time::Duration::seconds(normalized.seconds)
+        .checked_add(::time::Duration::nanoseconds(normalized.nanos.into()))
+        .ok_or(DurationError::OutOfRange)
+    }
+  }
+}
@@ -22,15 +22,35 @@ mod totokens {
   }
 }
 
-impl std::cmp::PartialOrd for Duration {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+// The durations are compared (and hashed) in their normalized form, so that `{ seconds: 1, nanos: -500_000_000 }`
+// is equal to `{ seconds: 0, nanos: 500_000_000 }`
+impl core::cmp::PartialEq for Duration {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == core::cmp::Ordering::Equal
+  }
+}
+
+impl core::cmp::Eq for Duration {}
+
+impl core::hash::Hash for Duration {
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    let normalized = self.normalized();
+
+    (normalized.seconds, normalized.nanos).hash(state);
+  }
+}
+
+impl core::cmp::PartialOrd for Duration {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
     Some(self.cmp(other))
   }
 }
 
-impl std::cmp::Ord for Duration {
-  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-    (self.seconds, self.nanos).cmp(&(other.seconds, other.nanos))
+impl core::cmp::Ord for Duration {
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    let (this, other) = (self.normalized(), other.normalized());
+
+    (this.seconds, this.nanos).cmp(&(other.seconds, other.nanos))
   }
 }
 
@@ -46,23 +66,7 @@ mod serde {
     where
       S: Serializer,
     {
-      let self_normalized = self.normalized();
-
-      let seconds = self_normalized.seconds;
-      let nanos = self_normalized.nanos;
-
-      let formatted_string = if nanos == 0 {
-        // If nanos are zero, just "Xs"
-        format!("{}s", seconds)
-      } else {
-        let fractional_seconds_str = format!("{:09}", nanos);
-
-        let trimmed_fractional_seconds = fractional_seconds_str.trim_end_matches('0');
-
-        format!("{}.{}s", seconds, trimmed_fractional_seconds)
-      };
-
-      serializer.serialize_str(&formatted_string)
+      serializer.serialize_str(&self.to_json_string())
     }
   }
 
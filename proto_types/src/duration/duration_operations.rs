@@ -1,23 +1,36 @@
-use std::ops::{Add, Div, Mul, Sub};
+use core::ops::{Add, Div, Mul, Neg, Sub};
 
 use crate::{constants::NANOS_PER_SECOND, Duration};
 
+/// Saturates at [`Duration::MIN`] and [`Duration::MAX`], like [`Duration::saturating_add`].
 impl Add for Duration {
   type Output = Duration;
   fn add(self, rhs: Self) -> Self::Output {
-    self
-      .checked_add(&rhs)
-      .expect("Duration addition overflowed")
+    self.saturating_add(&rhs)
   }
 }
 
+/// Saturates at [`Duration::MIN`] and [`Duration::MAX`], like [`Duration::saturating_sub`].
 impl Sub for Duration {
   type Output = Self;
 
   fn sub(self, other: Self) -> Self {
-    self
-      .checked_sub(&other)
-      .expect("Duration subtraction overflowed")
+    self.saturating_sub(&other)
+  }
+}
+
+/// Saturates at [`Duration::MIN`] and [`Duration::MAX`], for the durations that are out of range.
+impl Neg for Duration {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    let this = self.normalized();
+
+    Duration {
+      seconds: this.seconds.saturating_neg(),
+      nanos: -this.nanos,
+    }
+    .clamped()
   }
 }
 
@@ -99,49 +112,73 @@ impl Duration {
     Some(result)
   }
 
-  /// Adds another Duration to this one, returning `Some(Duration)` or `None` on overflow.
+  /// Adds another Duration to this one, returning `None` if the normalized result is outside of the protobuf range of [`Duration::MIN`] to [`Duration::MAX`].
   pub fn checked_add(&self, other: &Duration) -> Option<Self> {
-    let sum_nanos = self.nanos.checked_add(other.nanos)?;
-
-    let sum_seconds = self.seconds.checked_add(other.seconds)?;
+    let (this, other) = (self.normalized(), other.normalized());
 
+    // The normalized nanos have the same sign as the seconds, so they cannot overflow here
     let mut result = Duration {
-      seconds: sum_seconds,
-      nanos: sum_nanos,
+      seconds: this.seconds.checked_add(other.seconds)?,
+      nanos: this.nanos + other.nanos,
     };
 
-    let pre_normalize_seconds = result.seconds;
     result.normalize();
 
-    if (result.seconds == i64::MAX && pre_normalize_seconds != i64::MAX)
-      || (result.seconds == i64::MIN && pre_normalize_seconds != i64::MIN)
-    {
-      return None; // Overflow/underflow detected during or after normalization carry
-    }
-
-    Some(result)
+    result.is_in_range().then_some(result)
   }
 
-  /// Subtracts another Duration from this one, returning `Some(Duration)` or `None` on overflow.
+  /// Subtracts another Duration from this one, returning `None` if the normalized result is outside of the protobuf range of [`Duration::MIN`] to [`Duration::MAX`].
   pub fn checked_sub(&self, other: &Duration) -> Option<Self> {
-    let diff_nanos = self.nanos.checked_sub(other.nanos)?;
-
-    let diff_seconds = self.seconds.checked_sub(other.seconds)?;
+    let (this, other) = (self.normalized(), other.normalized());
 
+    // The normalized nanos have the same sign as the seconds, so they cannot overflow here
     let mut result = Duration {
-      seconds: diff_seconds,
-      nanos: diff_nanos,
+      seconds: this.seconds.checked_sub(other.seconds)?,
+      nanos: this.nanos - other.nanos,
     };
 
-    let pre_normalize_seconds = result.seconds;
     result.normalize();
 
-    if (result.seconds == i64::MAX && pre_normalize_seconds != i64::MAX)
-      || (result.seconds == i64::MIN && pre_normalize_seconds != i64::MIN)
-    {
-      return None; // Overflow/underflow detected during or after normalization carry
+    result.is_in_range().then_some(result)
+  }
+
+  /// Negates the Duration, returning `None` if it is outside of the protobuf range of [`Duration::MIN`] to [`Duration::MAX`] (the range is symmetric, so the valid durations can always be negated).
+  pub fn checked_neg(&self) -> Option<Self> {
+    let this = self.normalized();
+
+    let result = Duration {
+      seconds: this.seconds.checked_neg()?,
+      nanos: -this.nanos,
+    };
+
+    result.is_in_range().then_some(result)
+  }
+
+  /// Adds another Duration to this one, saturating at [`Duration::MIN`] and [`Duration::MAX`]. This is what the `+` operator does.
+  pub fn saturating_add(&self, other: &Duration) -> Self {
+    let (this, other) = (self.normalized(), other.normalized());
+
+    Duration {
+      seconds: this.seconds.saturating_add(other.seconds),
+      nanos: this.nanos + other.nanos,
     }
-    Some(result)
+    .clamped()
+  }
+
+  /// Subtracts another Duration from this one, saturating at [`Duration::MIN`] and [`Duration::MAX`]. This is what the `-` operator does.
+  pub fn saturating_sub(&self, other: &Duration) -> Self {
+    let (this, other) = (self.normalized(), other.normalized());
+
+    Duration {
+      seconds: this.seconds.saturating_sub(other.seconds),
+      nanos: this.nanos - other.nanos,
+    }
+    .clamped()
+  }
+
+  // Normalizes the duration and clamps it to the protobuf range
+  fn clamped(self) -> Self {
+    self.normalized().clamp(Duration::MIN, Duration::MAX)
   }
 
   /// Divides the Duration by an i64 scalar, returning `Some(Duration)` or `None` on overflow.
@@ -1,3 +1,5 @@
+use alloc::{format, string::String};
+
 /// A struct representing seconds. Wraps the value and provides extra formatting methods.
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Seconds {
@@ -1,12 +1,51 @@
 #![allow(clippy::option_map_unit_fn)]
-use std::fmt::Display;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Display;
 
 use super::data::DurationData;
 use crate::Duration;
 
+impl Duration {
+  /// Formats the duration like in the JSON mapping of protobuf, in seconds with 0, 3, 6 or 9 fractional digits, which is the format parsed by [`FromStr`](core::str::FromStr).
+  ///
+  /// The [`Display`] implementation uses a human readable form instead.
+  ///
+  /// # Examples
+  /// ```rust
+  /// use proto_types::Duration;
+  ///
+  /// let duration = Duration { seconds: -3, nanos: -500_000_000 };
+  ///
+  /// assert_eq!(duration.to_json_string(), "-3.500s");
+  /// assert_eq!("-3.500s".parse::<Duration>().unwrap(), duration);
+  /// ```
+  pub fn to_json_string(&self) -> String {
+    let normalized = self.normalized();
+
+    // The seconds and the nanos have the same sign once normalized
+    let sign = if normalized.seconds < 0 || normalized.nanos < 0 {
+      "-"
+    } else {
+      ""
+    };
+    let seconds = normalized.seconds.unsigned_abs();
+    let nanos = normalized.nanos.unsigned_abs();
+
+    if nanos == 0 {
+      format!("{sign}{seconds}s")
+    } else if nanos.is_multiple_of(1_000_000) {
+      format!("{sign}{seconds}.{:03}s", nanos / 1_000_000)
+    } else if nanos.is_multiple_of(1_000) {
+      format!("{sign}{seconds}.{:06}s", nanos / 1_000)
+    } else {
+      format!("{sign}{seconds}.{nanos:09}s")
+    }
+  }
+}
+
 /// Formats a duration in human readable form. (e.g. "2 days 15 hours 12 minutes and 15 seconds")
 impl Display for Duration {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     let DurationData {
       months,
       days,
@@ -1,7 +1,7 @@
 mod base;
 pub use base::DurationError;
 
-use crate::Duration;
+use crate::{constants::MAX_DURATION_SECONDS, Duration};
 
 mod duration_impls;
 
@@ -17,6 +17,23 @@ mod duration_operations;
 mod duration_units;
 
 impl Duration {
+  /// The shortest duration allowed by protobuf, which is about -10,000 years.
+  pub const MIN: Duration = Duration {
+    seconds: -(MAX_DURATION_SECONDS as i64),
+    nanos: -999_999_999,
+  };
+
+  /// The longest duration allowed by protobuf, which is about 10,000 years.
+  pub const MAX: Duration = Duration {
+    seconds: MAX_DURATION_SECONDS as i64,
+    nanos: 999_999_999,
+  };
+
+  /// Returns true if the seconds of the normalized duration are within ±315,576,000,000 (about 10,000 years), which is the range of the valid durations for protobuf.
+  pub fn is_in_range(&self) -> bool {
+    self.normalized().seconds.unsigned_abs() <= MAX_DURATION_SECONDS
+  }
+
   /// Whether the duration is negative or not.
   pub fn is_negative(&self) -> bool {
     self.normalized().seconds < 0
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 use prost::Name;
 
 use crate::{constants::PACKAGE_PREFIX, type_url_for, Empty};
@@ -20,7 +22,7 @@ impl Name for Empty {
 
 #[cfg(feature = "serde")]
 mod serde {
-  use std::fmt;
+  use core::fmt;
 
   use serde::{ser::SerializeStruct, Deserialize, Serialize};
 
@@ -1,5 +1,86 @@
+use alloc::{
+  string::{String, ToString},
+  vec::Vec,
+};
+use core::fmt;
+
+use thiserror::Error;
+
 use crate::FieldMask;
 
+/// The fields of a message, which are used to check the paths of a [`FieldMask`].
+///
+/// The messages with validators implement [`FieldMaskTarget`] with these, which are generated from their descriptor.
+#[derive(Debug)]
+pub struct MessageFields {
+  /// The full name of the message, like `myapp.v1.User`.
+  pub name: &'static str,
+  /// The fields of the message, including the ones of its oneofs.
+  pub fields: &'static [MessageField],
+}
+
+/// A field of [`MessageFields`].
+pub struct MessageField {
+  /// The name of the field, in snake_case like in the proto file.
+  pub name: &'static str,
+  /// The fields of the message type of the field, unless it is a list, a map or a scalar, which cannot be traversed by a field mask path.
+  pub message: Option<&'static MessageFields>,
+}
+
+// The messages can refer to themselves, so only the name of the message of the field is printed
+impl fmt::Debug for MessageField {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("MessageField")
+      .field("name", &self.name)
+      .field("message", &self.message.map(|message| message.name))
+      .finish()
+  }
+}
+
+impl MessageFields {
+  /// Returns the field with the given name.
+  pub fn field(&self, name: &str) -> Option<&'static MessageField> {
+    self.fields.iter().find(|field| field.name == name)
+  }
+
+  /// Returns true if the path refers to a chain of fields, like `address.zip`, where every segment except the last one is a singular message field.
+  pub fn has_path(&self, path: &str) -> bool {
+    let mut message = Some(self);
+
+    path.split('.').all(
+      |segment| match message.and_then(|message| message.field(segment)) {
+        Some(field) => {
+          message = field.message;
+          true
+        }
+        None => false,
+      },
+    )
+  }
+}
+
+/// Implemented by the messages with validators, with the fields described by their descriptor.
+pub trait FieldMaskTarget {
+  fn message_fields() -> &'static MessageFields;
+}
+
+/// The errors of the [`FieldMask`] methods.
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum FieldMaskError {
+  /// The paths do not refer to existing fields of the message.
+  #[error("invalid paths for {message}: {}", paths.join(", "))]
+  InvalidPaths {
+    /// The full name of the message.
+    message: &'static str,
+    /// The invalid paths, in the order of the mask.
+    paths: Vec<String>,
+  },
+
+  /// The path cannot be converted between snake_case and the lowerCamelCase of JSON.
+  #[error("the path {0} cannot be converted to or from its JSON name")]
+  InvalidJsonPath(String),
+}
+
 impl FieldMask {
   pub fn new(paths: Vec<String>) -> Self {
     FieldMask { paths }
@@ -16,11 +97,174 @@ impl FieldMask {
   pub fn add_path(&mut self, path: &str) {
     self.paths.push(path.to_string());
   }
+
+  /// Returns true if the path is in the mask, or if one of its parents is, as `a.b` covers `a.b.c` (but not `a.bc`).
+  ///
+  /// # Examples
+  /// ```rust
+  /// use proto_types::FieldMask;
+  ///
+  /// let mask = FieldMask::new(vec!["name".to_string(), "address.zip".to_string()]);
+  ///
+  /// assert!(mask.covers("address.zip"));
+  /// assert!(mask.covers("name.first"));
+  /// assert!(!mask.covers("address"));
+  /// ```
+  pub fn covers(&self, path: &str) -> bool {
+    self.paths.iter().any(|p| is_covered(path, p))
+  }
+
+  /// Sorts the paths, and removes the duplicates and the ones covered by another path, so that `b, a.c, a` becomes `a, b`.
+  pub fn normalize(&mut self) {
+    // The dot comes before all of the characters allowed in the field names, so the children follow their parent
+    self.paths.sort_unstable();
+
+    let mut normalized: Vec<String> = Vec::with_capacity(self.paths.len());
+
+    for path in self.paths.drain(..) {
+      if !normalized
+        .last()
+        .is_some_and(|last| is_covered(&path, last))
+      {
+        normalized.push(path);
+      }
+    }
+
+    self.paths = normalized;
+  }
+
+  /// Returns a normalized copy of the mask.
+  pub fn normalized(&self) -> Self {
+    let mut result = self.clone();
+
+    result.normalize();
+
+    result
+  }
+
+  /// Returns the normalized mask with the paths covered by either one of the masks.
+  pub fn union(&self, other: &FieldMask) -> Self {
+    let mut result = FieldMask::new(self.paths.iter().chain(&other.paths).cloned().collect());
+
+    result.normalize();
+
+    result
+  }
+
+  /// Returns the normalized mask with the paths covered by both the masks, so that the intersection of `a` and `a.b, c` is `a.b`.
+  pub fn intersect(&self, other: &FieldMask) -> Self {
+    let mut paths = Vec::new();
+
+    for path in &self.paths {
+      for other_path in &other.paths {
+        if is_covered(path, other_path) {
+          paths.push(path.clone());
+        } else if is_covered(other_path, path) {
+          paths.push(other_path.clone());
+        }
+      }
+    }
+
+    let mut result = FieldMask::new(paths);
+
+    result.normalize();
+
+    result
+  }
+
+  /// Returns the paths that do not refer to a chain of fields of the message, like `address.zip` where `address` is a singular message field with a `zip` field.
+  ///
+  /// The paths must use the snake_case names of the fields, which is their canonical form. The lowerCamelCase names of JSON are converted when deserializing the mask with serde.
+  pub fn invalid_paths<T: FieldMaskTarget>(&self) -> Vec<&str> {
+    let message = T::message_fields();
+
+    self
+      .paths
+      .iter()
+      .map(String::as_str)
+      .filter(|path| !message.has_path(path))
+      .collect()
+  }
+
+  /// Checks that every path refers to a chain of fields of the message, like [`invalid_paths`](FieldMask::invalid_paths), and returns the invalid ones in the error.
+  pub fn validate_for<T: FieldMaskTarget>(&self) -> Result<(), FieldMaskError> {
+    let invalid_paths = self.invalid_paths::<T>();
+
+    if invalid_paths.is_empty() {
+      Ok(())
+    } else {
+      Err(FieldMaskError::InvalidPaths {
+        message: T::message_fields().name,
+        paths: invalid_paths.into_iter().map(String::from).collect(),
+      })
+    }
+  }
+
+  /// Converts the paths into the lowerCamelCase names of the JSON mapping, like `user.display_name` into `user.displayName`.
+  ///
+  /// Like in the protobuf libraries, this fails for the paths with uppercase letters or with underscores that are not followed by a lowercase letter, which could not be converted back.
+  pub fn to_json_paths(&self) -> Result<Vec<String>, FieldMaskError> {
+    self.paths.iter().map(|path| snake_to_camel(path)).collect()
+  }
+
+  /// Creates a mask from the lowerCamelCase paths of the JSON mapping, converting them to snake_case. The paths with underscores are rejected.
+  pub fn from_json_paths<I, S>(paths: I) -> Result<Self, FieldMaskError>
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    paths
+      .into_iter()
+      .map(|path| camel_to_snake(path.as_ref()))
+      .collect::<Result<_, _>>()
+      .map(FieldMask::new)
+  }
+}
+
+fn is_covered(path: &str, parent: &str) -> bool {
+  path
+    .strip_prefix(parent)
+    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+fn snake_to_camel(path: &str) -> Result<String, FieldMaskError> {
+  let mut result = String::with_capacity(path.len());
+  let mut chars = path.chars();
+
+  while let Some(c) = chars.next() {
+    match c {
+      '_' => match chars.next() {
+        Some(next) if next.is_ascii_lowercase() => result.push(next.to_ascii_uppercase()),
+        _ => return Err(FieldMaskError::InvalidJsonPath(path.to_string())),
+      },
+      c if c.is_ascii_uppercase() => return Err(FieldMaskError::InvalidJsonPath(path.to_string())),
+      c => result.push(c),
+    }
+  }
+
+  Ok(result)
+}
+
+fn camel_to_snake(path: &str) -> Result<String, FieldMaskError> {
+  let mut result = String::with_capacity(path.len() + 4);
+
+  for c in path.chars() {
+    match c {
+      '_' => return Err(FieldMaskError::InvalidJsonPath(path.to_string())),
+      c if c.is_ascii_uppercase() => {
+        result.push('_');
+        result.push(c.to_ascii_lowercase());
+      }
+      c => result.push(c),
+    }
+  }
+
+  Ok(result)
 }
 
 #[cfg(feature = "serde")]
 mod serde {
-  use std::fmt;
+  use core::fmt;
 
   use serde::{Deserialize, Serialize};
 
@@ -30,7 +274,11 @@ mod serde {
     where
       S: serde::Serializer,
     {
-      let joined_paths = self.paths.join(",");
+      // The paths are written with their JSON names, like in the protobuf JSON mapping
+      let joined_paths = self
+        .to_json_paths()
+        .map_err(serde::ser::Error::custom)?
+        .join(",");
       serializer.serialize_str(&joined_paths)
     }
   }
@@ -57,9 +305,7 @@ mod serde {
             return Ok(FieldMask { paths: Vec::new() });
           }
 
-          let paths: Vec<String> = value.split(",").map(|s| s.trim().to_string()).collect();
-
-          Ok(FieldMask { paths })
+          FieldMask::from_json_paths(value.split(",").map(str::trim)).map_err(E::custom)
         }
       }
 
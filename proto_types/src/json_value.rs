@@ -0,0 +1,120 @@
+use thiserror::Error;
+
+use crate::{value::Kind, ListValue, NullValue, Struct, Value};
+
+// 2^53 - 1, the largest integer up to which all of the integers can be represented exactly by a double
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// The errors that can occur when converting a [`Value`] into a [`serde_json::Value`].
+#[derive(Debug, Error, PartialEq, Clone)]
+pub enum JsonValueError {
+  /// JSON cannot represent NaN and the infinite numbers.
+  #[error("{0} cannot be represented as a JSON number")]
+  NonFiniteNumber(f64),
+
+  /// The google.protobuf.Value has no kind, so it does not hold any of the JSON values.
+  #[error("the google.protobuf.Value has no kind")]
+  MissingValueKind,
+}
+
+// The doubles that hold an integer are written without a fractional part, like in the protobuf JSON mapping,
+// so that the integers in the JSON documents are preserved. Negative zero is kept as a float to keep its sign.
+pub(crate) fn as_safe_integer(number: f64) -> Option<i64> {
+  (number.fract() == 0.0
+    && number.abs() <= MAX_SAFE_INTEGER
+    && !(number == 0.0 && number.is_sign_negative()))
+  .then_some(number as i64)
+}
+
+/// Converts a JSON value, with the numbers converted to doubles (so the integers above 2^53 lose their precision).
+impl From<serde_json::Value> for Value {
+  fn from(value: serde_json::Value) -> Self {
+    let kind = match value {
+      serde_json::Value::Null => Kind::NullValue(NullValue::NullValue as i32),
+      serde_json::Value::Bool(v) => Kind::BoolValue(v),
+      // Without the arbitrary_precision feature of serde_json, all of the numbers can be converted to f64
+      serde_json::Value::Number(v) => Kind::NumberValue(v.as_f64().unwrap_or(f64::NAN)),
+      serde_json::Value::String(v) => Kind::StringValue(v),
+      serde_json::Value::Array(v) => Kind::ListValue(v.into()),
+      serde_json::Value::Object(v) => Kind::StructValue(v.into()),
+    };
+
+    Value { kind: Some(kind) }
+  }
+}
+
+impl From<serde_json::Map<String, serde_json::Value>> for Struct {
+  fn from(map: serde_json::Map<String, serde_json::Value>) -> Self {
+    Struct {
+      fields: map
+        .into_iter()
+        .map(|(key, value)| (key, value.into()))
+        .collect(),
+    }
+  }
+}
+
+impl From<Vec<serde_json::Value>> for ListValue {
+  fn from(values: Vec<serde_json::Value>) -> Self {
+    ListValue {
+      values: values.into_iter().map(Value::from).collect(),
+    }
+  }
+}
+
+/// Converts the value into JSON, failing for the non-finite numbers and the values without a kind. The numbers that hold an integer of up to 2^53 are converted to JSON integers.
+///
+/// # Examples
+/// ```rust
+/// use proto_types::{json_value::JsonValueError, Value};
+/// use serde_json::json;
+///
+/// let json = json!({ "name": "obiwan", "tags": ["jedi", 1, 1.5, null] });
+///
+/// assert_eq!(serde_json::Value::try_from(Value::from(json.clone())), Ok(json));
+/// assert!(matches!(
+///   serde_json::Value::try_from(Value::from(f64::INFINITY)),
+///   Err(JsonValueError::NonFiniteNumber(_))
+/// ));
+/// ```
+impl TryFrom<Value> for serde_json::Value {
+  type Error = JsonValueError;
+
+  fn try_from(value: Value) -> Result<Self, Self::Error> {
+    let json = match value.kind.ok_or(JsonValueError::MissingValueKind)? {
+      Kind::NullValue(_) => serde_json::Value::Null,
+      Kind::BoolValue(v) => serde_json::Value::Bool(v),
+      Kind::NumberValue(v) => match as_safe_integer(v) {
+        Some(integer) => integer.into(),
+        None => serde_json::Number::from_f64(v)
+          .ok_or(JsonValueError::NonFiniteNumber(v))?
+          .into(),
+      },
+      Kind::StringValue(v) => serde_json::Value::String(v),
+      Kind::ListValue(v) => serde_json::Value::Array(v.try_into()?),
+      Kind::StructValue(v) => serde_json::Value::Object(v.try_into()?),
+    };
+
+    Ok(json)
+  }
+}
+
+impl TryFrom<Struct> for serde_json::Map<String, serde_json::Value> {
+  type Error = JsonValueError;
+
+  fn try_from(value: Struct) -> Result<Self, Self::Error> {
+    value
+      .fields
+      .into_iter()
+      .map(|(key, value)| Ok((key, value.try_into()?)))
+      .collect()
+  }
+}
+
+impl TryFrom<ListValue> for Vec<serde_json::Value> {
+  type Error = JsonValueError;
+
+  fn try_from(value: ListValue) -> Result<Self, Self::Error> {
+    value.values.into_iter().map(TryInto::try_into).collect()
+  }
+}
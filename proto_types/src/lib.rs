@@ -1,9 +1,22 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
 //! # Feature flags
 #![doc = document_features::document_features!()]
 
+extern crate alloc;
+
+/// The synchronization primitives used for the statics of this crate and of the validators, which come from `spin` without the `std` feature.
+pub mod sync {
+  #[cfg(feature = "std")]
+  pub use std::sync::LazyLock;
+
+  /// A value that is initialized on its first access, like [`std::sync::LazyLock`].
+  #[cfg(not(feature = "std"))]
+  pub type LazyLock<T, F = fn() -> T> = spin::Lazy<T, F>;
+}
+
 /// Generated rust code from buf.validate protobuf package, with some added methods and structs.
 #[cfg(feature = "protovalidate")]
 pub mod protovalidate;
@@ -26,7 +39,12 @@ pub mod duration;
 
 pub mod timestamp;
 
+/// Conversions between [`Value`], [`Struct`] and [`ListValue`] and the types of serde_json.
+#[cfg(feature = "serde")]
+pub mod json_value;
+
 mod any;
+pub use any::{UnpackError, DEFAULT_TYPE_URL_PREFIX};
 mod any_impls;
 #[cfg(any(
   feature = "diesel-postgres",
@@ -36,6 +54,10 @@ mod any_impls;
 mod diesel_impls;
 
 mod field_mask;
+pub use field_mask::{FieldMaskError, FieldMaskTarget, MessageField, MessageFields};
+
+mod wrappers;
+pub use wrappers::WrapperExt;
 
 mod field_type;
 #[doc(inline)]
@@ -48,8 +70,7 @@ mod conversions;
 mod datetime_internal;
 mod type_url;
 
-use core::{convert::TryFrom, fmt, time};
-use std::str::FromStr;
+use core::{convert::TryFrom, fmt, str::FromStr, time};
 
 use prost::{
   alloc::{format, string::String, vec::Vec},
@@ -1778,7 +1778,7 @@ pub struct Mixin {
 /// be expressed in JSON format as "3.000000001s", and 3 seconds and 1
 /// microsecond should be expressed in JSON format as "3.000001s".
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, Copy, ::prost::Message)]
 #[cfg_attr(
   feature = "diesel-postgres",
   derive(diesel::QueryId, diesel::AsExpression, diesel::FromSqlRow),
@@ -2038,7 +2038,6 @@ pub struct FieldMask {
 /// The JSON representation for `Struct` is JSON object.
 
 #[derive(Clone, PartialEq, ::prost::Message)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Struct {
   /// Unordered map of dynamically typed values.
   #[prost(btree_map = "string, message", tag = "1")]
@@ -2221,7 +2220,7 @@ impl NullValue {
 /// [`strftime`](<https://docs.python.org/2/library/time.html#time.strftime>) with
 /// the time format spec '%Y-%m-%dT%H:%M:%S.%fZ'. Likewise, in Java, one can use
 /// the Joda Time's [`ISODateTimeFormat.dateTime()`](<http://www.joda.org/joda-time/apidocs/org/joda/time/format/ISODateTimeFormat.html#dateTime%2D%2D>) to obtain a formatter capable of generating timestamps in this format.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, Copy, ::prost::Message)]
 #[cfg_attr(
   any(feature = "diesel-postgres", feature = "diesel-sqlite"),
   derive(diesel::QueryId, diesel::AsExpression, diesel::FromSqlRow),
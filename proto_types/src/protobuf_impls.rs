@@ -8,7 +8,30 @@ mod serde {
     Deserialize, Deserializer, Serialize,
   };
 
-  use crate::{value::Kind, BytesValue, ListValue, NullValue, Struct, Value};
+  use crate::{
+    json_value::as_safe_integer, value::Kind, BytesValue, ListValue, NullValue, Struct, Value,
+  };
+
+  // Like in the protobuf JSON mapping, a Struct is a plain JSON object with its fields
+  impl Serialize for Struct {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+      S: Serializer,
+    {
+      self.fields.serialize(serializer)
+    }
+  }
+
+  impl<'de> Deserialize<'de> for Struct {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+      D: de::Deserializer<'de>,
+    {
+      let fields =
+        <::prost::alloc::collections::BTreeMap<String, Value>>::deserialize(deserializer)?;
+      Ok(Struct { fields })
+    }
+  }
 
   impl Serialize for ListValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -36,7 +59,13 @@ mod serde {
     {
       match self.kind {
         Some(Kind::NullValue(_)) => serializer.serialize_unit(),
-        Some(Kind::NumberValue(v)) => serializer.serialize_f64(v),
+        Some(Kind::NumberValue(v)) => match as_safe_integer(v) {
+          Some(integer) => serializer.serialize_i64(integer),
+          None if v.is_finite() => serializer.serialize_f64(v),
+          None => Err(serde::ser::Error::custom(
+            "NaN and infinite numbers cannot be serialized as a Value",
+          )),
+        },
         Some(Kind::StringValue(ref v)) => serializer.serialize_str(v),
         Some(Kind::BoolValue(v)) => serializer.serialize_bool(v),
         Some(Kind::StructValue(ref v)) => v.serialize(serializer),
@@ -60,7 +89,7 @@ mod serde {
   impl<'de> Visitor<'de> for ValueVisitor {
     type Value = Value;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
       formatter.write_str("a JSON value (null, number, string, boolean, object, or array)")
     }
 
@@ -82,6 +111,20 @@ mod serde {
       })
     }
 
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+      E: de::Error,
+    {
+      self.visit_f64(v as f64)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+      E: de::Error,
+    {
+      self.visit_f64(v as f64)
+    }
+
     fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
     where
       E: de::Error,
@@ -128,6 +171,15 @@ mod serde {
     }
   }
 
+  impl Serialize for BytesValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+      S: Serializer,
+    {
+      serializer.serialize_str(&BASE64_STANDARD.encode(&self.value))
+    }
+  }
+
   impl<'de> Deserialize<'de> for BytesValue {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -138,7 +190,7 @@ mod serde {
       impl<'de> Visitor<'de> for BytesValueVisitor {
         type Value = BytesValue;
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
           formatter.write_str("a base64 encoded string")
         }
 
@@ -3095,9 +3095,12 @@ pub mod timestamp_rules {
 /// Protovalidate when a proto message fails to meet the requirements set by the `Rule` validation rules.
 /// Each individual violation is represented by a `Violation` message.
 #[derive(Clone, PartialEq, ::prost::Message)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
 pub struct Violations {
   /// `violations` is a repeated field that contains all the `Violation` messages corresponding to the violations detected.
   #[prost(message, repeated, tag = "1")]
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
   pub violations: ::prost::alloc::vec::Vec<Violation>,
 }
 /// `Violation` represents a single instance where a validation rule, expressed
@@ -3144,6 +3147,8 @@ pub struct Violations {
 /// }
 /// ```
 #[derive(Clone, PartialEq, ::prost::Message)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
 pub struct Violation {
   /// `field` is a machine-readable path to the field that failed validation.
   /// This could be a nested field, in which case the path will include all the parent fields leading to the actual field that caused the violation.
@@ -3165,6 +3170,7 @@ pub struct Violation {
   /// }
   /// ```
   #[prost(message, optional, tag = "5")]
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
   pub field: ::core::option::Option<FieldPath>,
   /// `rule` is a machine-readable path that points to the specific rule that failed validation.
   /// This will be a nested field starting from the FieldRules of the field that failed validation.
@@ -3195,17 +3201,21 @@ pub struct Violation {
   /// }
   /// ```
   #[prost(message, optional, tag = "6")]
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
   pub rule: ::core::option::Option<FieldPath>,
   /// `rule_id` is the unique identifier of the `Rule` that was not fulfilled.
   /// This is the same `id` that was specified in the `Rule` message, allowing easy tracing of which rule was violated.
   #[prost(string, optional, tag = "2")]
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
   pub rule_id: ::core::option::Option<::prost::alloc::string::String>,
   /// `message` is a human-readable error message that describes the nature of the violation.
   /// This can be the default error message from the violated `Rule`, or it can be a custom message that gives more context about the violation.
   #[prost(string, optional, tag = "3")]
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
   pub message: ::core::option::Option<::prost::alloc::string::String>,
   /// `for_key` indicates whether the violation was caused by a map key, rather than a value.
   #[prost(bool, optional, tag = "4")]
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
   pub for_key: ::core::option::Option<bool>,
 }
 /// `FieldPath` provides a path to a nested protobuf field.
@@ -3213,9 +3223,12 @@ pub struct Violation {
 /// This message provides enough information to render a dotted field path even without protobuf descriptors.
 /// It also provides enough information to resolve a nested field through unknown wire data.
 #[derive(Clone, PartialEq, ::prost::Message)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
 pub struct FieldPath {
   /// `elements` contains each element of the path, starting from the root and recursing downward.
   #[prost(message, repeated, tag = "1")]
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
   pub elements: ::prost::alloc::vec::Vec<FieldPathElement>,
 }
 /// `FieldPathElement` provides enough information to nest through a single protobuf field.
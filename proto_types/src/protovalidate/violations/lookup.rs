@@ -0,0 +1,215 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::protovalidate::{
+  field_path_element::Subscript, FieldPathElement, Violation, Violations,
+};
+
+impl Violations {
+  /// Returns the violations of the field at the given path, which uses the same syntax as the [`Display`](core::fmt::Display) output of [`FieldPath`](crate::protovalidate::FieldPath), such as `person.friends[0].name` or `person.pets["luna"]`.
+  ///
+  /// The path is compared element by element, so `items[2].sku` only matches the `sku` of the third item, and the numeric subscripts match the indexes as well as the integer keys of a map. The violations of a map key (with `for_key` set) are included together with the ones of its value. A path that cannot be parsed matches nothing.
+  ///
+  /// # Examples
+  /// ```rust
+  /// use proto_types::protovalidate::{field_path_element::Subscript, FieldPathElement, Violation, Violations};
+  ///
+  /// let element = |name: &str, subscript: Option<Subscript>| FieldPathElement {
+  ///   field_name: Some(name.to_string()),
+  ///   subscript,
+  ///   ..Default::default()
+  /// };
+  ///
+  /// let violations = Violations {
+  ///   violations: vec![
+  ///     Violation::new(vec![element("items", Some(Subscript::Index(2))), element("sku", None)], "sku.format", "invalid sku"),
+  ///     Violation::new(vec![element("items", Some(Subscript::Index(3))), element("sku", None)], "sku.format", "invalid sku"),
+  ///   ],
+  /// };
+  ///
+  /// assert_eq!(violations.for_field("items[2].sku").count(), 1);
+  /// assert_eq!(violations.for_field_prefix("items").count(), 2);
+  /// ```
+  pub fn for_field(&self, path: &str) -> impl Iterator<Item = &Violation> {
+    self.matching(path, false)
+  }
+
+  /// Returns the violations of the field at the given path and of all the fields nested inside of it, such as the fields of a message or the items of a list or a map (when the path does not end with a subscript).
+  ///
+  /// The path uses the same syntax as in [`for_field`](Violations::for_field), and it is also compared element by element, so `address.zip` does not include `address.zip_code`.
+  pub fn for_field_prefix(&self, prefix: &str) -> impl Iterator<Item = &Violation> {
+    self.matching(prefix, true)
+  }
+
+  /// Returns the rule ids of the violations, in order.
+  pub fn rule_ids(&self) -> impl Iterator<Item = &str> {
+    self.violations.iter().map(Violation::rule_id)
+  }
+
+  fn matching<'a>(&'a self, path: &str, is_prefix: bool) -> impl Iterator<Item = &'a Violation> {
+    let query = parse_path(path);
+
+    self.violations.iter().filter(move |violation| {
+      let Some(query) = &query else {
+        return false;
+      };
+
+      let elements = violation
+        .field
+        .as_ref()
+        .map_or(&[][..], |field| field.elements.as_slice());
+
+      let length_matches = if is_prefix {
+        elements.len() >= query.len()
+      } else {
+        elements.len() == query.len()
+      };
+
+      length_matches
+        && elements
+          .iter()
+          .zip(query)
+          .enumerate()
+          .all(|(i, (element, query_element))| {
+            // The last element of a prefix without a subscript also includes all of the items of a list or map
+            let any_subscript =
+              is_prefix && i == query.len() - 1 && query_element.subscript.is_none();
+
+            query_element.matches(element, any_subscript)
+          })
+    })
+  }
+}
+
+// An element of a parsed path. The numeric subscripts are kept as numbers, because the same syntax
+// is used for the indexes and for the integer keys of the maps
+struct QueryElement {
+  name: String,
+  subscript: Option<QuerySubscript>,
+}
+
+enum QuerySubscript {
+  Number(i128),
+  Bool(bool),
+  String(String),
+}
+
+impl QueryElement {
+  fn matches(&self, element: &FieldPathElement, any_subscript: bool) -> bool {
+    if self.name != element.field_name() {
+      return false;
+    }
+
+    match (&self.subscript, &element.subscript) {
+      (None, None) => true,
+      (None, Some(_)) => any_subscript,
+      (Some(QuerySubscript::Number(number)), Some(Subscript::Index(value)))
+      | (Some(QuerySubscript::Number(number)), Some(Subscript::UintKey(value))) => {
+        *number == i128::from(*value)
+      }
+      (Some(QuerySubscript::Number(number)), Some(Subscript::IntKey(value))) => {
+        *number == i128::from(*value)
+      }
+      (Some(QuerySubscript::Bool(key)), Some(Subscript::BoolKey(value))) => key == value,
+      (Some(QuerySubscript::String(key)), Some(Subscript::StringKey(value))) => key == value,
+      _ => false,
+    }
+  }
+}
+
+// Parses a path like `person.friends[0].pets["luna"]`, with the string keys in double quotes and
+// with the same escapes used by the Display output of FieldPath (the ones of Rust's Debug)
+fn parse_path(path: &str) -> Option<Vec<QueryElement>> {
+  let mut elements = Vec::new();
+  let mut chars = path.chars().peekable();
+
+  loop {
+    let mut name = String::new();
+
+    while let Some(&c) = chars.peek() {
+      if c == '.' || c == '[' {
+        break;
+      }
+
+      name.push(c);
+      chars.next();
+    }
+
+    let subscript = if chars.peek() == Some(&'[') {
+      chars.next();
+      Some(parse_subscript(&mut chars)?)
+    } else {
+      None
+    };
+
+    // Only the first element can have no name, like in the paths of the items of a Vec of messages
+    if name.is_empty() && (subscript.is_none() || !elements.is_empty()) {
+      return None;
+    }
+
+    elements.push(QueryElement { name, subscript });
+
+    match chars.next() {
+      None => return Some(elements),
+      Some('.') => continue,
+      Some(_) => return None,
+    }
+  }
+}
+
+fn parse_subscript(chars: &mut core::iter::Peekable<core::str::Chars>) -> Option<QuerySubscript> {
+  if chars.peek() == Some(&'"') {
+    chars.next();
+    let mut key = String::new();
+
+    loop {
+      match chars.next()? {
+        '"' => break,
+        '\\' => key.push(parse_escape(chars)?),
+        c => key.push(c),
+      }
+    }
+
+    return (chars.next()? == ']').then_some(QuerySubscript::String(key));
+  }
+
+  let mut content = String::new();
+
+  loop {
+    match chars.next()? {
+      ']' => break,
+      c => content.push(c),
+    }
+  }
+
+  match content.as_str() {
+    "true" => Some(QuerySubscript::Bool(true)),
+    "false" => Some(QuerySubscript::Bool(false)),
+    number => number.parse().ok().map(QuerySubscript::Number),
+  }
+}
+
+fn parse_escape(chars: &mut core::iter::Peekable<core::str::Chars>) -> Option<char> {
+  match chars.next()? {
+    'n' => Some('\n'),
+    'r' => Some('\r'),
+    't' => Some('\t'),
+    '0' => Some('\0'),
+    'u' => {
+      if chars.next()? != '{' {
+        return None;
+      }
+
+      let mut hex = String::new();
+
+      loop {
+        match chars.next()? {
+          '}' => break,
+          c => hex.push(c),
+        }
+      }
+
+      char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+    }
+    c => Some(c),
+  }
+}
@@ -1,9 +1,17 @@
-use std::sync::LazyLock;
+use crate::sync::LazyLock;
 
 use crate::protobuf::field_descriptor_proto::Type;
 
 mod violation_impls;
 
+mod lookup;
+
+#[cfg(feature = "serde")]
+mod serde;
+
+#[cfg(feature = "tonic")]
+mod tonic;
+
 pub mod violations_data;
 
 pub use violations_data::*;
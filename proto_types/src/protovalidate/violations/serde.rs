@@ -0,0 +1,153 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::protobuf::field_descriptor_proto::Type;
+use crate::protovalidate::{field_path_element::Subscript, FieldPathElement};
+
+// The shape of a FieldPathElement in the canonical JSON encoding of protobuf, where the subscript
+// is a regular field, the field types are the names of the enum variants and the 64 bit integers are strings
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct FieldPathElementJson {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  field_number: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  field_name: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  field_type: Option<JsonEnum>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  key_type: Option<JsonEnum>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  value_type: Option<JsonEnum>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  index: Option<JsonInt<u64>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  bool_key: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  int_key: Option<JsonInt<i64>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  uint_key: Option<JsonInt<u64>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  string_key: Option<String>,
+}
+
+// Like in protojson, the numbers of the enums are also accepted when parsing, and they are used
+// for the values that are not known
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonEnum {
+  Name(String),
+  Number(i32),
+}
+
+impl From<i32> for JsonEnum {
+  fn from(value: i32) -> Self {
+    match Type::try_from(value) {
+      Ok(field_type) => Self::Name(field_type.as_str_name().to_string()),
+      Err(_) => Self::Number(value),
+    }
+  }
+}
+
+impl JsonEnum {
+  fn into_number<E: de::Error>(self) -> Result<i32, E> {
+    match self {
+      Self::Name(name) => Type::from_str_name(&name)
+        .map(|field_type| field_type as i32)
+        .ok_or_else(|| E::custom(format!("unknown field type `{}`", name))),
+      Self::Number(number) => Ok(number),
+    }
+  }
+}
+
+// The 64 bit integers are also accepted as numbers when parsing
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonInt<T> {
+  String(String),
+  Number(T),
+}
+
+impl<T: ToString> JsonInt<T> {
+  fn new(value: T) -> Self {
+    Self::String(value.to_string())
+  }
+}
+
+impl<T: core::str::FromStr> JsonInt<T> {
+  fn into_number<E: de::Error>(self) -> Result<T, E> {
+    match self {
+      Self::String(value) => value
+        .parse()
+        .map_err(|_| E::custom(format!("invalid integer `{}`", value))),
+      Self::Number(number) => Ok(number),
+    }
+  }
+}
+
+impl Serialize for FieldPathElement {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut json = FieldPathElementJson {
+      field_number: self.field_number,
+      field_name: self.field_name.clone(),
+      field_type: self.field_type.map(JsonEnum::from),
+      key_type: self.key_type.map(JsonEnum::from),
+      value_type: self.value_type.map(JsonEnum::from),
+      ..Default::default()
+    };
+
+    match &self.subscript {
+      Some(Subscript::Index(index)) => json.index = Some(JsonInt::new(*index)),
+      Some(Subscript::BoolKey(key)) => json.bool_key = Some(*key),
+      Some(Subscript::IntKey(key)) => json.int_key = Some(JsonInt::new(*key)),
+      Some(Subscript::UintKey(key)) => json.uint_key = Some(JsonInt::new(*key)),
+      Some(Subscript::StringKey(key)) => json.string_key = Some(key.clone()),
+      None => {}
+    }
+
+    json.serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for FieldPathElement {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let json = FieldPathElementJson::deserialize(deserializer)?;
+
+    let subscripts = [
+      json
+        .index
+        .map(JsonInt::into_number)
+        .transpose()?
+        .map(Subscript::Index),
+      json.bool_key.map(Subscript::BoolKey),
+      json
+        .int_key
+        .map(JsonInt::into_number)
+        .transpose()?
+        .map(Subscript::IntKey),
+      json
+        .uint_key
+        .map(JsonInt::into_number)
+        .transpose()?
+        .map(Subscript::UintKey),
+      json.string_key.map(Subscript::StringKey),
+    ];
+
+    let mut subscripts = subscripts.into_iter().flatten();
+    let subscript = subscripts.next();
+
+    if subscripts.next().is_some() {
+      return Err(de::Error::custom(
+        "a FieldPathElement can only have one subscript",
+      ));
+    }
+
+    Ok(Self {
+      field_number: json.field_number,
+      field_name: json.field_name,
+      field_type: json.field_type.map(JsonEnum::into_number).transpose()?,
+      key_type: json.key_type.map(JsonEnum::into_number).transpose()?,
+      value_type: json.value_type.map(JsonEnum::into_number).transpose()?,
+      subscript,
+    })
+  }
+}
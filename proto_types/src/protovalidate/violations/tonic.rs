@@ -0,0 +1,67 @@
+use bytes::Bytes;
+use prost::Message;
+
+use crate::{
+  protovalidate::Violations,
+  rpc::{bad_request::FieldViolation, BadRequest},
+  Any, Code, Status,
+};
+
+impl Violations {
+  /// Converts the violations into a [`tonic::Status`], like the [`From`] implementation.
+  pub fn into_status(self) -> tonic::Status {
+    self.into()
+  }
+}
+
+/// Creates a status with the `INVALID_ARGUMENT` code, with one line for each violation as its message.
+///
+/// The details are encoded like in the richer error model of gRPC (as in `tonic-types`), and they contain a `google.rpc.BadRequest`, with one `FieldViolation` for each violation, followed by the `buf.validate.Violations` themselves.
+impl From<Violations> for tonic::Status {
+  fn from(violations: Violations) -> Self {
+    let message = if violations.violations.is_empty() {
+      "Validation Error".to_string()
+    } else {
+      violations
+        .violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+    };
+
+    // The reason is left empty, because the rule ids don't follow the format expected by google.rpc
+    let bad_request = BadRequest {
+      field_violations: violations
+        .violations
+        .iter()
+        .map(|violation| FieldViolation {
+          field: violation.field_path_str().unwrap_or_default(),
+          description: violation.message().to_string(),
+          ..Default::default()
+        })
+        .collect(),
+    };
+
+    let status = Status {
+      code: Code::InvalidArgument.into(),
+      message: message.clone(),
+      details: vec![
+        Any {
+          type_url: "type.googleapis.com/google.rpc.BadRequest".to_string(),
+          value: bad_request.encode_to_vec(),
+        },
+        Any {
+          type_url: "type.googleapis.com/buf.validate.Violations".to_string(),
+          value: violations.encode_to_vec(),
+        },
+      ],
+    };
+
+    tonic::Status::with_details(
+      tonic::Code::InvalidArgument,
+      message,
+      Bytes::from(status.encode_to_vec()),
+    )
+  }
+}
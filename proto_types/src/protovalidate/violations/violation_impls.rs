@@ -1,7 +1,16 @@
+use alloc::{
+  string::{String, ToString},
+  vec,
+  vec::Vec,
+};
+use core::fmt;
+
 use prost::Message;
 
 use crate::{
-  protovalidate::{FieldPath, FieldPathElement, Violation, Violations},
+  protovalidate::{
+    field_path_element::Subscript, FieldPath, FieldPathElement, Violation, Violations,
+  },
   Any, Code, Status,
 };
 
@@ -58,13 +67,121 @@ impl FieldPath {
     path
   }
 
-  /// Returns all of the names from each path element (including any eventual Subscript like a vector index or map key), joined by a dot (e.g. `person.friends.0.address.street_name`)
+  /// Returns all of the names from each path element joined by a dot, with the eventual Subscripts in square brackets like in protovalidate (e.g. `person.friends[0].address.street_name` or `person.pets["luna"]`). This is the same as the [`Display`](fmt::Display) output.
   pub fn field_path_str(&self) -> String {
-    self.field_path().join(".")
+    self.to_string()
+  }
+}
+
+impl fmt::Display for FieldPath {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, field) in self.elements.iter().enumerate() {
+      if i > 0 {
+        write!(f, ".")?;
+      }
+
+      write!(f, "{}", field.field_name())?;
+
+      match &field.subscript {
+        Some(Subscript::StringKey(key)) => write!(f, "[{:?}]", key)?,
+        Some(key) => write!(f, "[{}]", key)?,
+        None => {}
+      }
+    }
+
+    Ok(())
   }
 }
 
 impl Violations {
+  /// Returns true if there are no violations.
+  pub fn is_empty(&self) -> bool {
+    self.violations.is_empty()
+  }
+
+  /// Returns the number of violations.
+  pub fn len(&self) -> usize {
+    self.violations.len()
+  }
+
+  /// Returns an iterator over the violations.
+  pub fn iter(&self) -> core::slice::Iter<'_, Violation> {
+    self.violations.iter()
+  }
+
+  /// Adds the violations of `other` after the ones already in this set.
+  ///
+  /// # Examples
+  /// A hand-written check that is added to the result of the generated validators.
+  /// ```rust
+  /// use proto_types::protovalidate::{FieldPathElement, Violation, Violations};
+  ///
+  /// fn validate_order(generated: Result<(), Violations>, total: u64, discount: u64) -> Result<(), Violations> {
+  ///   let mut violations = generated.err().unwrap_or_default();
+  ///
+  ///   if discount > total {
+  ///     violations.merge(Violations {
+  ///       violations: vec![Violation::new(
+  ///         vec![FieldPathElement {
+  ///           field_name: Some("discount".to_string()),
+  ///           ..Default::default()
+  ///         }],
+  ///         "order.discount",
+  ///         "the discount cannot be higher than the total",
+  ///       )],
+  ///     });
+  ///   }
+  ///
+  ///   if violations.is_empty() {
+  ///     Ok(())
+  ///   } else {
+  ///     Err(violations)
+  ///   }
+  /// }
+  ///
+  /// let violations = validate_order(Ok(()), 10, 20).unwrap_err();
+  /// assert_eq!(violations.len(), 1);
+  /// assert_eq!(violations.to_string(), "discount: the discount cannot be higher than the total (order.discount)");
+  /// ```
+  pub fn merge(&mut self, other: Violations) {
+    self.violations.extend(other.violations);
+  }
+
+  /// Adds the element at the start of the field path of every violation, so that the violations of a message can be placed under one of the fields of its parent.
+  ///
+  /// # Examples
+  /// ```rust
+  /// use proto_types::protovalidate::{field_path_element::Subscript, FieldPathElement, Violation, Violations};
+  ///
+  /// let name_element = FieldPathElement {
+  ///   field_name: Some("name".to_string()),
+  ///   ..Default::default()
+  /// };
+  ///
+  /// let child = Violations {
+  ///   violations: vec![Violation::new(vec![name_element], "name.required", "the name is required")],
+  /// };
+  ///
+  /// let parent = child.with_prefix(FieldPathElement {
+  ///   field_name: Some("friends".to_string()),
+  ///   subscript: Some(Subscript::Index(2)),
+  ///   ..Default::default()
+  /// });
+  ///
+  /// assert!(parent.violation_by_field_path("friends[2].name").is_some());
+  /// ```
+  pub fn with_prefix(mut self, element: FieldPathElement) -> Violations {
+    for violation in &mut self.violations {
+      violation
+        .field
+        .get_or_insert_with(Default::default)
+        .elements
+        .insert(0, element.clone());
+    }
+
+    self
+  }
+
   /// Searches for a violation with a specific rule id.
   pub fn violation_by_rule_id(&self, rule_id: &str) -> Option<&Violation> {
     self.violations.iter().find(|v| v.rule_id() == rule_id)
@@ -72,7 +189,7 @@ impl Violations {
 
   /// Searches for a violation with a specific `field_path` string.
   ///
-  /// Keep in mind the `field_path` will include Subscripts like vector indexes or map keys.
+  /// Keep in mind the `field_path` will include Subscripts like vector indexes or map keys, in square brackets (e.g. `person.friends[0].name`).
   ///
   /// # Examples
   /// ```rust
@@ -106,7 +223,31 @@ impl Violations {
   }
 }
 
+impl<'a> IntoIterator for &'a Violations {
+  type Item = &'a Violation;
+  type IntoIter = core::slice::Iter<'a, Violation>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
 impl Violation {
+  /// Creates a violation for a hand-written rule, with the given field path (or without one, if the path is empty, like for the rules of a message) and without a rule path.
+  pub fn new(
+    path: Vec<FieldPathElement>,
+    rule_id: impl Into<String>,
+    message: impl Into<String>,
+  ) -> Self {
+    Self {
+      field: (!path.is_empty()).then_some(FieldPath { elements: path }),
+      rule: None,
+      rule_id: Some(rule_id.into()),
+      message: Some(message.into()),
+      for_key: None,
+    }
+  }
+
   /// Returns the last member in the elements list, if there is one.
   pub fn last_field(&self) -> Option<&FieldPathElement> {
     if let Some(fields) = &self.field {
@@ -152,7 +293,7 @@ impl Violation {
     None
   }
 
-  /// If there is a FieldPath, it returns the path elements' names, joined by a dot (e.g. `person.friends.0.address.street_name`).
+  /// If there is a FieldPath, it returns the path elements' names, joined like in [`FieldPath::field_path_str`] (e.g. `person.friends[0].address.street_name`).
   pub fn field_path_str(&self) -> Option<String> {
     if let Some(fields) = &self.field {
       return Some(fields.field_path_str());
@@ -186,6 +327,42 @@ impl Violation {
   }
 }
 
+/// Formats the violation as a single line, with the field path (if there is one), the message and the rule id, like `customer.email: value must be a valid email address (string.email)`.
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(field) = &self.field
+      && field.has_fields()
+    {
+      write!(f, "{}: ", field)?;
+    }
+
+    write!(f, "{}", self.message())?;
+
+    if !self.rule_id().is_empty() {
+      write!(f, " ({})", self.rule_id())?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Formats each violation on its own line, like the [`Display`](fmt::Display) implementation of [`Violation`].
+impl fmt::Display for Violations {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, violation) in self.violations.iter().enumerate() {
+      if i > 0 {
+        writeln!(f)?;
+      }
+
+      write!(f, "{}", violation)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl core::error::Error for Violations {}
+
 impl From<Violations> for Status {
   fn from(value: Violations) -> Self {
     let message = if value.violations.len() == 1 && !value.violations[0].message().is_empty() {
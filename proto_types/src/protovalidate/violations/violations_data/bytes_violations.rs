@@ -76,8 +76,8 @@ pub static BYTES_PATTERN_VIOLATION: LazyLock<ViolationData> = LazyLock::new(|| {
     },
     FieldPathElement {
       field_name: Some("pattern".to_string()),
-      field_number: Some(6),
-      field_type: Some(Type::Bytes as i32),
+      field_number: Some(4),
+      field_type: Some(Type::String as i32),
       ..Default::default()
     },
   ];
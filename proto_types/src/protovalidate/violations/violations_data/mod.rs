@@ -1,3 +1,5 @@
+use alloc::{boxed::Box, string::ToString, vec};
+
 use crate::protovalidate::*;
 
 pub mod base_violations;
@@ -65,3 +65,19 @@ pub static TIMESTAMP_LT_NOW_VIOLATION: LazyLock<ViolationData> = LazyLock::new(|
     name: "timestamp.lt_now",
   }
 });
+
+// Not a rule of TimestampRules, this is reported when the nanos are out of range,
+// so the rule path only points at the timestamp rules
+pub static TIMESTAMP_VALID_VIOLATION: LazyLock<ViolationData> = LazyLock::new(|| {
+  let elements = vec![FieldPathElement {
+    field_name: Some("timestamp".to_string()),
+    field_number: Some(22),
+    field_type: Some(Type::Message as i32),
+    ..Default::default()
+  }];
+
+  ViolationData {
+    elements: Box::leak(elements.into_boxed_slice()),
+    name: "timestamp.valid",
+  }
+});
@@ -25,7 +25,7 @@ impl<'de> Deserialize<'de> for Code {
     impl<'de> Visitor<'de> for CodeVisitor {
       type Value = Code;
 
-      fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+      fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         formatter.write_str("a string representing a Code enum variant (e.g., \"OK\", \"UNKNOWN\")")
       }
 
@@ -8,7 +8,7 @@ mod timestamp_operations;
 // Partially taken from (prost-types)[https://github.com/tokio-rs/prost/blob/master/prost-types/src/timestamp.rs]
 use super::*;
 use crate::{
-  constants::{NANOS_PER_SECOND, PACKAGE_PREFIX},
+  constants::{MAX_TIMESTAMP_SECONDS, MIN_TIMESTAMP_SECONDS, NANOS_PER_SECOND, PACKAGE_PREFIX},
   Timestamp,
 };
 
@@ -101,6 +101,34 @@ impl Timestamp {
     result
   }
 
+  /// The earliest timestamp allowed by protobuf, 0001-01-01T00:00:00Z.
+  pub const MIN: Timestamp = Timestamp {
+    seconds: MIN_TIMESTAMP_SECONDS,
+    nanos: 0,
+  };
+
+  /// The latest timestamp allowed by protobuf, 9999-12-31T23:59:59.999999999Z.
+  pub const MAX: Timestamp = Timestamp {
+    seconds: MAX_TIMESTAMP_SECONDS,
+    nanos: 999_999_999,
+  };
+
+  /// Returns true if the normalized timestamp is between 0001-01-01T00:00:00Z and 9999-12-31T23:59:59.999999999Z, which is the range of the valid timestamps for protobuf.
+  pub fn is_in_range(&self) -> bool {
+    (MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&self.normalized().seconds)
+  }
+
+  // Used by the conversions from the other types, which can represent a wider range
+  pub(crate) fn checked_range(self) -> Result<Self, TimestampError> {
+    let normalized = self.normalized();
+
+    if normalized.is_in_range() {
+      Ok(normalized)
+    } else {
+      Err(TimestampError::OutOfRange(self))
+    }
+  }
+
   /// Creates a new `Timestamp` at the start of the provided UTC date.
   pub fn date(year: i64, month: u8, day: u8) -> Result<Timestamp, TimestampError> {
     Timestamp::date_time_nanos(year, month, day, 0, 0, 0, 0)
@@ -169,6 +197,7 @@ impl Name for Timestamp {
   }
 }
 
+#[cfg(feature = "std")]
 impl From<std::time::SystemTime> for Timestamp {
   fn from(system_time: std::time::SystemTime) -> Timestamp {
     let (seconds, nanos) = match system_time.duration_since(std::time::UNIX_EPOCH) {
@@ -213,6 +242,8 @@ pub enum TimestampError {
   ParseFailure,
   /// Indicates an error when constructing a timestamp due to invalid date or time data.
   InvalidDateTime,
+  /// Indicates that a timestamp is outside of the range supported by protobuf (from 0001-01-01T00:00:00Z to 9999-12-31T23:59:59.999999999Z), or by the type it is converted into.
+  OutOfRange(Timestamp),
 }
 
 impl fmt::Display for TimestampError {
@@ -233,12 +264,21 @@ impl fmt::Display for TimestampError {
       TimestampError::InvalidDateTime => {
         write!(f, "invalid date or time")
       }
+
+      TimestampError::OutOfRange(timestamp) => {
+        write!(
+          f,
+          "the timestamp with {} seconds and {} nanoseconds is out of range",
+          timestamp.seconds, timestamp.nanos
+        )
+      }
     }
   }
 }
 
-impl std::error::Error for TimestampError {}
+impl core::error::Error for TimestampError {}
 
+#[cfg(feature = "std")]
 impl TryFrom<Timestamp> for std::time::SystemTime {
   type Error = TimestampError;
 
@@ -266,14 +306,18 @@ impl TryFrom<Timestamp> for std::time::SystemTime {
   }
 }
 
+/// Parses an RFC 3339 timestamp, like `2024-05-01T12:30:00.5+02:00`. The offsets other than `Z` are converted to UTC, and the timestamps outside of the range of protobuf are rejected.
 impl FromStr for Timestamp {
   type Err = TimestampError;
 
   fn from_str(s: &str) -> Result<Timestamp, TimestampError> {
-    datetime_internal::parse_timestamp(s).ok_or(TimestampError::ParseFailure)
+    datetime_internal::parse_timestamp(s)
+      .ok_or(TimestampError::ParseFailure)?
+      .checked_range()
   }
 }
 
+/// Formats the timestamp in RFC 3339 in UTC (with the `Z` suffix), with 0, 3, 6 or 9 fractional digits, like in the JSON mapping of protobuf.
 impl fmt::Display for Timestamp {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     datetime_internal::DateTime::from(*self).fmt(f)
@@ -4,27 +4,26 @@ mod chrono {
 
   use crate::{timestamp::TimestampError, Timestamp};
 
-  impl From<DateTime<Utc>> for Timestamp {
-    fn from(datetime: DateTime<Utc>) -> Self {
-      let mut ts = Timestamp {
+  /// Fails if the datetime is outside of the range of the protobuf timestamps (after the year 9999).
+  impl TryFrom<DateTime<Utc>> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(datetime: DateTime<Utc>) -> Result<Self, Self::Error> {
+      Timestamp {
         seconds: datetime.timestamp(),
         // Safe casting as this value is limited by chrono
         nanos: datetime.timestamp_subsec_nanos() as i32,
-      };
-      ts.normalize();
-      ts
+      }
+      .checked_range()
     }
   }
 
-  impl From<NaiveDateTime> for Timestamp {
-    fn from(datetime: NaiveDateTime) -> Self {
-      let mut ts = Timestamp {
-        seconds: datetime.and_utc().timestamp(),
-        // Safe casting as this value is limited by chrono
-        nanos: datetime.and_utc().timestamp_subsec_nanos() as i32,
-      };
-      ts.normalize();
-      ts
+  /// Fails if the datetime is outside of the range of the protobuf timestamps (after the year 9999).
+  impl TryFrom<NaiveDateTime> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(datetime: NaiveDateTime) -> Result<Self, Self::Error> {
+      datetime.and_utc().try_into()
     }
   }
 
@@ -64,6 +63,40 @@ mod chrono {
   }
 }
 
+#[cfg(feature = "time")]
+mod time_crate {
+  use ::time::OffsetDateTime;
+
+  use crate::{timestamp::TimestampError, Timestamp};
+
+  /// Fails if the datetime is outside of the range of the protobuf timestamps.
+  impl TryFrom<OffsetDateTime> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(datetime: OffsetDateTime) -> Result<Self, Self::Error> {
+      Timestamp {
+        seconds: datetime.unix_timestamp(),
+        // Safe casting as this value is always lower than one billion
+        nanos: datetime.nanosecond() as i32,
+      }
+      .checked_range()
+    }
+  }
+
+  /// Fails if the timestamp cannot be represented by the time crate, which only supports the years from -9999 to 9999 by default. The datetime is in UTC.
+  impl TryFrom<Timestamp> for OffsetDateTime {
+    type Error = TimestampError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+      let normalized = timestamp.normalized();
+      let nanos = i128::from(normalized.seconds) * 1_000_000_000 + i128::from(normalized.nanos);
+
+      OffsetDateTime::from_unix_timestamp_nanos(nanos)
+        .map_err(|_| TimestampError::OutOfRange(timestamp))
+    }
+  }
+}
+
 #[cfg(feature = "totokens")]
 mod totokens {
   use proc_macro2::TokenStream;
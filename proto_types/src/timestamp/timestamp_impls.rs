@@ -1,6 +1,9 @@
-use std::time::SystemTime;
+#[cfg(not(feature = "chrono"))]
+use alloc::string::{String, ToString};
 
-use crate::{Duration, Timestamp};
+#[cfg(feature = "std")]
+use crate::Duration;
+use crate::Timestamp;
 
 #[cfg(not(feature = "chrono"))]
 impl crate::Timestamp {
@@ -33,15 +36,36 @@ mod chrono {
 }
 
 impl Timestamp {
-  /// Returns the current timestamp.
-  pub fn now() -> Self {
-    SystemTime::now().into()
-  }
-
   /// Creates a new instance.
   pub fn new(seconds: i64, nanos: i32) -> Self {
     Timestamp { seconds, nanos }
   }
+}
+
+// The methods that depend on the current time, which is not available without the standard library
+#[cfg(feature = "std")]
+impl Timestamp {
+  /// Returns the current timestamp.
+  ///
+  /// On `wasm32-unknown-unknown`, where the system time is not available, this requires the `wasm` feature, with which the time is taken from `Date.now()` of JavaScript.
+  pub fn now() -> Self {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+    {
+      // The milliseconds since the Unix epoch
+      let millis = js_sys::Date::now();
+      let seconds = (millis / 1_000.0).floor();
+
+      Self::new(
+        seconds as i64,
+        ((millis - seconds * 1_000.0) * 1_000_000.0) as i32,
+      )
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")))]
+    {
+      std::time::SystemTime::now().into()
+    }
+  }
 
   /// Checks whether the Timestamp instance is within the indicated range (positive or negative) from now.
   pub fn is_within_range_from_now(&self, range: Duration) -> bool {
@@ -1,24 +1,102 @@
-use std::{
+use core::{
   cmp::Ordering,
+  hash::{Hash, Hasher},
   ops::{Add, Sub},
 };
 
 use crate::{Duration, Timestamp};
 
-impl<'b> Sub<&'b Duration> for &Timestamp {
-  type Output = Timestamp;
+impl Timestamp {
+  /// Adds a duration to the timestamp, returning `None` if the normalized result is outside of the protobuf range of [`Timestamp::MIN`] to [`Timestamp::MAX`].
+  ///
+  /// # Examples
+  /// ```rust
+  /// use proto_types::{Duration, Timestamp};
+  ///
+  /// let timestamp = Timestamp::new(10, 500_000_000);
+  ///
+  /// assert_eq!(timestamp.checked_add(&Duration::new(0, 700_000_000)), Some(Timestamp::new(11, 200_000_000)));
+  /// assert_eq!(Timestamp::MAX.checked_add(&Duration::new(0, 1)), None);
+  /// ```
+  pub fn checked_add(&self, duration: &Duration) -> Option<Timestamp> {
+    let (this, duration) = (self.normalized(), duration.normalized());
+
+    // The normalized nanos are smaller than one second, so they cannot overflow here
+    let mut result = Timestamp {
+      seconds: this.seconds.checked_add(duration.seconds)?,
+      nanos: this.nanos + duration.nanos,
+    };
 
-  fn sub(self, rhs: &'b Duration) -> Self::Output {
-    let duration = rhs.normalized();
+    result.normalize();
+
+    result.is_in_range().then_some(result)
+  }
 
-    let mut new = Timestamp {
-      seconds: self.seconds - duration.seconds,
-      nanos: self.nanos - duration.nanos,
+  /// Subtracts a duration from the timestamp, returning `None` if the normalized result is outside of the protobuf range of [`Timestamp::MIN`] to [`Timestamp::MAX`].
+  pub fn checked_sub(&self, duration: &Duration) -> Option<Timestamp> {
+    let (this, duration) = (self.normalized(), duration.normalized());
+
+    let mut result = Timestamp {
+      seconds: this.seconds.checked_sub(duration.seconds)?,
+      nanos: this.nanos - duration.nanos,
     };
 
-    new.normalize();
+    result.normalize();
+
+    result.is_in_range().then_some(result)
+  }
+
+  /// Returns the duration from `earlier` to this timestamp, which is negative if `earlier` is actually later, or `None` if either timestamp is outside of the protobuf range. This is also what `Timestamp - Timestamp` returns.
+  ///
+  /// Any two timestamps in range are less than 10,000 years apart, so the result is always a valid duration.
+  pub fn checked_sub_timestamp(&self, earlier: &Timestamp) -> Option<Duration> {
+    if !self.is_in_range() || !earlier.is_in_range() {
+      return None;
+    }
+
+    let (this, earlier) = (self.normalized(), earlier.normalized());
 
-    new
+    Some(Duration::new(
+      this.seconds - earlier.seconds,
+      this.nanos - earlier.nanos,
+    ))
+  }
+
+  /// Adds a duration to the timestamp, saturating at [`Timestamp::MIN`] and [`Timestamp::MAX`]. This is what the `+` operator does.
+  pub fn saturating_add(&self, duration: &Duration) -> Timestamp {
+    let (this, duration) = (self.normalized(), duration.normalized());
+
+    Timestamp {
+      seconds: this.seconds.saturating_add(duration.seconds),
+      nanos: this.nanos + duration.nanos,
+    }
+    .clamped()
+  }
+
+  /// Subtracts a duration from the timestamp, saturating at [`Timestamp::MIN`] and [`Timestamp::MAX`]. This is what the `-` operator does.
+  pub fn saturating_sub(&self, duration: &Duration) -> Timestamp {
+    let (this, duration) = (self.normalized(), duration.normalized());
+
+    Timestamp {
+      seconds: this.seconds.saturating_sub(duration.seconds),
+      nanos: this.nanos - duration.nanos,
+    }
+    .clamped()
+  }
+
+  // Normalizes the timestamp and clamps it to the protobuf range
+  fn clamped(self) -> Timestamp {
+    self.normalized().clamp(Timestamp::MIN, Timestamp::MAX)
+  }
+}
+
+// The arithmetic operators saturate at the limits of the protobuf range instead of panicking, so that
+// they never produce a timestamp that cannot be serialized. The checked methods can be used to detect this.
+impl<'b> Sub<&'b Duration> for &Timestamp {
+  type Output = Timestamp;
+
+  fn sub(self, rhs: &'b Duration) -> Self::Output {
+    self.saturating_sub(rhs)
   }
 }
 
@@ -43,20 +121,26 @@ impl<'a> Sub<Duration> for &'a Timestamp {
   }
 }
 
+/// Returns the duration between the timestamps, like [`Timestamp::checked_sub_timestamp`].
+impl Sub for Timestamp {
+  type Output = Option<Duration>;
+  fn sub(self, rhs: Timestamp) -> Self::Output {
+    self.checked_sub_timestamp(&rhs)
+  }
+}
+
+impl<'b> Sub<&'b Timestamp> for &Timestamp {
+  type Output = Option<Duration>;
+  fn sub(self, rhs: &'b Timestamp) -> Self::Output {
+    self.checked_sub_timestamp(rhs)
+  }
+}
+
 impl<'b> Add<&'b Duration> for &Timestamp {
   type Output = Timestamp;
 
   fn add(self, rhs: &'b Duration) -> Self::Output {
-    let duration = rhs.normalized();
-
-    let mut new = Timestamp {
-      seconds: self.seconds + duration.seconds,
-      nanos: self.nanos + duration.nanos,
-    };
-
-    new.normalize();
-
-    new
+    self.saturating_add(rhs)
   }
 }
 
@@ -82,6 +166,24 @@ impl Add<Duration> for Timestamp {
   }
 }
 
+// The timestamps are compared (and hashed) in their normalized form, so that `{ seconds: 1, nanos: -1 }`
+// is equal to `{ seconds: 0, nanos: 999_999_999 }`
+impl PartialEq for Timestamp {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Timestamp {}
+
+impl Hash for Timestamp {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    let normalized = self.normalized();
+
+    (normalized.seconds, normalized.nanos).hash(state);
+  }
+}
+
 impl PartialOrd for Timestamp {
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
     Some(self.cmp(other))
@@ -90,6 +192,8 @@ impl PartialOrd for Timestamp {
 
 impl Ord for Timestamp {
   fn cmp(&self, other: &Self) -> Ordering {
-    (self.seconds, self.nanos).cmp(&(other.seconds, other.nanos))
+    let (this, other) = (self.normalized(), other.normalized());
+
+    (this.seconds, this.nanos).cmp(&(other.seconds, other.nanos))
   }
 }
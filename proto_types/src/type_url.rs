@@ -1,4 +1,6 @@
 // From (prost-types)[https://github.com/tokio-rs/prost/blob/master/prost-types/src/type_url.rs]
+use alloc::{format, string::String};
+
 use prost::Name;
 
 /// URL/resource name that uniquely identifies the type of the serialized protocol buffer message,
@@ -0,0 +1,219 @@
+use core::ops::Deref;
+
+use prost::bytes::Bytes;
+
+use crate::{
+  BoolValue, BytesValue, DoubleValue, FloatValue, Int32Value, Int64Value, String, StringValue,
+  UInt32Value, UInt64Value, Vec,
+};
+
+/// Accessors for the optional wrappers, which is how the fields with a wrapper type are generated.
+///
+/// ```
+/// use proto_types::{StringValue, WrapperExt};
+///
+/// let name = Some(StringValue::from("bob"));
+///
+/// assert_eq!(name.as_inner().map(String::as_str), Some("bob"));
+/// assert_eq!(name.as_deref(), Some("bob"));
+/// assert_eq!(name.unwrap_or_default_inner(), "bob");
+/// assert_eq!(None::<StringValue>.unwrap_or_default_inner(), "");
+/// ```
+pub trait WrapperExt {
+  /// The type of the wrapped value.
+  type Inner;
+
+  /// Returns a reference to the wrapped value, if there is one.
+  fn as_inner(&self) -> Option<&Self::Inner>;
+
+  /// Returns the wrapped value, if there is one.
+  fn into_inner(self) -> Option<Self::Inner>;
+
+  /// Returns the wrapped value, or the default value of the inner type (which is also the default in protobuf).
+  fn unwrap_or_default_inner(self) -> Self::Inner;
+}
+
+macro_rules! impl_wrapper {
+  ($wrapper:ident, $inner:ty) => {
+    impl From<$inner> for $wrapper {
+      fn from(value: $inner) -> Self {
+        Self { value }
+      }
+    }
+
+    impl From<$wrapper> for $inner {
+      fn from(value: $wrapper) -> Self {
+        value.value
+      }
+    }
+
+    impl PartialEq<$inner> for $wrapper {
+      fn eq(&self, other: &$inner) -> bool {
+        self.value == *other
+      }
+    }
+
+    impl PartialEq<$wrapper> for $inner {
+      fn eq(&self, other: &$wrapper) -> bool {
+        *self == other.value
+      }
+    }
+
+    impl WrapperExt for Option<$wrapper> {
+      type Inner = $inner;
+
+      fn as_inner(&self) -> Option<&$inner> {
+        self.as_ref().map(|wrapper| &wrapper.value)
+      }
+
+      fn into_inner(self) -> Option<$inner> {
+        self.map(|wrapper| wrapper.value)
+      }
+
+      fn unwrap_or_default_inner(self) -> $inner {
+        self.map(|wrapper| wrapper.value).unwrap_or_default()
+      }
+    }
+  };
+}
+
+macro_rules! impl_copy_wrapper {
+  ($wrapper:ident, $inner:ty) => {
+    impl_wrapper!($wrapper, $inner);
+
+    impl $wrapper {
+      /// Creates a new instance with the given value.
+      pub const fn new(value: $inner) -> Self {
+        Self { value }
+      }
+    }
+  };
+}
+
+impl_copy_wrapper!(DoubleValue, f64);
+impl_copy_wrapper!(FloatValue, f32);
+impl_copy_wrapper!(Int64Value, i64);
+impl_copy_wrapper!(UInt64Value, u64);
+impl_copy_wrapper!(Int32Value, i32);
+impl_copy_wrapper!(UInt32Value, u32);
+impl_copy_wrapper!(BoolValue, bool);
+impl_wrapper!(StringValue, String);
+impl_wrapper!(BytesValue, Bytes);
+
+impl StringValue {
+  /// Creates a new instance with the given value.
+  pub fn new(value: impl Into<String>) -> Self {
+    Self {
+      value: value.into(),
+    }
+  }
+}
+
+impl From<&str> for StringValue {
+  fn from(value: &str) -> Self {
+    Self {
+      value: value.into(),
+    }
+  }
+}
+
+impl Deref for StringValue {
+  type Target = str;
+
+  fn deref(&self) -> &str {
+    &self.value
+  }
+}
+
+impl PartialEq<str> for StringValue {
+  fn eq(&self, other: &str) -> bool {
+    self.value == other
+  }
+}
+
+impl PartialEq<&str> for StringValue {
+  fn eq(&self, other: &&str) -> bool {
+    self.value == *other
+  }
+}
+
+impl PartialEq<StringValue> for str {
+  fn eq(&self, other: &StringValue) -> bool {
+    self == other.value
+  }
+}
+
+impl PartialEq<StringValue> for &str {
+  fn eq(&self, other: &StringValue) -> bool {
+    *self == other.value
+  }
+}
+
+impl BytesValue {
+  /// Creates a new instance with the given value.
+  pub fn new(value: impl Into<Bytes>) -> Self {
+    Self {
+      value: value.into(),
+    }
+  }
+}
+
+impl From<Vec<u8>> for BytesValue {
+  fn from(value: Vec<u8>) -> Self {
+    Self {
+      value: value.into(),
+    }
+  }
+}
+
+impl From<&[u8]> for BytesValue {
+  fn from(value: &[u8]) -> Self {
+    Self {
+      value: Bytes::copy_from_slice(value),
+    }
+  }
+}
+
+impl From<BytesValue> for Vec<u8> {
+  fn from(value: BytesValue) -> Self {
+    value.value.into()
+  }
+}
+
+impl Deref for BytesValue {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    &self.value
+  }
+}
+
+impl PartialEq<[u8]> for BytesValue {
+  fn eq(&self, other: &[u8]) -> bool {
+    self.value == other
+  }
+}
+
+impl PartialEq<&[u8]> for BytesValue {
+  fn eq(&self, other: &&[u8]) -> bool {
+    self.value == *other
+  }
+}
+
+impl PartialEq<Vec<u8>> for BytesValue {
+  fn eq(&self, other: &Vec<u8>) -> bool {
+    self.value == *other
+  }
+}
+
+impl PartialEq<BytesValue> for [u8] {
+  fn eq(&self, other: &BytesValue) -> bool {
+    *self == other.value
+  }
+}
+
+impl PartialEq<BytesValue> for Vec<u8> {
+  fn eq(&self, other: &BytesValue) -> bool {
+    *self == other.value
+  }
+}
@@ -0,0 +1,2374 @@
+use std::{
+  collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+  ffi::{OsStr, OsString},
+  process,
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+use prost_reflect::{prost::Message, prost_types::FileDescriptorSet};
+
+use crate::*;
+
+const EMBEDDED_DESCRIPTOR_SET_FILE_NAME: &str = "descriptor.bin";
+
+// The packages which never receive validators, as they are provided by protocheck itself.
+const EXCLUDED_PACKAGES: &[&str] = &["google.protobuf", "buf.validate"];
+
+/// The types used for the `google.protobuf` package in the generated code.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum WellKnownTypes {
+  /// The types re-exported by protocheck, which are used by its validators.
+  #[default]
+  Protocheck,
+  /// The types from [`prost-types`](https://docs.rs/prost-types), for codebases that already use them elsewhere. The `Timestamp` and `Duration` values are converted into the protocheck equivalents when they are validated.
+  ProstTypes,
+  /// The types at a custom path, such as `::my_crate::well_known_types`. Like with [`ProstTypes`](Self::ProstTypes), the `Timestamp` and `Duration` types must have the same fields as the ones from protocheck.
+  Custom(String),
+}
+
+/// A builder for configuring how the validators are added to a [`prost_build::Config`].
+///
+/// ```rust,ignore
+/// let mut config = Config::new();
+///
+/// ValidatorsBuilder::new()
+///   .proto_files(&proto_files)
+///   .include_paths(["proto"])
+///   .packages(["myapp.v1"])
+///   .exclude_messages(["myapp.v1.Unchecked"])
+///   .compile(&mut config)?;
+///
+/// config.compile_protos(&proto_files, &["proto"])?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ValidatorsBuilder {
+  proto_files: Vec<PathBuf>,
+  include_paths: Vec<PathBuf>,
+  packages: Vec<String>,
+  excluded_messages: Vec<String>,
+  excluded_fields: Vec<String>,
+  include_transitive_messages: bool,
+  extra_message_attributes: Vec<(String, String)>,
+  extra_field_attributes: Vec<(String, String)>,
+  descriptor_set_path: Option<PathBuf>,
+  retain_descriptor: bool,
+  keep_descriptor_on_error: bool,
+  embed_descriptor_set: bool,
+  strict_packages: bool,
+  deny_unsupported_rules: bool,
+  #[cfg(feature = "serde")]
+  report_path: Option<PathBuf>,
+  #[cfg(feature = "serde")]
+  constraints_manifest_path: Option<PathBuf>,
+  well_known_types: WellKnownTypes,
+  emit_rerun_if_changed: bool,
+  protoc_args: Vec<OsString>,
+  protoc_path: Option<PathBuf>,
+  out_dir: Option<PathBuf>,
+  always_generate: bool,
+  validated_requests: bool,
+  type_names: bool,
+}
+
+impl Default for ValidatorsBuilder {
+  fn default() -> Self {
+    Self {
+      proto_files: Vec::new(),
+      include_paths: Vec::new(),
+      packages: Vec::new(),
+      excluded_messages: Vec::new(),
+      excluded_fields: Vec::new(),
+      include_transitive_messages: true,
+      extra_message_attributes: Vec::new(),
+      extra_field_attributes: Vec::new(),
+      descriptor_set_path: None,
+      retain_descriptor: false,
+      keep_descriptor_on_error: false,
+      embed_descriptor_set: false,
+      strict_packages: false,
+      deny_unsupported_rules: false,
+      #[cfg(feature = "serde")]
+      report_path: None,
+      #[cfg(feature = "serde")]
+      constraints_manifest_path: None,
+      well_known_types: WellKnownTypes::Protocheck,
+      emit_rerun_if_changed: true,
+      protoc_args: Vec::new(),
+      protoc_path: None,
+      out_dir: None,
+      always_generate: false,
+      validated_requests: false,
+      type_names: false,
+    }
+  }
+}
+
+impl ValidatorsBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds the proto files to compile in order to create the intermediary descriptor.
+  pub fn proto_files<I, P>(&mut self, proto_files: I) -> &mut Self
+  where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+  {
+    self
+      .proto_files
+      .extend(proto_files.into_iter().map(|p| p.as_ref().to_path_buf()));
+    self
+  }
+
+  /// Adds the include paths used when compiling the proto files.
+  ///
+  /// If none are set, they are derived from the locations and the packages of the proto files with [`derive_include_paths`](crate::derive_include_paths), and they are also added to the protoc arguments of the config.
+  pub fn include_paths<I, P>(&mut self, include_paths: I) -> &mut Self
+  where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+  {
+    self
+      .include_paths
+      .extend(include_paths.into_iter().map(|p| p.as_ref().to_path_buf()));
+    self
+  }
+
+  /// Adds the packages whose messages should receive the validators.
+  ///
+  /// Besides exact package names, this also accepts glob patterns where `*` matches any sequence of characters, such as `acme.*` (which matches all of the packages nested inside `acme`), `acme.billing.v*` or `*` (which matches every package).
+  ///
+  /// If no packages are selected, all packages receive the validators. The messages from `google.protobuf` and `buf.validate` are always excluded.
+  pub fn packages<I, S>(&mut self, packages: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self
+      .packages
+      .extend(packages.into_iter().map(|p| p.as_ref().to_string()));
+    self
+  }
+
+  /// Returns an error if one of the selected [`packages`](Self::packages) does not match any package in the descriptor. By default, a cargo warning is emitted instead.
+  pub fn strict_packages(&mut self, strict: bool) -> &mut Self {
+    self.strict_packages = strict;
+    self
+  }
+
+  /// Returns an error if one of the selected messages uses a rule which is not supported by protocheck (see [`BuildReport::unsupported_rules`]). By default, a cargo warning is emitted for each of them instead.
+  pub fn deny_unsupported_rules(&mut self, deny: bool) -> &mut Self {
+    self.deny_unsupported_rules = deny;
+    self
+  }
+
+  /// Writes the [`BuildReport`] to this path as JSON, so that it can be inspected or compared between builds.
+  #[cfg(feature = "serde")]
+  pub fn report_path(&mut self, path: impl AsRef<Path>) -> &mut Self {
+    self.report_path = Some(path.as_ref().to_path_buf());
+    self
+  }
+
+  /// Writes a [`ConstraintsManifest`](crate::ConstraintsManifest) to this path as JSON, with all of the rules of the selected messages and of their fields, such as `OUT_DIR/constraints.json`.
+  #[cfg(feature = "serde")]
+  pub fn constraints_manifest_path(&mut self, path: impl AsRef<Path>) -> &mut Self {
+    self.constraints_manifest_path = Some(path.as_ref().to_path_buf());
+    self
+  }
+
+  /// Selects the types used for the `google.protobuf` package in the generated code. Defaults to [`WellKnownTypes::Protocheck`].
+  pub fn well_known_types(&mut self, well_known_types: WellKnownTypes) -> &mut Self {
+    self.well_known_types = well_known_types;
+    self
+  }
+
+  /// Excludes some messages (by their fully qualified name, such as `myapp.v1.User`) from receiving the validators, even if their package is selected. Their oneofs don't receive the validators either.
+  ///
+  /// The names can be glob patterns like those of the [`packages`](Self::packages), such as `myapp.v1.Legacy*`. A cargo warning is emitted for each entry that does not match any message.
+  pub fn exclude_messages<I, S>(&mut self, messages: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self.excluded_messages.extend(
+      messages
+        .into_iter()
+        .map(|m| m.as_ref().trim_start_matches('.').to_string()),
+    );
+    self
+  }
+
+  /// Excludes some fields (by their fully qualified name, such as `myapp.v1.User.name`, or with a glob pattern) from the validation, so that their rules are not enforced, nor checked at build time. A cargo warning is emitted for each entry that does not match any field.
+  pub fn exclude_fields<I, S>(&mut self, fields: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self.excluded_fields.extend(
+      fields
+        .into_iter()
+        .map(|f| f.as_ref().trim_start_matches('.').to_string()),
+    );
+    self
+  }
+
+  /// Also adds the validators to the messages from other packages which are used (directly or through other messages) by the fields of the selected messages, so that they can be validated as nested messages. The well known types and the [excluded messages](Self::exclude_messages) are never included. Defaults to true.
+  ///
+  /// When disabled, the fields that use the messages from other packages are not validated as nested messages.
+  pub fn include_transitive_messages(&mut self, include: bool) -> &mut Self {
+    self.include_transitive_messages = include;
+    self
+  }
+
+  /// Adds an attribute to the messages matching the path, like [`Config::message_attribute`].
+  ///
+  /// The attributes added here are placed before the validator attributes added by protocheck, which are followed by the derives of prost. This avoids depending on the order of the calls on the config, as an attribute which is added to the config after [`compile`](Self::compile) ends up after the validator attributes.
+  pub fn extra_message_attribute(
+    &mut self,
+    path: impl AsRef<str>,
+    attribute: impl AsRef<str>,
+  ) -> &mut Self {
+    self
+      .extra_message_attributes
+      .push((path.as_ref().to_string(), attribute.as_ref().to_string()));
+    self
+  }
+
+  /// Adds an attribute to the fields matching the path, like [`Config::field_attribute`]. As with [`extra_message_attribute`](Self::extra_message_attribute), these attributes are placed before the ones added by protocheck.
+  pub fn extra_field_attribute(
+    &mut self,
+    path: impl AsRef<str>,
+    attribute: impl AsRef<str>,
+  ) -> &mut Self {
+    self
+      .extra_field_attributes
+      .push((path.as_ref().to_string(), attribute.as_ref().to_string()));
+    self
+  }
+
+  /// Adds the validators to all of the selected messages, including those which don't need them, so that every message has a `validate` method and implements [`ProtoValidator`](https://docs.rs/protocheck/latest/protocheck/trait.ProtoValidator.html).
+  ///
+  /// By default, the validators are only added to the messages with rules (in the message itself, in its oneofs or in its fields), to the messages which contain them (directly or through other messages), so that the validation reaches the nested rules, and to all of the messages contained in those, which are validated along with them. Defaults to false, so enable it to keep the behaviour of the versions up to 0.1.11, where every selected message got the validators.
+  pub fn always_generate(&mut self, always: bool) -> &mut Self {
+    self.always_generate = always;
+    self
+  }
+
+  /// Implements the [`ValidatedRequest`](https://docs.rs/protocheck/latest/protocheck/trait.ValidatedRequest.html) marker trait for the request messages of the methods (including the streaming ones) of the services in the selected packages. These messages always receive the validators, even if they don't have any rule.
+  ///
+  /// [`compile`](Self::compile) returns an error listing all of the methods whose request does not receive the validators, such as when it is outside of the selected packages, or when it is excluded or disabled. Defaults to false.
+  pub fn validated_requests(&mut self, validated: bool) -> &mut Self {
+    self.validated_requests = validated;
+    self
+  }
+
+  /// Enables [`Config::enable_type_names`] on the config, so that the generated messages implement [`prost::Name`](https://docs.rs/prost/latest/prost/trait.Name.html) with their full proto name, which is needed to pack them into an `Any` and to unpack them with `Any::pack` and `Any::unpack`. It is left to the caller, as it conflicts with the messages that implement it manually. Defaults to false.
+  pub fn type_names(&mut self, enable: bool) -> &mut Self {
+    self.type_names = enable;
+    self
+  }
+
+  /// Sets the path where the intermediary descriptor will be written. If unset, it defaults to a file with a unique name inside the [`out_dir`](Self::out_dir) or `OUT_DIR`, so that concurrent builds don't use the same file, and [`compile`](Self::compile) returns an error if neither is set.
+  pub fn descriptor_set_path(&mut self, path: impl AsRef<Path>) -> &mut Self {
+    self.descriptor_set_path = Some(path.as_ref().to_path_buf());
+    self
+  }
+
+  /// Keeps the intermediary descriptor instead of removing it at the end of [`compile`](Self::compile).
+  pub fn retain_descriptor(&mut self, retain: bool) -> &mut Self {
+    self.retain_descriptor = retain;
+    self
+  }
+
+  /// Keeps the intermediary descriptor at `OUT_DIR/descriptor.bin` (or inside the [`out_dir`](Self::out_dir), or at the [`descriptor_set_path`](Self::descriptor_set_path), if set), so that it can be embedded with `include_bytes!(concat!(env!("OUT_DIR"), "/descriptor.bin"))` and used at runtime, for example for gRPC server reflection.
+  ///
+  /// Inside of a build script, this also emits the `cargo:rustc-env=PROTO_DESCRIPTOR_SET` directive pointing to it, so that the protocheck macros use the same descriptor from which the validation attributes were extracted. Defaults to false.
+  pub fn embed_descriptor_set(&mut self, embed: bool) -> &mut Self {
+    self.embed_descriptor_set = embed;
+    self
+  }
+
+  /// Keeps the intermediary descriptor when [`compile`](Self::compile) fails, and prints its path as a cargo warning. Useful to debug the issues with decoding the descriptor. Defaults to false.
+  pub fn keep_descriptor_on_error(&mut self, keep: bool) -> &mut Self {
+    self.keep_descriptor_on_error = keep;
+    self
+  }
+
+  /// Emits a `cargo:rerun-if-changed` directive for every proto file and include path used by [`compile`](Self::compile). The directives are only emitted when running inside of a build script (when `OUT_DIR` is set). Defaults to true.
+  pub fn emit_rerun_if_changed(&mut self, emit: bool) -> &mut Self {
+    self.emit_rerun_if_changed = emit;
+    self
+  }
+
+  /// Adds arguments to the protoc invocation that creates the intermediary descriptor, like [`Config::protoc_arg`]. The intermediary descriptor is created with a separate config, so the arguments of the config passed to [`compile`](Self::compile) are not used for it.
+  ///
+  /// With the `protox` feature, protoc is not used, so these arguments are ignored.
+  pub fn protoc_args<I, S>(&mut self, args: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+  {
+    self
+      .protoc_args
+      .extend(args.into_iter().map(|arg| arg.as_ref().to_owned()));
+    self
+  }
+
+  /// Sets the protoc executable used to create the intermediary descriptor, like [`Config::protoc_executable`]. Defaults to the one from the `PROTOC` env variable, or to the `protoc` found in the `PATH`.
+  pub fn protoc_path(&mut self, path: impl AsRef<Path>) -> &mut Self {
+    self.protoc_path = Some(path.as_ref().to_path_buf());
+    self
+  }
+
+  /// Sets the directory for the generated code and for the files written by protocheck, instead of `OUT_DIR`, such as when generating the code with a standalone binary rather than with a build script. It is also set as the [`Config::out_dir`] of the config passed to [`compile`](Self::compile).
+  ///
+  /// When neither this nor `OUT_DIR` is set, [`compile`](Self::compile) returns an error, unless the [`descriptor_set_path`](Self::descriptor_set_path) is set and the include paths contain the protovalidate definitions (as the bundled ones are written to this directory).
+  pub fn out_dir(&mut self, path: impl AsRef<Path>) -> &mut Self {
+    self.out_dir = Some(path.as_ref().to_path_buf());
+    self
+  }
+
+  /// Compiles the proto files to create the intermediary descriptor, and uses it to add the validation attributes to the given config.
+  ///
+  /// The config must then be used to compile the same proto files.
+  ///
+  /// If none of the include paths contain `buf/validate/validate.proto`, the copy bundled with this crate is used instead (see [`vendored_protovalidate_include_path`](crate::vendored_protovalidate_include_path)), and its directory is also added to the protoc arguments of the config.
+  ///
+  /// Returns a [`BuildReport`] with the rules found in the selected messages.
+  pub fn compile(&self, config: &mut Config) -> Result<BuildReport, Box<dyn Error>> {
+    let out_dir = self
+      .out_dir
+      .clone()
+      .or_else(|| env::var_os("OUT_DIR").map(PathBuf::from));
+
+    let descriptor_path = match &self.descriptor_set_path {
+      Some(path) => path.clone(),
+      None if self.embed_descriptor_set => out_dir
+        .as_ref()
+        .ok_or("The embedded descriptor set is written to OUT_DIR, which is not set. Use ValidatorsBuilder::out_dir or ValidatorsBuilder::descriptor_set_path when compiling the protos outside of a build script.")?
+        .join(EMBEDDED_DESCRIPTOR_SET_FILE_NAME),
+      None => temp_descriptor_path(out_dir.as_deref().ok_or("The intermediary descriptor is written to OUT_DIR, but OUT_DIR is not set. Use ValidatorsBuilder::out_dir or ValidatorsBuilder::descriptor_set_path when compiling the protos outside of a build script.")?),
+    };
+
+    // Removes the descriptor at the end, including when returning early because of an error
+    let retain_descriptor = self.retain_descriptor || self.embed_descriptor_set;
+    let mut cleanup = (!retain_descriptor).then(|| DescriptorCleanup {
+      path: descriptor_path.clone(),
+      keep: self.keep_descriptor_on_error,
+    });
+
+    let mut include_paths = if self.include_paths.is_empty() {
+      let derived_include_paths = derive_include_paths(&self.proto_files)?;
+
+      // Lets the final codegen use them as well
+      for include_path in &derived_include_paths {
+        config.protoc_arg(format!("--proto_path={}", include_path.display()));
+      }
+
+      derived_include_paths
+    } else {
+      self.include_paths.clone()
+    };
+
+    if self.emit_rerun_if_changed {
+      for path in self.proto_files.iter().chain(&include_paths) {
+        rerun_if_changed(path);
+      }
+    }
+
+    if let Some(vendored_include_path) =
+      vendored_include_path_if_missing(&include_paths, out_dir.as_deref())?
+    {
+      // Lets the final codegen find the vendored protos as well
+      config.protoc_arg(format!("--proto_path={}", vendored_include_path.display()));
+      include_paths.push(vendored_include_path);
+    }
+
+    let mut descriptor_config = Config::new();
+
+    if let Some(out_dir) = &self.out_dir {
+      config.out_dir(out_dir);
+      descriptor_config.out_dir(out_dir);
+    }
+
+    for arg in &self.protoc_args {
+      descriptor_config.protoc_arg(arg);
+    }
+
+    if let Some(protoc_path) = &self.protoc_path {
+      descriptor_config.protoc_executable(protoc_path);
+    }
+
+    write_descriptor_set(
+      &mut descriptor_config,
+      &descriptor_path,
+      &self.proto_files,
+      &include_paths,
+    )?;
+
+    let fds_bytes = read_descriptor_set(&descriptor_path)?;
+
+    let report = self.compile_from_fds(config, &fds_bytes)?;
+
+    if let Some(cleanup) = &mut cleanup {
+      cleanup.keep = false;
+    }
+
+    if self.embed_descriptor_set {
+      rustc_env("PROTO_DESCRIPTOR_SET", descriptor_path.display());
+    }
+
+    Ok(report)
+  }
+
+  /// Adds the validation attributes to the given config, using an already serialized `FileDescriptorSet` instead of compiling the proto files.
+  pub fn compile_from_fds(
+    &self,
+    config: &mut Config,
+    fds_bytes: &[u8],
+  ) -> Result<BuildReport, Box<dyn Error>> {
+    // read pool directly from bytes to access custom options
+    // correctly. See: https://github.com/andrewhickman/prost-reflect/issues/21
+    check_editions(fds_bytes)?;
+
+    let pool = DescriptorPool::decode(fds_bytes)?;
+
+    self.apply(config, &pool)
+  }
+
+  fn apply(
+    &self,
+    config: &mut Config,
+    pool: &DescriptorPool,
+  ) -> Result<BuildReport, Box<dyn Error>> {
+    let protovalidate_field_option = pool
+      .get_extension_by_name(VALIDATE_EXT_FIELD_PATH)
+      .ok_or_else(|| {
+        format!(
+          "The {} extension was not found in the file descriptor set. Make sure that buf/validate/validate.proto is imported and that the descriptor set includes its imports.",
+          VALIDATE_EXT_FIELD_PATH
+        )
+      })?;
+
+    let unmatched_packages = self.unmatched_packages(pool);
+
+    if !unmatched_packages.is_empty() {
+      if self.strict_packages {
+        return Err(
+          format!(
+            "The following packages did not match any package in the file descriptor set: {}",
+            unmatched_packages.join(", ")
+          )
+          .into(),
+        );
+      }
+
+      for package in &unmatched_packages {
+        cargo_warning(format_args!(
+          "The package {} did not match any package in the file descriptor set",
+          package
+        ));
+      }
+    }
+
+    for entry in self.unmatched_exclusions(pool) {
+      cargo_warning(format_args!(
+        "The exclusion {} did not match anything in the file descriptor set",
+        entry
+      ));
+    }
+
+    // Older versions of protovalidate may not define it, in which case no message is disabled
+    let protovalidate_message_option = pool.get_extension_by_name(VALIDATE_EXT_MESSAGE_PATH);
+    let protovalidate_oneof_option = pool.get_extension_by_name(VALIDATE_EXT_ONEOF_PATH);
+    let protovalidate_predefined_option = pool.get_extension_by_name(VALIDATE_EXT_PREDEFINED_PATH);
+
+    if self.type_names {
+      config.enable_type_names();
+    }
+
+    // Prost emits the attributes matching a path in the order in which they were added
+    for (path, attribute) in &self.extra_message_attributes {
+      config.message_attribute(path, attribute);
+    }
+
+    for (path, attribute) in &self.extra_field_attributes {
+      config.field_attribute(path, attribute);
+    }
+
+    // <Request message, the methods which use it>
+    let request_methods = if self.validated_requests {
+      self.request_methods(pool)
+    } else {
+      BTreeMap::new()
+    };
+
+    let selected_messages = self.selected_messages(pool);
+    let needed_messages = (!self.always_generate).then(|| {
+      self.messages_needing_validators(
+        pool,
+        &protovalidate_field_option,
+        protovalidate_message_option.as_ref(),
+        protovalidate_oneof_option.as_ref(),
+        request_methods.keys(),
+      )
+    });
+
+    // The validators of the message fields call those of their message, so they must be skipped for the messages without them
+    let has_validators = |message_desc: &MessageDescriptor| {
+      protovalidate_message_option
+        .as_ref()
+        .is_none_or(|ext| !message_is_disabled(message_desc, ext))
+        && selected_messages.contains(message_desc.full_name())
+        && needed_messages
+          .as_ref()
+          .is_none_or(|needed| needed.contains(message_desc.full_name()))
+    };
+    let mut validated_requests: HashSet<String> = HashSet::new();
+    let mut report = BuildReport::default();
+    let mut invalid_rules: Vec<String> = Vec::new();
+    let mut patterns_checker = PatternsChecker::default();
+
+    #[cfg(feature = "serde")]
+    let manifest_extensions = crate::manifest::ManifestExtensions {
+      field: protovalidate_field_option.clone(),
+      message: protovalidate_message_option.clone(),
+      oneof: protovalidate_oneof_option.clone(),
+      predefined: protovalidate_predefined_option.clone(),
+    };
+    #[cfg(feature = "serde")]
+    let mut manifest = self
+      .constraints_manifest_path
+      .is_some()
+      .then(crate::ConstraintsManifest::default);
+
+    for message_desc in pool.all_messages() {
+      let message_name = message_desc.full_name();
+
+      // Messages marked with buf.validate.message.disabled = true don't get any attribute.
+      // Their nested messages are listed separately, so they are still validated unless they are disabled themselves.
+      if let Some(ext) = &protovalidate_message_option
+        && message_is_disabled(&message_desc, ext)
+      {
+        continue;
+      }
+
+      // The map entries are synthetic messages that become a HashMap in the generated code,
+      // and their rules (map.keys and map.values) are applied through the attributes of the map field
+      if message_desc.is_map_entry() {
+        continue;
+      }
+
+      if selected_messages.contains(message_name)
+        && needed_messages
+          .as_ref()
+          .is_none_or(|needed| needed.contains(message_name))
+      {
+        let is_excluded_field = |field: &FieldDescriptor| self.is_excluded_field(field);
+
+        report.add_message(
+          &message_desc,
+          &protovalidate_field_option,
+          protovalidate_message_option.as_ref(),
+          is_excluded_field,
+        );
+
+        #[cfg(feature = "serde")]
+        if let Some(manifest) = &mut manifest {
+          manifest.add_message(&message_desc, &manifest_extensions, is_excluded_field)?;
+        }
+
+        invalid_rules.extend(patterns_checker.check_message(
+          &message_desc,
+          &protovalidate_field_option,
+          is_excluded_field,
+        ));
+
+        #[cfg(feature = "cel")]
+        invalid_rules.extend(crate::cel_check::check_cel_rules(
+          &message_desc,
+          &protovalidate_field_option,
+          protovalidate_message_option.as_ref(),
+          is_excluded_field,
+        ));
+
+        // The ignore modes are resolved by name, as their numbers differ between versions of protovalidate
+        for field in message_desc.fields() {
+          let options = field.options();
+          let field_rules = options.get_extension(&protovalidate_field_option);
+          let field_rules = field_rules.as_message();
+
+          let ignore_mode = field_rules.and_then(|rules| {
+            let ignore_field = rules.descriptor().get_field_by_name("ignore")?;
+            let number = rules.get_field(&ignore_field).as_enum_number()?;
+
+            ignore_field
+              .kind()
+              .as_enum()?
+              .get_value(number)
+              .map(|value| value.name().to_string())
+          });
+
+          let field_path = field_attribute_path(&field);
+          let is_excluded = is_excluded_field(&field);
+
+          if is_excluded {
+            config.field_attribute(&field_path, "#[protocheck(skip_validation)]");
+          } else if let Some(attribute) = ignore_mode.as_deref().and_then(ignore_attribute) {
+            config.field_attribute(&field_path, attribute);
+          }
+
+          if !is_excluded
+            && validated_field_message(&field)
+              .is_some_and(|field_message| !has_validators(&field_message))
+          {
+            config.field_attribute(&field_path, "#[protocheck(skip_nested_validation)]");
+          }
+
+          // Tells the CEL conversions that these fields use other (but structurally identical) types
+          if cfg!(feature = "cel")
+            && self.well_known_types != WellKnownTypes::Protocheck
+            && let Some(attribute) = well_known_type_attribute(&field)
+          {
+            config.field_attribute(&field_path, attribute);
+          }
+
+          // The predefined rules are evaluated by the CEL validators
+          if cfg!(feature = "cel")
+            && !is_excluded
+            && let Some(ext) = &protovalidate_predefined_option
+            && let Some(rules) = field_rules
+          {
+            for attribute in predefined_rule_attributes(&field, rules, ext)? {
+              config.field_attribute(&field_path, attribute);
+            }
+          }
+        }
+
+        let attribute_str = format!(
+          r#"#[::protocheck::macros::protobuf_validate("{}")]"#,
+          message_name
+        );
+        config.message_attribute(message_name, &attribute_str);
+
+        if request_methods.contains_key(message_name) {
+          config.message_attribute(
+            message_name,
+            "#[derive(::protocheck::macros::ValidatedRequest)]",
+          );
+          validated_requests.insert(message_name.to_string());
+        }
+
+        // The fields of the synthetic oneofs (the proto3 optional fields) are plain optional fields in the generated code
+        for oneof in message_desc.oneofs().filter(|oneof| !oneof.is_synthetic()) {
+          let oneof_name = oneof.full_name();
+          config.type_attribute(
+            oneof_name,
+            format!(
+              r#"#[::protocheck::macros::protobuf_validate_oneof("{}")]"#,
+              oneof_name
+            ),
+          );
+
+          if let Some(ext) = &protovalidate_oneof_option
+            && oneof_is_required(&oneof, ext)
+          {
+            config.type_attribute(oneof_name, r#"#[protocheck(oneof_required)]"#);
+          }
+        }
+      }
+    }
+
+    // The Cel conversions of the messages with validators also convert their message fields,
+    // so the messages without validators that they contain only get the conversions
+    if cfg!(feature = "cel") {
+      for message_desc in messages_needing_conversions(pool, has_validators) {
+        let cel_attribute = "#[derive(::protocheck::macros::TryIntoCelValue)]";
+
+        config.message_attribute(message_desc.full_name(), cel_attribute);
+
+        for oneof in message_desc.oneofs().filter(|oneof| !oneof.is_synthetic()) {
+          config.type_attribute(oneof.full_name(), cel_attribute);
+        }
+
+        if self.well_known_types != WellKnownTypes::Protocheck {
+          for field in message_desc.fields() {
+            if let Some(attribute) = well_known_type_attribute(&field) {
+              config.field_attribute(field_attribute_path(&field), attribute);
+            }
+          }
+        }
+      }
+    }
+
+    let unvalidated_requests: Vec<String> = request_methods
+      .iter()
+      .filter(|(message_name, _)| !validated_requests.contains(*message_name))
+      .flat_map(|(message_name, methods)| {
+        methods
+          .iter()
+          .map(move |method| format!("  {} ({})", method, message_name))
+      })
+      .collect();
+
+    if !unvalidated_requests.is_empty() {
+      return Err(
+        format!(
+          "The requests of the following methods don't receive the validators, as they are outside of the selected packages, or they are excluded or disabled:\n{}",
+          unvalidated_requests.join("\n")
+        )
+        .into(),
+      );
+    }
+
+    // The patterns and the Cel expressions are checked here, so that the errors point to the proto files rather than to the generated code
+    if !invalid_rules.is_empty() {
+      return Err(
+        format!(
+          "The following rules are invalid:\n\n{}",
+          invalid_rules.join("\n\n")
+        )
+        .into(),
+      );
+    }
+
+    #[cfg(feature = "serde")]
+    if let Some(report_path) = &self.report_path {
+      report.write_json(report_path)?;
+    }
+
+    #[cfg(feature = "serde")]
+    if let (Some(manifest), Some(manifest_path)) = (&manifest, &self.constraints_manifest_path) {
+      manifest.write_json(manifest_path)?;
+    }
+
+    if !report.unsupported_rules.is_empty() {
+      if self.deny_unsupported_rules {
+        return Err(
+          format!(
+            "The following rules are not supported by protocheck:\n{}",
+            report
+              .unsupported_rules
+              .iter()
+              .map(|rule| format!("  {}", rule))
+              .collect::<Vec<_>>()
+              .join("\n")
+          )
+          .into(),
+        );
+      }
+
+      for rule in &report.unsupported_rules {
+        cargo_warning(format_args!(
+          "The rule {} is not supported by protocheck, so it is not enforced",
+          rule
+        ));
+      }
+    }
+
+    // The types of protovalidate are declared one by one rather than with the whole package, as prost
+    // does not generate the code of the extern paths, and that would include the packages nested inside
+    // of buf.validate (such as the ones of the protovalidate conformance tests)
+    for file in pool
+      .files()
+      .filter(|file| file.package_name() == "buf.validate")
+    {
+      let names = file
+        .messages()
+        .map(|message| message.name().to_string())
+        .chain(file.enums().map(|enum_desc| enum_desc.name().to_string()));
+
+      for name in names {
+        config.extern_path(
+          format!(".buf.validate.{}", name),
+          format!("::protocheck::types::protovalidate::{}", name),
+        );
+      }
+    }
+
+    match &self.well_known_types {
+      WellKnownTypes::Protocheck => {
+        config
+          .extern_path(".google.protobuf", "::protocheck::types")
+          .compile_well_known_types();
+      }
+      // This is what prost-build uses by default
+      WellKnownTypes::ProstTypes => {}
+      WellKnownTypes::Custom(path) => {
+        config
+          .extern_path(".google.protobuf", path)
+          .compile_well_known_types();
+      }
+    }
+
+    Ok(report)
+  }
+
+  // Returns the selected packages that don't match any package in the pool, along with a suggestion for each of them
+  fn unmatched_packages(&self, pool: &DescriptorPool) -> Vec<String> {
+    let package_names: BTreeSet<String> = pool
+      .files()
+      .map(|file| file.package_name().to_string())
+      .filter(|package| !package.is_empty() && !EXCLUDED_PACKAGES.contains(&package.as_str()))
+      .collect();
+
+    self
+      .packages
+      .iter()
+      .filter(|pattern| {
+        !package_names
+          .iter()
+          .any(|package| package_matches(pattern, package))
+      })
+      .map(|pattern| {
+        match package_names
+          .iter()
+          .min_by_key(|package| edit_distance(pattern, package))
+        {
+          Some(closest) => format!("`{}` (did you mean `{}`?)", pattern, closest),
+          None => format!("`{}`", pattern),
+        }
+      })
+      .collect()
+  }
+
+  // Returns the names of the messages that receive the validators, which are the ones in the selected packages
+  // and (if enabled) the ones that they reach through their fields. Each message is only visited once, so recursive messages are fine
+  fn selected_messages(&self, pool: &DescriptorPool) -> HashSet<String> {
+    let mut selected = HashSet::new();
+    let mut queue: Vec<MessageDescriptor> = pool
+      .all_messages()
+      .filter(|message_desc| self.is_selected(message_desc))
+      .collect();
+
+    while let Some(message_desc) = queue.pop() {
+      if !selected.insert(message_desc.full_name().to_string()) || !self.include_transitive_messages
+      {
+        continue;
+      }
+
+      for field in message_desc.fields() {
+        if let Some(field_message) = field.kind().as_message()
+          && !EXCLUDED_PACKAGES.contains(&field_message.package_name())
+          && !self.is_excluded(field_message)
+        {
+          queue.push(field_message.clone());
+        }
+      }
+    }
+
+    selected
+  }
+
+  // Returns the messages with rules, the ones which contain them, so that the validation reaches the nested rules,
+  // and all of the messages contained in those (or in the other given messages), as the validators of a message also validate its message fields
+  fn messages_needing_validators(
+    &self,
+    pool: &DescriptorPool,
+    field_ext: &ExtensionDescriptor,
+    message_ext: Option<&ExtensionDescriptor>,
+    oneof_ext: Option<&ExtensionDescriptor>,
+    other_messages: impl IntoIterator<Item = impl AsRef<str>>,
+  ) -> HashSet<String> {
+    let mut parents: HashMap<String, Vec<MessageDescriptor>> = HashMap::new();
+    let mut queue = Vec::new();
+
+    for message_desc in pool.all_messages().filter(|m| !m.is_map_entry()) {
+      for child in validated_message_fields(&message_desc) {
+        parents
+          .entry(child.full_name().to_string())
+          .or_default()
+          .push(message_desc.clone());
+      }
+
+      let has_rules = message_ext.is_some_and(|ext| message_desc.options().has_extension(ext))
+        || oneof_ext.is_some_and(|ext| {
+          message_desc
+            .oneofs()
+            .any(|oneof| !oneof.is_synthetic() && oneof.options().has_extension(ext))
+        })
+        || message_desc
+          .fields()
+          .any(|field| !self.is_excluded_field(&field) && field.options().has_extension(field_ext));
+
+      if has_rules {
+        queue.push(message_desc);
+      }
+    }
+
+    let mut with_nested_rules = HashSet::new();
+
+    while let Some(message_desc) = queue.pop() {
+      if with_nested_rules.insert(message_desc.full_name().to_string())
+        && let Some(message_parents) = parents.get(message_desc.full_name())
+      {
+        queue.extend(message_parents.iter().cloned());
+      }
+    }
+
+    let mut needed = HashSet::new();
+    let mut queue: Vec<MessageDescriptor> = with_nested_rules
+      .iter()
+      .filter_map(|name| pool.get_message_by_name(name))
+      .collect();
+    queue.extend(
+      other_messages
+        .into_iter()
+        .filter_map(|name| pool.get_message_by_name(name.as_ref())),
+    );
+
+    while let Some(message_desc) = queue.pop() {
+      if needed.insert(message_desc.full_name().to_string()) {
+        queue.extend(validated_message_fields(&message_desc));
+      }
+    }
+
+    needed
+  }
+
+  // Returns the methods of the services in the selected packages (as `package.Service/Method`), by the name of their request message
+  fn request_methods(&self, pool: &DescriptorPool) -> BTreeMap<String, Vec<String>> {
+    let mut request_methods: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for service in pool
+      .services()
+      .filter(|service| self.is_selected_package(service.package_name()))
+    {
+      for method in service.methods() {
+        request_methods
+          .entry(method.input().full_name().to_string())
+          .or_default()
+          .push(format!("{}/{}", service.full_name(), method.name()));
+      }
+    }
+
+    request_methods
+  }
+
+  // The exclusions use the same glob patterns as the packages
+  fn is_excluded(&self, message_desc: &MessageDescriptor) -> bool {
+    self
+      .excluded_messages
+      .iter()
+      .any(|m| package_matches(m, message_desc.full_name()))
+  }
+
+  fn is_excluded_field(&self, field: &FieldDescriptor) -> bool {
+    self
+      .excluded_fields
+      .iter()
+      .any(|f| package_matches(f, field.full_name()))
+  }
+
+  // Returns the excluded messages and fields that don't match any message or field in the descriptor
+  fn unmatched_exclusions(&self, pool: &DescriptorPool) -> Vec<String> {
+    let unmatched_messages = self
+      .excluded_messages
+      .iter()
+      .filter(|pattern| {
+        !pool
+          .all_messages()
+          .any(|message| package_matches(pattern, message.full_name()))
+      })
+      .map(|pattern| format!("of the message `{}`", pattern));
+
+    let unmatched_fields = self
+      .excluded_fields
+      .iter()
+      .filter(|pattern| {
+        !pool.all_messages().any(|message| {
+          message
+            .fields()
+            .any(|field| package_matches(pattern, field.full_name()))
+        })
+      })
+      .map(|pattern| format!("of the field `{}`", pattern));
+
+    unmatched_messages.chain(unmatched_fields).collect()
+  }
+
+  fn is_selected(&self, message_desc: &MessageDescriptor) -> bool {
+    self.is_selected_package(message_desc.package_name()) && !self.is_excluded(message_desc)
+  }
+
+  fn is_selected_package(&self, package: &str) -> bool {
+    !EXCLUDED_PACKAGES.contains(&package)
+      && (self.packages.is_empty() || self.packages.iter().any(|p| package_matches(p, package)))
+  }
+}
+
+// Maps the name of a buf.validate.Ignore value to the attribute used by protocheck, if any.
+// The older names of the same modes are included too. IGNORE_IF_ZERO_VALUE replaced IGNORE_IF_UNPOPULATED,
+// as it does not apply to the fields with presence
+fn ignore_attribute(ignore_mode: &str) -> Option<&'static str> {
+  match ignore_mode {
+    "IGNORE_ALWAYS" => Some("#[protocheck(ignore_field)]"),
+    "IGNORE_IF_UNPOPULATED" | "IGNORE_EMPTY" | "IGNORE_IF_ZERO_VALUE" => {
+      Some("#[protocheck(ignore_if_unpopulated)]")
+    }
+    "IGNORE_IF_DEFAULT_VALUE" | "IGNORE_DEFAULT" => Some("#[protocheck(ignore_if_default)]"),
+    _ => None,
+  }
+}
+
+// Creates a unique path for each invocation, so that concurrent builds sharing the same directory
+// (which can happen when falling back to the temporary directory) don't overwrite each other's descriptor
+fn temp_descriptor_path(dir: &Path) -> PathBuf {
+  static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+  dir.join(format!(
+    "temp_file_descriptor_set_for_protocheck_{}_{}.bin",
+    process::id(),
+    COUNTER.fetch_add(1, Ordering::Relaxed)
+  ))
+}
+
+struct DescriptorCleanup {
+  path: PathBuf,
+  // Only set while the compilation has not completed successfully
+  keep: bool,
+}
+
+impl Drop for DescriptorCleanup {
+  fn drop(&mut self) {
+    if self.keep {
+      if self.path.exists() {
+        cargo_warning(format_args!(
+          "The file descriptor set was kept at {}",
+          self.path.display()
+        ));
+      }
+    } else {
+      let _ = fs::remove_file(&self.path);
+    }
+  }
+}
+
+// prost-build matches the attributes of the fields inside of a oneof by the oneof's path
+fn field_attribute_path(field: &FieldDescriptor) -> String {
+  match field.containing_oneof() {
+    Some(oneof) if !oneof.is_synthetic() => format!("{}.{}", oneof.full_name(), field.name()),
+    _ => field.full_name().to_string(),
+  }
+}
+
+fn well_known_type_attribute(field: &FieldDescriptor) -> Option<&'static str> {
+  let kind = match field.kind() {
+    Kind::Message(entry) if field.is_map() => entry.map_entry_value_field().kind(),
+    kind => kind,
+  };
+
+  match kind.as_message()?.full_name() {
+    "google.protobuf.Timestamp" => Some("#[protocheck(timestamp)]"),
+    "google.protobuf.Duration" => Some("#[protocheck(duration)]"),
+    _ => None,
+  }
+}
+
+// The types of the message fields (including the items of the lists, the values of the maps and the oneof variants)
+// which are validated as nested messages. Like in the validators, the google packages are left out
+fn validated_message_fields(message_desc: &MessageDescriptor) -> Vec<MessageDescriptor> {
+  message_desc
+    .fields()
+    .filter_map(|field| validated_field_message(&field))
+    .collect()
+}
+
+// Returns the messages without validators which are contained in those with validators (or in the other messages of the list),
+// as the Cel conversions of a message also convert its message fields
+fn messages_needing_conversions(
+  pool: &DescriptorPool,
+  has_validators: impl Fn(&MessageDescriptor) -> bool,
+) -> Vec<MessageDescriptor> {
+  let mut visited = HashSet::new();
+  let mut needed = Vec::new();
+  let mut queue: Vec<MessageDescriptor> = pool
+    .all_messages()
+    .filter(|message_desc| !message_desc.is_map_entry() && has_validators(message_desc))
+    .collect();
+
+  while let Some(message_desc) = queue.pop() {
+    for field_message in validated_message_fields(&message_desc) {
+      if !has_validators(&field_message) && visited.insert(field_message.full_name().to_string()) {
+        queue.push(field_message.clone());
+        needed.push(field_message);
+      }
+    }
+  }
+
+  needed
+}
+
+// The message whose validators are called by those of the field, which is the value of the maps
+fn validated_field_message(field: &FieldDescriptor) -> Option<MessageDescriptor> {
+  let field_message = field.kind().as_message()?.clone();
+
+  let field_message = if field.is_map() {
+    field_message
+      .map_entry_value_field()
+      .kind()
+      .as_message()
+      .cloned()?
+  } else {
+    field_message
+  };
+
+  (!field_message.full_name().starts_with("google.")).then_some(field_message)
+}
+
+pub(crate) fn oneof_is_required(oneof_desc: &OneofDescriptor, ext: &ExtensionDescriptor) -> bool {
+  oneof_desc
+    .options()
+    .get_extension(ext)
+    .as_message()
+    .and_then(|rules| rules.get_field_by_name("required"))
+    .and_then(|required| required.as_bool())
+    .unwrap_or(false)
+}
+
+fn message_is_disabled(message_desc: &MessageDescriptor, ext: &ExtensionDescriptor) -> bool {
+  message_desc
+    .options()
+    .get_extension(ext)
+    .as_message()
+    .and_then(|rules| rules.get_field_by_name("disabled"))
+    .and_then(|disabled| disabled.as_bool())
+    .unwrap_or(false)
+}
+
+// The Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+  for (i, a_char) in a.chars().enumerate() {
+    let mut current = vec![i + 1];
+
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = previous[j] + usize::from(a_char != *b_char);
+      current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+    }
+
+    previous = current;
+  }
+
+  previous[b.len()]
+}
+
+// Matches a package name (or the name of a message or field) against an exact name or a glob pattern, where `*` matches any sequence of characters
+fn package_matches(pattern: &str, package: &str) -> bool {
+  match pattern.split_once('*') {
+    None => pattern == package,
+    Some((prefix, rest)) => {
+      let Some(remaining) = package.strip_prefix(prefix) else {
+        return false;
+      };
+
+      // Tries every possible length for the sequence matched by this wildcard
+      remaining
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([remaining.len()])
+        .any(|i| package_matches(rest, &remaining[i..]))
+    }
+  }
+}
+
+// Runs protoc to create the descriptor set without generating any code.
+// The raw bytes are read back from the file afterwards, because the custom options
+// would be lost in the decoded prost_types::FileDescriptorSet.
+#[cfg(not(feature = "protox"))]
+pub(crate) fn write_descriptor_set(
+  config: &mut Config,
+  descriptor_path: &Path,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+) -> Result<(), Box<dyn Error>> {
+  config.file_descriptor_set_path(descriptor_path);
+  config.load_fds(proto_files, proto_include_paths)?;
+
+  Ok(())
+}
+
+// Uses protox to create the descriptor set, so that protoc is not required.
+// The config is not used, as its protoc settings do not apply here.
+#[cfg(feature = "protox")]
+pub(crate) fn write_descriptor_set(
+  _config: &mut Config,
+  descriptor_path: &Path,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+) -> Result<(), Box<dyn Error>> {
+  let with_file_name = |e: protox::Error| match e.file() {
+    Some(file) => format!("Failed to compile {}: {}", file, e),
+    None => format!("Failed to compile the proto files: {}", e),
+  };
+
+  let fds_bytes = protox::Compiler::new(proto_include_paths)
+    .map_err(with_file_name)?
+    .include_imports(true)
+    .include_source_info(true)
+    .open_files(proto_files)
+    .map_err(with_file_name)?
+    .encode_file_descriptor_set();
+
+  fs::write(descriptor_path, fds_bytes)?;
+
+  Ok(())
+}
+
+// Neither prost-reflect nor prost-build support Protobuf Editions yet (prost-reflect even panics while decoding them),
+// so the files which use them are rejected before the descriptor set is decoded
+fn check_editions(fds_bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+  let fds = FileDescriptorSet::decode(fds_bytes)?;
+
+  let editions_files: Vec<&str> = fds
+    .file
+    .iter()
+    .filter(|file| file.syntax() == "editions")
+    .map(|file| file.name())
+    .collect();
+
+  if editions_files.is_empty() {
+    return Ok(());
+  }
+
+  Err(
+    format!(
+      "The following files use Protobuf Editions, which are not supported yet by prost-build and prost-reflect: {}. Use `syntax = \"proto2\"` or `syntax = \"proto3\"` for them in the meantime.",
+      editions_files.join(", ")
+    )
+    .into(),
+  )
+}
+
+pub(crate) fn read_descriptor_set(fds_path: &Path) -> io::Result<Vec<u8>> {
+  fs::read(fds_path).map_err(|e| {
+    io::Error::new(
+      e.kind(),
+      format!(
+        "Could not read the file descriptor set at {:?}: {}",
+        fds_path, e
+      ),
+    )
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const PROTO_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto");
+
+  fn proto_files() -> Vec<PathBuf> {
+    vec![
+      PathBuf::from(PROTO_DIR).join("myapp/v1/builder.proto"),
+      PathBuf::from(PROTO_DIR).join("other/v1/other.proto"),
+    ]
+  }
+
+  // Runs the builder and the final codegen, and returns the generated code for the given package
+  fn generate(builder: &mut ValidatorsBuilder, package: &str) -> String {
+    generate_files(builder, &proto_files(), package)
+  }
+
+  fn generate_files(
+    builder: &mut ValidatorsBuilder,
+    proto_files: &[PathBuf],
+    package: &str,
+  ) -> String {
+    let out_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::new();
+    config.out_dir(out_dir.path());
+
+    // Avoids sharing the default descriptor path between tests running in parallel
+    if builder.descriptor_set_path.is_none() {
+      builder.descriptor_set_path(out_dir.path().join("descriptor.bin"));
+    }
+
+    builder
+      .proto_files(proto_files)
+      .include_paths([PROTO_DIR])
+      .compile(&mut config)
+      .unwrap();
+
+    config.compile_protos(proto_files, &[PROTO_DIR]).unwrap();
+
+    fs::read_to_string(out_dir.path().join(format!("{package}.rs"))).unwrap()
+  }
+
+  fn validate_attr(message: &str) -> String {
+    format!(r#"#[::protocheck::macros::protobuf_validate("{message}")]"#)
+  }
+
+  #[test]
+  fn packages() {
+    let code = generate(ValidatorsBuilder::new().packages(["myapp.v1"]), "myapp.v1");
+    assert!(code.contains(&validate_attr("myapp.v1.User")));
+
+    let code = generate(ValidatorsBuilder::new().packages(["myapp.v1"]), "other.v1");
+    assert!(!code.contains(&validate_attr("other.v1.Other")));
+
+    let code = generate(
+      ValidatorsBuilder::new().packages(["myapp.v1", "other.v1"]),
+      "other.v1",
+    );
+    assert!(code.contains(&validate_attr("other.v1.Other")));
+  }
+
+  #[test]
+  fn excluded_message_patterns() {
+    let code = generate(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .exclude_messages(["myapp.v1.Un*"]),
+      "myapp.v1",
+    );
+    assert!(code.contains(&validate_attr("myapp.v1.User")));
+    assert!(!code.contains(&validate_attr("myapp.v1.Unchecked")));
+  }
+
+  #[test]
+  fn excluded_fields() {
+    let code = generate(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .exclude_fields([
+          "myapp.v1.User.name",
+          "myapp.v1.User.e*",
+          "myapp.v1.User.nickname",
+        ]),
+      "myapp.v1",
+    );
+
+    assert_eq!(code.matches("#[protocheck(skip_validation)]").count(), 3);
+    // The exclusion replaces the ignore attribute
+    assert!(!code.contains("#[protocheck(ignore_field)]"));
+    assert!(
+      code
+        .contains("#[protocheck(skip_validation)]\n    pub name: ::prost::alloc::string::String,")
+    );
+    assert!(code.contains("#[protocheck(skip_validation)]\n        Email("));
+  }
+
+  #[test]
+  fn unmatched_exclusions() {
+    let dir = tempfile::tempdir().unwrap();
+    let descriptor_path = dir.path().join("descriptor.bin");
+    write_descriptor_set(
+      &mut Config::new(),
+      &descriptor_path,
+      &proto_files(),
+      &[PROTO_DIR],
+    )
+    .unwrap();
+    let pool = DescriptorPool::decode(fs::read(&descriptor_path).unwrap().as_slice()).unwrap();
+
+    let mut builder = ValidatorsBuilder::new();
+    builder
+      .exclude_messages(["myapp.v1.User", "myapp.v1.Missing*"])
+      .exclude_fields(["myapp.v1.User.*", "myapp.v1.User.missing"]);
+
+    assert_eq!(
+      builder.unmatched_exclusions(&pool),
+      [
+        "of the message `myapp.v1.Missing*`",
+        "of the field `myapp.v1.User.missing`"
+      ]
+    );
+  }
+
+  #[test]
+  fn transitive_messages() {
+    let proto_files = [PathBuf::from(PROTO_DIR).join("pkg_a/v1/outer.proto")];
+
+    let code = generate_files(
+      ValidatorsBuilder::new().packages(["pkg_a.v1"]),
+      &proto_files,
+      "pkg_b.v1",
+    );
+    assert!(code.contains(&validate_attr("pkg_b.v1.Inner")));
+    assert!(!code.contains(&validate_attr("pkg_b.v1.Unused")));
+
+    let code = generate_files(
+      ValidatorsBuilder::new()
+        .packages(["pkg_a.v1"])
+        .exclude_messages(["pkg_b.v1.Inner"]),
+      &proto_files,
+      "pkg_b.v1",
+    );
+    assert!(!code.contains(&validate_attr("pkg_b.v1.Inner")));
+
+    let code = generate_files(
+      ValidatorsBuilder::new()
+        .packages(["pkg_a.v1"])
+        .include_transitive_messages(false),
+      &proto_files,
+      "pkg_b.v1",
+    );
+    assert!(!code.contains(&validate_attr("pkg_b.v1.Inner")));
+  }
+
+  #[test]
+  fn messages_without_validators() {
+    let proto_files = [PathBuf::from(PROTO_DIR).join("pkg_a/v1/outer.proto")];
+    let skip_attr = "#[protocheck(skip_nested_validation)]\n    pub inner:";
+
+    let code = generate_files(
+      ValidatorsBuilder::new().packages(["pkg_a.v1"]),
+      &proto_files,
+      "pkg_a.v1",
+    );
+    assert!(!code.contains("skip_nested_validation"));
+
+    for builder in [
+      ValidatorsBuilder::new()
+        .packages(["pkg_a.v1"])
+        .exclude_messages(["pkg_b.v1.Inner"]),
+      ValidatorsBuilder::new()
+        .packages(["pkg_a.v1"])
+        .include_transitive_messages(false),
+    ] {
+      let code = generate_files(&mut builder.clone(), &proto_files, "pkg_a.v1");
+      assert!(code.contains(skip_attr));
+      // The recursive field and the well known types are validated as usual
+      assert_eq!(code.matches("skip_nested_validation").count(), 1);
+
+      let code = generate_files(&mut builder.clone(), &proto_files, "pkg_b.v1");
+      assert!(!code.contains(&validate_attr("pkg_b.v1.Inner")));
+      assert_eq!(
+        code.contains("#[derive(::protocheck::macros::TryIntoCelValue)]\n"),
+        cfg!(feature = "cel")
+      );
+    }
+  }
+
+  #[test]
+  fn oneofs() {
+    let code = generate(ValidatorsBuilder::new().packages(["myapp.v1"]), "myapp.v1");
+    assert!(
+      code.contains(r#"#[::protocheck::macros::protobuf_validate_oneof("myapp.v1.User.contact")]"#)
+    );
+  }
+
+  #[test]
+  fn synthetic_oneofs() {
+    let code = generate_files(
+      &mut ValidatorsBuilder::new(),
+      &[PathBuf::from(PROTO_DIR).join("myapp/v1/optional.proto")],
+      "myapp.v1",
+    );
+
+    assert!(!code.contains("myapp.v1.Profile._nickname"));
+    assert!(code.contains(
+      "#[protocheck(ignore_if_unpopulated)]\n    pub nickname: ::core::option::Option<::prost::alloc::string::String>,"
+    ));
+  }
+
+  #[test]
+  fn map_entries() {
+    let code = generate_files(
+      &mut ValidatorsBuilder::new(),
+      &[PathBuf::from(PROTO_DIR).join("myapp/v1/maps.proto")],
+      "myapp.v1",
+    );
+
+    assert!(code.contains(&validate_attr("myapp.v1.Team")));
+    assert!(code.contains(&validate_attr("myapp.v1.Member")));
+    assert!(!code.contains("MembersEntry"));
+  }
+
+  #[test]
+  fn extra_attributes() {
+    let code = generate_files(
+      ValidatorsBuilder::new()
+        .extra_message_attribute("myapp.v1.Profile", "#[derive(::serde::Serialize)]")
+        .extra_field_attribute("myapp.v1.Profile.nickname", "#[serde(default)]"),
+      &[PathBuf::from(PROTO_DIR).join("myapp/v1/optional.proto")],
+      "myapp.v1",
+    );
+
+    let position = |pattern: &str| code.find(pattern).unwrap();
+
+    assert!(
+      position("#[derive(::serde::Serialize)]") < position(&validate_attr("myapp.v1.Profile"))
+    );
+    assert!(position(&validate_attr("myapp.v1.Profile")) < position("::prost::Message)]"));
+    assert!(position("#[serde(default)]") < position("#[protocheck(ignore_if_unpopulated)]"));
+  }
+
+  #[test]
+  fn type_names() {
+    let code = generate(ValidatorsBuilder::new().type_names(true), "myapp.v1");
+    assert!(code.contains("impl ::prost::Name for"));
+    assert!(code.contains("const PACKAGE: &'static str = \"myapp.v1\";"));
+
+    let code = generate(&mut ValidatorsBuilder::new(), "myapp.v1");
+    assert!(!code.contains("impl ::prost::Name for"));
+  }
+
+  // A protoc that records its arguments (one line per invocation) in the returned file before running the real one
+  #[cfg(all(unix, not(feature = "protox")))]
+  fn protoc_wrapper(dir: &Path) -> (PathBuf, PathBuf) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let args_path = dir.join("args.txt");
+    let wrapper_path = dir.join("protoc");
+
+    fs::write(
+      &wrapper_path,
+      format!(
+        "#!/bin/sh\necho \"$@\" >> {}\nexec {} \"$@\"\n",
+        args_path.display(),
+        prost_build::protoc_from_env().display()
+      ),
+    )
+    .unwrap();
+    fs::set_permissions(&wrapper_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+    (wrapper_path, args_path)
+  }
+
+  #[cfg(all(unix, not(feature = "protox")))]
+  #[test]
+  fn protoc_passthrough() {
+    let dir = tempfile::tempdir().unwrap();
+    let (wrapper_path, args_path) = protoc_wrapper(dir.path());
+
+    generate(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .protoc_path(&wrapper_path)
+        .protoc_args(["--experimental_allow_proto3_optional"]),
+      "myapp.v1",
+    );
+
+    let args = fs::read_to_string(&args_path).unwrap();
+    assert_eq!(args.lines().count(), 1);
+    assert!(args.contains("--experimental_allow_proto3_optional"));
+    assert!(args.contains("--include_imports"));
+  }
+
+  #[cfg(all(unix, not(feature = "protox")))]
+  #[test]
+  fn single_pass_protoc_runs() {
+    let protoc_runs = |single_pass: bool| {
+      let dir = tempfile::tempdir().unwrap();
+      let (wrapper_path, args_path) = protoc_wrapper(dir.path());
+      let descriptor_path = dir.path().join("descriptor.bin");
+
+      let mut config = Config::new();
+      config.out_dir(dir.path()).protoc_executable(&wrapper_path);
+
+      if single_pass {
+        compile_protos_with_validators_single_pass(
+          &mut config,
+          &proto_files(),
+          &[PROTO_DIR],
+          &descriptor_path,
+          &["myapp.v1"],
+        )
+        .unwrap();
+      } else {
+        ValidatorsBuilder::new()
+          .proto_files(proto_files())
+          .include_paths([PROTO_DIR])
+          .packages(["myapp.v1"])
+          .protoc_path(&wrapper_path)
+          .descriptor_set_path(&descriptor_path)
+          .compile(&mut config)
+          .unwrap();
+        config.compile_protos(&proto_files(), &[PROTO_DIR]).unwrap();
+      }
+
+      let code = fs::read_to_string(dir.path().join("myapp.v1.rs")).unwrap();
+      assert!(code.contains(&validate_attr("myapp.v1.User")));
+
+      fs::read_to_string(&args_path).unwrap().lines().count()
+    };
+
+    assert_eq!(protoc_runs(true), 1);
+    assert_eq!(protoc_runs(false), 2);
+  }
+
+  #[test]
+  fn derived_include_paths() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let proto_files = [PathBuf::from(PROTO_DIR).join("myapp/v1/optional.proto")];
+    let mut config = Config::new();
+    config.out_dir(out_dir.path());
+
+    ValidatorsBuilder::new()
+      .proto_files(&proto_files)
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .compile(&mut config)
+      .unwrap();
+
+    config
+      .compile_protos(&proto_files, &[] as &[&Path])
+      .unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("myapp.v1.rs")).unwrap();
+    assert!(code.contains(&validate_attr("myapp.v1.Profile")));
+  }
+
+  #[test]
+  fn messages_without_rules() {
+    let proto_files = [PathBuf::from(PROTO_DIR).join("myapp/v1/rules.proto")];
+    let code = generate_files(&mut ValidatorsBuilder::new(), &proto_files, "myapp.v1");
+
+    for message in [
+      "RuledAccount",
+      "PlainAddress",
+      "AccountsPage",
+      "AccountsCatalog",
+    ] {
+      assert!(code.contains(&validate_attr(&format!("myapp.v1.{message}"))));
+    }
+
+    for message in ["PlainDto", "PlainDetail", "PlainQuote"] {
+      assert!(!code.contains(&validate_attr(&format!("myapp.v1.{message}"))));
+    }
+
+    let code = generate_files(
+      ValidatorsBuilder::new().always_generate(true),
+      &proto_files,
+      "myapp.v1",
+    );
+    assert_eq!(
+      code
+        .matches("::protocheck::macros::protobuf_validate(")
+        .count(),
+      7
+    );
+  }
+
+  #[test]
+  fn validated_requests() {
+    let proto_files = [PathBuf::from(PROTO_DIR).join("myapp/v1/requests.proto")];
+    let validated_request_attr = "#[derive(::protocheck::macros::ValidatedRequest)]";
+
+    let code = generate_files(
+      ValidatorsBuilder::new().validated_requests(true),
+      &proto_files,
+      "myapp.v1",
+    );
+
+    // The requests get the validators even without rules, along with the messages that they contain
+    for message in ["GetOrderRequest", "WatchOrdersRequest", "OrderFilter"] {
+      assert!(code.contains(&validate_attr(&format!("myapp.v1.{message}"))));
+    }
+    assert!(!code.contains(&validate_attr("myapp.v1.Order")));
+    assert_eq!(code.matches(validated_request_attr).count(), 2);
+
+    let code = generate_files(&mut ValidatorsBuilder::new(), &proto_files, "myapp.v1");
+    assert!(!code.contains(validated_request_attr));
+    assert!(!code.contains(&validate_attr("myapp.v1.GetOrderRequest")));
+  }
+
+  #[test]
+  fn unvalidated_requests() {
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let err = ValidatorsBuilder::new()
+      .proto_files([PathBuf::from(PROTO_DIR).join("myapp/v1/requests.proto")])
+      .include_paths([PROTO_DIR])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .exclude_messages(["myapp.v1.*Request"])
+      .validated_requests(true)
+      .compile(&mut Config::new())
+      .unwrap_err()
+      .to_string();
+
+    assert!(err.contains("  myapp.v1.OrderService/GetOrder (myapp.v1.GetOrderRequest)"));
+    assert!(err.contains("  myapp.v1.OrderService/WatchOrders (myapp.v1.WatchOrdersRequest)"));
+  }
+
+  #[test]
+  fn required_oneofs() {
+    let code = generate(ValidatorsBuilder::new().packages(["myapp.v1"]), "myapp.v1");
+    assert_eq!(code.matches("#[protocheck(oneof_required)]").count(), 1);
+  }
+
+  #[test]
+  fn ignored_fields() {
+    let code = generate(ValidatorsBuilder::new().packages(["myapp.v1"]), "myapp.v1");
+    assert_eq!(code.matches("#[protocheck(ignore_field)]").count(), 1);
+  }
+
+  #[test]
+  fn ignore_modes() {
+    let code = generate(ValidatorsBuilder::new().packages(["myapp.v1"]), "myapp.v1");
+    assert_eq!(
+      code.matches("#[protocheck(ignore_if_unpopulated)]").count(),
+      1
+    );
+    assert!(!code.contains("#[protocheck(ignore_if_default)]"));
+  }
+
+  #[test]
+  fn legacy_ignore_modes() {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_legacy");
+    let proto_files = [PathBuf::from(proto_dir).join("legacy/v1/legacy.proto")];
+
+    let out_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::new();
+    config.out_dir(out_dir.path());
+
+    ValidatorsBuilder::new()
+      .proto_files(&proto_files)
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .compile(&mut config)
+      .unwrap();
+
+    config.compile_protos(&proto_files, &[proto_dir]).unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("legacy.v1.rs")).unwrap();
+    assert_eq!(
+      code.matches("#[protocheck(ignore_if_unpopulated)]").count(),
+      1
+    );
+    assert_eq!(code.matches("#[protocheck(ignore_if_default)]").count(), 1);
+    assert_eq!(code.matches("#[protocheck(ignore_field)]").count(), 1);
+  }
+
+  #[test]
+  fn exclude_messages() {
+    let code = generate(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .exclude_messages(["myapp.v1.Unchecked"]),
+      "myapp.v1",
+    );
+
+    assert!(code.contains(&validate_attr("myapp.v1.User")));
+    assert!(!code.contains(&validate_attr("myapp.v1.Unchecked")));
+  }
+
+  #[test]
+  fn descriptor_set_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let descriptor_path = dir.path().join("descriptor.bin");
+
+    generate(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .descriptor_set_path(&descriptor_path),
+      "myapp.v1",
+    );
+    assert!(!descriptor_path.exists());
+
+    generate(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .descriptor_set_path(&descriptor_path)
+        .retain_descriptor(true),
+      "myapp.v1",
+    );
+    assert!(descriptor_path.exists());
+
+    let pool = DescriptorPool::decode(fs::read(&descriptor_path).unwrap().as_slice()).unwrap();
+    assert!(pool.get_message_by_name("myapp.v1.User").is_some());
+  }
+
+  #[test]
+  fn package_patterns() {
+    assert!(package_matches("acme.billing.v1", "acme.billing.v1"));
+    assert!(!package_matches("acme.billing", "acme.billing.v1"));
+
+    // Nested packages
+    assert!(package_matches("acme.*", "acme.billing.v1"));
+    assert!(package_matches("acme.*.v1", "acme.billing.v1"));
+    assert!(package_matches("acme.*.v1", "acme.billing.internal.v1"));
+    assert!(!package_matches("acme.*.v1", "acme.billing.v2"));
+
+    // Trailing .* only matches sub-packages
+    assert!(package_matches("acme.*", "acme.users"));
+    assert!(!package_matches("acme.*", "acme"));
+    assert!(!package_matches("acme.*", "acmebilling.v1"));
+
+    assert!(package_matches("acme.billing.v*", "acme.billing.v1"));
+    assert!(package_matches("acme.billing.v*", "acme.billing.v2"));
+    assert!(!package_matches("acme.billing.v*", "acme.users.v1"));
+
+    // Literal * matches everything
+    assert!(package_matches("*", "acme.billing.v1"));
+    assert!(package_matches("*", "other"));
+  }
+
+  #[test]
+  fn package_globs() {
+    let code = generate(ValidatorsBuilder::new().packages(["*.v1"]), "other.v1");
+    assert!(code.contains(&validate_attr("other.v1.Other")));
+
+    let code = generate(ValidatorsBuilder::new().packages(["my*"]), "myapp.v1");
+    assert!(code.contains(&validate_attr("myapp.v1.User")));
+
+    let code = generate(ValidatorsBuilder::new().packages(["my*"]), "other.v1");
+    assert!(!code.contains(&validate_attr("other.v1.Other")));
+  }
+
+  #[test]
+  fn unmatched_packages() {
+    let dir = tempfile::tempdir().unwrap();
+    let descriptor_path = dir.path().join("descriptor.bin");
+    write_descriptor_set(
+      &mut Config::new(),
+      &descriptor_path,
+      &proto_files(),
+      &[PROTO_DIR],
+    )
+    .unwrap();
+    let fds_bytes = fs::read(&descriptor_path).unwrap();
+    let pool = DescriptorPool::decode(fds_bytes.as_slice()).unwrap();
+
+    let mut builder = ValidatorsBuilder::new();
+    builder.packages(["myapp.v1", "myapp.v1beta1", "oth*", "othr.*"]);
+    assert_eq!(
+      builder.unmatched_packages(&pool),
+      [
+        "`myapp.v1beta1` (did you mean `myapp.v1`?)",
+        "`othr.*` (did you mean `other.v1`?)"
+      ]
+    );
+
+    // Only warns by default
+    builder
+      .compile_from_fds(&mut Config::new(), &fds_bytes)
+      .unwrap();
+
+    let err = builder
+      .strict_packages(true)
+      .compile_from_fds(&mut Config::new(), &fds_bytes)
+      .unwrap_err();
+    assert!(
+      err
+        .to_string()
+        .contains("`myapp.v1beta1` (did you mean `myapp.v1`?)")
+    );
+
+    assert!(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .strict_packages(true)
+        .compile_from_fds(&mut Config::new(), &fds_bytes)
+        .is_ok()
+    );
+  }
+
+  #[test]
+  fn edit_distances() {
+    assert_eq!(edit_distance("orders.v1", "orders.v1"), 0);
+    assert_eq!(edit_distance("orders.v1", "orders.v1beta1"), 5);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+  }
+
+  #[test]
+  fn all_packages() {
+    let dir = tempfile::tempdir().unwrap();
+    let descriptor_path = dir.path().join("descriptor.bin");
+    write_descriptor_set(
+      &mut Config::new(),
+      &descriptor_path,
+      &proto_files(),
+      &[PROTO_DIR],
+    )
+    .unwrap();
+    let pool = DescriptorPool::decode(fs::read(&descriptor_path).unwrap().as_slice()).unwrap();
+
+    let builder = ValidatorsBuilder::new();
+    let is_selected = |name: &str| builder.is_selected(&pool.get_message_by_name(name).unwrap());
+
+    assert!(is_selected("myapp.v1.User"));
+    assert!(is_selected("other.v1.Other"));
+    assert!(!is_selected("buf.validate.FieldRules"));
+    assert!(!is_selected("google.protobuf.FieldOptions"));
+
+    let code = generate(&mut ValidatorsBuilder::new(), "other.v1");
+    assert!(code.contains(&validate_attr("other.v1.Other")));
+  }
+
+  #[test]
+  fn disabled_messages() {
+    let code = generate(ValidatorsBuilder::new().packages(["myapp.v1"]), "myapp.v1");
+    assert!(!code.contains(&validate_attr("myapp.v1.Disabled")));
+    assert!(!code.contains(r#"protobuf_validate_oneof("myapp.v1.Disabled.choice")"#));
+    assert!(code.contains(&validate_attr("myapp.v1.Disabled.Inner")));
+  }
+
+  #[test]
+  fn vendored_protovalidate() {
+    // This directory does not contain buf/validate/validate.proto
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let proto_files = [PathBuf::from(proto_dir).join("vendored/v1/vendored.proto")];
+
+    let out_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::new();
+    config.out_dir(out_dir.path());
+
+    ValidatorsBuilder::new()
+      .proto_files(&proto_files)
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .out_dir(out_dir.path())
+      .compile(&mut config)
+      .unwrap();
+
+    config.compile_protos(&proto_files, &[proto_dir]).unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("vendored.v1.rs")).unwrap();
+    assert!(code.contains(&validate_attr("vendored.v1.Vendored")));
+
+    assert!(
+      crate::vendored_protovalidate_include_path()
+        .join("buf/validate/validate.proto")
+        .is_file()
+    );
+  }
+
+  // The packages nested inside of buf.validate, like the ones of the protovalidate conformance tests
+  #[test]
+  fn nested_protovalidate_packages() {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let proto_files = [PathBuf::from(proto_dir).join("buf/validate/nested/v1/nested.proto")];
+
+    let out_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::new();
+    config.out_dir(out_dir.path());
+
+    ValidatorsBuilder::new()
+      .proto_files(&proto_files)
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .out_dir(out_dir.path())
+      .compile(&mut config)
+      .unwrap();
+
+    config.compile_protos(&proto_files, &[proto_dir]).unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("buf.validate.nested.v1.rs")).unwrap();
+    assert!(code.contains(&validate_attr("buf.validate.nested.v1.Case")));
+    assert!(code.contains("pub rules: ::core::option::Option<::protocheck::types::protovalidate::FieldRules>"));
+
+    // The types of protovalidate itself still come from protocheck
+    assert!(!out_dir.path().join("buf.validate.rs").exists());
+  }
+
+  // Like in a standalone binary, where OUT_DIR is not set
+  #[test]
+  fn explicit_out_dir() {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let proto_files = [PathBuf::from(proto_dir).join("vendored/v1/vendored.proto")];
+
+    let out_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::new();
+
+    ValidatorsBuilder::new()
+      .proto_files(&proto_files)
+      .include_paths([proto_dir])
+      .out_dir(out_dir.path())
+      .compile(&mut config)
+      .unwrap();
+
+    config.compile_protos(&proto_files, &[proto_dir]).unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("vendored.v1.rs")).unwrap();
+    assert!(code.contains(&validate_attr("vendored.v1.Vendored")));
+    assert!(
+      out_dir
+        .path()
+        .join("protocheck_vendored_protos/buf/validate/validate.proto")
+        .is_file()
+    );
+
+    let err = ValidatorsBuilder::new()
+      .proto_files(&proto_files)
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .compile(&mut Config::new())
+      .unwrap_err();
+
+    assert!(err.to_string().starts_with("None of the include paths contain buf/validate/validate.proto"));
+
+    // The intermediary descriptor is not written to the system's temporary directory either
+    let err = ValidatorsBuilder::new()
+      .proto_files([PathBuf::from(PROTO_DIR).join("myapp/v1/optional.proto")])
+      .include_paths([PROTO_DIR])
+      .compile(&mut Config::new())
+      .unwrap_err();
+
+    assert!(err.to_string().starts_with("The intermediary descriptor is written to OUT_DIR, but OUT_DIR is not set."));
+
+    // The single pass does not fall back to the system's temporary directory either
+    let err = compile_protos_with_validators_single_pass(
+      &mut Config::new(),
+      &proto_files,
+      &[proto_dir],
+      out_dir.path().join("descriptor.bin"),
+      &["vendored.v1"],
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().contains("OUT_DIR is not set"));
+  }
+
+  #[test]
+  fn embedded_descriptor_set() {
+    let dir = tempfile::tempdir().unwrap();
+    let descriptor_path = dir.path().join("embedded.bin");
+
+    generate(
+      ValidatorsBuilder::new()
+        .packages(["myapp.v1"])
+        .descriptor_set_path(&descriptor_path)
+        .embed_descriptor_set(true),
+      "myapp.v1",
+    );
+
+    let pool = DescriptorPool::decode(fs::read(&descriptor_path).unwrap().as_slice()).unwrap();
+    assert!(pool.get_message_by_name("myapp.v1.User").is_some());
+    assert!(
+      pool
+        .get_extension_by_name(VALIDATE_EXT_FIELD_PATH)
+        .is_some()
+    );
+  }
+
+  #[test]
+  fn unique_temp_descriptor_paths() {
+    let dir = env::temp_dir();
+    assert_ne!(temp_descriptor_path(&dir), temp_descriptor_path(&dir));
+  }
+
+  // Fails after the descriptor has been written, as it does not contain the protovalidate extensions
+  fn compile_without_protovalidate(builder: &mut ValidatorsBuilder) -> PathBuf {
+    let out_dir = tempfile::tempdir().unwrap().keep();
+    let descriptor_path = out_dir.join("descriptor.bin");
+
+    builder
+      .proto_files([PathBuf::from(PROTO_DIR).join("plain/v1/plain.proto")])
+      .include_paths([PROTO_DIR])
+      .descriptor_set_path(&descriptor_path)
+      .compile(&mut Config::new())
+      .unwrap_err();
+
+    descriptor_path
+  }
+
+  #[test]
+  fn descriptor_removed_on_error() {
+    let descriptor_path = compile_without_protovalidate(&mut ValidatorsBuilder::new());
+    assert!(!descriptor_path.exists());
+    fs::remove_dir_all(descriptor_path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn descriptor_kept_on_error() {
+    let descriptor_path =
+      compile_without_protovalidate(ValidatorsBuilder::new().keep_descriptor_on_error(true));
+    assert!(descriptor_path.exists());
+    fs::remove_dir_all(descriptor_path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn missing_protovalidate() {
+    let err = ValidatorsBuilder::new()
+      .packages(["myapp.v1"])
+      .compile_from_fds(&mut Config::new(), &[])
+      .unwrap_err();
+
+    assert!(err.to_string().contains(VALIDATE_EXT_FIELD_PATH));
+  }
+
+  // Uses the bundled protovalidate definitions, which contain all of the rules
+  fn compile_report(builder: &mut ValidatorsBuilder) -> Result<BuildReport, Box<dyn Error>> {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let out_dir = tempfile::tempdir().unwrap();
+
+    builder
+      .proto_files([PathBuf::from(proto_dir).join("report/v1/report.proto")])
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .out_dir(out_dir.path())
+      .compile(&mut Config::new())
+  }
+
+  // The predefined rules are only supported with the CEL validators
+  #[cfg(feature = "cel")]
+  #[test]
+  fn build_report() {
+    let report = compile_report(&mut ValidatorsBuilder::new()).unwrap();
+
+    assert_eq!(
+      report.messages,
+      [MessageReport {
+        name: "report.v1.Report".to_string(),
+        file: "report/v1/report.proto".to_string(),
+        rules: vec!["cel".to_string()],
+        fields: vec![
+          FieldReport {
+            name: "name".to_string(),
+            rules: vec![
+              "string.min_len".to_string(),
+              "string.max_len".to_string(),
+              "required".to_string(),
+            ],
+          },
+          FieldReport {
+            name: "tags".to_string(),
+            rules: vec!["repeated.items.string.min_len".to_string()],
+          },
+          FieldReport {
+            name: "ticker".to_string(),
+            rules: vec!["string.[report.v1.is_ticker]".to_string()],
+          },
+        ],
+      }]
+    );
+
+    let unsupported: Vec<(Option<&str>, &str)> = report
+      .unsupported_rules
+      .iter()
+      .map(|rule| (rule.field.as_deref(), rule.rule.as_str()))
+      .collect();
+    assert_eq!(
+      unsupported,
+      [
+        (None, "oneof"),
+        (Some("id"), "string.ulid"),
+        (Some("mask"), "field_mask.in"),
+        (
+          Some("tickers"),
+          "repeated.items.string.[report.v1.is_ticker]"
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn deny_unsupported_rules() {
+    let err = compile_report(ValidatorsBuilder::new().deny_unsupported_rules(true)).unwrap_err();
+
+    assert!(
+      err
+        .to_string()
+        .contains("`string.ulid` in the field report.v1.Report.id (report/v1/report.proto)")
+    );
+  }
+
+  #[cfg(feature = "cel")]
+  #[test]
+  fn predefined_rules() {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let proto_files = [PathBuf::from(proto_dir).join("report/v1/report.proto")];
+
+    let out_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::new();
+    config.out_dir(out_dir.path());
+
+    ValidatorsBuilder::new()
+      .proto_files(&proto_files)
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .out_dir(out_dir.path())
+      .compile(&mut config)
+      .unwrap();
+
+    config.compile_protos(&proto_files, &[proto_dir]).unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("report.v1.rs")).unwrap();
+
+    // The predefined rules of the list items are not supported
+    assert_eq!(code.matches("#[protocheck(").count(), 1);
+    assert_eq!(
+      code
+        .matches(r#"rule = "string.[report.v1.is_ticker]""#)
+        .count(),
+      1
+    );
+    assert!(code.contains(r#"expression = "!rule || this.matches('^[A-Z]{1,5}$')""#));
+  }
+
+  #[cfg(feature = "cel")]
+  #[test]
+  fn invalid_cel_expressions() {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let err = ValidatorsBuilder::new()
+      .proto_files([PathBuf::from(proto_dir).join("cel/v1/invalid.proto")])
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .out_dir(out_dir.path())
+      .compile(&mut Config::new())
+      .unwrap_err()
+      .to_string();
+
+    assert!(err.contains(
+      "Rule `invalid.missing_field` of the message cel.v1.Invalid (cel/v1/invalid.proto) failed execution: `this.nam != ''`"
+    ));
+    assert!(err.contains(
+      "Rule `invalid.syntax` of the field cel.v1.Invalid.name (cel/v1/invalid.proto) failed to compile:\nERROR: <input>:1:14:"
+    ));
+    assert!(err.contains("| this.size() >\n| .............^"));
+    assert!(err.contains(
+      "Rule `invalid.not_bool` of the field cel.v1.Invalid.tags (cel/v1/invalid.proto) does not return a boolean or a string: `this.size()` returned int"
+    ));
+    // `rules` holds the rules of the type of the field
+    assert!(err.contains(
+      "Rule `invalid.unknown_rule` of the field cel.v1.Invalid.label (cel/v1/invalid.proto) failed execution: `this.size() <= int(rules.max_size)`"
+    ));
+    // The expressions can also return the error message, built with `format`
+    assert!(err.contains(
+      "Rule `invalid.format` of the field cel.v1.Invalid.message (cel/v1/invalid.proto) failed execution: `'%d characters'.format([this])`"
+    ));
+    assert!(!err.contains("`valid`"));
+  }
+
+  #[test]
+  fn invalid_patterns() {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let err = ValidatorsBuilder::new()
+      .proto_files([PathBuf::from(proto_dir).join("patterns/v1/patterns.proto")])
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .out_dir(out_dir.path())
+      .compile(&mut Config::new())
+      .unwrap_err()
+      .to_string();
+
+    assert!(err.contains(
+      "The `string.pattern` rule of the field patterns.v1.Patterns.unclosed (patterns/v1/patterns.proto) has an invalid pattern `^[a-z`:\nregex parse error:"
+    ));
+    assert!(err.contains("error: unclosed character class"));
+    assert!(err.contains(
+      "The `string.pattern` rule of the field patterns.v1.Patterns.backreference (patterns/v1/patterns.proto) uses an unsupported construct (backreferences)"
+    ));
+    assert!(err.contains(
+      "The `bytes.pattern` rule of the field patterns.v1.Patterns.look_ahead (patterns/v1/patterns.proto) uses an unsupported construct (look-around assertions)"
+    ));
+    assert!(err.contains(
+      "The `repeated.items.string.pattern` rule of the field patterns.v1.Patterns.tags (patterns/v1/patterns.proto) has an invalid pattern `^[a-z`"
+    ));
+    assert!(!err.contains("labels"));
+    assert!(!err.contains("Patterns.valid"));
+  }
+
+  #[test]
+  fn editions_files() {
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let err = ValidatorsBuilder::new()
+      .proto_files([PathBuf::from(PROTO_DIR).join("editions/v1/editions.proto")])
+      .include_paths([PROTO_DIR])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .compile(&mut Config::new())
+      .unwrap_err();
+
+    // Protox rejects these files on its own, before they reach the check
+    #[cfg(feature = "protox")]
+    assert!(err.to_string().contains("editions/v1/editions.proto"), "{err}");
+
+    #[cfg(not(feature = "protox"))]
+    assert!(
+      err
+        .to_string()
+        .starts_with("The following files use Protobuf Editions, which are not supported yet by prost-build and prost-reflect: editions/v1/editions.proto.")
+    );
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn report_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let report_path = dir.path().join("report.json");
+
+    let report = compile_report(ValidatorsBuilder::new().report_path(&report_path)).unwrap();
+
+    let written: BuildReport =
+      serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+    assert_eq!(written, report);
+  }
+
+  // Set UPDATE_GOLDEN=1 to write the expected manifest again after changing the format
+  #[cfg(feature = "serde")]
+  #[test]
+  fn constraints_manifest() {
+    let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/proto_vendored");
+    let golden_path = concat!(
+      env!("CARGO_MANIFEST_DIR"),
+      "/tests/golden/constraints_manifest.json"
+    );
+    let out_dir = tempfile::tempdir().unwrap();
+    let manifest_path = out_dir.path().join("constraints.json");
+
+    ValidatorsBuilder::new()
+      .proto_files([PathBuf::from(proto_dir).join("manifest/v1/manifest.proto")])
+      .include_paths([proto_dir])
+      .descriptor_set_path(out_dir.path().join("descriptor.bin"))
+      .out_dir(out_dir.path())
+      .constraints_manifest_path(&manifest_path)
+      .compile(&mut Config::new())
+      .unwrap();
+
+    let manifest = fs::read_to_string(&manifest_path).unwrap();
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+      fs::write(golden_path, &manifest).unwrap();
+    }
+
+    assert_eq!(manifest, fs::read_to_string(golden_path).unwrap());
+
+    let parsed: crate::ConstraintsManifest = serde_json::from_str(&manifest).unwrap();
+    assert_eq!(
+      parsed.fields["manifest.v1.Account.handle"].rules["string.pattern"],
+      "^[a-z]+$"
+    );
+  }
+
+  #[cfg(feature = "tonic")]
+  #[test]
+  fn tonic_services() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let proto_files = [PathBuf::from(PROTO_DIR).join("myapp/v1/service.proto")];
+
+    crate::compile_protos_with_validators_tonic(
+      tonic_prost_build::configure().out_dir(out_dir.path()),
+      &proto_files,
+      &[PROTO_DIR],
+      &["myapp.v1"],
+    )
+    .unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("myapp.v1.rs")).unwrap();
+    assert!(code.contains(&validate_attr("myapp.v1.CreateUserRequest")));
+    assert!(code.contains(&validate_attr("myapp.v1.CreateUserResponse")));
+    assert!(code.contains("pub mod user_service_server"));
+    assert!(code.contains("pub mod user_service_client"));
+  }
+
+  #[cfg(feature = "tonic")]
+  #[test]
+  fn validating_codec() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let proto_files = [PathBuf::from(PROTO_DIR).join("myapp/v1/service.proto")];
+
+    crate::compile_protos_with_validators_tonic(
+      tonic_prost_build::configure()
+        .codec_path(crate::VALIDATING_CODEC_PATH)
+        .out_dir(out_dir.path()),
+      &proto_files,
+      &[PROTO_DIR],
+      &["myapp.v1"],
+    )
+    .unwrap();
+
+    let code = fs::read_to_string(out_dir.path().join("myapp.v1.rs")).unwrap();
+    assert!(code.contains("::protocheck::tonic::ValidatingCodec::default()"));
+    assert!(!code.contains("tonic_prost::ProstCodec"));
+  }
+}
@@ -0,0 +1,248 @@
+use std::{collections::HashMap, sync::Arc};
+
+use cel::{Program, Value as CelValue, objects::Key as CelKey};
+use prost_reflect::{DynamicMessage, MapKey, Value};
+use protocheck_core::validators::cel::cel_context;
+
+use crate::*;
+
+// Same limit as the one used by the proc macro when it creates the test values
+const MAX_RECURSION_DEPTH: usize = 10;
+
+// The field names that prost turns into raw identifiers or suffixes with an underscore, which are also the keys used by the Cel conversions
+const RUST_KEYWORDS_RENAMED_WITH_R: &[&str] = &[
+  "as", "break", "const", "continue", "else", "enum", "false", "fn", "for", "if", "impl", "in",
+  "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct",
+  "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+  "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "async",
+  "await",
+];
+const RUST_KEYWORDS_RENAMED_WITH_UNDERSCORE: &[&str] = &["crate", "extern", "super", "self"];
+
+// The Json values, whose content (and so the keys of a Struct) is only known during the validation
+const JSON_VALUE_TYPES: &[&str] = &[
+  "google.protobuf.Struct",
+  "google.protobuf.Value",
+  "google.protobuf.ListValue",
+];
+
+// Compiles the Cel rules of a message and of its fields, and executes them with the default values of their target
+// and with the variables available to the validators (`rules` and `now`), so that the errors are caught before the
+// code is generated. The expressions over Json values are only compiled, as they can access keys that are not in the
+// default values (such as `this.metadata.labels['env']`). Returns a description of each invalid expression
+pub(crate) fn check_cel_rules(
+  message_desc: &MessageDescriptor,
+  field_ext: &ExtensionDescriptor,
+  message_ext: Option<&ExtensionDescriptor>,
+  is_excluded_field: impl Fn(&FieldDescriptor) -> bool,
+) -> Vec<String> {
+  let mut errors = Vec::new();
+  let file = message_desc.parent_file().name().to_string();
+
+  if let Some(ext) = message_ext
+    && let Some(rules) = message_desc.options().get_extension(ext).as_message()
+  {
+    let this = cel_value(
+      &Value::Message(DynamicMessage::new(message_desc.clone())),
+      0,
+    );
+    let rules_value = cel_value(&Value::Message(rules.clone()), 0);
+    let has_json_values = contains_json_values(message_desc, 0);
+
+    for (id, expression) in cel_rules(rules) {
+      if let Err(e) = check_expression(&expression, &this, &rules_value, has_json_values) {
+        errors.push(format!(
+          "Rule `{}` of the message {} ({}) {}",
+          id,
+          message_desc.full_name(),
+          file,
+          e
+        ));
+      }
+    }
+  }
+
+  for field in message_desc
+    .fields()
+    .filter(|field| !is_excluded_field(field))
+  {
+    let options = field.options();
+    let rules = options.get_extension(field_ext);
+    let Some(rules) = rules.as_message() else {
+      continue;
+    };
+
+    let this = cel_value(&Value::default_value_for_field(&field), 0);
+    let rules_value = type_rules_value(rules);
+    let has_json_values = match field.kind() {
+      Kind::Message(field_message) => contains_json_values(&field_message, 0),
+      _ => false,
+    };
+
+    for (id, expression) in cel_rules(rules) {
+      if let Err(e) = check_expression(&expression, &this, &rules_value, has_json_values) {
+        errors.push(format!(
+          "Rule `{}` of the field {} ({}) {}",
+          id,
+          field.full_name(),
+          file,
+          e
+        ));
+      }
+    }
+  }
+
+  errors
+}
+
+// Returns the id and the expression of each rule in the `cel` list of the FieldRules or MessageRules
+fn cel_rules(rules: &DynamicMessage) -> Vec<(String, String)> {
+  let Some(cel_rules) = rules.get_field_by_name("cel") else {
+    return Vec::new();
+  };
+
+  cel_rules
+    .as_list()
+    .unwrap_or_default()
+    .iter()
+    .filter_map(Value::as_message)
+    .map(|rule| {
+      let string_field = |name: &str| {
+        rule
+          .get_field_by_name(name)
+          .and_then(|value| value.as_str().map(str::to_string))
+          .unwrap_or_default()
+      };
+
+      (string_field("id"), string_field("expression"))
+    })
+    .collect()
+}
+
+// The rules of the type of the field (such as StringRules) in the FieldRules, which are available as `rules`
+fn type_rules_value(rules: &DynamicMessage) -> CelValue {
+  rules
+    .descriptor()
+    .oneofs()
+    .find(|oneof| oneof.name() == "type")
+    .and_then(|oneof| oneof.fields().find(|field| rules.has_field(field)))
+    .map_or_else(
+      || CelValue::Map(HashMap::<CelKey, CelValue>::new().into()),
+      |field| cel_value(&rules.get_field(&field), 0),
+    )
+}
+
+// Whether the message is a Json value or has one in its fields, including the maps and the nested messages
+fn contains_json_values(message_desc: &MessageDescriptor, depth: usize) -> bool {
+  JSON_VALUE_TYPES.contains(&message_desc.full_name())
+    || (depth < MAX_RECURSION_DEPTH
+      && message_desc.fields().any(|field| match field.kind() {
+        Kind::Message(field_message) => contains_json_values(&field_message, depth + 1),
+        _ => false,
+      }))
+}
+
+fn check_expression(
+  expression: &str,
+  this: &CelValue,
+  rules: &CelValue,
+  has_json_values: bool,
+) -> Result<(), String> {
+  let program = Program::compile(expression).map_err(|e| format!("failed to compile:\n{}", e))?;
+
+  let mut context = cel_context();
+  context.add_variable_from_value("this", this.clone());
+  context.add_variable_from_value("rules", rules.clone());
+  context.add_variable_from_value("now", CelValue::Timestamp(cel::Timestamp::default().into()));
+
+  match program.execute(&context) {
+    Ok(CelValue::Bool(_) | CelValue::String(_)) => Ok(()),
+    Ok(result) => Err(format!(
+      "does not return a boolean or a string: `{}` returned {}",
+      expression,
+      result.type_of()
+    )),
+    Err(_) if has_json_values => Ok(()),
+    Err(e) => Err(format!("failed execution: `{}`: {}", expression, e)),
+  }
+}
+
+// Converts the values into Cel values in the same way as the conversions of the validators.
+// Oneof fields are left out unless they are set, which is only the case in the rules
+fn cel_value(value: &Value, depth: usize) -> CelValue {
+  match value {
+    Value::Bool(v) => CelValue::Bool(*v),
+    Value::I32(v) => CelValue::Int(i64::from(*v)),
+    Value::I64(v) => CelValue::Int(*v),
+    Value::EnumNumber(v) => CelValue::Int(i64::from(*v)),
+    Value::U32(v) => CelValue::UInt(u64::from(*v)),
+    Value::U64(v) => CelValue::UInt(*v),
+    Value::F32(v) => CelValue::Float(f64::from(*v)),
+    Value::F64(v) => CelValue::Float(*v),
+    Value::String(v) => CelValue::String(Arc::new(v.clone())),
+    Value::Bytes(v) => CelValue::Bytes(Arc::new(v.to_vec())),
+    Value::List(values) => CelValue::List(Arc::new(
+      values.iter().map(|v| cel_value(v, depth + 1)).collect(),
+    )),
+    Value::Map(values) => {
+      let map: HashMap<CelKey, CelValue> = values
+        .iter()
+        .map(|(key, v)| (cel_key(key), cel_value(v, depth + 1)))
+        .collect();
+
+      CelValue::Map(map.into())
+    }
+    Value::Message(message) => match message.descriptor().full_name() {
+      "google.protobuf.Timestamp" => CelValue::Timestamp(cel::Timestamp::default().into()),
+      "google.protobuf.Duration" => CelValue::Duration(chrono::Duration::zero()),
+      "google.protobuf.FieldMask" => {
+        let map: HashMap<CelKey, CelValue> =
+          HashMap::from([("paths".into(), CelValue::List(Arc::new(Vec::new())))]);
+
+        CelValue::Map(map.into())
+      }
+      "google.protobuf.Struct" => CelValue::Map(HashMap::<CelKey, CelValue>::new().into()),
+      "google.protobuf.ListValue" => CelValue::List(Arc::new(Vec::new())),
+      "google.protobuf.Value" => CelValue::Null,
+      _ => {
+        let mut map: HashMap<CelKey, CelValue> = HashMap::new();
+
+        if depth < MAX_RECURSION_DEPTH {
+          for field in message
+            .descriptor()
+            .fields()
+            .filter(|field| field.containing_oneof().is_none() || message.has_field(field))
+          {
+            map.insert(
+              rust_field_name(field.name()).into(),
+              cel_value(&message.get_field(&field), depth + 1),
+            );
+          }
+        }
+
+        CelValue::Map(map.into())
+      }
+    },
+  }
+}
+
+fn rust_field_name(name: &str) -> String {
+  if RUST_KEYWORDS_RENAMED_WITH_UNDERSCORE.contains(&name) {
+    format!("{}_", name)
+  } else if RUST_KEYWORDS_RENAMED_WITH_R.contains(&name) {
+    format!("r#{}", name)
+  } else {
+    name.to_string()
+  }
+}
+
+fn cel_key(key: &MapKey) -> CelKey {
+  match key {
+    MapKey::Bool(v) => CelKey::Bool(*v),
+    MapKey::I32(v) => CelKey::Int(i64::from(*v)),
+    MapKey::I64(v) => CelKey::Int(*v),
+    MapKey::U32(v) => CelKey::Uint(u64::from(*v)),
+    MapKey::U64(v) => CelKey::Uint(*v),
+    MapKey::String(v) => CelKey::String(Arc::new(v.clone())),
+  }
+}
@@ -3,150 +3,226 @@
 use std::{
   env,
   error::Error,
-  fs,
-  io::{self, Read},
+  fmt::Display,
+  fs, io,
   path::{Path, PathBuf},
 };
 
 use prost_build::Config;
+use prost_reflect::{
+  DescriptorPool, ExtensionDescriptor, FieldDescriptor, Kind, MessageDescriptor, OneofDescriptor,
+  ReflectMessage,
+};
+
+mod builder;
+pub use builder::{ValidatorsBuilder, WellKnownTypes};
+use builder::{read_descriptor_set, write_descriptor_set};
+
+#[cfg(feature = "cel")]
+mod cel_check;
+
+#[cfg(feature = "serde")]
+mod manifest;
+#[cfg(feature = "serde")]
+pub use manifest::{
+  CelConstraint, ConstraintsManifest, FieldConstraints, MessageConstraints, OneofConstraint,
+};
+
+mod patterns;
+use patterns::PatternsChecker;
 
-static VALIDATE_EXT_FIELD_PATH : &str = "buf.validate.field";
+mod predefined;
+use predefined::{is_collection_rules, predefined_rule_attributes};
+
+mod proto_files;
+pub use proto_files::{ProtoFilesCollector, derive_include_paths};
+
+mod report;
+pub use report::{BuildReport, FieldReport, MessageReport, UnsupportedRule};
+
+mod vendored;
+use vendored::vendored_include_path_if_missing;
+pub use vendored::vendored_protovalidate_include_path;
+
+static VALIDATE_EXT_FIELD_PATH: &str = "buf.validate.field";
+static VALIDATE_EXT_MESSAGE_PATH: &str = "buf.validate.message";
+static VALIDATE_EXT_ONEOF_PATH: &str = "buf.validate.oneof";
+static VALIDATE_EXT_PREDEFINED_PATH: &str = "buf.validate.predefined";
 
 /// This function compiles the proto_files in the list, it creates an intermediary file descriptor and it uses it to extract information about the messages, enums and oneofs which can later be used to generate the validation logic with protocheck.
+///
+/// The intermediary descriptor is created with a descriptor-only protoc run, so no code is generated at this stage. The caller is still responsible for calling [`Config::compile_protos`] afterwards, which means that protoc runs twice in total. To run it only once, use [`compile_protos_with_validators_single_pass`].
+///
+/// If `proto_include_paths` is empty, the include paths are derived from the proto files with [`derive_include_paths`].
+///
+/// Returns a [`BuildReport`] with the rules found in the selected messages, and emits a cargo warning for each rule that is not supported by protocheck.
+///
+/// For more configuration options, use the [`ValidatorsBuilder`].
 pub fn compile_protos_with_validators(
   config: &mut Config,
   proto_files: &[impl AsRef<Path>],
   proto_include_paths: &[impl AsRef<Path>],
   packages: &[&str],
-) -> Result<(), Box<dyn Error>> {
-  let out_dir = env::var("OUT_DIR")
-    .map(PathBuf::from)
-    .unwrap_or_else(|_| env::temp_dir());
+) -> Result<BuildReport, Box<dyn Error>> {
+  ValidatorsBuilder::new()
+    .proto_files(proto_files)
+    .include_paths(proto_include_paths)
+    .packages(packages)
+    .compile(config)
+}
 
-  let temp_descriptor_path = out_dir.join("temp_file_descriptor_set_for_protocheck.bin");
+/// Compiles the protos and adds the validators to them with a single protoc invocation.
+///
+/// Protoc is only used to create the file descriptor set at `descriptor_path` (using the protoc settings of the given config). After the validation attributes have been added, the config generates the code from that same descriptor with [`Config::skip_protoc_run`], so there is no need to call [`Config::compile_protos`] afterwards.
+///
+/// The descriptor is not removed at the end, as it is the one that should be passed to `protocheck` via the `PROTO_DESCRIPTOR_SET` env variable.
+///
+/// If none of the include paths contain `buf/validate/validate.proto`, the bundled copy is written to `OUT_DIR`, so an error is returned when it is not set.
+pub fn compile_protos_with_validators_single_pass(
+  config: &mut Config,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+  descriptor_path: impl AsRef<Path>,
+  packages: &[&str],
+) -> Result<BuildReport, Box<dyn Error>> {
+  let descriptor_path = descriptor_path.as_ref();
+
+  for path in proto_files
+    .iter()
+    .map(AsRef::as_ref)
+    .chain(proto_include_paths.iter().map(AsRef::as_ref))
   {
-    let mut temp_config = prost_build::Config::new();
-    temp_config.file_descriptor_set_path(&temp_descriptor_path);
-    temp_config.out_dir(&out_dir);
-    temp_config.compile_protos(proto_files, proto_include_paths)?;
+    rerun_if_changed(path);
   }
 
-  let mut fds_file = std::fs::File::open(&temp_descriptor_path)?;
-  let mut fds_bytes = Vec::new();
-  fds_file.read_to_end(&mut fds_bytes)?;
-  
-  // read pool directly from bytes to access custom options
-  // correctly. See: https://github.com/andrewhickman/prost-reflect/issues/21
-  let pool = prost_reflect::DescriptorPool::decode(fds_bytes.as_slice())?;
-
-  let protovalidate_field_option = pool.get_extension_by_name(VALIDATE_EXT_FIELD_PATH);
-   
-  for message_desc in pool.all_messages() {
-    let message_name = message_desc.full_name();
-  
-
-    // -------------->
-    // Add protocheck(ignore_field) attribute to fields that are marked by 
-    // buf.validate.field = ALWAYS_IGNORE
-    if let Some(validate_option) = &protovalidate_field_option {
-        for field in message_desc.fields() {
-          let options = field.options();
-          let validate_spec = options.get_extension(validate_option);
-          let ignore_field = match validate_spec.as_message() {
-                        None => false,
-                        Some(msg) => match msg
-                            .fields()
-                            .find(|f| f.0.full_name() == "buf.validate.FieldRules.ignore")
-                            .and_then(|f| f.1.as_enum_number()) {
-                            None => false,
-                            Some(v) => v == 3 // ALWAYS_IGNORE = 3
-                        }
-                    };
-          if ignore_field {
-            config.field_attribute(
-                        field.full_name(), 
-                        r#"#[protocheck(ignore_field)]"#
-            );
-          }
-        }
-    }
-    // <---------------- 
- 
-    if packages.contains(&message_desc.package_name()) {
-      let attribute_str = format!(
-        r#"#[::protocheck::macros::protobuf_validate("{}")]"#,
-        message_name
-      );
-      config.message_attribute(message_name, &attribute_str);
-
-      for oneof in message_desc.oneofs() {
-        let oneof_name = oneof.full_name();
-        config.type_attribute(
-          oneof_name,
-          format!(
-            r#"#[::protocheck::macros::protobuf_validate_oneof("{}")]"#,
-            oneof_name
-          ),
-        );
-      }
-    }
-  }
+  let mut include_paths: Vec<PathBuf> = proto_include_paths
+    .iter()
+    .map(|path| path.as_ref().to_path_buf())
+    .collect();
+  // Like in ValidatorsBuilder::compile, the vendored protos outlive this call, so they are not written to the system's temporary directory
+  let out_dir = env::var_os("OUT_DIR").map(PathBuf::from);
+  include_paths.extend(vendored_include_path_if_missing(
+    proto_include_paths,
+    out_dir.as_deref(),
+  )?);
+
+  write_descriptor_set(config, descriptor_path, proto_files, &include_paths)?;
+
+  // The descriptor is an output here, so it's read directly to avoid emitting a rerun directive for it
+  let fds_bytes = read_descriptor_set(descriptor_path)?;
+
+  let report = compile_protos_with_validators_from_fds(config, &fds_bytes, packages)?;
 
-  config.extern_path(".buf.validate", "::protocheck::types::protovalidate");
   config
-    .extern_path(".google.protobuf", "::protocheck::types")
-    .compile_well_known_types();
+    .file_descriptor_set_path(descriptor_path)
+    .skip_protoc_run()
+    .compile_protos(proto_files, &include_paths)?;
+
+  Ok(report)
+}
+
+/// Like [`compile_protos_with_validators`], but it uses an already serialized `FileDescriptorSet` (such as one produced by `buf build -o descriptor.bin` or by an earlier prost-build invocation) instead of compiling the protos to create one.
+///
+/// The descriptor set must contain the `buf.validate` definitions, so it has to be built with the imports included.
+pub fn compile_protos_with_validators_from_fds(
+  config: &mut Config,
+  fds_bytes: &[u8],
+  packages: &[&str],
+) -> Result<BuildReport, Box<dyn Error>> {
+  ValidatorsBuilder::new()
+    .packages(packages)
+    .compile_from_fds(config, fds_bytes)
+}
+
+/// Like [`compile_protos_with_validators_from_fds`], but it reads the serialized `FileDescriptorSet` from the given path.
+pub fn compile_protos_with_validators_from_fds_path(
+  config: &mut Config,
+  fds_path: impl AsRef<Path>,
+  packages: &[&str],
+) -> Result<BuildReport, Box<dyn Error>> {
+  let fds_path = fds_path.as_ref();
 
-  std::fs::remove_file(&temp_descriptor_path)?;
+  rerun_if_changed(fds_path);
 
-  Ok(())
+  let fds_bytes = read_descriptor_set(fds_path)?;
+
+  compile_protos_with_validators_from_fds(config, &fds_bytes, packages)
 }
 
-/// A helper to use when gathering the names of proto files to pass to [`prost_build::Config::compile_protos`].
-/// Recursively collects all .proto files in a given directory and its subdirectories.
+/// The path of the codec of protocheck that validates the decoded messages, to pass to [`tonic_prost_build::Builder::codec_path`].
+///
+/// The requests of the generated services are then validated before they reach the handlers (see the `protocheck::tonic` module for the details). The clients use the same codec to decode the responses, so they can only be generated with it if the responses have the validators too, or else they should be generated separately, without this codec.
+#[cfg(feature = "tonic")]
+pub const VALIDATING_CODEC_PATH: &str = "::protocheck::tonic::ValidatingCodec";
+
+/// Compiles the protos with [`tonic_prost_build`], adding the validators to the messages (including the request and response types of the services) in the selected packages.
+///
+/// The validation attributes are applied to a new [`Config`], which is then passed to [`tonic_prost_build::Builder::compile_with_config`] together with the rest of the builder's settings, so there is no need to compile the protos afterwards.
+#[cfg(feature = "tonic")]
+pub fn compile_protos_with_validators_tonic(
+  builder: tonic_prost_build::Builder,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+  packages: &[&str],
+) -> Result<BuildReport, Box<dyn Error>> {
+  let mut config = Config::new();
+
+  let report =
+    compile_protos_with_validators(&mut config, proto_files, proto_include_paths, packages)?;
+
+  let proto_files: Vec<&Path> = proto_files.iter().map(AsRef::as_ref).collect();
+  let proto_include_paths: Vec<&Path> = proto_include_paths.iter().map(AsRef::as_ref).collect();
+
+  builder.compile_with_config(config, &proto_files, &proto_include_paths)?;
+
+  Ok(report)
+}
+
+/// A helper to use when gathering the paths of the proto files to pass to [`prost_build::Config::compile_protos`].
+/// Recursively collects all .proto files in a given directory and its subdirectories, sorted by path.
+///
+/// For more options, use the [`ProtoFilesCollector`].
+pub fn get_proto_paths_recursive(base_dir: impl Into<PathBuf>) -> io::Result<Vec<PathBuf>> {
+  ProtoFilesCollector::new(base_dir).collect()
+}
+
+/// Like [`get_proto_paths_recursive`], but it returns the paths as strings, so it fails if any of them is not valid Unicode.
+#[deprecated(
+  note = "use `get_proto_paths_recursive`, which does not fail on paths that are not valid Unicode"
+)]
 pub fn get_proto_files_recursive(base_dir: impl Into<PathBuf>) -> io::Result<Vec<String>> {
-  let base_dir: PathBuf = base_dir.into();
-  let mut proto_files = Vec::new();
-
-  if !base_dir.is_dir() {
-    return Err(io::Error::new(
-      io::ErrorKind::InvalidInput,
-      format!("Path {:?} is not a directory.", base_dir),
-    ));
-  }
+  get_proto_paths_recursive(base_dir)?
+    .into_iter()
+    .map(|path| {
+      path.into_os_string().into_string().map_err(|path| {
+        io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!("Path {:?} contains invalid Unicode.", path),
+        )
+      })
+    })
+    .collect()
+}
 
-  // We'll use a helper function to do the actual recursive work
-  // This helps keep the public function's signature clean.
-  collect_proto_files_recursive_helper(base_dir.as_path(), &mut proto_files)?;
+// Emits the cargo:rerun-if-changed directive, but only when running inside of a build script
+pub(crate) fn rerun_if_changed(path: &Path) {
+  if env::var_os("OUT_DIR").is_some() {
+    println!("cargo:rerun-if-changed={}", path.display());
+  }
+}
 
-  Ok(proto_files)
+// Sets an environment variable for the compilation of the crate, but only when running inside of a build script
+pub(crate) fn rustc_env(key: &str, value: impl Display) {
+  if env::var_os("OUT_DIR").is_some() {
+    println!("cargo:rustc-env={key}={value}");
+  }
 }
 
-fn collect_proto_files_recursive_helper(
-  current_dir: &Path,
-  proto_files: &mut Vec<String>,
-) -> io::Result<()> {
-  for entry in fs::read_dir(current_dir)? {
-    let entry = entry?;
-    let path = entry.path();
-
-    if path.is_file() {
-      if path.extension().is_some_and(|ext| ext == "proto") {
-        proto_files.push(
-          path
-            .to_str()
-            .ok_or_else(|| {
-              io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Path {:?} contains invalid Unicode.", path),
-              )
-            })?
-            .to_owned(),
-        );
-      }
-    } else if path.is_dir() {
-      collect_proto_files_recursive_helper(&path, proto_files)?;
-    }
+// Emits a cargo warning inside of a build script, and prints it to stderr otherwise, so that the stdout of the other callers is left alone
+pub(crate) fn cargo_warning(message: impl Display) {
+  if env::var_os("OUT_DIR").is_some() {
+    println!("cargo:warning={message}");
+  } else {
+    eprintln!("warning: {message}");
   }
-  Ok(())
 }
@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use prost_reflect::{DynamicMessage, SerializeOptions, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::{builder::oneof_is_required, report::is_rules_message, *};
+
+/// A description of every rule of the selected messages, written by [`ValidatorsBuilder::constraints_manifest_path`](crate::ValidatorsBuilder::constraints_manifest_path) so that other tools (such as documentation generators) can use it without parsing the proto files.
+///
+/// The rules are identified by their path inside of `buf.validate.FieldRules` or `buf.validate.MessageRules`, like in the [`BuildReport`], and their values use the protobuf JSON format (with the 64 bit integers as numbers), so durations are written as `"1.5s"` and bytes as base64 strings.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintsManifest {
+  /// The rules of the messages, by their fully qualified name, such as `myapp.v1.User`.
+  pub messages: BTreeMap<String, MessageConstraints>,
+  /// The rules of the fields, by their fully qualified name, such as `myapp.v1.User.name`. Only the fields with at least one rule are listed.
+  pub fields: BTreeMap<String, FieldConstraints>,
+}
+
+/// The rules of a message.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessageConstraints {
+  /// The proto file where the message is defined.
+  pub file: String,
+  /// The Cel rules of the message.
+  pub cel: Vec<CelConstraint>,
+  /// The oneofs of the message, followed by the groups of fields defined with the `oneof` rule of the message.
+  pub oneofs: Vec<OneofConstraint>,
+}
+
+/// A oneof, or a group of fields of which at most one can be set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneofConstraint {
+  /// The name of the oneof, or `None` for the groups defined with the `oneof` rule of the message.
+  pub name: Option<String>,
+  /// The names of the fields in the group.
+  pub fields: Vec<String>,
+  /// Whether one of the fields must be set.
+  pub required: bool,
+}
+
+/// The rules of a field.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldConstraints {
+  /// The type of the rules, such as `string` or `repeated`.
+  pub rule_type: Option<String>,
+  pub required: bool,
+  /// The name of the ignore mode, such as `IGNORE_IF_ZERO_VALUE`.
+  pub ignore: Option<String>,
+  /// The values of the rules, by their path (such as `string.min_len`). The Cel rules are listed separately.
+  pub rules: BTreeMap<String, serde_json::Value>,
+  /// The Cel rules of the field, including those of the items of a list, of the keys and values of a map and of the predefined rules.
+  pub cel: Vec<CelConstraint>,
+}
+
+/// A Cel rule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CelConstraint {
+  /// The path of the rule, such as `cel`, `repeated.items.cel` or `string.[acme.is_ticker]` for the predefined rules.
+  pub rule: String,
+  pub id: String,
+  pub message: String,
+  pub expression: String,
+}
+
+impl ConstraintsManifest {
+  /// Writes the manifest as pretty printed JSON.
+  pub fn write_json(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+    fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+    Ok(())
+  }
+
+  // Adds the rules of a message, of its oneofs and of its fields (except for the excluded ones) to the manifest
+  pub(crate) fn add_message(
+    &mut self,
+    message_desc: &MessageDescriptor,
+    extensions: &ManifestExtensions,
+    is_excluded_field: impl Fn(&FieldDescriptor) -> bool,
+  ) -> Result<(), Box<dyn Error>> {
+    let mut message = MessageConstraints {
+      file: message_desc.parent_file().name().to_string(),
+      ..Default::default()
+    };
+
+    for oneof in message_desc.oneofs().filter(|oneof| !oneof.is_synthetic()) {
+      message.oneofs.push(OneofConstraint {
+        name: Some(oneof.name().to_string()),
+        fields: oneof
+          .fields()
+          .map(|field| field.name().to_string())
+          .collect(),
+        required: extensions
+          .oneof
+          .as_ref()
+          .is_some_and(|ext| oneof_is_required(&oneof, ext)),
+      });
+    }
+
+    if let Some(ext) = &extensions.message
+      && let Some(rules) = message_desc.options().get_extension(ext).as_message()
+    {
+      message.cel = cel_constraints(rules, "");
+
+      for group in list_field(rules, "oneof")
+        .iter()
+        .filter_map(Value::as_message)
+      {
+        message.oneofs.push(OneofConstraint {
+          name: None,
+          fields: list_field(group, "fields")
+            .iter()
+            .filter_map(|field| field.as_str().map(str::to_string))
+            .collect(),
+          required: group
+            .get_field_by_name("required")
+            .and_then(|required| required.as_bool())
+            .unwrap_or(false),
+        });
+      }
+    }
+
+    self
+      .messages
+      .insert(message_desc.full_name().to_string(), message);
+
+    for field in message_desc
+      .fields()
+      .filter(|field| !is_excluded_field(field))
+    {
+      let options = field.options();
+
+      if !options.has_extension(&extensions.field) {
+        continue;
+      }
+
+      let rules = options.get_extension(&extensions.field);
+      let Some(rules) = rules.as_message() else {
+        continue;
+      };
+
+      let json = rules.serialize_with_options(
+        serde_json::value::Serializer,
+        &SerializeOptions::new()
+          .use_proto_field_name(true)
+          .stringify_64_bit_integers(false),
+      )?;
+
+      let mut constraints = FieldConstraints {
+        rule_type: rules
+          .fields()
+          .find(|(rules_field, _)| {
+            rules_field
+              .containing_oneof()
+              .is_some_and(|oneof| oneof.name() == "type")
+          })
+          .map(|(rules_field, _)| rules_field.name().to_string()),
+        required: json["required"].as_bool().unwrap_or(false),
+        ignore: json["ignore"].as_str().map(str::to_string),
+        ..Default::default()
+      };
+
+      collect_rules(
+        rules,
+        &json,
+        "",
+        extensions.predefined.as_ref(),
+        &mut constraints,
+      );
+
+      self
+        .fields
+        .insert(field.full_name().to_string(), constraints);
+    }
+
+    Ok(())
+  }
+}
+
+// The extensions of protovalidate used to read the rules. Older versions may not define the ones for messages, oneofs and predefined rules
+pub(crate) struct ManifestExtensions {
+  pub(crate) field: ExtensionDescriptor,
+  pub(crate) message: Option<ExtensionDescriptor>,
+  pub(crate) oneof: Option<ExtensionDescriptor>,
+  pub(crate) predefined: Option<ExtensionDescriptor>,
+}
+
+// Adds the values of the rules that are set, descending into the rules of each type and into the nested FieldRules
+fn collect_rules(
+  rules: &DynamicMessage,
+  json: &serde_json::Value,
+  prefix: &str,
+  predefined_ext: Option<&ExtensionDescriptor>,
+  constraints: &mut FieldConstraints,
+) {
+  for (field, value) in rules.fields() {
+    let path = format!("{}{}", prefix, field.name());
+
+    match value {
+      Value::Message(nested) if is_rules_message(&field) => collect_rules(
+        nested,
+        &json[field.name()],
+        &format!("{}.", path),
+        predefined_ext,
+        constraints,
+      ),
+      Value::List(_) if field.name() == "cel" => {
+        constraints.cel.extend(cel_constraints(rules, prefix));
+      }
+      // These are listed separately for the field itself
+      _ if prefix.is_empty() && matches!(field.name(), "required" | "ignore") => {}
+      _ => {
+        constraints.rules.insert(path, json[field.name()].clone());
+      }
+    }
+  }
+
+  for (ext, _) in rules.extensions() {
+    let path = format!("{}[{}]", prefix, ext.full_name());
+
+    constraints
+      .rules
+      .insert(path.clone(), json[ext.json_name()].clone());
+
+    if let Some(predefined_ext) = predefined_ext
+      && let Some(predefined) = ext.options().get_extension(predefined_ext).as_message()
+    {
+      constraints.cel.extend(
+        cel_constraints(predefined, "")
+          .into_iter()
+          .map(|constraint| CelConstraint {
+            rule: path.clone(),
+            ..constraint
+          }),
+      );
+    }
+  }
+}
+
+// Returns the rules in the `cel` list of the FieldRules, MessageRules or PredefinedRules
+fn cel_constraints(rules: &DynamicMessage, prefix: &str) -> Vec<CelConstraint> {
+  list_field(rules, "cel")
+    .iter()
+    .filter_map(Value::as_message)
+    .map(|rule| {
+      let string_field = |name: &str| {
+        rule
+          .get_field_by_name(name)
+          .and_then(|value| value.as_str().map(str::to_string))
+          .unwrap_or_default()
+      };
+
+      CelConstraint {
+        rule: format!("{}cel", prefix),
+        id: string_field("id"),
+        message: string_field("message"),
+        expression: string_field("expression"),
+      }
+    })
+    .collect()
+}
+
+fn list_field(message: &DynamicMessage, name: &str) -> Vec<Value> {
+  message
+    .get_field_by_name(name)
+    .and_then(|value| value.as_list().map(<[Value]>::to_vec))
+    .unwrap_or_default()
+}
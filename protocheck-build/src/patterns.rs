@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use prost_reflect::{DynamicMessage, Value};
+use regex_syntax::ast::{ErrorKind, parse::Parser};
+
+use crate::*;
+
+// Compiles the `pattern` rules with the regex crate, which is the engine used by the validators.
+// The results are cached, as the same pattern is often reused across many fields
+#[derive(Default)]
+pub(crate) struct PatternsChecker {
+  results: HashMap<(String, bool), Result<(), String>>,
+}
+
+impl PatternsChecker {
+  // Returns a description of each invalid pattern in the fields of the message
+  pub(crate) fn check_message(
+    &mut self,
+    message_desc: &MessageDescriptor,
+    field_ext: &ExtensionDescriptor,
+    is_excluded_field: impl Fn(&FieldDescriptor) -> bool,
+  ) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for field in message_desc
+      .fields()
+      .filter(|field| !is_excluded_field(field))
+    {
+      let options = field.options();
+      let rules = options.get_extension(field_ext);
+      let Some(rules) = rules.as_message() else {
+        continue;
+      };
+
+      let mut patterns = Vec::new();
+      collect_patterns(rules, "", &mut patterns);
+
+      for (rule, pattern, is_bytes) in patterns {
+        let result = self
+          .results
+          .entry((pattern.clone(), is_bytes))
+          .or_insert_with(|| check_pattern(&pattern, is_bytes));
+
+        if let Err(e) = result {
+          errors.push(format!(
+            "The `{}` rule of the field {} ({}) {}",
+            rule,
+            field.full_name(),
+            message_desc.parent_file().name(),
+            e
+          ));
+        }
+      }
+    }
+
+    errors
+  }
+}
+
+// Lists the string and bytes patterns, including those for the items of a list and for the keys and values of a map
+fn collect_patterns(
+  rules: &DynamicMessage,
+  prefix: &str,
+  patterns: &mut Vec<(String, String, bool)>,
+) {
+  for (field, value) in rules.fields() {
+    let Some(nested) = value.as_message() else {
+      continue;
+    };
+
+    let path = format!("{}{}", prefix, field.name());
+
+    match field.name() {
+      "string" | "bytes" => {
+        let Some(pattern_field) = nested.descriptor().get_field_by_name("pattern") else {
+          continue;
+        };
+
+        if !nested.has_field(&pattern_field) {
+          continue;
+        }
+
+        let pattern = match nested.get_field(&pattern_field).as_ref() {
+          Value::String(pattern) => pattern.clone(),
+          Value::Bytes(pattern) => String::from_utf8_lossy(pattern).into_owned(),
+          _ => continue,
+        };
+
+        patterns.push((
+          format!("{}.pattern", path),
+          pattern,
+          field.name() == "bytes",
+        ));
+      }
+      "repeated" | "map" => {
+        for (nested_field, nested_value) in nested.fields() {
+          if let Some(nested_rules) = nested_value.as_message() {
+            collect_patterns(
+              nested_rules,
+              &format!("{}.{}.", path, nested_field.name()),
+              patterns,
+            );
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+fn check_pattern(pattern: &str, is_bytes: bool) -> Result<(), String> {
+  let result = if is_bytes {
+    regex::bytes::Regex::new(pattern).map(|_| ())
+  } else {
+    regex::Regex::new(pattern).map(|_| ())
+  };
+
+  let Err(e) = result else {
+    return Ok(());
+  };
+
+  // The regex crate does not support the constructs that require backtracking
+  let unsupported = Parser::new()
+    .parse(pattern)
+    .err()
+    .and_then(|e| match e.kind() {
+      ErrorKind::UnsupportedBackreference => Some("backreferences"),
+      ErrorKind::UnsupportedLookAround => Some("look-around assertions"),
+      _ => None,
+    });
+
+  match unsupported {
+    Some(construct) => Err(format!(
+      "uses an unsupported construct ({}) in the pattern `{}`, as the regex crate does not support them:\n{}",
+      construct, pattern, e
+    )),
+    None => Err(format!("has an invalid pattern `{}`:\n{}", pattern, e)),
+  }
+}
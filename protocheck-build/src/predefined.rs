@@ -0,0 +1,211 @@
+use prost_reflect::{DynamicMessage, Value};
+
+use crate::*;
+
+// Returns a #[protocheck(predefined(...))] attribute for each Cel rule of the predefined rules that are set
+// in the rules of the field's type, such as (buf.validate.field).string.(acme.is_ticker) = true.
+// The value of the extension is passed as a Cel literal, so that the validator can provide it as the `rule` variable
+pub(crate) fn predefined_rule_attributes(
+  field: &FieldDescriptor,
+  field_rules: &DynamicMessage,
+  predefined_ext: &ExtensionDescriptor,
+) -> Result<Vec<String>, Box<dyn Error>> {
+  let mut attributes = Vec::new();
+
+  for (rules_field, value) in field_rules.fields() {
+    let Value::Message(type_rules) = value else {
+      continue;
+    };
+
+    // The predefined rules of lists and maps, and of their items, keys and values, are not supported yet
+    if rules_field
+      .containing_oneof()
+      .is_none_or(|oneof| oneof.name() != "type")
+      || is_collection_rules(rules_field.name())
+    {
+      continue;
+    }
+
+    for (ext, ext_value) in type_rules.extensions() {
+      let rule_path = format!("{}.[{}]", rules_field.name(), ext.full_name());
+
+      let Some(rule_value) = cel_literal(ext_value) else {
+        return Err(
+          format!(
+            "The value of the predefined rule `{}` in the field {} cannot be used in a Cel expression",
+            rule_path,
+            field.full_name()
+          )
+          .into(),
+        );
+      };
+
+      let options = ext.options();
+      let predefined = options.get_extension(predefined_ext);
+      let Some(cel_rules) = predefined
+        .as_message()
+        .and_then(|predefined| predefined.get_field_by_name("cel"))
+      else {
+        continue;
+      };
+
+      for cel_rule in cel_rules.as_list().unwrap_or_default() {
+        let Some(cel_rule) = cel_rule.as_message() else {
+          continue;
+        };
+
+        let string_field = |name: &str| {
+          cel_rule
+            .get_field_by_name(name)
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+        };
+
+        attributes.push(format!(
+          "#[protocheck(predefined(rule = {:?}, value = {:?}, id = {:?}, message = {:?}, expression = {:?}))]",
+          rule_path,
+          rule_value,
+          string_field("id"),
+          string_field("message"),
+          string_field("expression")
+        ));
+      }
+    }
+  }
+
+  Ok(attributes)
+}
+
+pub(crate) fn is_collection_rules(rules_type: &str) -> bool {
+  matches!(rules_type, "repeated" | "map")
+}
+
+// Converts the value of a predefined rule into the equivalent Cel literal
+fn cel_literal(value: &Value) -> Option<String> {
+  let literal = match value {
+    Value::Bool(v) => v.to_string(),
+    Value::I32(v) => v.to_string(),
+    Value::I64(v) => v.to_string(),
+    Value::EnumNumber(v) => v.to_string(),
+    Value::U32(v) => format!("{}u", v),
+    Value::U64(v) => format!("{}u", v),
+    Value::F32(v) if v.is_finite() => format!("{:?}", f64::from(*v)),
+    Value::F64(v) if v.is_finite() => format!("{:?}", v),
+    Value::String(v) => string_literal(v),
+    Value::Bytes(v) => format!(
+      "b\"{}\"",
+      v.iter()
+        .map(|b| format!("\\x{:02x}", b))
+        .collect::<String>()
+    ),
+    Value::List(values) => format!(
+      "[{}]",
+      values
+        .iter()
+        .map(cel_literal)
+        .collect::<Option<Vec<_>>>()?
+        .join(", ")
+    ),
+    Value::Message(message) => well_known_type_literal(message)?,
+    _ => return None,
+  };
+
+  Some(literal)
+}
+
+fn string_literal(value: &str) -> String {
+  let mut literal = String::from("\"");
+
+  for c in value.chars() {
+    match c {
+      '"' => literal.push_str("\\\""),
+      '\\' => literal.push_str("\\\\"),
+      '\n' => literal.push_str("\\n"),
+      '\r' => literal.push_str("\\r"),
+      '\t' => literal.push_str("\\t"),
+      c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+      c => literal.push(c),
+    }
+  }
+
+  literal.push('"');
+  literal
+}
+
+fn well_known_type_literal(message: &DynamicMessage) -> Option<String> {
+  let int_field = |name: &str| message.get_field_by_name(name).and_then(|v| v.as_i64());
+  let seconds = int_field("seconds").unwrap_or_default();
+  let nanos = message
+    .get_field_by_name("nanos")
+    .and_then(|v| v.as_i32())
+    .unwrap_or_default();
+
+  match message.descriptor().full_name() {
+    "google.protobuf.Duration" => {
+      let total_nanos = i128::from(seconds) * 1_000_000_000 + i128::from(nanos);
+      Some(format!("duration(\"{}ns\")", total_nanos))
+    }
+    "google.protobuf.Timestamp" => Some(format!("timestamp(\"{}\")", rfc3339(seconds, nanos))),
+    _ => None,
+  }
+}
+
+// Formats a unix timestamp as an RFC 3339 date in UTC
+fn rfc3339(seconds: i64, nanos: i32) -> String {
+  let days = seconds.div_euclid(86_400);
+  let seconds_of_day = seconds.rem_euclid(86_400);
+
+  // The days to civil date conversion from http://howardhinnant.github.io/date_algorithms.html
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let day_of_era = z.rem_euclid(146_097);
+  let year_of_era =
+    (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let mp = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+  format!(
+    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+    year,
+    month,
+    day,
+    seconds_of_day / 3600,
+    seconds_of_day % 3600 / 60,
+    seconds_of_day % 60,
+    nanos
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn cel_literals() {
+    assert_eq!(cel_literal(&Value::U64(5)).unwrap(), "5u");
+    assert_eq!(cel_literal(&Value::F64(2.0)).unwrap(), "2.0");
+    assert_eq!(cel_literal(&Value::F64(f64::NAN)), None);
+    assert_eq!(
+      cel_literal(&Value::String("a\"b\\c\n".to_string())).unwrap(),
+      r#""a\"b\\c\n""#
+    );
+    assert_eq!(
+      cel_literal(&Value::Bytes(vec![0, 255].into())).unwrap(),
+      r#"b"\x00\xff""#
+    );
+    assert_eq!(
+      cel_literal(&Value::List(vec![Value::I32(1), Value::I32(-2)])).unwrap(),
+      "[1, -2]"
+    );
+  }
+
+  #[test]
+  fn timestamps() {
+    assert_eq!(rfc3339(0, 0), "1970-01-01T00:00:00.000000000Z");
+    assert_eq!(rfc3339(951_782_400, 5), "2000-02-29T00:00:00.000000005Z");
+    assert_eq!(rfc3339(-1, 0), "1969-12-31T23:59:59.000000000Z");
+  }
+}
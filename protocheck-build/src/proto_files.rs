@@ -0,0 +1,497 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::*;
+
+/// A helper to use when gathering the names of proto files to pass to [`prost_build::Config::compile_protos`].
+/// Recursively collects all .proto files in a given directory and its subdirectories.
+///
+/// ```rust,ignore
+/// let proto_files = ProtoFilesCollector::new("proto")
+///   .exclude(["third_party/**", "**/*_internal.proto"])
+///   .skip_hidden(true)
+///   .emit_rerun_if_changed(true)
+///   .collect()?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProtoFilesCollector {
+  base_dir: PathBuf,
+  include: Vec<String>,
+  exclude: Vec<String>,
+  skip_hidden: bool,
+  follow_symlinks: bool,
+  emit_rerun_if_changed: bool,
+}
+
+impl ProtoFilesCollector {
+  pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+    Self {
+      base_dir: base_dir.into(),
+      include: Vec::new(),
+      exclude: Vec::new(),
+      skip_hidden: false,
+      follow_symlinks: true,
+      emit_rerun_if_changed: false,
+    }
+  }
+
+  /// Only collects the proto files whose path (relative to the base directory) matches at least one of these glob patterns.
+  /// `*` and `?` match any sequence of characters or any single character within a path component, and `**` matches any number of components. By default, all proto files are collected.
+  pub fn include<I, S>(&mut self, patterns: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self.include = patterns
+      .into_iter()
+      .map(|p| p.as_ref().to_string())
+      .collect();
+    self
+  }
+
+  /// Skips the files and directories whose path (relative to the base directory) matches one of these glob patterns, using the same syntax as [`include`](Self::include).
+  /// Exclusions take precedence over inclusions, and excluded directories are not traversed at all.
+  pub fn exclude<I, S>(&mut self, patterns: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self.exclude = patterns
+      .into_iter()
+      .map(|p| p.as_ref().to_string())
+      .collect();
+    self
+  }
+
+  /// Skips the hidden files and directories (the ones starting with a `.`, such as `.git`). Defaults to false.
+  pub fn skip_hidden(&mut self, skip: bool) -> &mut Self {
+    self.skip_hidden = skip;
+    self
+  }
+
+  /// Follows the symlinks pointing to directories. Each directory is only visited once, so symlinks that point back up the tree do not cause infinite recursion. Defaults to true.
+  pub fn follow_symlinks(&mut self, follow: bool) -> &mut Self {
+    self.follow_symlinks = follow;
+    self
+  }
+
+  /// Emits a `cargo:rerun-if-changed` directive for every directory and proto file that is visited. The directives are only emitted when running inside of a build script (when `OUT_DIR` is set).
+  pub fn emit_rerun_if_changed(&mut self, emit: bool) -> &mut Self {
+    self.emit_rerun_if_changed = emit;
+    self
+  }
+
+  /// Collects the paths of the proto files, sorted so that the generated code does not depend on the order in which the filesystem lists them.
+  pub fn collect(&self) -> io::Result<Vec<PathBuf>> {
+    let mut proto_files = Vec::new();
+
+    if !self.base_dir.is_dir() {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Path {:?} is not a directory.", self.base_dir),
+      ));
+    }
+
+    // We'll use a helper function to do the actual recursive work
+    // This helps keep the public function's signature clean.
+    self.collect_recursive(
+      self.base_dir.as_path(),
+      &mut proto_files,
+      &mut HashSet::new(),
+    )?;
+
+    proto_files.sort();
+
+    Ok(proto_files)
+  }
+
+  fn collect_recursive(
+    &self,
+    current_dir: &Path,
+    proto_files: &mut Vec<PathBuf>,
+    visited_dirs: &mut HashSet<PathBuf>,
+  ) -> io::Result<()> {
+    // Breaks the cycles created by symlinks
+    if !visited_dirs.insert(fs::canonicalize(current_dir)?) {
+      return Ok(());
+    }
+
+    // Catches the files being added or removed in this directory
+    if self.emit_rerun_if_changed {
+      rerun_if_changed(current_dir);
+    }
+
+    for entry in fs::read_dir(current_dir)? {
+      let entry = entry?;
+      let path = entry.path();
+
+      if self.skip_hidden && entry.file_name().as_encoded_bytes().starts_with(b".") {
+        continue;
+      }
+
+      if entry.file_type()?.is_symlink() {
+        match fs::metadata(&path) {
+          Err(_) => {
+            cargo_warning(format_args!("Skipping the broken symlink {:?}", path));
+            continue;
+          }
+          Ok(target) if target.is_dir() && !self.follow_symlinks => continue,
+          Ok(_) => {}
+        }
+      }
+
+      let relative_path = self.relative_path(&path);
+
+      if self
+        .exclude
+        .iter()
+        .any(|pattern| glob_matches(pattern, &relative_path))
+      {
+        continue;
+      }
+
+      if path.is_file() {
+        if path.extension().is_some_and(|ext| ext == "proto")
+          && (self.include.is_empty()
+            || self
+              .include
+              .iter()
+              .any(|pattern| glob_matches(pattern, &relative_path)))
+        {
+          if self.emit_rerun_if_changed {
+            rerun_if_changed(&path);
+          }
+
+          proto_files.push(path);
+        }
+      } else if path.is_dir() {
+        self.collect_recursive(&path, proto_files, visited_dirs)?;
+      }
+    }
+    Ok(())
+  }
+
+  // The path relative to the base directory, with `/` as the separator, used for matching the patterns
+  fn relative_path(&self, path: &Path) -> String {
+    path
+      .strip_prefix(&self.base_dir)
+      .unwrap_or(path)
+      .components()
+      .map(|c| c.as_os_str().to_string_lossy())
+      .collect::<Vec<_>>()
+      .join("/")
+  }
+}
+
+/// Returns the include paths needed to compile the given proto files, which are the directories that contain the directory structure of their packages. For example, the include path of `protos/acme/billing/v1/invoice.proto` with `package acme.billing.v1;` is `protos`.
+///
+/// The include paths are deduplicated, and the nested ones come before the directories that contain them, so that protoc resolves each file relative to its own include path.
+///
+/// Returns an error if the directory of a file does not match its package, as there is no include path that would make its imports resolve in that case.
+pub fn derive_include_paths(proto_files: &[impl AsRef<Path>]) -> io::Result<Vec<PathBuf>> {
+  let mut include_paths = BTreeSet::new();
+
+  for file in proto_files {
+    let file = file.as_ref();
+    let package = proto_package(&fs::read_to_string(file)?);
+
+    let mut include_path = match file.parent() {
+      Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+      _ => PathBuf::from("."),
+    };
+
+    if let Some(package) = &package {
+      for component in package.rsplit('.') {
+        if include_path
+          .file_name()
+          .is_none_or(|name| name != component)
+        {
+          return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+              "Cannot derive the include path of {:?}, as its directory does not match its package `{}` (it should be inside of a `{}` directory). Move the file or set the include paths explicitly.",
+              file,
+              package,
+              package.replace('.', "/")
+            ),
+          ));
+        }
+
+        include_path.pop();
+      }
+
+      if include_path.as_os_str().is_empty() {
+        include_path = PathBuf::from(".");
+      }
+    }
+
+    include_paths.insert(include_path);
+  }
+
+  let mut include_paths: Vec<PathBuf> = include_paths.into_iter().collect();
+
+  // The nested include paths come first
+  include_paths.sort_by(|a, b| {
+    b.components()
+      .count()
+      .cmp(&a.components().count())
+      .then_with(|| a.cmp(b))
+  });
+
+  Ok(include_paths)
+}
+
+// Finds the package statement, ignoring the comments
+fn proto_package(content: &str) -> Option<String> {
+  let mut code = String::with_capacity(content.len());
+  let mut rest = content;
+
+  while !rest.is_empty() {
+    if let Some(comment) = rest.strip_prefix("//") {
+      rest = comment.find('\n').map_or("", |end| &comment[end..]);
+    } else if let Some(comment) = rest.strip_prefix("/*") {
+      rest = comment.find("*/").map_or("", |end| &comment[end + 2..]);
+      code.push(' ');
+    } else {
+      let c = rest.chars().next()?;
+      code.push(c);
+      rest = &rest[c.len_utf8()..];
+    }
+  }
+
+  code.split(';').find_map(|statement| {
+    let package = statement.trim().strip_prefix("package")?;
+
+    package
+      .starts_with(char::is_whitespace)
+      .then(|| package.trim().to_string())
+  })
+}
+
+// Matches a `/` separated path against a glob pattern, where `**` matches any number of components,
+// while `*` and `?` match any sequence of characters or any single character within a component
+fn glob_matches(pattern: &str, path: &str) -> bool {
+  let pattern: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty()).collect();
+  let path: Vec<&str> = path.split('/').collect();
+
+  components_match(&pattern, &path)
+}
+
+fn components_match(pattern: &[&str], path: &[&str]) -> bool {
+  match pattern.split_first() {
+    None => path.is_empty(),
+    Some((&"**", rest)) => (0..=path.len()).any(|skipped| components_match(rest, &path[skipped..])),
+    Some((first, rest)) => match path.split_first() {
+      Some((component, path_rest)) => {
+        component_matches(first.as_bytes(), component.as_bytes())
+          && components_match(rest, path_rest)
+      }
+      None => false,
+    },
+  }
+}
+
+fn component_matches(pattern: &[u8], component: &[u8]) -> bool {
+  match pattern.split_first() {
+    None => component.is_empty(),
+    Some((b'*', rest)) => (0..=component.len()).any(|i| component_matches(rest, &component[i..])),
+    Some((b'?', rest)) => !component.is_empty() && component_matches(rest, &component[1..]),
+    Some((c, rest)) => component.first() == Some(c) && component_matches(rest, &component[1..]),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn touch(path: PathBuf) -> PathBuf {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "").unwrap();
+    path
+  }
+
+  #[test]
+  fn sorted_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let c = touch(dir.path().join("c.proto"));
+    let b = touch(dir.path().join("a/b.proto"));
+    let a = touch(dir.path().join("a.proto"));
+    touch(dir.path().join("a/readme.md"));
+
+    assert_eq!(get_proto_paths_recursive(dir.path()).unwrap(), [b, a, c]);
+  }
+
+  #[test]
+  fn filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let user = touch(dir.path().join("myapp/v1/user.proto"));
+    touch(dir.path().join("myapp/v1/user_internal.proto"));
+    touch(dir.path().join("myapp/v1/vendor/ignored.proto"));
+    touch(dir.path().join("third_party/google/api.proto"));
+    let other = touch(dir.path().join("other/other.proto"));
+
+    let files = ProtoFilesCollector::new(dir.path())
+      .exclude(["third_party/**", "**/*_internal.proto", "myapp/*/vendor"])
+      .collect()
+      .unwrap();
+    assert_eq!(files, [user.clone(), other]);
+
+    let files = ProtoFilesCollector::new(dir.path())
+      .include(["myapp/**"])
+      .exclude(["**/*_internal.proto", "**/vendor/**"])
+      .collect()
+      .unwrap();
+    assert_eq!(files, [user]);
+  }
+
+  #[test]
+  fn excluded_dir_with_included_file() {
+    let dir = tempfile::tempdir().unwrap();
+    touch(dir.path().join("third_party/api.proto"));
+    let user = touch(dir.path().join("user.proto"));
+
+    let files = ProtoFilesCollector::new(dir.path())
+      .include(["third_party/api.proto", "*.proto"])
+      .exclude(["third_party"])
+      .collect()
+      .unwrap();
+    assert_eq!(files, [user]);
+  }
+
+  #[test]
+  fn hidden() {
+    let dir = tempfile::tempdir().unwrap();
+    touch(dir.path().join(".git/hidden.proto"));
+    let user = touch(dir.path().join("user.proto"));
+
+    assert_eq!(get_proto_paths_recursive(dir.path()).unwrap().len(), 2);
+
+    let files = ProtoFilesCollector::new(dir.path())
+      .skip_hidden(true)
+      .collect()
+      .unwrap();
+    assert_eq!(files, [user]);
+  }
+
+  #[test]
+  fn globs() {
+    assert!(glob_matches("**/*.proto", "a.proto"));
+    assert!(glob_matches("**/*.proto", "a/b/c.proto"));
+    assert!(glob_matches("a/**", "a"));
+    assert!(glob_matches("a/**/c.proto", "a/b/b/c.proto"));
+    assert!(glob_matches("a/?.proto", "a/b.proto"));
+    assert!(!glob_matches("*.proto", "a/b.proto"));
+    assert!(!glob_matches("a/?.proto", "a/bc.proto"));
+  }
+
+  #[test]
+  fn include_paths_single_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let user = dir.path().join("protos/myapp/v1/user.proto");
+    let order = dir.path().join("protos/myapp/v1/order.proto");
+    touch(user.clone());
+    fs::write(&user, "syntax = \"proto3\";\n\npackage myapp.v1;\n").unwrap();
+    touch(order.clone());
+    fs::write(
+      &order,
+      "// package other.v1;\nsyntax = \"proto3\";\n/* the package */ package   myapp.v1 ;\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+      derive_include_paths(&[user, order]).unwrap(),
+      [dir.path().join("protos")]
+    );
+  }
+
+  #[test]
+  fn include_paths_multiple_roots() {
+    let dir = tempfile::tempdir().unwrap();
+    let files = [
+      ("protos/myapp/v1/user.proto", "package myapp.v1;"),
+      ("vendor/acme/common.proto", "package acme;"),
+      (
+        "protos/third_party/google/api/http.proto",
+        "package google.api;",
+      ),
+      ("protos/plain.proto", "syntax = \"proto3\";"),
+    ]
+    .map(|(path, content)| {
+      let path = touch(dir.path().join(path));
+      fs::write(&path, content).unwrap();
+      path
+    });
+
+    assert_eq!(
+      derive_include_paths(&files).unwrap(),
+      [
+        dir.path().join("protos/third_party"),
+        dir.path().join("protos"),
+        dir.path().join("vendor"),
+      ]
+    );
+  }
+
+  #[test]
+  fn include_paths_mismatched_package() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = touch(dir.path().join("protos/user.proto"));
+    fs::write(&file, "package myapp.v1;").unwrap();
+
+    let err = derive_include_paths(&[file]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains(
+      "as its directory does not match its package `myapp.v1` (it should be inside of a `myapp/v1` directory)"
+    ));
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn symlinks() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir().unwrap();
+    let user = touch(dir.path().join("protos/user.proto"));
+    let linked = touch(dir.path().join("linked/linked.proto"));
+    symlink(dir.path().join("protos"), dir.path().join("protos/vendor")).unwrap();
+    symlink(&linked, dir.path().join("protos/file_link.proto")).unwrap();
+    symlink(
+      dir.path().join("linked"),
+      dir.path().join("protos/dir_link"),
+    )
+    .unwrap();
+    symlink(dir.path().join("missing"), dir.path().join("protos/broken")).unwrap();
+
+    let base_dir = dir.path().join("protos");
+
+    let files = get_proto_paths_recursive(&base_dir).unwrap();
+    assert_eq!(
+      files,
+      [
+        base_dir.join("dir_link/linked.proto"),
+        base_dir.join("file_link.proto"),
+        user.clone(),
+      ]
+    );
+
+    let files = ProtoFilesCollector::new(&base_dir)
+      .follow_symlinks(false)
+      .collect()
+      .unwrap();
+    assert_eq!(files, [base_dir.join("file_link.proto"), user]);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn non_unicode_paths() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = touch(dir.path().join(OsStr::from_bytes(b"inv\xFFlid/a.proto")));
+
+    assert_eq!(get_proto_paths_recursive(dir.path()).unwrap(), [file]);
+
+    #[allow(deprecated)]
+    let err = get_proto_files_recursive(dir.path()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+  }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+
+use prost_reflect::{DynamicMessage, Value};
+
+use crate::*;
+
+// The protovalidate rules which are not implemented by protocheck yet.
+// A rule is unsupported if its path (relative to the FieldRules or MessageRules that contain it) is one of these, or if it is nested inside one of them
+const UNSUPPORTED_FIELD_RULES: &[&str] = &[
+  "cel_expression",
+  "field_mask",
+  "string.ulid",
+  "string.protobuf_fqn",
+  "string.protobuf_dot_fqn",
+  "bytes.uuid",
+];
+const UNSUPPORTED_MESSAGE_RULES: &[&str] = &["cel_expression", "oneof"];
+
+// The rules for the items of a list and for the keys and values of a map are FieldRules themselves
+const NESTED_FIELD_RULES: &[&str] = &["repeated.items.", "map.keys.", "map.values."];
+
+/// A summary of the messages that received the validators and of the rules found in them, returned by [`ValidatorsBuilder::compile`](crate::ValidatorsBuilder::compile) and by the other compilation functions.
+///
+/// The rules are identified by their path inside of `buf.validate.FieldRules` or `buf.validate.MessageRules`, such as `string.min_len` or `repeated.items.string.min_len`. Predefined rules are listed with their extension's name in brackets, such as `string.[acme.is_ticker]`, and they are supported (with the `cel` feature) unless they are set in the rules of a list or a map.
+///
+/// With the `serde` feature, the report can be serialized, for example to compare the reports produced by different versions of protocheck.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildReport {
+  /// The messages that received the validators, in the order in which they appear in the descriptor.
+  pub messages: Vec<MessageReport>,
+  /// The rules that were found, but which are not enforced because protocheck does not support them yet.
+  pub unsupported_rules: Vec<UnsupportedRule>,
+}
+
+/// The rules found in a message that received the validators.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageReport {
+  /// The fully qualified name of the message, such as `myapp.v1.User`.
+  pub name: String,
+  /// The proto file where the message is defined.
+  pub file: String,
+  /// The supported rules of the message itself.
+  pub rules: Vec<String>,
+  /// The fields which have at least one supported rule.
+  pub fields: Vec<FieldReport>,
+}
+
+/// The supported rules found in a field.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldReport {
+  pub name: String,
+  pub rules: Vec<String>,
+}
+
+/// A rule that is not supported by protocheck.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnsupportedRule {
+  /// The proto file where the message is defined.
+  pub file: String,
+  /// The fully qualified name of the message.
+  pub message: String,
+  /// The name of the field, or `None` if this is a rule of the message itself.
+  pub field: Option<String>,
+  pub rule: String,
+}
+
+impl fmt::Display for UnsupportedRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.field {
+      Some(field) => write!(
+        f,
+        "`{}` in the field {}.{} ({})",
+        self.rule, self.message, field, self.file
+      ),
+      None => write!(
+        f,
+        "`{}` in the message {} ({})",
+        self.rule, self.message, self.file
+      ),
+    }
+  }
+}
+
+impl BuildReport {
+  /// Writes the report as pretty printed JSON.
+  #[cfg(feature = "serde")]
+  pub fn write_json(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+    fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+    Ok(())
+  }
+
+  // Adds the rules of a message and of its fields (except for the excluded ones) to the report
+  pub(crate) fn add_message(
+    &mut self,
+    message_desc: &MessageDescriptor,
+    field_ext: &ExtensionDescriptor,
+    message_ext: Option<&ExtensionDescriptor>,
+    is_excluded_field: impl Fn(&FieldDescriptor) -> bool,
+  ) {
+    let file = message_desc.parent_file().name().to_string();
+    let message = message_desc.full_name().to_string();
+
+    let mut unsupported = |field: Option<&str>, rule: String| {
+      self.unsupported_rules.push(UnsupportedRule {
+        file: file.clone(),
+        message: message.clone(),
+        field: field.map(str::to_string),
+        rule,
+      })
+    };
+
+    let mut message_rules = Vec::new();
+
+    if let Some(ext) = message_ext
+      && let Some(rules) = message_desc.options().get_extension(ext).as_message()
+    {
+      for rule in rule_paths(rules) {
+        if is_unsupported(&rule, UNSUPPORTED_MESSAGE_RULES) {
+          unsupported(None, rule);
+        } else {
+          message_rules.push(rule);
+        }
+      }
+    }
+
+    let mut fields = Vec::new();
+
+    for field in message_desc
+      .fields()
+      .filter(|field| !is_excluded_field(field))
+    {
+      let options = field.options();
+      let rules = options.get_extension(field_ext);
+      let Some(rules) = rules.as_message() else {
+        continue;
+      };
+
+      let mut field_rules = Vec::new();
+
+      for rule in rule_paths(rules) {
+        if is_unsupported(&rule, UNSUPPORTED_FIELD_RULES) {
+          unsupported(Some(field.name()), rule);
+        } else {
+          field_rules.push(rule);
+        }
+      }
+
+      if !field_rules.is_empty() {
+        fields.push(FieldReport {
+          name: field.name().to_string(),
+          rules: field_rules,
+        });
+      }
+    }
+
+    self.messages.push(MessageReport {
+      name: message,
+      file,
+      rules: message_rules,
+      fields,
+    });
+  }
+}
+
+// Lists the paths of all the rules that are set, descending into the rules of each type and into the nested FieldRules
+fn rule_paths(rules: &DynamicMessage) -> Vec<String> {
+  let mut paths = Vec::new();
+  collect_rule_paths(rules, "", &mut paths);
+  paths
+}
+
+fn collect_rule_paths(rules: &DynamicMessage, prefix: &str, paths: &mut Vec<String>) {
+  for (field, value) in rules.fields() {
+    let path = format!("{}{}", prefix, field.name());
+
+    match value {
+      Value::Message(nested) if is_rules_message(&field) => {
+        collect_rule_paths(nested, &format!("{}.", path), paths)
+      }
+      _ => paths.push(path),
+    }
+  }
+
+  // Predefined rules
+  for (ext, _) in rules.extensions() {
+    paths.push(format!("{}[{}]", prefix, ext.full_name()));
+  }
+}
+
+pub(crate) fn is_rules_message(field: &FieldDescriptor) -> bool {
+  field.kind().as_message().is_some_and(|message| {
+    message.package_name() == "buf.validate" && message.name().ends_with("Rules")
+  })
+}
+
+fn is_unsupported(rule: &str, unsupported_rules: &[&str]) -> bool {
+  // The predefined rules are only supported in the rules of the field's own type, and they require the CEL validators
+  if rule.contains('[') {
+    let rules_type = rule.split('.').next().unwrap_or_default();
+
+    return !cfg!(feature = "cel") || is_collection_rules(rules_type);
+  }
+
+  let mut rule = rule;
+
+  while let Some(nested) = NESTED_FIELD_RULES
+    .iter()
+    .find_map(|prefix| rule.strip_prefix(prefix))
+  {
+    rule = nested;
+  }
+
+  unsupported_rules.iter().any(|unsupported| {
+    rule
+      .strip_prefix(unsupported)
+      .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+  })
+}
@@ -0,0 +1,83 @@
+use std::sync::Mutex;
+
+use crate::*;
+
+const PROTOVALIDATE_PROTO: &str = "buf/validate/validate.proto";
+
+// The protovalidate definitions bundled with this crate.
+// Its imports are all well known types, which are already provided by protoc and protox.
+const VENDORED_PROTOS: &[(&str, &str)] = &[(
+  PROTOVALIDATE_PROTO,
+  include_str!("../proto/buf/validate/validate.proto"),
+)];
+
+// Makes sure that the files are only written once per process (for each output directory), so that parallel builds never read a partially written file
+static VENDORED_INCLUDE_PATHS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Writes the `buf/validate/validate.proto` file bundled with this crate into a subdirectory of `OUT_DIR` (or of the system's temporary directory, if `OUT_DIR` is not set), and returns the path to that directory, so that it can be used as an include path.
+///
+/// This is done automatically by [`compile_protos_with_validators`](crate::compile_protos_with_validators) and the [`ValidatorsBuilder`](crate::ValidatorsBuilder) when none of the include paths contain `buf/validate/validate.proto`. They use the [`out_dir`](crate::ValidatorsBuilder::out_dir) of the builder instead, if set.
+///
+/// # Panics
+///
+/// Panics if the files cannot be written.
+pub fn vendored_protovalidate_include_path() -> PathBuf {
+  let out_dir = env::var("OUT_DIR")
+    .map(PathBuf::from)
+    .unwrap_or_else(|_| env::temp_dir());
+
+  write_vendored_protos(&out_dir)
+    .unwrap_or_else(|e| panic!("Could not write the vendored protovalidate files: {}", e))
+}
+
+pub(crate) fn write_vendored_protos(out_dir: &Path) -> io::Result<PathBuf> {
+  let mut written = VENDORED_INCLUDE_PATHS
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+  let include_path = out_dir.join("protocheck_vendored_protos");
+
+  if written.contains(&include_path) {
+    return Ok(include_path);
+  }
+
+  // Removes the files left by other versions of this crate
+  if include_path.exists() {
+    fs::remove_dir_all(&include_path)?;
+  }
+
+  for (name, content) in VENDORED_PROTOS {
+    let path = include_path.join(name);
+
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&path, content)?;
+  }
+
+  written.push(include_path.clone());
+
+  Ok(include_path)
+}
+
+// Returns the vendored include path, unless the user already provides a copy of protovalidate,
+// in which case that one is used to avoid mixing different versions.
+// The files must outlive the compilation of the descriptor, as the config uses them too, so they need a known output directory
+pub(crate) fn vendored_include_path_if_missing(
+  proto_include_paths: &[impl AsRef<Path>],
+  out_dir: Option<&Path>,
+) -> Result<Option<PathBuf>, Box<dyn Error>> {
+  if proto_include_paths
+    .iter()
+    .any(|path| path.as_ref().join(PROTOVALIDATE_PROTO).is_file())
+  {
+    return Ok(None);
+  }
+
+  let out_dir = out_dir.ok_or(
+    "None of the include paths contain buf/validate/validate.proto, and the bundled copy cannot be written because OUT_DIR is not set. Use ValidatorsBuilder::out_dir when compiling the protos outside of a build script.",
+  )?;
+
+  Ok(Some(write_vendored_protos(out_dir)?))
+}
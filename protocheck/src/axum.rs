@@ -0,0 +1,220 @@
+//! Axum extractors for the protobuf bodies of the requests, which decode and validate the messages before they reach the handlers.
+//!
+//! ```rust,ignore
+//! async fn create_user(Protobuf(request): Protobuf<CreateUserRequest>) -> StatusCode {
+//!   // The request is valid here
+//!   StatusCode::CREATED
+//! }
+//!
+//! let app = Router::new().route("/users", post(create_user));
+//! ```
+//!
+//! The requests must have the `application/x-protobuf` (or `application/protobuf`) content type. When a request is rejected, the response is the one of the [`ProtobufRejection`], which can be replaced by a custom one with [`WithRejection`]:
+//!
+//! ```rust,ignore
+//! async fn create_user(
+//!   WithRejection(Protobuf(request), _): WithRejection<Protobuf<CreateUserRequest>, ApiError>,
+//! ) -> StatusCode {
+//!   StatusCode::CREATED
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+use axum::{
+  body::Bytes,
+  extract::{rejection::BytesRejection, FromRequest, Request},
+  http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    HeaderMap, HeaderValue, StatusCode,
+  },
+  response::{IntoResponse, Response},
+};
+use prost::{DecodeError, Message};
+use thiserror::Error;
+
+use crate::{types::protovalidate::Violations, ProtoValidator};
+
+/// The content type of the protobuf bodies, and of the violations that are encoded with protobuf.
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// An extractor that decodes the protobuf body of the request, and validates the message.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Protobuf<T>(pub T);
+
+/// Like [`Protobuf`], but without the validation of the message.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProtobufLenient<T>(pub T);
+
+/// Wraps an extractor, and converts its rejection into `R`, so that the apps can respond with their own error bodies (like the extractor of the same name in `axum-extra`).
+///
+/// The custom rejections only need to implement [`IntoResponse`] and [`From`] for the rejection of the extractor, such as [`ProtobufRejection`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WithRejection<E, R>(pub E, pub PhantomData<R>);
+
+/// The encoding of the violations in the body of the responses, which is selected with the `Accept` header of the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViolationsFormat {
+  /// The protobuf encoding of `buf.validate.Violations`, which is the default.
+  #[default]
+  Protobuf,
+  /// The JSON encoding of `buf.validate.Violations`, when the request accepts `application/json`.
+  Json,
+}
+
+impl ViolationsFormat {
+  /// Selects the format from the `Accept` header.
+  pub fn from_headers(headers: &HeaderMap) -> Self {
+    let accepts_json = headers
+      .get_all(ACCEPT)
+      .iter()
+      .filter_map(|value| value.to_str().ok())
+      .flat_map(|value| value.split(','))
+      .any(|media_type| media_type_is(media_type, &["application/json"]));
+
+    if accepts_json {
+      Self::Json
+    } else {
+      Self::Protobuf
+    }
+  }
+}
+
+/// The rejection of the protobuf extractors.
+#[derive(Debug, Error)]
+pub enum ProtobufRejection {
+  /// The content type of the request is missing, or it is not the one of protobuf. The response has the `415 Unsupported Media Type` status.
+  #[error("expected a request with the `{PROTOBUF_CONTENT_TYPE}` content type")]
+  UnsupportedContentType,
+  /// The body could not be read, and the response is the one of the [`BytesRejection`].
+  #[error(transparent)]
+  Body(#[from] BytesRejection),
+  /// The body is not a valid encoding of the message. The response has the `400 Bad Request` status.
+  #[error("failed to decode the message: {0}")]
+  Decode(#[from] DecodeError),
+  /// The message did not pass the validation. The response has the `400 Bad Request` status, and it contains the violations in the requested format.
+  #[error("{violations}")]
+  Invalid {
+    violations: Violations,
+    format: ViolationsFormat,
+  },
+}
+
+impl ProtobufRejection {
+  /// Returns the violations, if the message was decoded but not valid.
+  pub fn violations(&self) -> Option<&Violations> {
+    match self {
+      Self::Invalid { violations, .. } => Some(violations),
+      _ => None,
+    }
+  }
+}
+
+impl IntoResponse for ProtobufRejection {
+  fn into_response(self) -> Response {
+    match self {
+      Self::UnsupportedContentType => {
+        (StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string()).into_response()
+      }
+      Self::Body(rejection) => rejection.into_response(),
+      Self::Decode(_) => (StatusCode::BAD_REQUEST, self.to_string()).into_response(),
+      Self::Invalid {
+        violations,
+        format: ViolationsFormat::Protobuf,
+      } => (
+        StatusCode::BAD_REQUEST,
+        [(
+          CONTENT_TYPE,
+          HeaderValue::from_static(PROTOBUF_CONTENT_TYPE),
+        )],
+        violations.encode_to_vec(),
+      )
+        .into_response(),
+      Self::Invalid {
+        violations,
+        format: ViolationsFormat::Json,
+      } => match serde_json::to_vec(&violations) {
+        Ok(body) => (
+          StatusCode::BAD_REQUEST,
+          [(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+          body,
+        )
+          .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+      },
+    }
+  }
+}
+
+// Compares the media type without its parameters (such as the charset)
+fn media_type_is(value: &str, media_types: &[&str]) -> bool {
+  let media_type = value.split(';').next().unwrap_or_default().trim();
+
+  media_types
+    .iter()
+    .any(|expected| media_type.eq_ignore_ascii_case(expected))
+}
+
+async fn decode_body<T, S>(req: Request, state: &S) -> Result<T, ProtobufRejection>
+where
+  T: Message + Default,
+  S: Send + Sync,
+{
+  let is_protobuf = req
+    .headers()
+    .get(CONTENT_TYPE)
+    .and_then(|value| value.to_str().ok())
+    .is_some_and(|value| media_type_is(value, &[PROTOBUF_CONTENT_TYPE, "application/protobuf"]));
+
+  if !is_protobuf {
+    return Err(ProtobufRejection::UnsupportedContentType);
+  }
+
+  let body = Bytes::from_request(req, state).await?;
+
+  Ok(T::decode(body)?)
+}
+
+impl<T, S> FromRequest<S> for Protobuf<T>
+where
+  T: Message + Default + ProtoValidator,
+  S: Send + Sync,
+{
+  type Rejection = ProtobufRejection;
+
+  async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+    let format = ViolationsFormat::from_headers(req.headers());
+    let message: T = decode_body(req, state).await?;
+
+    if let Err(violations) = message.validate() {
+      return Err(ProtobufRejection::Invalid { violations, format });
+    }
+
+    Ok(Self(message))
+  }
+}
+
+impl<T, S> FromRequest<S> for ProtobufLenient<T>
+where
+  T: Message + Default,
+  S: Send + Sync,
+{
+  type Rejection = ProtobufRejection;
+
+  async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+    Ok(Self(decode_body(req, state).await?))
+  }
+}
+
+impl<E, R, S> FromRequest<S> for WithRejection<E, R>
+where
+  E: FromRequest<S>,
+  R: From<E::Rejection> + IntoResponse,
+  S: Send + Sync,
+{
+  type Rejection = R;
+
+  async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+    Ok(Self(E::from_request(req, state).await?, PhantomData))
+  }
+}
@@ -0,0 +1,87 @@
+use prost::{bytes::Buf, DecodeError, Message};
+use thiserror::Error;
+
+use crate::{types::protovalidate::Violations, ProtoValidator};
+
+/// The error returned by [`decode_and_validate`] and [`decode_length_delimited_and_validate`].
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum DecodeOrValidateError {
+  /// The bytes are not a valid encoding of the message.
+  #[error("failed to decode the message: {0}")]
+  Decode(#[cfg_attr(feature = "std", source)] DecodeError),
+  /// The message was decoded, but it did not pass the validation.
+  #[error("{0}")]
+  Invalid(#[from] Violations),
+}
+
+// The DecodeError only implements Error with the std feature of prost
+impl From<DecodeError> for DecodeOrValidateError {
+  fn from(error: DecodeError) -> Self {
+    Self::Decode(error)
+  }
+}
+
+impl DecodeOrValidateError {
+  /// Returns the violations, if the message was decoded but not valid.
+  pub fn violations(&self) -> Option<&Violations> {
+    match self {
+      Self::Invalid(violations) => Some(violations),
+      Self::Decode(_) => None,
+    }
+  }
+}
+
+/// Decodes a message and validates it, so that the two errors can be handled together.
+///
+/// # Examples
+/// ```rust
+/// use protocheck::{decode_and_validate, DecodeOrValidateError};
+/// # #[derive(Clone, PartialEq, prost::Message)]
+/// # struct User {}
+/// # impl protocheck::ProtoValidator for User {
+/// #   fn validate(&self) -> Result<(), protocheck::types::protovalidate::Violations> { Ok(()) }
+/// #   fn validate_fail_fast(&self) -> Result<(), protocheck::types::protovalidate::Violations> { Ok(()) }
+/// # }
+///
+/// fn handle(body: &[u8]) -> Result<User, DecodeOrValidateError> {
+///   let user: User = decode_and_validate(body)?;
+///
+///   Ok(user)
+/// }
+///
+/// assert!(handle(&[]).is_ok());
+/// assert!(matches!(handle(&[0xff]), Err(DecodeOrValidateError::Decode(_))));
+/// ```
+pub fn decode_and_validate<T>(buf: impl Buf) -> Result<T, DecodeOrValidateError>
+where
+  T: Message + Default + ProtoValidator,
+{
+  let message = T::decode(buf)?;
+  message.validate()?;
+
+  Ok(message)
+}
+
+/// Like [`decode_and_validate`], for a message that is prefixed by its length, such as the messages written with [`Message::encode_length_delimited`] in a stream.
+pub fn decode_length_delimited_and_validate<T>(buf: impl Buf) -> Result<T, DecodeOrValidateError>
+where
+  T: Message + Default + ProtoValidator,
+{
+  let message = T::decode_length_delimited(buf)?;
+  message.validate()?;
+
+  Ok(message)
+}
+
+/// The decoding errors have the `INVALID_ARGUMENT` code, and the violations are converted like in the [`From<Violations>`](Violations) implementation for [`tonic::Status`].
+#[cfg(feature = "tonic")]
+impl From<DecodeOrValidateError> for tonic::Status {
+  fn from(error: DecodeOrValidateError) -> Self {
+    match error {
+      DecodeOrValidateError::Decode(e) => {
+        tonic::Status::invalid_argument(format!("failed to decode the message: {}", e))
+      }
+      DecodeOrValidateError::Invalid(violations) => violations.into(),
+    }
+  }
+}
@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+
+use prost::{bytes::BufMut, EncodeError, Message};
+use thiserror::Error;
+
+use crate::{types::protovalidate::Violations, ProtoValidator};
+
+/// The error returned by [`encode_validated`].
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum EncodeOrValidateError {
+  /// The message is valid, but the buffer does not have enough capacity for it.
+  #[error("failed to encode the message: {0}")]
+  Encode(#[cfg_attr(feature = "std", source)] EncodeError),
+  /// The message did not pass the validation, so it was not encoded.
+  #[error("{0}")]
+  Invalid(#[from] Violations),
+}
+
+// Like the DecodeError, the EncodeError only implements Error with the std feature of prost
+impl From<EncodeError> for EncodeOrValidateError {
+  fn from(error: EncodeError) -> Self {
+    Self::Encode(error)
+  }
+}
+
+impl EncodeOrValidateError {
+  /// Returns the violations, if the message was not valid.
+  pub fn violations(&self) -> Option<&Violations> {
+    match self {
+      Self::Invalid(violations) => Some(violations),
+      Self::Encode(_) => None,
+    }
+  }
+}
+
+/// Validates a message and encodes it into the buffer only if it is valid, so that the invalid messages are never sent to other services.
+///
+/// # Examples
+/// ```rust
+/// use protocheck::encode_validated;
+/// # #[derive(Clone, PartialEq, prost::Message)]
+/// # struct Event {}
+/// # impl protocheck::ProtoValidator for Event {
+/// #   fn validate(&self) -> Result<(), protocheck::types::protovalidate::Violations> { Ok(()) }
+/// #   fn validate_fail_fast(&self) -> Result<(), protocheck::types::protovalidate::Violations> { Ok(()) }
+/// # }
+///
+/// let mut buf = Vec::new();
+///
+/// if let Err(e) = encode_validated(&Event {}, &mut buf) {
+///   eprintln!("The event was not sent: {}", e);
+/// }
+/// ```
+pub fn encode_validated<T>(message: &T, buf: &mut impl BufMut) -> Result<(), EncodeOrValidateError>
+where
+  T: Message + ProtoValidator,
+{
+  message.validate()?;
+  message.encode(buf)?;
+
+  Ok(())
+}
+
+/// Like [`encode_validated`], but it encodes the message into a new `Vec`, so the only possible error are the violations.
+pub fn encode_validated_to_vec<T>(message: &T) -> Result<Vec<u8>, Violations>
+where
+  T: Message + ProtoValidator,
+{
+  message.validate()?;
+
+  Ok(message.encode_to_vec())
+}
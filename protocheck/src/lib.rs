@@ -1,20 +1,153 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../../README.md")]
 //! # Feature flags
 #![doc = document_features::document_features!()]
 
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+
 pub mod types {
   pub use proto_types::*;
 }
 
+// The items of `alloc` used by the code of the macros, which must also be reachable from the `no_std` crates
+#[doc(hidden)]
+pub mod __private {
+  pub use alloc::vec::Vec;
+}
+
+mod decode;
+pub use decode::{
+  decode_and_validate, decode_length_delimited_and_validate, DecodeOrValidateError,
+};
+
+mod encode;
+pub use encode::{encode_validated, encode_validated_to_vec, EncodeOrValidateError};
+
+#[cfg(feature = "tonic")]
+pub mod tonic;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+#[cfg(feature = "tower")]
+pub mod tower;
+
 /// The shared trait for all structs that have validators in them. The `validate` method is available on the structs themselves, so it is not necessary to import the trait just for validation, but this is useful for making functions that accept any struct implementing ProtoValidator, such as a Tower layer.
+///
+/// The trait is object safe, so it can also be used as `&dyn ProtoValidator` or `Box<dyn ProtoValidator>`, and it is implemented for the [`Option`], [`Vec`] and [`Box`] containing the messages.
+///
+/// # Examples
+/// ```rust
+/// use protocheck::{types::protovalidate::Violations, ProtoValidator};
+///
+/// fn check(messages: &[&dyn ProtoValidator]) -> Result<(), Violations> {
+///   messages.iter().try_for_each(|msg| msg.validate())
+/// }
+///
+/// assert!(check(&[]).is_ok());
+/// ```
 pub trait ProtoValidator {
   /// The method that performs validation on the message struct.
   /// This is available on the generated structs on its own, so you don't need to import the trait unless you want to use it with a generic.
   fn validate(&self) -> Result<(), Violations>;
+
+  /// Like [`validate`](ProtoValidator::validate), but stops at the first violation (including the ones of the items of lists and maps, of the nested messages and of the CEL rules) and returns only that one.
+  fn validate_fail_fast(&self) -> Result<(), Violations>;
+
+  /// Performs the validation with the given [`ValidationOptions`].
+  fn validate_with(&self, options: &ValidationOptions) -> Result<(), Violations> {
+    validators::recursion::with_max_depth(options.max_depth, || {
+      if options.fail_fast {
+        self.validate_fail_fast()
+      } else {
+        self.validate()
+      }
+    })
+  }
+}
+
+/// The options for [`ProtoValidator::validate_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationOptions {
+  /// Stops at the first violation, like [`ProtoValidator::validate_fail_fast`].
+  pub fail_fast: bool,
+  /// The maximum depth of the nested messages that are validated, which is [`DEFAULT_MAX_DEPTH`](validators::recursion::DEFAULT_MAX_DEPTH) by default.
+  pub max_depth: usize,
+}
+
+impl Default for ValidationOptions {
+  fn default() -> Self {
+    Self {
+      fail_fast: false,
+      max_depth: validators::recursion::DEFAULT_MAX_DEPTH,
+    }
+  }
 }
 
-use proto_types::protovalidate::Violations;
+impl<T: ProtoValidator + ?Sized> ProtoValidator for Box<T> {
+  fn validate(&self) -> Result<(), Violations> {
+    (**self).validate()
+  }
+
+  fn validate_fail_fast(&self) -> Result<(), Violations> {
+    (**self).validate_fail_fast()
+  }
+}
+
+/// The unset messages are valid.
+impl<T: ProtoValidator> ProtoValidator for Option<T> {
+  fn validate(&self) -> Result<(), Violations> {
+    self.as_ref().map_or(Ok(()), T::validate)
+  }
+
+  fn validate_fail_fast(&self) -> Result<(), Violations> {
+    self.as_ref().map_or(Ok(()), T::validate_fail_fast)
+  }
+}
+
+/// Validates every message, with the index of the message at the start of the field paths (e.g. `[1].name`).
+impl<T: ProtoValidator> ProtoValidator for Vec<T> {
+  fn validate(&self) -> Result<(), Violations> {
+    let mut violations = Violations::default();
+
+    for (idx, item) in self.iter().enumerate() {
+      if let Err(item_violations) = item.validate() {
+        violations.merge(item_violations.with_prefix(index_element(idx)));
+      }
+    }
+
+    if violations.is_empty() {
+      Ok(())
+    } else {
+      Err(violations)
+    }
+  }
+
+  fn validate_fail_fast(&self) -> Result<(), Violations> {
+    for (idx, item) in self.iter().enumerate() {
+      if let Err(item_violations) = item.validate_fail_fast() {
+        return Err(item_violations.with_prefix(index_element(idx)));
+      }
+    }
+
+    Ok(())
+  }
+}
+
+fn index_element(idx: usize) -> FieldPathElement {
+  FieldPathElement {
+    subscript: Some(Subscript::Index(idx as u64)),
+    ..Default::default()
+  }
+}
+
+/// A marker trait for the messages used as the request of a gRPC method, which is implemented when protocheck-build is used with `ValidatorsBuilder::validated_requests`. In that case, the build fails if the request of any method does not receive the validators, so this can be used to bound the generics of a middleware (such as `T: ValidatedRequest`) with the assurance that all of the requests can be validated.
+pub trait ValidatedRequest: ProtoValidator {}
+
+use proto_types::protovalidate::{field_path_element::Subscript, FieldPathElement, Violations};
 pub use protocheck_core::*;
 #[doc(inline)]
 pub use protocheck_proc_macro as macros;
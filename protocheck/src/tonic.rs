@@ -0,0 +1,270 @@
+//! The validation of the requests at the boundaries of the tonic services and clients.
+//!
+//! # Services
+//!
+//! The services can use a codec that validates the messages as they are decoded, so that the invalid requests are rejected before they reach the handlers.
+//!
+//! The interceptors of tonic only receive the metadata of the requests, so the validation happens in the codec of the generated services instead. The codec is selected with the `codec_path` of [`tonic_prost_build`](https://docs.rs/tonic-prost-build), for which protocheck-build provides the `VALIDATING_CODEC_PATH` constant:
+//!
+//! ```rust,ignore
+//! compile_protos_with_validators_tonic(
+//!   tonic_prost_build::configure()
+//!     .build_client(false)
+//!     .codec_path(protocheck_build::VALIDATING_CODEC_PATH),
+//!   &proto_files,
+//!   &include_paths,
+//!   &["myapp.v1"],
+//! )?;
+//! ```
+//!
+//! For the unary methods, an invalid request is answered with the status of its violations (see the [`From<Violations>`](crate::types::protovalidate::Violations) implementation for [`Status`]), and the handler is not called.
+//!
+//! For the streaming methods, the stream yields the status in place of the first invalid message, and every item after it is an error as well, so a handler that stops at the first error (as with `while let Some(request) = stream.message().await? { ... }`) ends the call with that status, and none of the following messages are handled.
+//!
+//! The generated clients use the same codec, with which they validate the responses in the same way. This requires the responses to have the validators, which is not the case for the messages without any rules, so the clients are usually generated separately, without this codec.
+//!
+//! # Clients
+//!
+//! The clients can validate the requests before sending them, so that the invalid ones never leave the client, and the violations are returned as a local error. This is opt-in for each call, with [`IntoValidatedRequest`] for the request messages and with [`call_with_validated_stream`] for the request streams:
+//!
+//! ```rust,ignore
+//! async fn greet(client: &mut GreeterClient<Channel>, name: String) -> Result<String, CallError> {
+//!   let request = GreetRequest { name }.into_validated_request()?;
+//!
+//!   Ok(client.greet(request).await?.into_inner().text)
+//! }
+//! ```
+
+use std::{
+  future::{poll_fn, Future},
+  marker::PhantomData,
+  pin::{pin, Pin},
+  sync::{Arc, Mutex},
+  task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+use prost::{bytes::Buf, Message};
+use thiserror::Error;
+use tonic::{
+  codec::{BufferSettings, Codec, DecodeBuf, Decoder},
+  Request, Status,
+};
+use tonic_prost::{ProstDecoder, ProstEncoder};
+
+use crate::{types::protovalidate::Violations, ProtoValidator};
+
+/// A [`Codec`] that works like [`tonic_prost::ProstCodec`], but validates the messages that it decodes.
+#[derive(Debug, Clone)]
+pub struct ValidatingCodec<T, U> {
+  _pd: PhantomData<(T, U)>,
+}
+
+impl<T, U> Default for ValidatingCodec<T, U> {
+  fn default() -> Self {
+    Self { _pd: PhantomData }
+  }
+}
+
+impl<T, U> Codec for ValidatingCodec<T, U>
+where
+  T: Message + Send + 'static,
+  U: Message + Default + ProtoValidator + Send + 'static,
+{
+  type Encode = T;
+  type Decode = U;
+
+  type Encoder = ProstEncoder<T>;
+  type Decoder = ValidatingDecoder<U>;
+
+  fn encoder(&mut self) -> Self::Encoder {
+    ProstEncoder::new(BufferSettings::default())
+  }
+
+  fn decoder(&mut self) -> Self::Decoder {
+    ValidatingDecoder {
+      inner: ProstDecoder::new(BufferSettings::default()),
+      error: None,
+    }
+  }
+}
+
+/// The [`Decoder`] of [`ValidatingCodec`].
+///
+/// After the first invalid message, it keeps returning the same status, so that none of the following messages of the stream are decoded.
+#[derive(Debug)]
+pub struct ValidatingDecoder<U> {
+  inner: ProstDecoder<U>,
+  error: Option<Status>,
+}
+
+impl<U> Decoder for ValidatingDecoder<U>
+where
+  U: Message + Default + ProtoValidator,
+{
+  type Item = U;
+  type Error = Status;
+
+  fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+    if let Some(status) = &self.error {
+      src.advance(src.remaining());
+
+      return Err(status.clone());
+    }
+
+    let Some(message) = self.inner.decode(src)? else {
+      return Ok(None);
+    };
+
+    if let Err(violations) = message.validate() {
+      let status = Status::from(violations);
+      self.error = Some(status.clone());
+
+      return Err(status);
+    }
+
+    Ok(Some(message))
+  }
+
+  fn buffer_settings(&self) -> BufferSettings {
+    self.inner.buffer_settings()
+  }
+}
+
+/// The error of the calls of the clients that validate the requests.
+#[derive(Debug, Error, Clone)]
+pub enum CallError {
+  /// The request did not pass the validation, so it was not sent.
+  #[error("{0}")]
+  Invalid(#[from] Violations),
+  /// The request was sent, and the call failed with this status.
+  #[error(transparent)]
+  Status(#[from] Status),
+}
+
+impl CallError {
+  /// Returns the violations, if the request was not sent because it was not valid.
+  pub fn violations(&self) -> Option<&Violations> {
+    match self {
+      Self::Invalid(violations) => Some(violations),
+      Self::Status(_) => None,
+    }
+  }
+}
+
+/// The violations are converted like in the [`From<Violations>`](Violations) implementation for [`Status`], so that a service that makes calls to another one can return the error to its own clients.
+impl From<CallError> for Status {
+  fn from(error: CallError) -> Self {
+    match error {
+      CallError::Invalid(violations) => violations.into(),
+      CallError::Status(status) => status,
+    }
+  }
+}
+
+/// Creates the [`Request`] for a call after validating its message, so that the invalid messages are never sent.
+///
+/// This is implemented for the messages with validators and for the requests that contain them (to keep their metadata and extensions).
+pub trait IntoValidatedRequest<T> {
+  /// Validates the message, and returns the request that contains it.
+  fn into_validated_request(self) -> Result<Request<T>, Violations>;
+}
+
+impl<T: ProtoValidator> IntoValidatedRequest<T> for T {
+  fn into_validated_request(self) -> Result<Request<T>, Violations> {
+    self.validate()?;
+
+    Ok(Request::new(self))
+  }
+}
+
+impl<T: ProtoValidator> IntoValidatedRequest<T> for Request<T> {
+  fn into_validated_request(self) -> Result<Request<T>, Violations> {
+    self.get_ref().validate()?;
+
+    Ok(self)
+  }
+}
+
+#[derive(Default)]
+struct StreamState {
+  violations: Option<Violations>,
+  waker: Option<Waker>,
+}
+
+/// The stream passed to the call by [`call_with_validated_stream`], which validates each message before it is sent.
+///
+/// At the first invalid message, it stops yielding messages without ending, so that the server never receives a complete stream.
+pub struct ValidatedStream<S> {
+  inner: Pin<Box<S>>,
+  state: Arc<Mutex<StreamState>>,
+}
+
+impl<S> Stream for ValidatedStream<S>
+where
+  S: Stream,
+  S::Item: ProtoValidator,
+{
+  type Item = S::Item;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    if self.state.lock().unwrap().violations.is_some() {
+      return Poll::Pending;
+    }
+
+    match self.inner.as_mut().poll_next(cx) {
+      Poll::Ready(Some(message)) => match message.validate() {
+        Ok(()) => Poll::Ready(Some(message)),
+        Err(violations) => {
+          let mut state = self.state.lock().unwrap();
+          state.violations = Some(violations);
+
+          if let Some(waker) = state.waker.take() {
+            waker.wake();
+          }
+
+          Poll::Pending
+        }
+      },
+      poll => poll,
+    }
+  }
+}
+
+/// Makes a call with a request stream whose messages are validated before they are sent.
+///
+/// The stream is passed to the `call` as a [`ValidatedStream`]. When one of the messages is not valid, the call is dropped (which cancels it, so the server sees a cancelled stream rather than one that ended normally), and its violations are returned. The messages that came before it have already been sent by then.
+///
+/// ```rust,ignore
+/// let stream = tokio_stream::iter(requests);
+///
+/// let response = call_with_validated_stream(stream, |stream| client.record_route(stream)).await?;
+/// ```
+pub async fn call_with_validated_stream<S, F, Fut, R>(stream: S, call: F) -> Result<R, CallError>
+where
+  S: Stream,
+  S::Item: ProtoValidator,
+  F: FnOnce(ValidatedStream<S>) -> Fut,
+  Fut: Future<Output = Result<R, Status>>,
+{
+  let state = Arc::new(Mutex::new(StreamState::default()));
+
+  let mut call = pin!(call(ValidatedStream {
+    inner: Box::pin(stream),
+    state: state.clone(),
+  }));
+
+  poll_fn(|cx| {
+    // The stream may be polled by another task, such as the one of the connection
+    state.lock().unwrap().waker = Some(cx.waker().clone());
+
+    let result = call.as_mut().poll(cx);
+
+    if let Some(violations) = state.lock().unwrap().violations.take() {
+      return Poll::Ready(Err(CallError::Invalid(violations)));
+    }
+
+    result.map_err(CallError::from)
+  })
+  .await
+}
@@ -0,0 +1,188 @@
+//! A tower [`Layer`] that validates the messages of the requests before they reach the inner service.
+//!
+//! The layer works with any type of request: a projection selects the message to validate, and the violations are mapped to the response (or to the error) of the inner service, so that the service does not need to know about protocheck.
+//!
+//! ```rust,ignore
+//! let service = ServiceBuilder::new()
+//!   .layer(ValidateRequestLayer::new(
+//!     |request: &Envelope| request.message.as_ref(),
+//!     |violations| Ok(Reply::invalid(violations)),
+//!   ))
+//!   .service(handler);
+//! ```
+
+use std::{
+  fmt,
+  future::Future,
+  marker::PhantomData,
+  pin::Pin,
+  task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{types::protovalidate::Violations, ProtoValidator};
+
+/// A [`Layer`] that validates the message of each request (selected by a projection), and responds with the result of `on_invalid` when it is not valid, without calling the inner service.
+///
+/// The requests for which the projection returns `None` are passed to the inner service without being validated.
+pub struct ValidateRequestLayer<T, Req, P, F> {
+  project: P,
+  on_invalid: F,
+  _pd: PhantomData<fn(&Req) -> &T>,
+}
+
+impl<T, Req, P, F> ValidateRequestLayer<T, Req, P, F>
+where
+  T: ProtoValidator,
+  P: Fn(&Req) -> Option<&T>,
+{
+  /// Creates the layer with the projection that selects the message of the requests, and the function that maps the violations to the response (or to the error) of the service.
+  pub fn new<R>(project: P, on_invalid: F) -> Self
+  where
+    F: Fn(Violations) -> R,
+  {
+    Self {
+      project,
+      on_invalid,
+      _pd: PhantomData,
+    }
+  }
+}
+
+impl<T, Req, P: Clone, F: Clone> Clone for ValidateRequestLayer<T, Req, P, F> {
+  fn clone(&self) -> Self {
+    Self {
+      project: self.project.clone(),
+      on_invalid: self.on_invalid.clone(),
+      _pd: PhantomData,
+    }
+  }
+}
+
+impl<T, Req, P, F> fmt::Debug for ValidateRequestLayer<T, Req, P, F> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ValidateRequestLayer")
+      .finish_non_exhaustive()
+  }
+}
+
+impl<S, T, Req, P: Clone, F: Clone> Layer<S> for ValidateRequestLayer<T, Req, P, F> {
+  type Service = ValidateRequest<S, T, Req, P, F>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    ValidateRequest {
+      inner,
+      project: self.project.clone(),
+      on_invalid: self.on_invalid.clone(),
+      _pd: PhantomData,
+    }
+  }
+}
+
+/// The [`Service`] of [`ValidateRequestLayer`].
+pub struct ValidateRequest<S, T, Req, P, F> {
+  inner: S,
+  project: P,
+  on_invalid: F,
+  _pd: PhantomData<fn(&Req) -> &T>,
+}
+
+impl<S, T, Req, P, F> ValidateRequest<S, T, Req, P, F> {
+  /// Returns a reference to the inner service.
+  pub fn get_ref(&self) -> &S {
+    &self.inner
+  }
+
+  /// Consumes the service, and returns the inner one.
+  pub fn into_inner(self) -> S {
+    self.inner
+  }
+}
+
+impl<S: Clone, T, Req, P: Clone, F: Clone> Clone for ValidateRequest<S, T, Req, P, F> {
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      project: self.project.clone(),
+      on_invalid: self.on_invalid.clone(),
+      _pd: PhantomData,
+    }
+  }
+}
+
+impl<S: fmt::Debug, T, Req, P, F> fmt::Debug for ValidateRequest<S, T, Req, P, F> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ValidateRequest")
+      .field("inner", &self.inner)
+      .finish_non_exhaustive()
+  }
+}
+
+impl<S, T, Req, P, F> Service<Req> for ValidateRequest<S, T, Req, P, F>
+where
+  S: Service<Req>,
+  T: ProtoValidator,
+  P: Fn(&Req) -> Option<&T>,
+  F: Fn(Violations) -> Result<S::Response, S::Error>,
+{
+  type Response = S::Response;
+  type Error = S::Error;
+  type Future = ResponseFuture<S::Future, S::Response, S::Error>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx)
+  }
+
+  fn call(&mut self, req: Req) -> Self::Future {
+    if let Some(Err(violations)) = (self.project)(&req).map(T::validate) {
+      return ResponseFuture {
+        kind: Kind::Invalid {
+          result: Some((self.on_invalid)(violations)),
+        },
+      };
+    }
+
+    ResponseFuture {
+      kind: Kind::Inner {
+        future: self.inner.call(req),
+      },
+    }
+  }
+}
+
+pin_project! {
+  /// The future of the responses of [`ValidateRequest`].
+  pub struct ResponseFuture<Fut, Res, Err> {
+    #[pin]
+    kind: Kind<Fut, Res, Err>,
+  }
+}
+
+pin_project! {
+  #[project = KindProj]
+  enum Kind<Fut, Res, Err> {
+    Inner { #[pin] future: Fut },
+    Invalid { result: Option<Result<Res, Err>> },
+  }
+}
+
+impl<Fut, Res, Err> Future for ResponseFuture<Fut, Res, Err>
+where
+  Fut: Future<Output = Result<Res, Err>>,
+{
+  type Output = Result<Res, Err>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    match self.project().kind.project() {
+      KindProj::Inner { future } => future.poll(cx),
+      KindProj::Invalid { result } => Poll::Ready(
+        result
+          .take()
+          .expect("the future was polled after it completed"),
+      ),
+    }
+  }
+}
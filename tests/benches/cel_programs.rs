@@ -0,0 +1,57 @@
+// Compares the validation of a message with three Cel rules, whose programs are compiled once by the generated code,
+// with the compilation of the same programs (and the creation of the Cel context) on every call.
+// Run with `cargo bench -p tests --bench cel_programs`, and add `-- --save-baseline <name>` and `-- --baseline <name>`
+// to compare the results with the ones of another revision
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use protocheck::{
+  cel::{Program, Value},
+  validators::cel::cel_context,
+};
+
+use common::myapp;
+
+mod common;
+
+// The expressions of the message rules of CelCacheTests
+const EXPRESSIONS: [&str; 3] = [
+  "this.name != ''",
+  "this.items.size() <= 10",
+  "this.score >= 0 && this.score <= 100 ? '' : 'the score must be between 0 and 100, got %d'.format([this.score])",
+];
+
+fn cel_programs(c: &mut Criterion) {
+  let message = myapp::v1::CelCacheTests {
+    name: "obiwan".to_string(),
+    items: vec!["lightsaber".to_string(), "robe".to_string()],
+    score: 42,
+  };
+
+  assert!(message.validate().is_ok());
+
+  let mut group = c.benchmark_group("three_cel_rules");
+
+  group.bench_function("compiled_once", |b| {
+    b.iter(|| black_box(&message).validate().unwrap())
+  });
+
+  group.bench_function("compiled_per_call", |b| {
+    b.iter(|| {
+      let this: Value = black_box(&message).clone().try_into().unwrap();
+
+      for expression in EXPRESSIONS {
+        let program = Program::compile(black_box(expression)).unwrap();
+        let mut context = cel_context();
+        context.add_variable_from_value("this", this.clone());
+
+        black_box(program.execute(&context)).unwrap();
+      }
+    })
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, cel_programs);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+// The generated types shared by the benches
+#[allow(dead_code, clippy::all)]
+pub mod myapp {
+  pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/myapp.v1.rs"));
+  }
+}
+
+#[allow(dead_code, clippy::all)]
+pub mod prosttypes {
+  pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/prosttypes.v1.rs"));
+  }
+}
+
+#[allow(dead_code, clippy::all)]
+pub mod shared {
+  pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/shared.v1.rs"));
+  }
+}
@@ -0,0 +1,37 @@
+// Compares the validation of a message with 50 invalid items, which are all checked by `validate`, with
+// `validate_fail_fast`, which stops at the first one. Run with `cargo bench -p tests --bench fail_fast`
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use common::myapp;
+
+mod common;
+
+fn fail_fast(c: &mut Criterion) {
+  let message = myapp::v1::FailFastTests {
+    tags: vec![String::new(); 50],
+    ..Default::default()
+  };
+
+  assert_eq!(message.validate().unwrap_err().violations.len(), 50);
+  assert_eq!(
+    message.validate_fail_fast().unwrap_err().violations.len(),
+    1
+  );
+
+  let mut group = c.benchmark_group("fifty_violations");
+
+  group.bench_function("validate", |b| {
+    b.iter(|| black_box(&message).validate().unwrap_err())
+  });
+
+  group.bench_function("validate_fail_fast", |b| {
+    b.iter(|| black_box(&message).validate_fail_fast().unwrap_err())
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, fail_fast);
+criterion_main!(benches);
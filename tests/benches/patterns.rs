@@ -0,0 +1,41 @@
+// Compares the validation of the `string.pattern` rule, which uses the regex compiled once by the generated code,
+// with the compilation of the same regex on every call. Run with `cargo bench -p tests --bench patterns`
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use regex::Regex;
+
+use common::myapp;
+
+mod common;
+
+fn patterns(c: &mut Criterion) {
+  let message = myapp::v1::StringTests {
+    email: "obiwan@force.com".to_string(),
+    regex_test: "abc".to_string(),
+    contains_test: "abc".to_string(),
+    not_contains_test: "they're taking the hobbits to isengard".to_string(),
+    prefix_test: "abc".to_string(),
+    suffix_test: "abc".to_string(),
+  };
+
+  let mut group = c.benchmark_group("string_pattern");
+
+  // All of the rules of StringTests
+  group.bench_function("validate", |b| {
+    b.iter(|| black_box(&message).validate().unwrap())
+  });
+
+  // The pattern alone
+  group.bench_function("regex_compiled_per_call", |b| {
+    b.iter(|| {
+      let regex = Regex::new(black_box("^abc$")).unwrap();
+      regex.is_match(black_box(&message.regex_test))
+    })
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, patterns);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
 use std::{env, path::PathBuf};
 
 use prost_build::Config;
-use protocheck_build::{compile_protos_with_validators, get_proto_files_recursive};
+use protocheck_build::{get_proto_paths_recursive, ValidatorsBuilder, WellKnownTypes};
 use protoschema::*;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -79,7 +79,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
   let proto_include_paths = &["proto", "proto_deps"];
 
-  let files = get_proto_files_recursive(PathBuf::from("proto/myapp/v1"))?;
+  let files = get_proto_paths_recursive(PathBuf::from("proto/myapp/v1"))?;
+  let prost_types_files = get_proto_paths_recursive(PathBuf::from("proto/prosttypes/v1"))?;
+
+  // All of the files are compiled here, so that the descriptor used by the macros contains every package
+  let all_files: Vec<PathBuf> = files.iter().chain(&prost_types_files).cloned().collect();
 
   let mut config = Config::new();
   config
@@ -87,13 +91,68 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     .extern_path(".google.type", "::proto_types")
     .extern_path(".google.rpc", "::proto_types")
     .bytes(["."])
-    .enable_type_names()
+    .btree_map([".myapp.v1.BTreeMapTests"])
+    .type_name_domain(["."], "type.googleapis.com")
     .type_attribute(".", "#[derive(::serde::Serialize, ::serde::Deserialize)]")
+    // These are only packed in the Any fields by the tests, so the binary never constructs them
+    .type_attribute(".myapp.v1.AnyRulesTests.Card", "#[allow(dead_code)]")
+    .type_attribute(".myapp.v1.AnyRulesTests.Transfer", "#[allow(dead_code)]")
+    // The attribute that protocheck-build adds for the IGNORE_IF_DEFAULT_VALUE mode of the older versions of protovalidate
+    .field_attribute(
+      ".myapp.v1.IgnoreIfDefaultValueTests",
+      "#[protocheck(ignore_if_default)]",
+    )
+    // The mode resolved by name, which differs from the one that the number of the rules points to
+    .field_attribute(
+      ".myapp.v1.RenumberedIgnoreTests.choice.code",
+      "#[protocheck(ignore_if_unpopulated)]",
+    )
     .out_dir(&out_dir);
 
-  compile_protos_with_validators(&mut config, &files, proto_include_paths, &["myapp.v1"])?;
-
-  config.compile_protos(&files, proto_include_paths)?;
+  ValidatorsBuilder::new()
+    .proto_files(&all_files)
+    .include_paths(proto_include_paths)
+    .packages(["myapp.v1"])
+    .exclude_fields(["myapp.v1.ExclusionTest.legacy*"])
+    .exclude_messages(["myapp.v1.Unvalidated"])
+    .validated_requests(true)
+    .type_names(true)
+    .compile(&mut config)?;
+
+  // The services validate their requests in the codec
+  tonic_prost_build::configure()
+    .build_client(false)
+    .build_transport(false)
+    .codec_path(protocheck_build::VALIDATING_CODEC_PATH)
+    .compile_with_config(config, &all_files, &proto_include_paths.map(PathBuf::from))?;
+
+  // The clients are generated on their own, with the codec of prost, because the responses do not have validators
+  let clients_dir = out_dir.join("clients");
+  std::fs::create_dir_all(&clients_dir)?;
+
+  tonic_prost_build::configure()
+    .build_server(false)
+    .build_transport(false)
+    .extern_path(".myapp.v1", "crate::myapp::v1")
+    .extern_path(".buf.validate", "::protocheck::types::protovalidate")
+    .out_dir(&clients_dir)
+    .compile_protos(
+      &[PathBuf::from("proto/myapp/v1/services.proto")],
+      &proto_include_paths.map(PathBuf::from),
+    )?;
+
+  // Replaces the code of this package with one that uses prost-types for the well known types
+  let mut prost_types_config = Config::new();
+  prost_types_config.out_dir(&out_dir);
+
+  ValidatorsBuilder::new()
+    .proto_files(&prost_types_files)
+    .include_paths(proto_include_paths)
+    .packages(["prosttypes.v1"])
+    .well_known_types(WellKnownTypes::ProstTypes)
+    .compile(&mut prost_types_config)?;
+
+  prost_types_config.compile_protos(&prost_types_files, proto_include_paths)?;
 
   println!(
     "cargo:rustc-env=PROTO_DESCRIPTOR_SET={}",
@@ -6,6 +6,25 @@ mod myapp {
   }
 }
 
+mod prosttypes {
+  pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/prosttypes.v1.rs"));
+  }
+}
+
+mod shared {
+  pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/shared.v1.rs"));
+  }
+}
+
+// The clients of the services of myapp.v1, which are generated without the validating codec
+mod clients {
+  pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/clients/myapp.v1.rs"));
+  }
+}
+
 fn main() {}
 
 #[cfg(test)]
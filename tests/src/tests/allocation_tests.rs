@@ -0,0 +1,149 @@
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  cell::Cell,
+  collections::HashMap,
+};
+
+use protocheck::types::protovalidate::{field_path_element::Subscript, FieldPath, Violation};
+
+use crate::myapp::v1::{
+  allocation_tests::{Branch, Contact, Leaf},
+  AllocationTests,
+};
+
+// Counts the allocations made by each thread, so that the tests running in parallel don't affect each other
+struct CountingAllocator;
+
+thread_local! {
+  static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.with(|count| count.set(count.get() + 1));
+    unsafe { System.alloc(layout) }
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) }
+  }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+  let before = ALLOCATIONS.with(Cell::get);
+  let output = f();
+
+  (output, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn leaf(sku: &str) -> Leaf {
+  Leaf {
+    sku: sku.to_string(),
+    quantity: 1,
+  }
+}
+
+fn branch(depth: usize) -> Branch {
+  Branch {
+    leaf: Some(leaf("abc")),
+    leaves: vec![leaf("def"), leaf("ghi")],
+    by_sku: HashMap::from([("jkl".to_string(), leaf("jkl"))]),
+    labels: HashMap::from([(-1, "label".to_string())]),
+    child: (depth > 0).then(|| Box::new(branch(depth - 1))),
+  }
+}
+
+fn message() -> AllocationTests {
+  AllocationTests {
+    root: Some(branch(5)),
+    email: "obiwan@jedi.org".to_string(),
+    tags: vec!["red".to_string(), "blue".to_string()],
+    contact: Some(Contact::Phone("555-1234".to_string())),
+  }
+}
+
+#[test]
+fn valid_messages_do_not_allocate() {
+  let msg = message();
+
+  // The first call initializes the thread locals and the lazy statics
+  msg.validate().unwrap();
+
+  let (result, count) = allocations(|| msg.validate());
+  assert!(result.is_ok());
+  assert_eq!(count, 0);
+
+  let (result, count) = allocations(|| msg.validate_fail_fast());
+  assert!(result.is_ok());
+  assert_eq!(count, 0);
+}
+
+#[test]
+fn paths_of_the_deep_violations() {
+  let mut msg = message();
+
+  let mut branch = msg.root.as_mut().unwrap();
+  while let Some(child) = branch.child.as_mut() {
+    branch = child;
+  }
+  branch.by_sku.insert("jk".to_string(), leaf("mn"));
+
+  let violations = msg.validate().unwrap_err();
+
+  assert_eq!(
+    violations.to_string(),
+    [
+      r#"root.child.child.child.child.child.by_sku["jk"]: must contain at least 3 characters (string.min_len)"#,
+      r#"root.child.child.child.child.child.by_sku["jk"].sku: must contain at least 3 characters (string.min_len)"#,
+    ]
+    .join("\n")
+  );
+}
+
+// The Vec and the strings owned by a violation, which are the only allocations needed to create it
+fn owned_allocations(violation: &Violation) -> usize {
+  let path = |path: &Option<FieldPath>| {
+    path.as_ref().map_or(0, |path| {
+      1 + path
+        .elements
+        .iter()
+        .map(|element| {
+          usize::from(element.field_name.is_some())
+            + usize::from(matches!(element.subscript, Some(Subscript::StringKey(_))))
+        })
+        .sum::<usize>()
+    })
+  };
+
+  path(&violation.field)
+    + path(&violation.rule)
+    + usize::from(violation.rule_id.is_some())
+    + usize::from(violation.message.is_some())
+}
+
+#[test]
+fn violations_allocate_only_what_they_own() {
+  let mut msg = message();
+  msg.tags.push("yellow".to_string());
+  msg
+    .root
+    .as_mut()
+    .unwrap()
+    .by_sku
+    .insert("jk".to_string(), leaf("jkl"));
+
+  msg.validate().unwrap_err();
+
+  let (result, count) = allocations(|| msg.validate());
+  let violations = result.unwrap_err();
+
+  assert_eq!(violations.len(), 2);
+  // The only other allocation is the one of the Vec of the violations
+  assert_eq!(
+    count,
+    1 + violations.iter().map(owned_allocations).sum::<usize>()
+  );
+}
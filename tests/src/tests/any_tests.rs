@@ -0,0 +1,98 @@
+use protocheck::types::{Any, Duration, UnpackError};
+
+use crate::myapp::v1::{
+  any_rules_tests::{Card, Transfer},
+  AnyRulesTests,
+};
+
+fn card() -> Card {
+  Card {
+    number: "4242".to_string(),
+  }
+}
+
+#[test]
+fn pack_and_unpack() {
+  let any = Any::pack(&card());
+
+  assert_eq!(
+    any.type_url,
+    "type.googleapis.com/myapp.v1.AnyRulesTests.Card"
+  );
+  assert!(any.is::<Card>());
+  assert!(!any.is::<Transfer>());
+  assert_eq!(any.unpack::<Card>(), Ok(card()));
+
+  // The packed messages match the type URLs of the `any` rules
+  let message = AnyRulesTests {
+    payment: Some(any.clone()),
+    refund: None,
+    receipt: Some(Any::pack(&Transfer {
+      iban: "DE89".to_string(),
+    })),
+  };
+  assert!(message.validate().is_ok());
+}
+
+#[test]
+fn mismatched_type_urls() {
+  let any = Any::pack(&card());
+
+  assert_eq!(
+    any.unpack::<Transfer>(),
+    Err(UnpackError::TypeMismatch {
+      expected: "myapp.v1.AnyRulesTests.Transfer".to_string(),
+      type_url: "type.googleapis.com/myapp.v1.AnyRulesTests.Card".to_string(),
+    })
+  );
+  assert!(matches!(
+    any.unpack::<Duration>(),
+    Err(UnpackError::TypeMismatch { .. })
+  ));
+
+  // The full name must be the whole last segment, after a slash
+  for type_url in [
+    "myapp.v1.AnyRulesTests.Card",
+    "type.googleapis.com/v1.AnyRulesTests.Card",
+    "type.googleapis.com/.myapp.v1.AnyRulesTests.Card",
+    "type.googleapis.com/myapp.v1.AnyRulesTests.Card/",
+  ] {
+    let any = Any {
+      type_url: type_url.to_string(),
+      ..any.clone()
+    };
+
+    assert!(!any.is::<Card>(), "{type_url}");
+    assert!(any.unpack::<Card>().is_err(), "{type_url}");
+  }
+}
+
+#[test]
+fn truncated_payloads() {
+  let mut any = Any::pack(&card());
+  any.value.truncate(any.value.len() - 2);
+
+  assert!(any.is::<Card>());
+  assert!(matches!(any.unpack::<Card>(), Err(UnpackError::Decode(_))));
+}
+
+#[test]
+fn custom_prefixes() {
+  for prefix in ["type.example.com", "type.example.com/"] {
+    let any = Any::pack_with_prefix(&card(), prefix);
+
+    assert_eq!(any.type_url, "type.example.com/myapp.v1.AnyRulesTests.Card");
+    // The prefix is not checked when unpacking
+    assert_eq!(any.unpack::<Card>(), Ok(card()));
+  }
+
+  // Unlike the `any` rules, which compare the whole URL
+  let message = AnyRulesTests {
+    receipt: Some(Any::pack_with_prefix(
+      &Transfer::default(),
+      "type.example.com",
+    )),
+    ..Default::default()
+  };
+  assert!(message.validate().is_err());
+}
@@ -0,0 +1,171 @@
+use axum::{
+  body::{to_bytes, Body},
+  http::{header::CONTENT_TYPE, Request, StatusCode},
+  response::{IntoResponse, Response},
+  routing::post,
+  Router,
+};
+use prost::Message;
+use protocheck::{
+  axum::{Protobuf, ProtobufLenient, ProtobufRejection, WithRejection},
+  types::protovalidate::Violations,
+};
+use tower::ServiceExt;
+
+use crate::myapp::v1::GreetRequest;
+
+// A rejection with the app's own error body
+struct ApiError(String);
+
+impl From<ProtobufRejection> for ApiError {
+  fn from(rejection: ProtobufRejection) -> Self {
+    Self(format!("invalid request: {rejection}"))
+  }
+}
+
+impl IntoResponse for ApiError {
+  fn into_response(self) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, self.0).into_response()
+  }
+}
+
+fn app() -> Router {
+  Router::new()
+    .route(
+      "/greet",
+      post(|Protobuf(request): Protobuf<GreetRequest>| async move {
+        format!("Hello, {}!", request.name)
+      }),
+    )
+    .route(
+      "/greet_lenient",
+      post(
+        |ProtobufLenient(request): ProtobufLenient<GreetRequest>| async move {
+          format!("Hello, {}!", request.name)
+        },
+      ),
+    )
+    .route(
+      "/greet_custom",
+      post(
+        |WithRejection(Protobuf(request), _): WithRejection<Protobuf<GreetRequest>, ApiError>| async move {
+          format!("Hello, {}!", request.name)
+        },
+      ),
+    )
+}
+
+fn name(name: &str) -> Vec<u8> {
+  GreetRequest {
+    name: name.to_string(),
+  }
+  .encode_to_vec()
+}
+
+async fn send(
+  path: &str,
+  content_type: Option<&str>,
+  accept: Option<&str>,
+  body: Vec<u8>,
+) -> (StatusCode, Option<String>, Vec<u8>) {
+  let mut request = Request::post(path);
+  if let Some(content_type) = content_type {
+    request = request.header(CONTENT_TYPE, content_type);
+  }
+  if let Some(accept) = accept {
+    request = request.header("accept", accept);
+  }
+
+  let response = app()
+    .oneshot(request.body(Body::from(body)).unwrap())
+    .await
+    .unwrap();
+
+  let status = response.status();
+  let content_type = response
+    .headers()
+    .get(CONTENT_TYPE)
+    .map(|value| value.to_str().unwrap().to_string());
+  let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+  (status, content_type, body.to_vec())
+}
+
+const PROTOBUF: Option<&str> = Some("application/x-protobuf");
+
+#[tokio::test]
+async fn valid_requests() {
+  let (status, _, body) = send("/greet", PROTOBUF, None, name("obiwan")).await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, b"Hello, obiwan!");
+
+  // The parameters of the content type are ignored
+  let (status, _, _) = send(
+    "/greet",
+    Some("application/protobuf; proto=myapp.v1.GreetRequest"),
+    None,
+    name("obiwan"),
+  )
+  .await;
+  assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn invalid_bodies() {
+  let (status, _, _) = send("/greet", None, None, name("obiwan")).await;
+  assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+  let (status, _, _) = send("/greet", Some("application/json"), None, name("obiwan")).await;
+  assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+  let (status, _, body) = send("/greet", PROTOBUF, None, vec![0xff]).await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+  assert!(String::from_utf8(body)
+    .unwrap()
+    .starts_with("failed to decode the message"));
+}
+
+#[tokio::test]
+async fn invalid_messages() {
+  let expected = GreetRequest::default().validate().unwrap_err();
+
+  // The violations are encoded with protobuf by default
+  let (status, content_type, body) = send("/greet", PROTOBUF, None, name("")).await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+  assert_eq!(content_type.as_deref(), PROTOBUF);
+  assert_eq!(Violations::decode(body.as_slice()).unwrap(), expected);
+
+  // Or with JSON, when the client accepts it
+  let (status, content_type, body) = send(
+    "/greet",
+    PROTOBUF,
+    Some("text/plain, application/json; q=0.9"),
+    name(""),
+  )
+  .await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+  assert_eq!(content_type.as_deref(), Some("application/json"));
+  assert_eq!(
+    serde_json::from_slice::<Violations>(&body).unwrap(),
+    expected
+  );
+
+  // The lenient extractor does not validate the messages
+  let (status, _, body) = send("/greet_lenient", PROTOBUF, None, name("")).await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, b"Hello, !");
+}
+
+#[tokio::test]
+async fn custom_rejections() {
+  let (status, _, body) = send("/greet_custom", PROTOBUF, None, name("")).await;
+  assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+  assert_eq!(
+    String::from_utf8(body).unwrap(),
+    "invalid request: name: must contain at least 1 character (string.min_len)"
+  );
+
+  let (status, _, body) = send("/greet_custom", PROTOBUF, None, name("obiwan")).await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, b"Hello, obiwan!");
+}
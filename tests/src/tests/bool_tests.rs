@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use protocheck::types::{protovalidate::Violations, BoolValue};
+
+use crate::{myapp::v1::BoolConstTests, prosttypes::v1::BoolValueTest};
+
+fn bool_violations(test: &BoolConstTests) -> Vec<(String, String, String)> {
+  match test.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => {
+      let mut violations: Vec<(String, String, String)> = violations
+        .iter()
+        .map(|v| {
+          (
+            v.field_path_str().unwrap(),
+            v.rule_id().to_string(),
+            v.message().to_string(),
+          )
+        })
+        .collect();
+      violations.sort();
+      violations
+    }
+  }
+}
+
+#[test]
+fn bool_const() {
+  let valid = BoolConstTests {
+    terms_accepted: true,
+    newsletter: Some(false),
+    confirmations: vec![true, true],
+    verified: Some(BoolValue { value: true }),
+    flags: HashMap::from([("beta".to_string(), BoolValue { value: false })]),
+  };
+
+  assert!(bool_violations(&valid).is_empty());
+
+  // The optional fields are only validated when they are set
+  let unset = BoolConstTests {
+    newsletter: None,
+    verified: None,
+    ..valid.clone()
+  };
+
+  assert!(bool_violations(&unset).is_empty());
+
+  let invalid = BoolConstTests {
+    terms_accepted: false,
+    newsletter: Some(true),
+    confirmations: vec![true, false],
+    verified: Some(BoolValue { value: false }),
+    flags: HashMap::from([("beta".to_string(), BoolValue { value: true })]),
+  };
+
+  let violation = |path: &str, message: &str| {
+    (
+      path.to_string(),
+      "bool.const".to_string(),
+      message.to_string(),
+    )
+  };
+
+  assert_eq!(
+    bool_violations(&invalid),
+    [
+      violation("confirmations[1]", "must be equal to true"),
+      violation(r#"flags["beta"]"#, "must be equal to false"),
+      violation("newsletter", "must be equal to false"),
+      violation("terms_accepted", "must be equal to true"),
+      violation("verified", "must be equal to true"),
+    ]
+  );
+}
+
+#[test]
+fn prost_types_bool_value() {
+  // prost turns the wrappers into the wrapped type when the well known types are not compiled
+  let valid = BoolValueTest {
+    verified: Some(true),
+    confirmations: vec![true],
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = BoolValueTest {
+    verified: Some(false),
+    confirmations: vec![true, false],
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  let paths: Vec<String> = violations
+    .iter()
+    .map(|v| v.field_path_str().unwrap())
+    .collect();
+
+  assert_eq!(paths, ["verified", "confirmations[1]"]);
+}
@@ -1,7 +1,7 @@
 use bytes::Bytes;
 use protocheck::types::protovalidate::Violations;
 
-use crate::myapp::v1::BytesTests;
+use crate::{myapp::v1::BytesTests, prosttypes::v1::VecBytesTest};
 
 #[test]
 fn bytes_tests() {
@@ -22,8 +22,11 @@ fn bytes_tests() {
   assert_eq!(violations.len(), 7);
 
   let correct = b"abc";
-  let valid_ipv4 = b"192.168.1.0";
-  let valid_ipv6 = b"2a01:c23:7b6d:a900:1de7:5cbe:d8d2:f4a1";
+  // The ip rules expect the raw addresses
+  let valid_ipv4 = &[192, 168, 1, 0];
+  let valid_ipv6 = &[
+    0x2a, 0x01, 0x0c, 0x23, 0x7b, 0x6d, 0xa9, 0x00, 0x1d, 0xe7, 0x5c, 0xbe, 0xd8, 0xd2, 0xf4, 0xa1,
+  ];
 
   let test = BytesTests {
     contains_field: Bytes::from_static(correct),
@@ -37,3 +40,118 @@ fn bytes_tests() {
 
   assert!(test.validate().is_ok())
 }
+
+#[test]
+fn raw_ip_lengths() {
+  let rule_ids = |ip: &'static [u8], ipv4: &'static [u8], ipv6: &'static [u8]| -> Vec<String> {
+    let test = BytesTests {
+      contains_field: Bytes::from_static(b"abc"),
+      prefix_field: Bytes::from_static(b"abc"),
+      pattern_field: Bytes::from_static(b"abc"),
+      suffix_field: Bytes::from_static(b"abc"),
+      ip: Bytes::from_static(ip),
+      ipv4: Bytes::from_static(ipv4),
+      ipv6: Bytes::from_static(ipv6),
+    };
+
+    match test.validate() {
+      Ok(()) => Vec::new(),
+      Err(Violations { violations }) => {
+        violations.iter().map(|v| v.rule_id().to_string()).collect()
+      }
+    }
+  };
+
+  assert!(rule_ids(&[0; 16], &[0; 4], &[0; 16]).is_empty());
+
+  // The textual addresses are not accepted
+  assert_eq!(
+    rule_ids(b"10.0.0.1", b"10.0.0.1", b"::1"),
+    ["bytes.ip", "bytes.ipv4", "bytes.ipv6"]
+  );
+
+  assert_eq!(
+    rule_ids(&[0; 5], &[0; 16], &[0; 4]),
+    ["bytes.ip", "bytes.ipv4", "bytes.ipv6"]
+  );
+
+  assert_eq!(
+    rule_ids(&[], &[0; 3], &[0; 17]),
+    ["bytes.ip_empty", "bytes.ipv4", "bytes.ipv6"]
+  );
+}
+
+#[test]
+fn non_utf8_pattern() {
+  let test = BytesTests {
+    contains_field: Bytes::from_static(b"abc"),
+    prefix_field: Bytes::from_static(b"abc"),
+    pattern_field: Bytes::from_static(b"abc\xff\xfe"),
+    suffix_field: Bytes::from_static(b"abc"),
+    ip: Bytes::from_static(&[0; 4]),
+    ipv4: Bytes::from_static(&[0; 4]),
+    ipv6: Bytes::from_static(&[0; 16]),
+  };
+
+  assert!(test.validate().is_ok());
+
+  let test = BytesTests {
+    pattern_field: Bytes::from_static(b"\xffabc"),
+    ..test
+  };
+
+  let Violations { violations } = test.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "bytes.pattern");
+}
+
+#[test]
+fn vec_bytes() {
+  let valid = VecBytesTest {
+    payload: b"abcz".to_vec(),
+    address: vec![127, 0, 0, 1],
+    token: b"b".to_vec(),
+    magic: vec![0xca, 0xfe],
+    chunks: vec![b"a".to_vec(), b"bc".to_vec()],
+    checksum: Some(vec![1, 2, 3]),
+    small: vec![0xff],
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = VecBytesTest {
+    payload: b"\xffabcdefg".to_vec(),
+    address: b"127.0.0.1".to_vec(),
+    token: b"c".to_vec(),
+    magic: vec![0xca],
+    chunks: vec![b"a".to_vec(), b"a".to_vec(), b"abc".to_vec()],
+    checksum: Some(vec![1]),
+    small: vec![0; 4],
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  let rule_ids: Vec<&str> = violations.iter().map(|v| v.rule_id()).collect();
+
+  for rule_id in [
+    "bytes.max_len",
+    "bytes.pattern",
+    "bytes.prefix",
+    "bytes.suffix",
+    "bytes.ipv4",
+    "bytes.in",
+    "bytes.const",
+    "repeated.unique",
+    "bytes.len",
+    "small",
+  ] {
+    assert!(rule_ids.contains(&rule_id), "missing {}", rule_id);
+  }
+
+  // The third chunk is also too long
+  assert_eq!(
+    rule_ids.iter().filter(|id| **id == "bytes.max_len").count(),
+    2
+  );
+}
@@ -0,0 +1,40 @@
+use std::{sync::Barrier, thread};
+
+use super::rule_ids;
+use crate::myapp::v1::CelCacheTests;
+
+#[test]
+fn concurrent_first_use() {
+  let threads = 8;
+  let barrier = Barrier::new(threads);
+
+  // The static programs and Cel context are initialized by whichever thread gets there first
+  thread::scope(|scope| {
+    for index in 0..threads {
+      let barrier = &barrier;
+
+      scope.spawn(move || {
+        let valid = CelCacheTests {
+          name: "obiwan".to_string(),
+          items: vec!["lightsaber".to_string()],
+          score: index as i32,
+        };
+        let invalid = CelCacheTests {
+          name: "".to_string(),
+          items: vec!["robe".to_string(); 11],
+          score: 101,
+        };
+
+        barrier.wait();
+
+        for _ in 0..100 {
+          assert!(rule_ids(&valid).is_empty());
+          assert_eq!(
+            rule_ids(&invalid),
+            ["name.not_empty", "items.max_items", "score.range"]
+          );
+        }
+      });
+    }
+  });
+}
@@ -0,0 +1,85 @@
+use protocheck::{
+  types::{protovalidate::Violations, Duration, Timestamp},
+  validators::timestamps::with_now,
+};
+
+use super::rule_ids;
+use crate::myapp::v1::CelEnvironmentTests;
+
+#[test]
+fn cel_environment() {
+  let now = Timestamp::new(1_000_000, 0);
+  let day = Duration::new(86_400, 0);
+  let nanosecond = Duration::new(0, 1);
+
+  let test = |name: &str, created: Timestamp, deadline: Timestamp| CelEnvironmentTests {
+    name: name.to_string(),
+    created: Some(created),
+    deadline: Some(deadline),
+  };
+
+  // `this` and `rules`, with the max_len of the string rules
+  assert!(with_now(now, || rule_ids(&test("abcde", now, now + day))).is_empty());
+  assert_eq!(
+    with_now(now, || rule_ids(&test("abcdef", now, now + day))),
+    ["name.half_max_len"]
+  );
+
+  // The same `now` is used by all of the rules, including gt_now, so the rules fail exactly at the boundaries
+  assert_eq!(
+    with_now(now, || rule_ids(&test("", now + nanosecond, now + day))),
+    ["created.not_future"]
+  );
+  assert_eq!(
+    with_now(now, || rule_ids(&test("", now - day, now))),
+    ["timestamp.gt_now"]
+  );
+  assert_eq!(
+    with_now(now, || { rule_ids(&test("", now, now + day + nanosecond)) }),
+    ["deadline.within_a_day"]
+  );
+
+  // The message rules are also evaluated with `now`
+  assert_eq!(
+    with_now(now, || rule_ids(&test("", now + day, now + day))),
+    ["created_before_deadline", "created.not_future"]
+  );
+
+  let current = Timestamp::now();
+
+  assert!(rule_ids(&test("abc", current, current + Duration::new(3600, 0))).is_empty());
+}
+
+// The rules that use `now` are not evaluated against another time when the pinned one is not a valid Cel timestamp
+#[test]
+fn out_of_range_now() {
+  let now = Timestamp::new(300_000_000_000, 0);
+  let test = CelEnvironmentTests {
+    name: String::new(),
+    created: Some(Timestamp::new(1_000_000, 0)),
+    deadline: Some(Timestamp::new(2_000_000, 0)),
+  };
+
+  let Violations { violations } = with_now(now, || test.validate()).unwrap_err();
+
+  let errors: Vec<(&str, &str)> = violations
+    .iter()
+    .map(|v| (v.rule_id(), v.message()))
+    .collect();
+
+  assert_eq!(
+    errors,
+    [
+      (
+        "created.not_future",
+        "failed to evaluate the rule: the current time +11476-08-15T05:20:00Z is out of the range of the Cel timestamps"
+      ),
+      // The other rules still use the pinned time
+      ("timestamp.gt_now", "must be in the future"),
+      (
+        "deadline.within_a_day",
+        "failed to evaluate the rule: the current time +11476-08-15T05:20:00Z is out of the range of the Cel timestamps"
+      ),
+    ]
+  );
+}
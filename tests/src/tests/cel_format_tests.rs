@@ -0,0 +1,90 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::CelFormatTests;
+
+fn valid() -> CelFormatTests {
+  CelFormatTests {
+    amount: 5,
+    ratio: 0.5,
+    template: "".to_string(),
+  }
+}
+
+fn violations(test: &CelFormatTests) -> Vec<(String, String)> {
+  match test.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.rule_id().to_string(), v.message().to_string()))
+      .collect(),
+  }
+}
+
+#[test]
+fn formatted_messages() {
+  assert!(violations(&valid()).is_empty());
+
+  // The string returned by the expression is the message of the violation
+  assert_eq!(
+    violations(&CelFormatTests {
+      amount: 12,
+      ..valid()
+    }),
+    [(
+      "amount.range".to_string(),
+      "must be between 1 and 10, got 12".to_string()
+    )]
+  );
+  assert_eq!(
+    violations(&CelFormatTests {
+      ratio: 1052.5,
+      ..valid()
+    }),
+    [(
+      "ratio.max".to_string(),
+      "must be at most 1.00, got 1.052500e+03".to_string()
+    )]
+  );
+  assert_eq!(
+    violations(&CelFormatTests {
+      amount: 7,
+      ..valid()
+    }),
+    [(
+      "amount.not_seven".to_string(),
+      "the amount 7 is not allowed".to_string()
+    )]
+  );
+
+  assert!(violations(&CelFormatTests {
+    template: "value: %d".to_string(),
+    ..valid()
+  })
+  .is_empty());
+}
+
+#[test]
+fn malformed_templates() {
+  // The evaluation errors are reported as violations of the rule, with the error as the message
+  for (template, error) in [
+    ("%d and %d", "not enough arguments"),
+    ("no clauses", "too many arguments"),
+    ("%s%q", "unrecognized formatting clause %q"),
+    ("value: %d%", "unterminated formatting clause"),
+  ] {
+    let violations = violations(&CelFormatTests {
+      template: template.to_string(),
+      ..valid()
+    });
+
+    assert_eq!(violations.len(), 1, "{template}");
+
+    let (rule_id, message) = &violations[0];
+
+    assert_eq!(rule_id, "template.format");
+    assert!(
+      message.starts_with("failed to evaluate the rule") && message.contains(error),
+      "{template}: {message}"
+    );
+  }
+}
@@ -0,0 +1,134 @@
+use bytes::Bytes;
+
+use super::rule_ids;
+use crate::myapp::v1::CelFunctionsTests;
+
+fn valid() -> CelFunctionsTests {
+  CelFunctionsTests {
+    email: "me@example.com".to_string(),
+    hostname: "example.com".to_string(),
+    uri_ref: "/path".to_string(),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn string_functions() {
+  assert!(rule_ids(&valid()).is_empty());
+
+  // The functions use the same checks as the string rules, so they always agree with them (the empty strings have their own rule id)
+  for email in [
+    "me@example.com",
+    "a.b+c@sub.example.org",
+    "not an email",
+    "me@",
+    "@example.com",
+    "",
+  ] {
+    let ids = rule_ids(&CelFunctionsTests {
+      email: email.to_string(),
+      ..valid()
+    });
+
+    assert_eq!(
+      ids.iter().any(|id| id.starts_with("string.email")),
+      ids.iter().any(|id| id == "email.is_email"),
+      "{email}: {ids:?}"
+    );
+  }
+
+  for hostname in [
+    "example.com",
+    "localhost",
+    "-example.com",
+    "example.123",
+    "a..b",
+    "",
+  ] {
+    let ids = rule_ids(&CelFunctionsTests {
+      hostname: hostname.to_string(),
+      ..valid()
+    });
+
+    assert_eq!(
+      ids.iter().any(|id| id.starts_with("string.hostname")),
+      ids.iter().any(|id| id == "hostname.is_hostname"),
+      "{hostname}: {ids:?}"
+    );
+  }
+
+  let ip = |ip: &str, ipv4: &str, ipv6: &str| {
+    rule_ids(&CelFunctionsTests {
+      ip: ip.to_string(),
+      ipv4: ipv4.to_string(),
+      ipv6: ipv6.to_string(),
+      ..valid()
+    })
+  };
+
+  assert!(ip("127.0.0.1", "127.0.0.1", "::1").is_empty());
+  assert!(ip("::1", "10.0.0.1", "fe80::a%en1").is_empty());
+  assert_eq!(
+    ip("localhost", "::1", "127.0.0.1"),
+    ["ip.is_ip", "ipv4.is_ipv4", "ipv6.is_ipv6"]
+  );
+
+  let uri = |uri: &str, uri_ref: &str| {
+    rule_ids(&CelFunctionsTests {
+      uri: uri.to_string(),
+      uri_ref: uri_ref.to_string(),
+      ..valid()
+    })
+  };
+
+  assert!(uri("https://example.com/path?q=1", "../relative").is_empty());
+  assert_eq!(
+    uri("/relative", "::invalid"),
+    ["uri.is_uri", "uri_ref.is_uri_ref"]
+  );
+}
+
+#[test]
+fn double_functions() {
+  let floats = |ratio: f64, limit: f64| {
+    rule_ids(&CelFunctionsTests {
+      ratio,
+      limit,
+      ..valid()
+    })
+  };
+
+  assert!(floats(0.5, f64::INFINITY).is_empty());
+  assert_eq!(floats(f64::NAN, 0.0), ["ratio.finite"]);
+  assert_eq!(floats(f64::INFINITY, 0.0), ["ratio.finite"]);
+  assert_eq!(
+    floats(f64::NEG_INFINITY, f64::NEG_INFINITY),
+    ["ratio.finite", "limit.not_negative_infinity"]
+  );
+}
+
+#[test]
+fn unique_function() {
+  let test = CelFunctionsTests {
+    tags: vec!["a".to_string(), "b".to_string()],
+    ids: vec![1, 2, 3],
+    flags: vec![true, false],
+    hashes: vec![Bytes::from_static(b"ab"), Bytes::from_static(b"ba")],
+    ..valid()
+  };
+
+  assert!(rule_ids(&test).is_empty());
+
+  let duplicates = CelFunctionsTests {
+    tags: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+    ids: vec![1, 2, 1],
+    flags: vec![true, true],
+    hashes: vec![Bytes::from_static(b"ab"), Bytes::from_static(b"ab")],
+    ..valid()
+  };
+
+  assert_eq!(
+    rule_ids(&duplicates),
+    ["tags.unique", "ids.unique", "flags.unique", "hashes.unique"]
+  );
+}
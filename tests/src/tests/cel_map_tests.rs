@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, HashMap};
+
+use protocheck::{
+  cel::{objects::Key, Program, Value as CelValue},
+  macros::TryIntoCelValue,
+  validators::cel::cel_context,
+};
+
+use super::sorted_rule_ids;
+use crate::myapp::v1::{cel_map_tests::Member, CelMapTests, TestEnum};
+
+fn member(name: &str) -> Member {
+  Member {
+    name: name.to_string(),
+  }
+}
+
+fn valid() -> CelMapTests {
+  CelMapTests {
+    labels: HashMap::from([("env".to_string(), "prod".to_string())]),
+    status: TestEnum::Active as i32,
+    history: vec![TestEnum::Active as i32],
+    statuses: HashMap::from([("obiwan".to_string(), TestEnum::Active as i32)]),
+    members: HashMap::from([(1, member("obiwan"))]),
+    flags: HashMap::from([(true, 1)]),
+  }
+}
+
+#[test]
+fn message_rules() {
+  assert!(sorted_rule_ids(&valid()).is_empty());
+
+  let mut labels = valid().labels;
+  labels.extend((0..3).map(|i| (format!("label_{i}"), "value".to_string())));
+
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests { labels, ..valid() }),
+    ["labels.max_pairs"]
+  );
+  // The enums are ints
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      status: TestEnum::Unspecified as i32,
+      ..valid()
+    }),
+    ["status.specified"]
+  );
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      history: vec![TestEnum::Active as i32, TestEnum::Inactive as i32],
+      ..valid()
+    }),
+    ["history.active"]
+  );
+  // The messages inside of the maps are converted like the other messages
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      members: HashMap::from([(1, member("obiwan")), (2, member(""))]),
+      ..valid()
+    }),
+    ["members.named"]
+  );
+}
+
+#[test]
+fn field_rules() {
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      labels: HashMap::from([("region".to_string(), "eu".to_string())]),
+      ..valid()
+    }),
+    ["labels.env"]
+  );
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      history: vec![TestEnum::Active as i32; 3],
+      ..valid()
+    }),
+    ["history.max_items"]
+  );
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      statuses: HashMap::from([
+        ("obiwan".to_string(), TestEnum::Active as i32),
+        ("anakin".to_string(), TestEnum::Unspecified as i32),
+      ]),
+      ..valid()
+    }),
+    ["statuses.active", "statuses.not_unspecified"]
+  );
+  // The uint32 keys are uints
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      members: HashMap::from([(0, member("obiwan"))]),
+      ..valid()
+    }),
+    ["members.ids"]
+  );
+  // The bool keys are bools
+  assert_eq!(
+    sorted_rule_ids(&CelMapTests {
+      flags: HashMap::from([(true, 1), (false, 0)]),
+      ..valid()
+    }),
+    ["flags.positive"]
+  );
+}
+
+// The derive works on any struct with these field shapes, not only on the ones generated by prost
+#[derive(Clone, TryIntoCelValue)]
+struct Profile {
+  name: String,
+}
+
+#[derive(Clone, TryIntoCelValue)]
+struct Account {
+  labels: HashMap<String, String>,
+  scores: BTreeMap<u32, f32>,
+  statuses: HashMap<String, i32>,
+  history: Vec<i32>,
+  profiles: BTreeMap<i64, Profile>,
+  flags: HashMap<bool, u64>,
+  main_profile: Option<Profile>,
+}
+
+fn account() -> Account {
+  Account {
+    labels: HashMap::from([("env".to_string(), "prod".to_string())]),
+    scores: BTreeMap::from([(7, 1.5)]),
+    statuses: HashMap::from([("obiwan".to_string(), TestEnum::Active as i32)]),
+    history: vec![TestEnum::Inactive as i32],
+    profiles: BTreeMap::from([(
+      -1,
+      Profile {
+        name: "obiwan".to_string(),
+      },
+    )]),
+    flags: HashMap::from([(true, 1)]),
+    main_profile: None,
+  }
+}
+
+fn evaluate(expression: &str, this: CelValue) -> CelValue {
+  let mut context = cel_context();
+  context.add_variable_from_value("this", this);
+
+  Program::compile(expression)
+    .unwrap()
+    .execute(&context)
+    .unwrap()
+}
+
+#[test]
+fn derived_conversions() {
+  let this: CelValue = account().try_into().unwrap();
+
+  let CelValue::Map(fields) = &this else {
+    panic!("a struct is converted to a map");
+  };
+
+  let field = |name: &str| fields.get(&Key::from(name)).unwrap().clone();
+
+  let CelValue::Map(scores) = field("scores") else {
+    panic!("a BTreeMap is converted to a map");
+  };
+  assert_eq!(scores.get(&Key::Uint(7)), Some(&CelValue::Float(1.5)));
+
+  let CelValue::Map(statuses) = field("statuses") else {
+    panic!("a HashMap is converted to a map");
+  };
+  assert_eq!(statuses.get(&Key::from("obiwan")), Some(&CelValue::Int(1)));
+
+  assert_eq!(
+    field("history"),
+    CelValue::List(vec![CelValue::Int(2)].into())
+  );
+  assert_eq!(field("main_profile"), CelValue::Null);
+
+  for (expression, expected) in [
+    ("size(this.labels) <= 10", true),
+    (
+      "this.labels.size() == 1 && this.labels['env'] == 'prod'",
+      true,
+    ),
+    ("this.scores[7u] == 1.5", true),
+    ("this.statuses.all(name, this.statuses[name] == 1)", true),
+    ("this.history.exists(status, status == 2)", true),
+    ("this.profiles[-1].name == 'obiwan'", true),
+    ("this.flags[true] == 1u", true),
+    ("size(this.labels) > 10", false),
+  ] {
+    assert_eq!(
+      evaluate(expression, this.clone()),
+      CelValue::Bool(expected),
+      "{expression}"
+    );
+  }
+}
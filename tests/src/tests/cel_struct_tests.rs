@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use protocheck::{
+  cel::Value as CelValue,
+  types::{
+    cel::CelConversionError, protovalidate::Violations, Any, Duration, ListValue, Struct, Value,
+  },
+};
+
+use super::sorted_rule_ids;
+use crate::myapp::v1::CelStructTests;
+
+fn metadata(env: &str) -> Struct {
+  let labels = BTreeMap::from([("env".to_string(), Value::from(env))]);
+
+  Struct {
+    fields: BTreeMap::from([
+      ("owner".to_string(), Value::from("obiwan")),
+      ("labels".to_string(), Value::from(labels)),
+    ]),
+  }
+}
+
+fn valid() -> CelStructTests {
+  CelStructTests {
+    metadata: Some(metadata("prod")),
+    replicas: Some(Value::from(3)),
+    tags: Some(ListValue {
+      values: vec![Value::from("jedi"), Value::from(true)],
+    }),
+    payload: Some(Any::from_msg(&Duration::new(5, 0)).unwrap()),
+  }
+}
+
+#[test]
+fn json_values() {
+  assert!(sorted_rule_ids(&valid()).is_empty());
+
+  // The keys of a Struct, including the nested ones
+  assert_eq!(
+    sorted_rule_ids(&CelStructTests {
+      metadata: Some(metadata("dev")),
+      ..valid()
+    }),
+    ["metadata.env"]
+  );
+
+  let mut without_owner = metadata("prod");
+  without_owner.fields.remove("owner");
+
+  assert_eq!(
+    sorted_rule_ids(&CelStructTests {
+      metadata: Some(without_owner),
+      ..valid()
+    }),
+    ["metadata.owner"]
+  );
+
+  // The numbers of a Value are doubles
+  assert_eq!(
+    sorted_rule_ids(&CelStructTests {
+      replicas: Some(Value::from(6)),
+      ..valid()
+    }),
+    ["replicas.range"]
+  );
+
+  assert_eq!(
+    sorted_rule_ids(&CelStructTests {
+      tags: Some(ListValue {
+        values: vec![Value::from("a"); 4],
+      }),
+      ..valid()
+    }),
+    ["tags.max_items"]
+  );
+
+  assert_eq!(
+    sorted_rule_ids(&CelStructTests {
+      payload: Some(Any {
+        type_url: "example.com/myapp.v1.User".to_string(),
+        value: Vec::new(),
+      }),
+      ..valid()
+    }),
+    ["payload.type_url"]
+  );
+}
+
+#[test]
+fn conversions() {
+  let value = Value::from(vec![
+    Value::from("a"),
+    Value::from(1.5),
+    Value::from(false),
+    Value {
+      kind: Some(protocheck::types::value::Kind::NullValue(0)),
+    },
+  ]);
+
+  assert_eq!(
+    CelValue::try_from(value),
+    Ok(CelValue::List(
+      vec![
+        CelValue::String("a".to_string().into()),
+        CelValue::Float(1.5),
+        CelValue::Bool(false),
+        CelValue::Null,
+      ]
+      .into()
+    ))
+  );
+
+  let CelValue::Map(map) = CelValue::try_from(metadata("prod")).unwrap() else {
+    panic!("a Struct is converted to a map");
+  };
+
+  assert_eq!(
+    map.get(&"owner".into()),
+    Some(&CelValue::String("obiwan".to_string().into()))
+  );
+
+  let any = Any::from_msg(&Duration::new(5, 0)).unwrap();
+  let CelValue::Map(map) = CelValue::from(any.clone()) else {
+    panic!("an Any is converted to a map");
+  };
+
+  assert_eq!(
+    map.get(&"type_url".into()),
+    Some(&CelValue::String(any.type_url.into()))
+  );
+  assert_eq!(
+    map.get(&"value".into()),
+    Some(&CelValue::Bytes(any.value.into()))
+  );
+}
+
+#[test]
+fn value_without_kind() {
+  let empty_value = Value { kind: None };
+
+  assert_eq!(
+    CelValue::try_from(empty_value.clone()),
+    Err(CelConversionError::MissingValueKind)
+  );
+
+  // Also when it is nested in a Struct or a ListValue
+  assert_eq!(
+    CelValue::try_from(Struct {
+      fields: BTreeMap::from([("key".to_string(), empty_value.clone())]),
+    }),
+    Err(CelConversionError::MissingValueKind)
+  );
+  assert_eq!(
+    CelValue::try_from(ListValue {
+      values: vec![Value::from(1), empty_value.clone()],
+    }),
+    Err(CelConversionError::MissingValueKind)
+  );
+
+  // The values that cannot be converted are reported as violations, without panicking
+  let Violations { violations } = CelStructTests {
+    replicas: Some(empty_value),
+    ..valid()
+  }
+  .validate()
+  .unwrap_err();
+
+  let mut violations: Vec<(&str, &str)> = violations
+    .iter()
+    .map(|v| (v.rule_id(), v.message()))
+    .collect();
+  violations.sort();
+
+  assert_eq!(
+    violations,
+    [
+      ("internal_server_error", "internal server error"),
+      ("replicas.range", "internal server error"),
+    ]
+  );
+}
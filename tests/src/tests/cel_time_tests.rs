@@ -0,0 +1,177 @@
+use protocheck::{
+  cel::Value as CelValue,
+  types::{cel::CelConversionError, protovalidate::Violations, Duration, Timestamp},
+};
+
+use super::sorted_rule_ids;
+use crate::myapp::v1::CelTimeTests;
+
+fn timestamp(s: &str) -> Timestamp {
+  s.parse().unwrap()
+}
+
+fn hours(hours: i64) -> Duration {
+  Duration::new(hours * 3600, 0)
+}
+
+fn valid() -> CelTimeTests {
+  CelTimeTests {
+    // A Tuesday
+    created_at: Some(timestamp("2024-03-05T10:00:00Z")),
+    expires_at: Some(timestamp("2024-03-06T10:00:00Z")),
+    ttl: Some(hours(24)),
+  }
+}
+
+// A message with the given times, and the ttl that matches them
+fn created_and_expires(created_at: &str, expires_at: &str) -> CelTimeTests {
+  let created_at = timestamp(created_at);
+  let expires_at = timestamp(expires_at);
+
+  CelTimeTests {
+    created_at: Some(created_at),
+    expires_at: Some(expires_at),
+    ttl: Some(Duration::new(
+      expires_at.seconds - created_at.seconds,
+      expires_at.nanos - created_at.nanos,
+    )),
+  }
+}
+
+#[test]
+fn timestamp_accessors() {
+  assert!(sorted_rule_ids(&valid()).is_empty());
+
+  // getDayOfWeek, with a Sunday
+  assert_eq!(
+    sorted_rule_ids(&created_and_expires(
+      "2024-03-03T10:00:00Z",
+      "2024-03-04T10:00:00Z"
+    )),
+    ["created_at.office_hours"]
+  );
+  // getHours
+  assert_eq!(
+    sorted_rule_ids(&created_and_expires(
+      "2024-03-05T18:00:00Z",
+      "2024-03-06T18:00:00Z"
+    )),
+    ["created_at.office_hours"]
+  );
+  // getFullYear
+  assert_eq!(
+    sorted_rule_ids(&created_and_expires(
+      "2099-12-31T10:00:00Z",
+      "2100-01-01T10:00:00Z"
+    )),
+    ["expires_at.this_century"]
+  );
+}
+
+#[test]
+fn timestamp_and_duration_operations() {
+  // Subtraction of two timestamps, compared to a duration
+  assert_eq!(
+    sorted_rule_ids(&CelTimeTests {
+      ttl: Some(hours(12)),
+      ..valid()
+    }),
+    ["ttl.matches_expiration"]
+  );
+  // Comparison of two timestamps
+  assert_eq!(
+    sorted_rule_ids(&CelTimeTests {
+      created_at: Some(timestamp("2024-03-07T10:00:00Z")),
+      ..valid()
+    }),
+    ["expires_at.after_created_at", "ttl.matches_expiration"]
+  );
+
+  // Comparison of durations
+  assert_eq!(
+    sorted_rule_ids(&created_and_expires(
+      "2024-03-05T10:00:00Z",
+      "2024-03-12T10:00:00.000000001Z"
+    )),
+    ["ttl.max"]
+  );
+  assert_eq!(
+    sorted_rule_ids(&CelTimeTests {
+      ttl: Some(Duration::new(0, 0)),
+      ..valid()
+    }),
+    ["ttl.matches_expiration", "ttl.max"]
+  );
+
+  // The nanos are normalized before the conversion
+  assert!(sorted_rule_ids(&CelTimeTests {
+    ttl: Some(Duration {
+      seconds: 86_399,
+      nanos: 1_000_000_000,
+    }),
+    ..valid()
+  })
+  .is_empty());
+}
+
+#[test]
+fn out_of_range_values() {
+  let max_timestamp = timestamp("9999-12-31T23:59:59.999999999Z");
+  let after_max = Timestamp {
+    seconds: max_timestamp.seconds + 1,
+    nanos: 0,
+  };
+
+  assert!(matches!(
+    CelValue::try_from(max_timestamp),
+    Ok(CelValue::Timestamp(_))
+  ));
+  assert_eq!(
+    CelValue::try_from(after_max),
+    Err(CelConversionError::TimestampOutOfRange(after_max))
+  );
+  assert_eq!(
+    CelValue::try_from(Timestamp {
+      seconds: i64::MIN,
+      nanos: 0,
+    }),
+    Err(CelConversionError::TimestampOutOfRange(Timestamp {
+      seconds: i64::MIN,
+      nanos: 0,
+    }))
+  );
+
+  let max_duration = Duration::new(315_576_000_000, 0);
+  let after_max_duration = Duration::new(315_576_000_001, 0);
+
+  assert!(matches!(
+    CelValue::try_from(max_duration),
+    Ok(CelValue::Duration(_))
+  ));
+  assert_eq!(
+    CelValue::try_from(after_max_duration),
+    Err(CelConversionError::DurationOutOfRange(after_max_duration))
+  );
+
+  // The values that cannot be converted are reported as violations, without panicking
+  let Violations { violations } = CelTimeTests {
+    expires_at: Some(after_max),
+    ..valid()
+  }
+  .validate()
+  .unwrap_err();
+
+  let mut violations: Vec<(&str, &str)> = violations
+    .iter()
+    .map(|v| (v.rule_id(), v.message()))
+    .collect();
+  violations.sort();
+
+  assert_eq!(
+    violations,
+    [
+      ("expires_at.this_century", "internal server error"),
+      ("internal_server_error", "internal server error"),
+    ]
+  );
+}
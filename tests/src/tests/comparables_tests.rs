@@ -1,6 +1,6 @@
 use protocheck::types::{protovalidate::Violations, Duration, Timestamp};
 
-use crate::myapp::v1::ComparableRulesTests;
+use crate::myapp::v1::{ComparableRulesTests, DurationRulesTests, IntegerRulesTests};
 
 #[test]
 fn comparables() {
@@ -48,3 +48,180 @@ fn comparables() {
 
   assert!(test.validate().is_ok());
 }
+
+// Builds a message where every field of the given integer types has the same value
+macro_rules! integer_rules_test {
+  ($value:expr) => {
+    IntegerRulesTests {
+      int32_bounds: $value,
+      int32_in: $value,
+      int32_const: $value,
+      int64_bounds: $value,
+      int64_in: $value,
+      int64_const: $value,
+      uint32_bounds: $value,
+      uint32_in: $value,
+      uint32_const: $value,
+      uint64_bounds: $value,
+      uint64_in: $value,
+      uint64_const: $value,
+      sint32_bounds: $value,
+      sint32_in: $value,
+      sint32_const: $value,
+      sint64_bounds: $value,
+      sint64_in: $value,
+      sint64_const: $value,
+      fixed32_bounds: $value,
+      fixed32_in: $value,
+      fixed32_const: $value,
+      fixed64_bounds: $value,
+      fixed64_in: $value,
+      fixed64_const: $value,
+      sfixed32_bounds: $value,
+      sfixed32_in: $value,
+      sfixed32_const: $value,
+      sfixed64_bounds: $value,
+      sfixed64_in: $value,
+      sfixed64_const: $value,
+    }
+  };
+}
+
+fn integer_violations(test: &IntegerRulesTests) -> Vec<(String, String)> {
+  match test.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+      .collect(),
+  }
+}
+
+const INTEGER_TYPES: [&str; 10] = [
+  "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32", "fixed64", "sfixed32",
+  "sfixed64",
+];
+
+#[test]
+fn integer_rules() {
+  let expected =
+    |bounds_rule: Option<&str>, is_in: bool, is_const: bool| -> Vec<(String, String)> {
+      INTEGER_TYPES
+        .iter()
+        .flat_map(|typ| {
+          let mut violations = Vec::new();
+
+          if let Some(rule) = bounds_rule {
+            violations.push((format!("{}_bounds", typ), format!("{}.{}", typ, rule)));
+          }
+
+          if !is_in {
+            violations.push((format!("{}_in", typ), format!("{}.in", typ)));
+          }
+
+          if !is_const {
+            violations.push((format!("{}_const", typ), format!("{}.const", typ)));
+          }
+
+          violations
+        })
+        .collect()
+    };
+
+  let mut test = integer_rules_test!(2);
+  test.int32_const = 7;
+  test.int64_const = 7;
+  test.uint32_const = 7;
+  test.uint64_const = 7;
+  test.sint32_const = 7;
+  test.sint64_const = 7;
+  test.fixed32_const = 7;
+  test.fixed64_const = 7;
+  test.sfixed32_const = 7;
+  test.sfixed64_const = 7;
+
+  assert_eq!(
+    integer_violations(&test),
+    expected(Some("gte_lt"), true, true)
+  );
+
+  assert_eq!(
+    integer_violations(&integer_rules_test!(7)),
+    expected(Some("gte_lt"), false, true)
+  );
+
+  assert_eq!(
+    integer_violations(&integer_rules_test!(50)),
+    expected(Some("not_in"), false, false)
+  );
+
+  assert_eq!(
+    integer_violations(&integer_rules_test!(100)),
+    expected(Some("gte_lt"), false, false)
+  );
+
+  assert_eq!(
+    integer_violations(&integer_rules_test!(10)),
+    expected(None, false, false)
+  );
+}
+
+fn duration_violations(test: &DurationRulesTests) -> Vec<(String, String)> {
+  match test.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+      .collect(),
+  }
+}
+
+#[test]
+fn duration_rules() {
+  let valid = DurationRulesTests {
+    // Equal to 1s once normalized
+    fixed: Some(Duration::new(0, 1_000_000_000)),
+    negative: Some(Duration::new(-1, -500_000_000)),
+    listed: Some(Duration::new(3, -1_000_000_000)),
+    excluded: Some(Duration::new(1, -5)),
+    required: Some(Duration::new(0, 1)),
+  };
+
+  assert!(duration_violations(&valid).is_empty());
+
+  // The optional fields are only validated when they are set, unless they are required
+  let unset = DurationRulesTests {
+    fixed: None,
+    negative: None,
+    listed: None,
+    excluded: None,
+    required: None,
+  };
+
+  assert_eq!(
+    duration_violations(&unset),
+    [("required".to_string(), "required".to_string())]
+  );
+
+  let invalid = DurationRulesTests {
+    fixed: Some(Duration::new(1, 1)),
+    // -0.5s, with mixed signs
+    negative: Some(Duration::new(-1, 500_000_000)),
+    listed: Some(Duration::new(1, 1)),
+    excluded: Some(Duration::new(2, -1_000_000_000)),
+    required: Some(Duration::new(1, -1_000_000_000)),
+  };
+
+  let rule = |path: &str, rule_id: &str| (path.to_string(), rule_id.to_string());
+
+  assert_eq!(
+    duration_violations(&invalid),
+    [
+      rule("fixed", "duration.const"),
+      rule("negative", "duration.gte_lt"),
+      rule("listed", "duration.in"),
+      rule("excluded", "duration.not_in"),
+      rule("required", "duration.gt"),
+    ]
+  );
+}
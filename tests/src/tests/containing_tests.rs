@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+
 use bytes::Bytes;
 use protocheck::types::{
   protovalidate::{Violation, Violations},
   Any, Duration,
 };
 
-use crate::myapp::v1::ContainingTests;
+use crate::myapp::v1::{
+  any_rules_tests::{Card, Transfer},
+  AnyRulesTests, ContainingTests, EnumCollectionsTests, ListLookupTests,
+};
 
 #[test]
 fn containing_tests() {
@@ -44,3 +49,179 @@ fn containing_tests() {
     .iter()
     .any(|v| v.rule_id() == "enum.defined_only"));
 }
+
+fn lookup_violations(message: &ListLookupTests) -> Vec<(String, String)> {
+  match message.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+      .collect(),
+  }
+}
+
+#[test]
+fn list_lookups() {
+  let valid = ListLookupTests {
+    country: "SE".to_string(),
+    reserved_code: 4,
+    small_list: f64::INFINITY,
+    large_list: f64::NEG_INFINITY,
+    // NaN never matches the items of a list
+    excluded: f32::NAN,
+  };
+
+  assert!(lookup_violations(&valid).is_empty());
+
+  let invalid = ListLookupTests {
+    country: "US".to_string(),
+    reserved_code: 1597,
+    small_list: f64::NAN,
+    large_list: f64::NAN,
+    excluded: 0.5,
+  };
+
+  assert_eq!(
+    lookup_violations(&invalid),
+    [
+      ("country".to_string(), "string.in".to_string()),
+      ("reserved_code".to_string(), "int32.not_in".to_string()),
+      ("small_list".to_string(), "double.in".to_string()),
+      ("large_list".to_string(), "double.in".to_string()),
+      ("excluded".to_string(), "float.not_in".to_string()),
+    ]
+  );
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  assert_eq!(
+    violations[0].message(),
+    "must be in list [AT, BE, CH, DE, DK, ES, FI, FR, GB, IE, IT, LU, NL, NO, PT, SE]"
+  );
+  assert_eq!(violations[4].message(), "must not be in list [0.5, NaN]");
+}
+
+#[test]
+fn enum_collections() {
+  let valid = EnumCollectionsTests {
+    statuses: vec![1, 2],
+    statuses_by_name: HashMap::from([("a".to_string(), 1)]),
+    fixed_status: 2,
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = EnumCollectionsTests {
+    statuses: vec![1, 0, 15],
+    statuses_by_name: HashMap::from([("a".to_string(), 2), ("b".to_string(), -3)]),
+    fixed_status: 1,
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  let mut violations: Vec<(String, &str, &str)> = violations
+    .iter()
+    .map(|v| (v.field_path_str().unwrap(), v.rule_id(), v.message()))
+    .collect();
+  violations.sort();
+
+  assert_eq!(
+    violations,
+    [
+      (
+        "fixed_status".to_string(),
+        "enum.const",
+        "must be equal to 2"
+      ),
+      (
+        "statuses[1]".to_string(),
+        "enum.not_in",
+        "must not be in list [0]"
+      ),
+      (
+        "statuses[2]".to_string(),
+        "enum.defined_only",
+        "must be a defined value of `test_enum`, but is 15"
+      ),
+      (
+        r#"statuses_by_name["a"]"#.to_string(),
+        "enum.in",
+        "must be in list [1]"
+      ),
+      (
+        r#"statuses_by_name["b"]"#.to_string(),
+        "enum.defined_only",
+        "must be a defined value of `test_enum`, but is -3"
+      ),
+      (
+        r#"statuses_by_name["b"]"#.to_string(),
+        "enum.in",
+        "must be in list [1]"
+      ),
+    ]
+  );
+}
+
+fn any_violations(message: &AnyRulesTests) -> Vec<(String, String)> {
+  match message.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+      .collect(),
+  }
+}
+
+#[test]
+fn any_rules() {
+  let card = Any::from_msg(&Card {
+    number: "4242".to_string(),
+  })
+  .unwrap();
+  let transfer = Any::from_msg(&Transfer {
+    iban: "SE45".to_string(),
+  })
+  .unwrap();
+
+  let valid = AnyRulesTests {
+    payment: Some(card.clone()),
+    refund: Some(transfer.clone()),
+    receipt: Some(transfer.clone()),
+  };
+
+  assert!(any_violations(&valid).is_empty());
+
+  // The rules are skipped for the unset fields, unless they are required
+  let unset = AnyRulesTests {
+    payment: None,
+    refund: None,
+    receipt: None,
+  };
+
+  assert_eq!(
+    any_violations(&unset),
+    vec![("receipt".to_string(), "required".to_string())]
+  );
+
+  // The full URLs are compared, so an empty or unprefixed type URL is not in the list
+  let invalid = AnyRulesTests {
+    payment: Some(Any {
+      type_url: String::new(),
+      value: vec![],
+    }),
+    refund: Some(card.clone()),
+    receipt: Some(Any {
+      type_url: "myapp.v1.AnyRulesTests.Transfer".to_string(),
+      value: transfer.value.clone(),
+    }),
+  };
+
+  assert_eq!(
+    any_violations(&invalid),
+    vec![
+      ("payment".to_string(), "any.in".to_string()),
+      ("refund".to_string(), "any.not_in".to_string()),
+      ("receipt".to_string(), "any.in".to_string()),
+    ]
+  );
+}
@@ -0,0 +1,73 @@
+use prost::{bytes::Bytes, Message};
+use protocheck::{
+  decode_and_validate, decode_length_delimited_and_validate, DecodeOrValidateError,
+};
+
+use crate::myapp::v1::{display_tests::Customer, DisplayTests};
+
+fn message(email: &str) -> DisplayTests {
+  DisplayTests {
+    customer: Some(Customer {
+      email: email.to_string(),
+    }),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn decode_and_validate_messages() {
+  let valid = message("obiwan@jedi.org");
+  let decoded: DisplayTests = decode_and_validate(valid.encode_to_vec().as_slice()).unwrap();
+  assert_eq!(decoded, valid);
+
+  // Any Buf can be used
+  let invalid = message("obiwan");
+  let err = decode_and_validate::<DisplayTests>(Bytes::from(invalid.encode_to_vec())).unwrap_err();
+
+  let DecodeOrValidateError::Invalid(violations) = &err else {
+    panic!("the message is decoded and then validated");
+  };
+  assert_eq!(violations.rule_ids().collect::<Vec<_>>(), ["string.email"]);
+  assert_eq!(
+    err.to_string(),
+    "customer.email: must be a valid email address (string.email)"
+  );
+
+  let err = decode_and_validate::<DisplayTests>([0xff].as_slice()).unwrap_err();
+  assert!(matches!(err, DecodeOrValidateError::Decode(_)));
+  assert!(err.to_string().starts_with("failed to decode the message"));
+}
+
+#[test]
+fn length_delimited() {
+  let mut stream = Vec::new();
+  message("obiwan@jedi.org")
+    .encode_length_delimited(&mut stream)
+    .unwrap();
+  message("anakin")
+    .encode_length_delimited(&mut stream)
+    .unwrap();
+
+  let mut buf = stream.as_slice();
+
+  assert!(decode_length_delimited_and_validate::<DisplayTests>(&mut buf).is_ok());
+  assert!(matches!(
+    decode_length_delimited_and_validate::<DisplayTests>(&mut buf),
+    Err(DecodeOrValidateError::Invalid(_))
+  ));
+  assert!(buf.is_empty());
+}
+
+#[test]
+fn into_status() {
+  let status =
+    tonic::Status::from(decode_and_validate::<DisplayTests>([0xff].as_slice()).unwrap_err());
+  assert_eq!(status.code(), tonic::Code::InvalidArgument);
+  assert!(status.details().is_empty());
+
+  let status = tonic::Status::from(
+    decode_and_validate::<DisplayTests>(message("obiwan").encode_to_vec().as_slice()).unwrap_err(),
+  );
+  assert_eq!(status.code(), tonic::Code::InvalidArgument);
+  assert!(!status.details().is_empty());
+}
@@ -0,0 +1,252 @@
+use bytes::Bytes;
+use maplit::hashmap;
+use prost::{Message, Name};
+use protocheck::{
+  dynamic::DynamicValidator,
+  prost_reflect::{DescriptorPool, DynamicMessage},
+  types::{protovalidate::Violations, Any, Duration, Timestamp},
+  ProtoValidator,
+};
+
+use crate::myapp::v1::{
+  fail_fast_tests::Item,
+  message_map::Person as MapPerson,
+  nested_tests::{Address, Customer},
+  recursive::Oneofs,
+  repeated_tests::Person,
+  required_oneof_test::Contact,
+  ComparableRulesTests, ContainingTests, DurationRulesTests, FailFastTests, FloatSemanticsTests,
+  GreetRequest, IntegerRulesTests, MessageMap, NestedTests, Recursive, RepeatedTests,
+  RequiredOneofTest, RuleIdsTests, StringLengthTests, TimestampRulesTests,
+};
+
+fn validator() -> DynamicValidator {
+  let pool =
+    DescriptorPool::decode(include_bytes!(env!("PROTO_DESCRIPTOR_SET")).as_slice()).unwrap();
+
+  DynamicValidator::new(pool)
+}
+
+fn dynamic<T: Message + Name>(validator: &DynamicValidator, msg: &T) -> DynamicMessage {
+  let desc = validator
+    .pool()
+    .get_message_by_name(&T::full_name())
+    .unwrap();
+
+  DynamicMessage::decode(desc, msg.encode_to_vec().as_slice()).unwrap()
+}
+
+// The dynamic validator must find the same violations as the generated one, in the same order
+fn assert_same_violations<T: ProtoValidator + Message + Name>(msg: &T) {
+  let validator = validator();
+  let dynamic_msg = dynamic(&validator, msg);
+
+  assert_eq!(validator.validate(&dynamic_msg), msg.validate());
+  assert_eq!(
+    validator.validate_fail_fast(&dynamic_msg),
+    msg.validate_fail_fast()
+  );
+}
+
+fn person(name: &str) -> Person {
+  Person {
+    name: name.to_string(),
+  }
+}
+
+#[test]
+fn scalar_rules() {
+  let validator = validator();
+
+  let valid = GreetRequest {
+    name: "obiwan".to_string(),
+  };
+  assert!(validator.validate(&dynamic(&validator, &valid)).is_ok());
+
+  let invalid = GreetRequest {
+    name: String::new(),
+  };
+  let Violations { violations } = validator
+    .validate(&dynamic(&validator, &invalid))
+    .unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "string.min_len");
+  assert_eq!(violations[0].field_path_str().unwrap(), "name");
+
+  assert_same_violations(&invalid);
+
+  assert_same_violations(&RuleIdsTests {
+    tags: vec!["".to_string()],
+    labels: vec!["a".to_string(), "a".to_string()],
+    ..Default::default()
+  });
+}
+
+#[test]
+fn cel_rules() {
+  assert_same_violations(&RepeatedTests {
+    people: vec![person("alfonso"), person("obiwan")],
+    ..Default::default()
+  });
+
+  assert_same_violations(&Recursive {
+    id: 1,
+    recursive: Some(Box::new(Recursive {
+      id: 3,
+      ..Default::default()
+    })),
+    oneofs: Some(Oneofs::RecursiveOneof(Box::new(Recursive {
+      id: 2,
+      ..Default::default()
+    }))),
+  });
+
+  assert_same_violations(&MessageMap {
+    message_map: hashmap! {
+      1 => MapPerson { name: "obiwan".to_string() },
+    },
+  });
+}
+
+#[test]
+fn repeated_and_maps() {
+  assert_same_violations(&RepeatedTests {
+    unique_floats: vec![1.0, 1.0],
+    unique_doubles: vec![1.0, 2.0],
+    unique_strings: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+    ..Default::default()
+  });
+
+  assert_same_violations(&NestedTests {
+    customer: Some(Customer {
+      email: "obiwan".to_string(),
+      addresses: vec![Address {
+        street: String::new(),
+        labels: hashmap! { "a".to_string() => "b".to_string() },
+      }],
+    }),
+    customers: hashmap! {
+      "anakin".to_string() => Customer {
+        email: "anakin".to_string(),
+        addresses: vec![],
+      },
+    },
+    ..Default::default()
+  });
+}
+
+#[test]
+fn oneofs() {
+  assert_same_violations(&RequiredOneofTest { contact: None });
+
+  assert_same_violations(&RequiredOneofTest {
+    contact: Some(Contact::Email("me@example.com".to_string())),
+  });
+}
+
+#[test]
+fn fail_fast() {
+  let invalid_item = || Item {
+    name: String::new(),
+  };
+
+  assert_same_violations(&FailFastTests {
+    name: "abcd".to_string(),
+    tags: vec!["".to_string(); 5],
+    labels: hashmap! { "a".to_string() => "".to_string() },
+    item: Some(invalid_item()),
+    items: vec![invalid_item(), invalid_item()],
+    level: -1,
+  });
+
+  assert_same_violations(&FailFastTests {
+    name: "ab".to_string(),
+    items: vec![invalid_item()],
+    level: 100,
+    ..Default::default()
+  });
+}
+
+// The messages of the comparison, list, length and const rules come from the same helpers in both validators
+#[test]
+fn rule_messages() {
+  let timestamp = Timestamp::new(50, 0);
+  let duration = Duration::new(50, 0);
+
+  let comparables = ComparableRulesTests {
+    timestamp_gt: Some(timestamp),
+    timestamp_gte: Some(timestamp),
+    timestamp_lt: Some(Timestamp::new(150, 0)),
+    timestamp_lte: Some(Timestamp::new(150, 0)),
+    duration_gt: Some(duration),
+    duration_gte: Some(duration),
+    duration_lt: Some(Duration::new(150, 0)),
+    duration_lte: Some(Duration::new(150, 0)),
+    int64_gt: 1,
+    int64_gte: 1,
+    int64_lt: 10,
+    int64_lte: 10,
+  };
+
+  let ranges = TimestampRulesTests {
+    fixed: Some(timestamp),
+    around_epoch: Some(Timestamp::new(2, 0)),
+    ..Default::default()
+  };
+
+  let durations = DurationRulesTests {
+    fixed: Some(duration),
+    negative: Some(duration),
+    listed: Some(duration),
+    excluded: Some(Duration::new(1, 0)),
+    required: Some(Duration::default()),
+  };
+
+  let floats = FloatSemanticsTests {
+    float_gt: -1.5,
+    float_const: 0.5,
+    float_in: 2.5,
+    double_gt: -1.5,
+    double_const: 0.5,
+    double_in: 2.5,
+    ..Default::default()
+  };
+
+  let lists = ContainingTests {
+    double_field: 2.0,
+    float_field: 2.0,
+    duration_field: Some(Duration::default()),
+    int64_field: 5,
+    name: "ermenegildo".to_string(),
+    any_field: Some(Any {
+      type_url: "type.googleapis.com/Nope".to_string(),
+      value: vec![],
+    }),
+    enum_field: 15,
+    bytes_field: Bytes::from_static(b"\x02\x03"),
+  };
+
+  let lengths = StringLengthTests {
+    max_len: "abcdef".to_string(),
+    min_len: "a".to_string(),
+    len: "a".to_string(),
+    max_bytes: "abcdef".to_string(),
+    min_bytes: "a".to_string(),
+    len_bytes: "a".to_string(),
+  };
+
+  assert!(comparables.validate().is_err());
+  assert_same_violations(&comparables);
+  assert!(ranges.validate().is_err());
+  assert_same_violations(&ranges);
+  assert!(durations.validate().is_err());
+  assert_same_violations(&durations);
+  assert!(floats.validate().is_err());
+  assert_same_violations(&floats);
+  assert!(lists.validate().is_err());
+  assert_same_violations(&lists);
+  assert!(lengths.validate().is_err());
+  assert_same_violations(&lengths);
+  assert_same_violations(&IntegerRulesTests::default());
+}
@@ -0,0 +1,56 @@
+use prost::Message;
+use protocheck::{encode_validated, encode_validated_to_vec, EncodeOrValidateError};
+
+use crate::myapp::v1::{display_tests::Customer, DisplayTests};
+
+fn message(email: &str) -> DisplayTests {
+  DisplayTests {
+    customer: Some(Customer {
+      email: email.to_string(),
+    }),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn valid_messages() {
+  let valid = message("obiwan@jedi.org");
+
+  let mut buf = Vec::new();
+  encode_validated(&valid, &mut buf).unwrap();
+  assert_eq!(buf, valid.encode_to_vec());
+
+  assert_eq!(encode_validated_to_vec(&valid).unwrap(), buf);
+}
+
+#[test]
+fn invalid_messages() {
+  let invalid = message("obiwan");
+
+  let mut buf = Vec::new();
+  let err = encode_validated(&invalid, &mut buf).unwrap_err();
+
+  // Nothing is written
+  assert!(buf.is_empty());
+  assert_eq!(
+    err.violations().unwrap().rule_ids().collect::<Vec<_>>(),
+    ["string.email"]
+  );
+
+  assert_eq!(
+    encode_validated_to_vec(&invalid).unwrap_err(),
+    *err.violations().unwrap()
+  );
+}
+
+#[test]
+fn insufficient_capacity() {
+  let valid = message("obiwan@jedi.org");
+
+  let mut storage = [0u8; 4];
+  let mut buf = storage.as_mut_slice();
+
+  let err = encode_validated(&valid, &mut buf).unwrap_err();
+  assert!(matches!(err, EncodeOrValidateError::Encode(_)));
+  assert!(err.violations().is_none());
+}
@@ -0,0 +1,25 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::{exclusion_test::Choice, ExclusionTest};
+
+#[test]
+fn exclusion_test() {
+  let excluded = ExclusionTest {
+    legacy: "a".to_string(),
+    checked: "abcde".to_string(),
+    choice: Some(Choice::LegacyChoice("a".to_string())),
+  };
+
+  assert!(excluded.validate().is_ok());
+
+  let invalid = ExclusionTest {
+    legacy: "a".to_string(),
+    checked: "a".to_string(),
+    choice: Some(Choice::CheckedChoice("a".to_string())),
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 2);
+  assert!(violations.iter().all(|v| v.rule_id() == "string.min_len"));
+}
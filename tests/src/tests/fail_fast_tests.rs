@@ -0,0 +1,107 @@
+use maplit::hashmap;
+use protocheck::{types::protovalidate::Violations, ProtoValidator};
+
+use crate::myapp::v1::{fail_fast_tests::Item, FailFastTests};
+
+fn violations(result: Result<(), Violations>) -> Vec<(String, String)> {
+  match result {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.field_path_str().unwrap_or_default(), v.rule_id().to_string()))
+      .collect(),
+  }
+}
+
+fn invalid_item() -> Item {
+  Item {
+    name: "".to_string(),
+  }
+}
+
+#[test]
+fn fail_fast() {
+  let msg = FailFastTests {
+    name: "abcd".to_string(),
+    tags: vec!["".to_string(); 50],
+    labels: hashmap! { "a".to_string() => "".to_string() },
+    item: Some(invalid_item()),
+    items: vec![invalid_item(), invalid_item()],
+    level: -1,
+  };
+
+  let all = violations(msg.validate());
+
+  assert_eq!(all.len(), 56);
+  assert_eq!(violations(msg.validate_fail_fast()), all[..1]);
+
+  // The fail-fast mode is only active for that call
+  assert_eq!(violations(msg.validate()), all);
+
+  fn validate_fail_fast<T: ProtoValidator>(msg: &T) -> Result<(), Violations> {
+    msg.validate_fail_fast()
+  }
+
+  assert_eq!(violations(validate_fail_fast(&msg)), all[..1]);
+}
+
+#[test]
+fn fail_fast_rules() {
+  let rule = |path: &str, rule_id: &str| vec![(path.to_string(), rule_id.to_string())];
+
+  // Both rules of the field fail, but only the first one is reported
+  let scalar = FailFastTests {
+    name: "ABCD".to_string(),
+    ..Default::default()
+  };
+
+  assert_eq!(violations(scalar.validate()).len(), 2);
+  assert_eq!(
+    violations(scalar.validate_fail_fast()),
+    rule("name", "string.max_len")
+  );
+
+  let repeated = FailFastTests {
+    tags: vec!["a".to_string(), "".to_string(), "".to_string()],
+    ..Default::default()
+  };
+
+  assert_eq!(
+    violations(repeated.validate_fail_fast()),
+    rule("tags[1]", "string.min_len")
+  );
+
+  let map = FailFastTests {
+    labels: hashmap! {
+      "a".to_string() => "".to_string(),
+      "b".to_string() => "".to_string(),
+    },
+    ..Default::default()
+  };
+
+  assert_eq!(violations(map.validate_fail_fast()).len(), 1);
+
+  let nested = FailFastTests {
+    items: vec![invalid_item(), invalid_item()],
+    ..Default::default()
+  };
+
+  assert_eq!(
+    violations(nested.validate_fail_fast()),
+    rule("items[0].name", "string.min_len")
+  );
+
+  // The message rules are checked before the fields
+  let cel = FailFastTests {
+    item: Some(invalid_item()),
+    level: 100,
+    ..Default::default()
+  };
+
+  assert_eq!(
+    violations(cel.validate_fail_fast()),
+    rule("", "fail_fast.max_level")
+  );
+
+  assert!(FailFastTests::default().validate_fail_fast().is_ok());
+}
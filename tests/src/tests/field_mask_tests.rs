@@ -0,0 +1,158 @@
+use protocheck::types::{FieldMask, FieldMaskError, FieldMaskTarget};
+
+use crate::myapp::v1::{field_mask_tests::Address, FieldMaskTests, UpdateFieldMaskTestsRequest};
+
+fn mask(paths: &[&str]) -> FieldMask {
+  FieldMask::new(paths.iter().map(|p| p.to_string()).collect())
+}
+
+#[test]
+fn normalize() {
+  assert_eq!(
+    mask(&["b", "a.c", "a", "a.b.c", "b", "ab", "a_b"]).normalized(),
+    mask(&["a", "a_b", "ab", "b"])
+  );
+  assert_eq!(
+    mask(&["a.b.c", "a.b", "a.bc"]).normalized(),
+    mask(&["a.b", "a.bc"])
+  );
+  assert_eq!(mask(&[]).normalized(), mask(&[]));
+}
+
+#[test]
+fn union_and_intersect() {
+  let first = mask(&["address", "display_name", "manager.email"]);
+  let second = mask(&["address.zip_code", "manager", "phone"]);
+
+  assert_eq!(
+    first.union(&second),
+    mask(&["address", "display_name", "manager", "phone"])
+  );
+  assert_eq!(
+    first.intersect(&second),
+    mask(&["address.zip_code", "manager.email"])
+  );
+  assert_eq!(second.intersect(&first), first.intersect(&second));
+  assert!(first.intersect(&mask(&[])).is_empty());
+}
+
+#[test]
+fn covers() {
+  let mask = mask(&["address", "manager.display_name"]);
+
+  assert!(mask.covers("address"));
+  assert!(mask.covers("address.zip_code"));
+  assert!(mask.covers("manager.display_name"));
+  assert!(!mask.covers("manager"));
+  assert!(!mask.covers("manager.display"));
+  assert!(!mask.covers("addresses_by_label"));
+}
+
+#[test]
+fn paths_of_the_messages() {
+  let valid = mask(&[
+    "display_name",
+    "address",
+    "address.zip_code",
+    // The recursive messages and the well known types
+    "manager.manager.address.street",
+    "created_at.seconds",
+    // The fields of the oneofs, and the lists and maps as a whole
+    "email",
+    "previous_addresses",
+    "addresses_by_label",
+  ]);
+  assert_eq!(valid.validate_for::<FieldMaskTests>(), Ok(()));
+
+  let invalid = mask(&[
+    "displayName",
+    "address.zip",
+    "contact",
+    "previous_addresses.street",
+    "addresses_by_label.home",
+    "display_name.first",
+    "",
+    "address.",
+  ]);
+  assert_eq!(
+    invalid.validate_for::<FieldMaskTests>(),
+    Err(FieldMaskError::InvalidPaths {
+      message: "myapp.v1.FieldMaskTests",
+      paths: invalid.paths.clone(),
+    })
+  );
+
+  assert_eq!(
+    mask(&["street", "display_name"]).invalid_paths::<Address>(),
+    ["display_name"]
+  );
+  assert_eq!(
+    Address::message_fields().name,
+    "myapp.v1.FieldMaskTests.Address"
+  );
+}
+
+#[test]
+fn update_requests() {
+  // The rules check that the mask is set, and the handler checks its paths
+  let handle = |request: &UpdateFieldMaskTestsRequest| -> Result<(), String> {
+    request.validate().map_err(|e| e.to_string())?;
+
+    request
+      .update_mask
+      .as_ref()
+      .unwrap()
+      .validate_for::<FieldMaskTests>()
+      .map_err(|e| e.to_string())
+  };
+
+  let request = |paths: &[&str]| UpdateFieldMaskTestsRequest {
+    value: Some(FieldMaskTests {
+      display_name: "obiwan".to_string(),
+      ..Default::default()
+    }),
+    update_mask: Some(mask(paths)),
+  };
+
+  assert_eq!(handle(&request(&["display_name"])), Ok(()));
+  assert_eq!(
+    handle(&request(&["display_name", "nickname"])),
+    Err("invalid paths for myapp.v1.FieldMaskTests: nickname".to_string())
+  );
+  assert!(handle(&UpdateFieldMaskTestsRequest {
+    update_mask: None,
+    ..request(&[])
+  })
+  .is_err());
+}
+
+#[test]
+fn json_names() {
+  let mask = mask(&["display_name", "address.zip_code", "created_at"]);
+
+  assert_eq!(
+    serde_json::to_string(&mask).unwrap(),
+    "\"displayName,address.zipCode,createdAt\""
+  );
+  assert_eq!(
+    serde_json::from_str::<FieldMask>("\"displayName, address.zipCode,createdAt\"").unwrap(),
+    mask
+  );
+  assert_eq!(
+    serde_json::from_str::<FieldMask>("\"\"").unwrap(),
+    FieldMask::default()
+  );
+
+  // The names that could not be converted back
+  for path in ["display__name", "display_Name", "displayName", "zip_code_"] {
+    assert_eq!(
+      self::mask(&[path]).to_json_paths(),
+      Err(FieldMaskError::InvalidJsonPath(path.to_string()))
+    );
+  }
+  assert!(serde_json::from_str::<FieldMask>("\"display_name\"").is_err());
+  assert_eq!(
+    FieldMask::from_json_paths(["address.zipCode"]),
+    Ok(self::mask(&["address.zip_code"]))
+  );
+}
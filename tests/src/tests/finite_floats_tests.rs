@@ -1,6 +1,6 @@
 use protocheck::types::protovalidate::Violations;
 
-use crate::myapp::v1::FiniteRulesTests;
+use crate::myapp::v1::{FiniteRulesTests, FloatSemanticsTests};
 
 #[test]
 fn finite_floats() {
@@ -12,4 +12,93 @@ fn finite_floats() {
   let Violations { violations } = test.validate().unwrap_err();
 
   assert_eq!(violations.len(), 2);
+
+  for (finite_float, finite_double) in [
+    (f32::INFINITY, f64::INFINITY),
+    (f32::NEG_INFINITY, f64::NEG_INFINITY),
+  ] {
+    let test = FiniteRulesTests {
+      finite_float,
+      finite_double,
+    };
+
+    let Violations { violations } = test.validate().unwrap_err();
+
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0].rule_id(), "float.finite");
+    assert_eq!(violations[1].rule_id(), "double.finite");
+  }
+
+  let test = FiniteRulesTests {
+    finite_float: f32::MAX,
+    finite_double: -0.0,
+  };
+
+  assert!(test.validate().is_ok());
+}
+
+// Returns the names of the rules (the fields are named after them) that fail for the value
+fn failing_rules(value: f64) -> Vec<String> {
+  let float = value as f32;
+
+  let test = FloatSemanticsTests {
+    float_gt: float,
+    float_gte: float,
+    float_lt: float,
+    float_lte: float,
+    float_const: float,
+    float_in: float,
+    float_not_in: float,
+    float_finite: float,
+    double_gt: value,
+    double_gte: value,
+    double_lt: value,
+    double_lte: value,
+    double_const: value,
+    double_in: value,
+    double_not_in: value,
+    double_finite: value,
+  };
+
+  let Err(Violations { violations }) = test.validate() else {
+    return Vec::new();
+  };
+
+  let float_rules: Vec<String> = violations
+    .iter()
+    .filter_map(|v| v.rule_id().strip_prefix("float.").map(str::to_string))
+    .collect();
+
+  let double_rules: Vec<String> = violations
+    .iter()
+    .filter_map(|v| v.rule_id().strip_prefix("double.").map(str::to_string))
+    .collect();
+
+  assert_eq!(float_rules, double_rules);
+  assert_eq!(float_rules.len() * 2, violations.len());
+
+  float_rules
+}
+
+#[test]
+fn non_finite_values() {
+  // NaN fails all the comparisons and never matches const or in
+  assert_eq!(
+    failing_rules(f64::NAN),
+    ["gt", "gte", "lt", "lte", "const", "in", "finite"]
+  );
+
+  assert_eq!(
+    failing_rules(f64::INFINITY),
+    ["lt", "lte", "const", "in", "finite"]
+  );
+
+  assert_eq!(
+    failing_rules(f64::NEG_INFINITY),
+    ["gt", "gte", "const", "in", "finite"]
+  );
+
+  // -0.0 is equal to 0.0
+  assert_eq!(failing_rules(-0.0), ["gt", "lt", "not_in"]);
+  assert_eq!(failing_rules(0.0), ["gt", "lt", "not_in"]);
 }
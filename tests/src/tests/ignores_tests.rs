@@ -1,7 +1,26 @@
+use std::collections::HashMap;
+
 use bytes::Bytes;
-use protocheck::types::{protovalidate::Violations, Duration, Timestamp};
+use protocheck::types::{protovalidate::Violations, Duration, Int64Value, Timestamp};
+
+use crate::myapp::v1::{
+  ignore_if_default_value_tests, ignore_modes_tests, renumbered_ignore_tests, IgnoreAlwaysTest,
+  IgnoreIfDefaultValueTests, IgnoreIfZeroValueTest, IgnoreModesTests, RenumberedIgnoreTests,
+};
 
-use crate::myapp::v1::{IgnoreAlwaysTest, IgnoreIfZeroValueTest};
+fn violated_fields(result: Result<(), Violations>) -> Vec<String> {
+  match result {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => {
+      let mut fields: Vec<String> = violations
+        .iter()
+        .map(|v| v.field_path_str().unwrap())
+        .collect();
+      fields.sort();
+      fields
+    }
+  }
+}
 
 #[test]
 fn ignore_always_test() {
@@ -88,3 +107,92 @@ fn ignore_if_zero_value_test() {
 
   assert!(test.validate().is_ok());
 }
+
+#[test]
+fn ignore_if_zero_value_shapes() {
+  let unpopulated = IgnoreModesTests {
+    tags: vec![],
+    scores: HashMap::new(),
+    level: None,
+    balance: None,
+    choice: None,
+  };
+
+  assert!(unpopulated.validate().is_ok());
+
+  // The fields with presence are still validated when they are set to the zero value
+  let zero_values = IgnoreModesTests {
+    level: Some(0),
+    balance: Some(Int64Value { value: 0 }),
+    choice: Some(ignore_modes_tests::Choice::Code(String::new())),
+    ..unpopulated.clone()
+  };
+
+  assert_eq!(
+    violated_fields(zero_values.validate()),
+    ["balance", "code", "level"]
+  );
+
+  let populated = IgnoreModesTests {
+    tags: vec!["a".to_string()],
+    scores: HashMap::from([("a".to_string(), 1)]),
+    ..unpopulated
+  };
+
+  assert_eq!(violated_fields(populated.validate()), ["scores", "tags"]);
+}
+
+#[test]
+fn ignore_if_default_value_shapes() {
+  let unpopulated = IgnoreIfDefaultValueTests::default();
+
+  assert!(unpopulated.validate().is_ok());
+
+  // Unlike IGNORE_IF_ZERO_VALUE, the fields with presence are skipped when they are set to the zero value
+  let default_values = IgnoreIfDefaultValueTests {
+    level: Some(0),
+    balance: Some(Int64Value { value: 0 }),
+    timeout: Some(Duration::default()),
+    name: String::new(),
+    tags: vec![],
+    choice: Some(ignore_if_default_value_tests::Choice::Code(String::new())),
+  };
+
+  assert!(default_values.validate().is_ok());
+
+  let populated = IgnoreIfDefaultValueTests {
+    level: Some(-1),
+    balance: Some(Int64Value { value: -1 }),
+    timeout: Some(Duration::new(1, 0)),
+    name: "ab".to_string(),
+    tags: vec!["a".to_string()],
+    choice: Some(ignore_if_default_value_tests::Choice::Code(
+      "ab".to_string(),
+    )),
+  };
+
+  assert_eq!(
+    violated_fields(populated.validate()),
+    ["balance", "code", "level", "name", "tags", "timeout"]
+  );
+}
+
+// The required fields of the oneofs follow the mode resolved by protocheck-build, not the number of the rules
+#[test]
+fn renumbered_ignore_modes() {
+  let unset = RenumberedIgnoreTests { choice: None };
+
+  assert_eq!(violated_fields(unset.validate()), ["code"]);
+
+  let other_field = RenumberedIgnoreTests {
+    choice: Some(renumbered_ignore_tests::Choice::Name(String::new())),
+  };
+
+  assert_eq!(violated_fields(other_field.validate()), ["code"]);
+
+  let set = RenumberedIgnoreTests {
+    choice: Some(renumbered_ignore_tests::Choice::Code(String::new())),
+  };
+
+  assert!(set.validate().is_ok());
+}
@@ -0,0 +1,121 @@
+use proptest::prelude::*;
+use protocheck::types::{json_value::JsonValueError, ListValue, Struct, Value};
+use serde_json::json;
+
+use crate::myapp::v1::CelStructTests;
+
+// The JSON documents that can be converted without losing anything: the integers are exactly
+// representable as doubles, and the floats have a fractional part (the other ones come back as integers)
+fn json_values() -> impl Strategy<Value = serde_json::Value> {
+  let leaf = prop_oneof![
+    Just(serde_json::Value::Null),
+    any::<bool>().prop_map(serde_json::Value::Bool),
+    (-(1i64 << 53) + 1..1i64 << 53).prop_map(|v| json!(v)),
+    prop::num::f64::NORMAL
+      .prop_filter("not integral", |v| v.fract() != 0.0)
+      .prop_map(|v| json!(v)),
+    ".{0,8}".prop_map(serde_json::Value::String),
+  ];
+
+  leaf.prop_recursive(4, 64, 8, |inner| {
+    prop_oneof![
+      prop::collection::vec(inner.clone(), 0..8).prop_map(serde_json::Value::Array),
+      prop::collection::btree_map(".{0,8}", inner, 0..8)
+        .prop_map(|map| serde_json::Value::Object(map.into_iter().collect())),
+    ]
+  })
+}
+
+proptest! {
+  #[test]
+  fn round_trip(json in json_values()) {
+    let value = Value::from(json.clone());
+
+    prop_assert_eq!(serde_json::Value::try_from(value.clone()), Ok(json.clone()));
+
+    // Serde produces the same JSON as the conversion
+    prop_assert_eq!(serde_json::to_value(&value).unwrap(), json.clone());
+    prop_assert_eq!(serde_json::to_string(&value).unwrap(), serde_json::to_string(&json).unwrap());
+    prop_assert_eq!(serde_json::from_value::<Value>(json).unwrap(), value);
+  }
+}
+
+#[test]
+fn structs_and_lists() {
+  let serde_json::Value::Object(map) = json!({ "name": "obiwan", "nested": { "age": 57 } }) else {
+    unreachable!()
+  };
+
+  let object = Struct::from(map.clone());
+  assert_eq!(object.fields["name"], Value::from("obiwan"));
+  assert_eq!(serde_json::Map::try_from(object), Ok(map.clone()));
+
+  let list = ListValue::from(vec![json!(1), json!("a"), json!([true])]);
+  assert_eq!(list.values[0], Value::from(1));
+  assert_eq!(
+    Vec::<serde_json::Value>::try_from(list),
+    Ok(vec![json!(1), json!("a"), json!([true])])
+  );
+
+  // A Struct field is a plain object, not a message with its `fields`
+  let message = CelStructTests {
+    metadata: Some(Struct::from(map)),
+    replicas: Some(Value::from(3)),
+    tags: Some(ListValue::from(vec![json!("jedi")])),
+    payload: None,
+  };
+
+  let serialized = serde_json::to_value(&message).unwrap();
+  assert_eq!(
+    serialized,
+    json!({
+      "metadata": { "name": "obiwan", "nested": { "age": 57 } },
+      "replicas": 3,
+      "tags": ["jedi"],
+      "payload": null,
+    })
+  );
+  assert_eq!(
+    serde_json::from_value::<CelStructTests>(serialized).unwrap(),
+    message
+  );
+}
+
+#[test]
+fn numbers() {
+  let convert = |number: f64| serde_json::Value::try_from(Value::from(number));
+
+  assert_eq!(convert(2.0), Ok(json!(2)));
+  assert_eq!(convert(-2.0), Ok(json!(-2)));
+  assert_eq!(convert(2.5), Ok(json!(2.5)));
+  assert_eq!(serde_json::to_string(&Value::from(-0.0)).unwrap(), "-0.0");
+
+  // The integers that do not fit in a double lose their precision, and they come back as floats
+  let big = Value::from(json!(9_007_199_254_740_993u64));
+  assert_eq!(big, Value::from(9_007_199_254_740_992.0));
+  assert_eq!(
+    serde_json::Value::try_from(big),
+    Ok(json!(9_007_199_254_740_992.0))
+  );
+  assert_eq!(
+    convert(9_007_199_254_740_991.0),
+    Ok(json!(9_007_199_254_740_991i64))
+  );
+
+  for number in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+    assert!(matches!(
+      convert(number),
+      Err(JsonValueError::NonFiniteNumber(_))
+    ));
+    assert!(serde_json::to_string(&Value::from(number)).is_err());
+  }
+
+  assert_eq!(
+    serde_json::Value::try_from(Value::default()),
+    Err(JsonValueError::MissingValueKind)
+  );
+  assert_eq!(
+    serde_json::Value::try_from(Value::from(vec![Value::default()])),
+    Err(JsonValueError::MissingValueKind)
+  );
+}
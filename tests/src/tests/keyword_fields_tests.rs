@@ -0,0 +1,22 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::{keyword_fields::Kind, KeywordFields};
+
+#[test]
+fn keyword_field_paths() {
+  let message = KeywordFields {
+    r#type: String::new(),
+    self_: String::new(),
+    kind: Some(Kind::Match(String::new())),
+  };
+
+  let Violations { violations } = message.validate().unwrap_err();
+
+  let field_paths: Vec<String> = violations
+    .iter()
+    .map(|v| v.field_path_str().unwrap())
+    .collect();
+
+  assert_eq!(field_paths, ["match", "type", "self"]);
+  assert!(violations.iter().all(|v| v.rule_id() == "string.min_len"));
+}
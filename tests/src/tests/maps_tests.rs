@@ -1,11 +1,14 @@
-use maplit::hashmap;
+use maplit::{btreemap, hashmap};
 use protocheck::types::{
   field_descriptor_proto::Type,
   protovalidate::{field_path_element::Subscript, Violation, Violations},
   Duration, Timestamp,
 };
 
-use crate::myapp::v1::{message_map::Person, BasicMap, DurationMap, MessageMap, TimestampMap};
+use crate::myapp::v1::{
+  keyed_message_map::Member, message_map::Person, BTreeMapTests, BasicMap, DurationMap,
+  KeyedMessageMap, MessageMap, TimestampMap,
+};
 
 #[test]
 fn message_map() {
@@ -272,3 +275,76 @@ fn basic_map() {
 
   assert!(violations.iter().any(|v| v.rule_id() == "map.max_pairs"));
 }
+
+#[test]
+fn keyed_message_map() {
+  let members = hashmap! {
+    "a".to_string() => Member { name: "alfonso".to_string() },
+    "bc".to_string() => Member { name: String::new() },
+  };
+
+  let msg = KeyedMessageMap { members };
+
+  let Violations { violations } = msg.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 2);
+
+  let key_violation = violations
+    .iter()
+    .find(|v| v.rule_path_str().as_deref() == Some("map.keys.string.min_len"))
+    .unwrap();
+
+  assert_eq!(key_violation.for_key(), true);
+  assert_eq!(
+    key_violation.last_field().unwrap().subscript,
+    Some(Subscript::StringKey("a".to_string()))
+  );
+
+  let value_violation = violations
+    .iter()
+    .find(|v| v.rule_path_str().as_deref() == Some("string.min_len"))
+    .unwrap();
+
+  assert_eq!(value_violation.last_field().unwrap().field_name(), "name");
+  assert_eq!(
+    value_violation.parent_field().unwrap().subscript,
+    Some(Subscript::StringKey("bc".to_string()))
+  );
+
+  let msg = KeyedMessageMap {
+    members: hashmap! { "ab".to_string() => Member { name: "alfonso".to_string() } },
+  };
+
+  assert!(msg.validate().is_ok());
+}
+
+#[test]
+fn btree_map() {
+  let msg = BTreeMapTests {
+    scores: btreemap! { 0 => 1, 1 => 99 },
+  };
+
+  assert!(msg.validate().is_ok());
+
+  let msg = BTreeMapTests {
+    scores: btreemap! { -1 => 1, 1 => 100, 2 => 3, 3 => 4 },
+  };
+
+  let Violations { violations } = msg.validate().unwrap_err();
+
+  let mut paths: Vec<(Option<String>, &str, bool)> = violations
+    .iter()
+    .map(|v| (v.field_path_str(), v.rule_id(), v.for_key()))
+    .collect();
+  paths.sort();
+
+  assert_eq!(
+    paths,
+    [
+      (None, "btree_map.has_default", false),
+      (Some("scores".to_string()), "map.max_pairs", false),
+      (Some("scores[-1]".to_string()), "int32.gte", true),
+      (Some("scores[1]".to_string()), "int32.lt", false),
+    ]
+  );
+}
@@ -0,0 +1,45 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::{price_range::Limits, PriceRange};
+
+fn price_range(min: i64, max: i64, ceiling: i64) -> PriceRange {
+  PriceRange {
+    min,
+    max,
+    limits: Some(Limits {
+      currency: "EUR".to_string(),
+      ceiling,
+    }),
+  }
+}
+
+#[test]
+fn message_rules() {
+  assert!(price_range(1, 5, 10).validate().is_ok());
+
+  let Violations { violations } = price_range(6, 5, 10).validate().unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "range");
+  assert_eq!(violations[0].message(), "min must not be greater than max");
+  assert!(!violations[0].has_fields());
+}
+
+#[test]
+fn multiple_message_rules() {
+  let Violations { violations } = price_range(20, 15, 10).validate().unwrap_err();
+
+  let rule_ids: Vec<&str> = violations.iter().map(|v| v.rule_id()).collect();
+
+  assert_eq!(rule_ids, ["range", "ceiling"]);
+  assert!(violations.iter().all(|v| !v.has_fields()));
+}
+
+#[test]
+fn message_rule_with_nested_field() {
+  let Violations { violations } = price_range(1, 15, 10).validate().unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "ceiling");
+  assert!(!violations[0].has_fields());
+}
@@ -1,14 +1,72 @@
+mod allocation_tests;
+mod any_tests;
+mod axum_tests;
+mod bool_tests;
 mod bytes_tests;
+mod cel_cache_tests;
+mod cel_environment_tests;
+mod cel_format_tests;
+mod cel_functions_tests;
+mod cel_map_tests;
+mod cel_struct_tests;
+mod cel_time_tests;
 mod common_types_tests;
 mod comparables_tests;
 mod containing_tests;
+mod decode_tests;
 mod docs_example;
+mod dynamic_tests;
+mod encode_tests;
+mod exclusion_tests;
+mod fail_fast_tests;
+mod field_mask_tests;
 mod finite_floats_tests;
 mod ignores_tests;
+mod json_value_tests;
+mod keyword_fields_tests;
 mod maps_tests;
+mod message_rules_tests;
+mod nested_tests;
+mod oneof_tests;
 mod optional_tests;
+mod predefined_rules_tests;
+mod prost_types_tests;
 mod recursion_tests;
 mod repeated_tests;
+mod required_tests;
+mod rule_ids_tests;
 mod strings_tests;
+mod time_arithmetic_tests;
+mod time_conversions_tests;
+mod time_formatting_tests;
 mod timestamps_tests;
+mod tonic_client_tests;
+mod tonic_service_tests;
+mod tonic_status_tests;
+mod tower_tests;
+mod transitive_tests;
+mod validated_requests_tests;
+mod validator_trait_tests;
+mod violations_display_tests;
+mod violations_lookup_tests;
+mod violations_serde_tests;
 mod well_known_strings_tests;
+mod wrappers_tests;
+
+use protocheck::ProtoValidator;
+
+// The ids of the rules violated by the message, in the order of the violations
+fn rule_ids(msg: &impl ProtoValidator) -> Vec<String> {
+  msg
+    .validate()
+    .err()
+    .map(|violations| violations.rule_ids().map(str::to_string).collect())
+    .unwrap_or_default()
+}
+
+// Like `rule_ids`, for the messages whose violations come in no particular order, such as the ones of the maps
+fn sorted_rule_ids(msg: &impl ProtoValidator) -> Vec<String> {
+  let mut ids = rule_ids(msg);
+  ids.sort();
+  ids
+}
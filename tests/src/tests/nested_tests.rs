@@ -0,0 +1,202 @@
+use maplit::hashmap;
+use prost::Message;
+use protocheck::types::{
+  field_descriptor_proto::Type,
+  protovalidate::{field_path_element::Subscript, Violations},
+};
+
+use crate::myapp::v1::{
+  nested_tests::{Address, Choice, Customer},
+  NestedTests, Unvalidated,
+};
+
+fn unvalidated() -> Unvalidated {
+  Unvalidated {
+    name: "".to_string(),
+  }
+}
+
+#[test]
+fn nested_paths() {
+  let msg = NestedTests {
+    customer: Some(Customer {
+      email: "not an email".to_string(),
+      addresses: vec![
+        Address {
+          street: "Main Street".to_string(),
+          labels: hashmap! { "a".to_string() => "home".to_string() },
+        },
+        Address {
+          street: "".to_string(),
+          labels: Default::default(),
+        },
+      ],
+    }),
+    customers: hashmap! {
+      "vip".to_string() => Customer {
+        email: "also not an email".to_string(),
+        addresses: vec![],
+      },
+    },
+    ..Default::default()
+  };
+
+  let Violations { violations } = msg.validate().unwrap_err();
+
+  let mut paths: Vec<(String, &str, bool)> = violations
+    .iter()
+    .map(|v| (v.field_path_str().unwrap(), v.rule_id(), v.for_key()))
+    .collect();
+  paths.sort();
+
+  assert_eq!(
+    paths,
+    [
+      (
+        "customer.addresses[0].labels[\"a\"]".to_string(),
+        "string.min_len",
+        true
+      ),
+      (
+        "customer.addresses[1].street".to_string(),
+        "string.min_len",
+        false
+      ),
+      ("customer.email".to_string(), "string.email", false),
+      (
+        "customers[\"vip\"].email".to_string(),
+        "string.email",
+        false
+      ),
+    ]
+  );
+
+  let street_violation = violations
+    .iter()
+    .find(|v| v.rule_id() == "string.min_len" && !v.for_key())
+    .unwrap();
+  let elements = &street_violation.field.as_ref().unwrap().elements;
+
+  assert_eq!(elements[0].field_number(), 1);
+  assert_eq!(elements[0].field_type(), Type::Message);
+  assert_eq!(elements[1].field_number(), 2);
+  assert_eq!(elements[1].field_type(), Type::Message);
+  assert_eq!(elements[2].field_number(), 1);
+  assert_eq!(elements[2].field_type(), Type::String);
+}
+
+#[test]
+fn messages_without_validators() {
+  let msg = NestedTests {
+    unvalidated: Some(unvalidated()),
+    unvalidated_list: vec![unvalidated()],
+    unvalidated_map: hashmap! { "key".to_string() => unvalidated() },
+    choice: Some(Choice::UnvalidatedChoice(unvalidated())),
+    ..Default::default()
+  };
+
+  assert!(msg.validate().is_ok());
+}
+
+#[test]
+fn repeated_items_and_map_values() {
+  let invalid_address = || Address {
+    street: "".to_string(),
+    labels: Default::default(),
+  };
+  let invalid_customer = || Customer {
+    email: "not an email".to_string(),
+    addresses: vec![],
+  };
+
+  let msg = NestedTests {
+    customer: Some(Customer {
+      email: "me@example.com".to_string(),
+      addresses: vec![
+        invalid_address(),
+        Address {
+          street: "Main Street".to_string(),
+          labels: Default::default(),
+        },
+        invalid_address(),
+      ],
+    }),
+    customers: hashmap! {
+      "home".to_string() => invalid_customer(),
+      "work".to_string() => invalid_customer(),
+    },
+    ..Default::default()
+  };
+
+  let Violations { violations } = msg.validate().unwrap_err();
+
+  // Every invalid item and value is reported
+  let mut paths: Vec<String> = violations
+    .iter()
+    .map(|v| v.field_path_str().unwrap())
+    .collect();
+  paths.sort();
+
+  assert_eq!(
+    paths,
+    [
+      "customer.addresses[0].street",
+      "customer.addresses[2].street",
+      "customers[\"home\"].email",
+      "customers[\"work\"].email",
+    ]
+  );
+
+  let item_violation = violations
+    .iter()
+    .find(|v| v.field_path_str().unwrap() == "customer.addresses[2].street")
+    .unwrap();
+  let item_element = &item_violation.field.as_ref().unwrap().elements[1];
+
+  assert_eq!(item_element.field_name(), "addresses");
+  assert_eq!(item_element.subscript, Some(Subscript::Index(2)));
+
+  let value_violation = violations
+    .iter()
+    .find(|v| v.field_path_str().unwrap() == "customers[\"home\"].email")
+    .unwrap();
+  let value_element = &value_violation.field.as_ref().unwrap().elements[0];
+
+  assert_eq!(value_element.field_name(), "customers");
+  assert_eq!(
+    value_element.subscript,
+    Some(Subscript::StringKey("home".to_string()))
+  );
+  assert_eq!(value_element.key_type(), Type::String);
+  assert_eq!(value_element.value_type(), Type::Message);
+}
+
+#[test]
+fn field_path_wire_format() {
+  let msg = NestedTests {
+    customers: hashmap! {
+      "vip".to_string() => Customer {
+        email: "not an email".to_string(),
+        addresses: vec![],
+      },
+    },
+    ..Default::default()
+  };
+
+  let violations = msg.validate().unwrap_err();
+  let field_path = violations.violations[0].field.as_ref().unwrap();
+
+  assert_eq!(field_path.to_string(), "customers[\"vip\"].email");
+
+  // Same encoding as the FieldPathElement of protovalidate, with the number, name, type, key type, value type and key
+  let mut expected = vec![0x08, 0x02, 0x12, 0x09];
+  expected.extend(b"customers");
+  expected.extend([0x18, 0x0b, 0x20, 0x09, 0x28, 0x0b, 0x52, 0x03]);
+  expected.extend(b"vip");
+
+  assert_eq!(field_path.elements[0].encode_to_vec(), expected);
+
+  let decoded = Violations::decode(violations.encode_to_vec().as_slice()).unwrap();
+
+  assert_eq!(decoded, violations);
+}
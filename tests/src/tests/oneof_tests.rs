@@ -0,0 +1,29 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::{required_oneof_test::Contact, RequiredOneofTest};
+
+#[test]
+fn required_oneof_test() {
+  let unset = RequiredOneofTest { contact: None };
+
+  let Violations { violations } = unset.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "oneof.required");
+  assert_eq!(
+    violations[0].field.as_ref().unwrap().elements[0].field_name(),
+    "contact"
+  );
+
+  let email = RequiredOneofTest {
+    contact: Some(Contact::Email("me@example.com".to_string())),
+  };
+
+  assert!(email.validate().is_ok());
+
+  let phone = RequiredOneofTest {
+    contact: Some(Contact::Phone("123".to_string())),
+  };
+
+  assert!(phone.validate().is_ok());
+}
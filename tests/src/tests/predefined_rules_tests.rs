@@ -0,0 +1,58 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::{predefined_rules_test::Choice, PredefinedRulesTest};
+
+fn rule_path(violation: &protocheck::types::protovalidate::Violation) -> Vec<String> {
+  violation
+    .rule
+    .as_ref()
+    .unwrap()
+    .elements
+    .iter()
+    .map(|element| element.field_name().to_string())
+    .collect()
+}
+
+#[test]
+fn predefined_rules_test() {
+  let valid = PredefinedRulesTest {
+    ticker: "ABC".to_string(),
+    quantity: 10,
+    choice: Some(Choice::Lot(200)),
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = PredefinedRulesTest {
+    ticker: "abc".to_string(),
+    quantity: 7,
+    choice: Some(Choice::Lot(150)),
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 3);
+
+  // The oneofs are validated first
+  assert_eq!(
+    violations[0].field.as_ref().unwrap().elements[0].field_name(),
+    "lot"
+  );
+  assert_eq!(
+    rule_path(&violations[0]),
+    ["int32", "[myapp.v1.multiple_of]"]
+  );
+
+  assert_eq!(violations[1].rule_id(), "string.is_ticker");
+  assert_eq!(violations[1].message(), "must be a ticker symbol");
+  assert_eq!(
+    rule_path(&violations[1]),
+    ["string", "[myapp.v1.is_ticker]"]
+  );
+
+  assert_eq!(violations[2].rule_id(), "int32.multiple_of");
+  assert_eq!(
+    rule_path(&violations[2]),
+    ["int32", "[myapp.v1.multiple_of]"]
+  );
+}
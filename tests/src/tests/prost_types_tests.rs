@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use prost_types::{Duration, Timestamp};
+use protocheck::types::protovalidate::Violations;
+
+use crate::prosttypes::v1::{prost_types_test::OneofDuration, ProstTypesTest};
+
+fn timestamp(seconds: i64) -> Timestamp {
+  Timestamp { seconds, nanos: 0 }
+}
+
+fn duration(seconds: i64) -> Duration {
+  Duration { seconds, nanos: 0 }
+}
+
+#[test]
+fn prost_types_test() {
+  let valid = ProstTypesTest {
+    timestamp: Some(timestamp(2000)),
+    duration: Some(duration(30)),
+    timestamps: vec![timestamp(2000)],
+    durations: HashMap::from([("a".to_string(), duration(10))]),
+    cel_timestamp: Some(timestamp(1_700_000_000)),
+    oneof_duration: Some(OneofDuration::DurationVariant(duration(5))),
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = ProstTypesTest {
+    timestamp: Some(timestamp(500)),
+    duration: Some(duration(120)),
+    timestamps: vec![timestamp(500)],
+    durations: HashMap::from([("a".to_string(), duration(5))]),
+    cel_timestamp: Some(timestamp(0)),
+    oneof_duration: Some(OneofDuration::DurationVariant(duration(0))),
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  let mut rule_ids: Vec<&str> = violations.iter().map(|v| v.rule_id()).collect();
+  rule_ids.sort();
+
+  assert_eq!(
+    rule_ids,
+    [
+      "cel_timestamp",
+      "duration.const",
+      "duration.gt",
+      "duration.lte",
+      "timestamp.gt",
+      "timestamp.gt"
+    ]
+  );
+}
@@ -1,6 +1,9 @@
-use protocheck::types::protovalidate::Violations;
+use protocheck::{
+  types::protovalidate::Violations,
+  validators::recursion::{with_max_depth, DEFAULT_MAX_DEPTH},
+};
 
-use crate::myapp::v1::{recursive::Oneofs, Recursive};
+use crate::myapp::v1::{recursive::Oneofs, Node, Recursive};
 
 #[test]
 fn recursion_test() {
@@ -26,3 +29,64 @@ fn recursion_test() {
   assert!(err2);
   assert!(err3);
 }
+
+fn node(name: &str) -> Node {
+  Node {
+    name: name.to_string(),
+    ..Default::default()
+  }
+}
+
+// A chain of nodes through the children, with the given depth for the last one
+fn nested_children(depth: usize) -> Node {
+  (0..depth).fold(node("leaf"), |child, _| Node {
+    children: vec![child],
+    ..node("node")
+  })
+}
+
+#[test]
+fn self_referential_fields() {
+  let tree = Node {
+    children: vec![node("a"), node("")],
+    parent: Some(Box::new(Node {
+      parent: Some(Box::new(node(""))),
+      ..node("parent")
+    })),
+    ..node("root")
+  };
+
+  let Violations { violations } = tree.validate().unwrap_err();
+
+  let mut paths: Vec<String> = violations
+    .iter()
+    .map(|v| v.field_path_str().unwrap())
+    .collect();
+  paths.sort();
+
+  assert_eq!(paths, ["children[1].name", "parent.parent.name"]);
+}
+
+#[test]
+fn recursion_limit() {
+  assert!(nested_children(DEFAULT_MAX_DEPTH).validate().is_ok());
+
+  let Violations { violations } = nested_children(DEFAULT_MAX_DEPTH + 50)
+    .validate()
+    .unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "message.recursion_limit");
+  assert_eq!(
+    violations[0].field.as_ref().unwrap().elements.len(),
+    DEFAULT_MAX_DEPTH + 1
+  );
+
+  let Violations { violations } =
+    with_max_depth(10, || nested_children(11).validate()).unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "message.recursion_limit");
+
+  assert!(with_max_depth(10, || nested_children(10).validate()).is_ok());
+}
@@ -1,6 +1,14 @@
-use protocheck::types::{field_descriptor_proto::Type, protovalidate::Violation};
+use std::collections::HashMap;
 
-use crate::myapp::v1::{repeated_tests::Person, RepeatedTests};
+use bytes::Bytes;
+use protocheck::types::{
+  field_descriptor_proto::Type,
+  protovalidate::{field_path_element::Subscript, Violation, Violations},
+};
+
+use crate::myapp::v1::{
+  items_rules_tests::Tag, repeated_tests::Person, ItemsRulesTests, RepeatedTests, UniqueItemsTests,
+};
 
 #[test]
 fn repeated_tests() {
@@ -83,3 +91,128 @@ fn repeated_tests() {
     assert_eq!(v.rule_path_str().unwrap(), "repeated.items.cel");
   }
 }
+
+fn unique_items_violations(test: &UniqueItemsTests) -> Vec<(String, String)> {
+  match test.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+      .collect(),
+  }
+}
+
+#[test]
+fn unique_items() {
+  let many_strings = || -> Vec<String> { (0..20).map(|i| i.to_string()).collect() };
+
+  let valid = UniqueItemsTests {
+    // NaN is never equal to another value
+    doubles: vec![f64::NAN, f64::NAN, 1.0],
+    strings: many_strings(),
+    chunks: vec![Bytes::from_static(b"a"), Bytes::from_static(b"b")],
+    ints: vec![1],
+  };
+
+  assert!(unique_items_violations(&valid).is_empty());
+
+  let mut strings = many_strings();
+  strings.push("7".to_string());
+
+  let invalid = UniqueItemsTests {
+    // -0.0 is equal to 0.0
+    doubles: vec![0.0, -0.0],
+    strings,
+    chunks: vec![Bytes::from_static(b"a"), Bytes::from_static(b"a")],
+    ints: vec![],
+  };
+
+  assert_eq!(
+    unique_items_violations(&invalid),
+    [
+      ("doubles".to_string(), "repeated.unique".to_string()),
+      ("strings".to_string(), "repeated.unique".to_string()),
+      ("chunks".to_string(), "repeated.unique".to_string()),
+      ("ints".to_string(), "repeated.min_items".to_string()),
+    ]
+  );
+
+  let invalid = UniqueItemsTests {
+    ints: vec![0; 1_000_000],
+    ..valid
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "repeated.max_items");
+  assert_eq!(violations[0].message(), "cannot contain more than 3 items");
+}
+
+#[test]
+fn items_rules() {
+  let tag = |name: &str| Tag {
+    name: name.to_string(),
+  };
+
+  let valid = ItemsRulesTests {
+    tags: vec!["a".to_string(), "b".to_string()],
+    labels: vec![tag("a")],
+    scores: HashMap::from([("a".to_string(), 1)]),
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = ItemsRulesTests {
+    tags: vec![
+      "a".to_string(),
+      String::new(),
+      String::new(),
+      "b".to_string(),
+    ],
+    labels: vec![tag("a"), tag(""), tag("a b")],
+    scores: HashMap::from([("a b".to_string(), 0)]),
+  };
+
+  let violations = invalid.validate().unwrap_err().violations;
+
+  let mut paths: Vec<(String, &str)> = violations
+    .iter()
+    .map(|v| (v.field_path_str().unwrap(), v.rule_id()))
+    .collect();
+  paths.sort();
+
+  assert_eq!(
+    paths,
+    [
+      ("labels[1].name".to_string(), "string.min_len"),
+      ("labels[2]".to_string(), "label.no_spaces"),
+      (r#"scores["a b"]"#.to_string(), "int32.gt"),
+      ("tags[1]".to_string(), "string.min_len"),
+      ("tags[2]".to_string(), "string.min_len"),
+    ]
+  );
+
+  let tag_violation = violations
+    .iter()
+    .find(|v| v.field_path_str().as_deref() == Some("tags[2]"))
+    .unwrap();
+  let element = tag_violation.last_field().unwrap();
+
+  assert_eq!(element.field_name(), "tags");
+  assert_eq!(element.subscript, Some(Subscript::Index(2)));
+  assert_eq!(
+    tag_violation.rule_path_str().unwrap(),
+    "repeated.items.string.min_len"
+  );
+
+  let nested_violation = violations
+    .iter()
+    .find(|v| v.field_path_str().as_deref() == Some("labels[1].name"))
+    .unwrap();
+
+  assert_eq!(
+    nested_violation.parent_field().unwrap().subscript,
+    Some(Subscript::Index(1))
+  );
+}
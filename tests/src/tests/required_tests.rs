@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use protocheck::types::{protovalidate::Violations, StringValue};
+
+use crate::myapp::v1::{
+  required_tests::{Address, Contact},
+  RequiredTests,
+};
+
+fn required_violations(message: &RequiredTests) -> Vec<(String, String)> {
+  match message.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => {
+      let mut violations: Vec<(String, String)> = violations
+        .iter()
+        .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+        .collect();
+      violations.sort();
+      violations
+    }
+  }
+}
+
+fn violation(path: &str, rule_id: &str) -> (String, String) {
+  (path.to_string(), rule_id.to_string())
+}
+
+fn valid_message() -> RequiredTests {
+  RequiredTests {
+    address: Some(Address {
+      city: "Rome".to_string(),
+    }),
+    age: Some(0),
+    nickname: Some(StringValue::default()),
+    tags: vec!["ab".to_string()],
+    scores: HashMap::from([("math".to_string(), 0)]),
+    name: "bob".to_string(),
+    count: 1,
+    legacy: String::new(),
+    contact: Some(Contact::Email("bob@example.com".to_string())),
+  }
+}
+
+#[test]
+fn required_set() {
+  // The fields with presence only need to be set, even to the zero value
+  assert!(required_violations(&valid_message()).is_empty());
+}
+
+#[test]
+fn required_unset() {
+  let unset = RequiredTests {
+    address: None,
+    age: None,
+    nickname: None,
+    tags: vec![],
+    scores: HashMap::new(),
+    name: String::new(),
+    count: 0,
+    legacy: String::new(),
+    contact: None,
+  };
+
+  // The other rules are not applied to the missing values
+  assert_eq!(
+    required_violations(&unset),
+    [
+      violation("address", "required"),
+      violation("age", "required"),
+      violation("count", "required"),
+      violation("email", "required"),
+      violation("name", "required"),
+      violation("nickname", "required"),
+      violation("scores", "required"),
+      violation("tags", "required"),
+    ]
+  );
+
+  let violations = unset.validate().unwrap_err().violations;
+
+  assert!(violations
+    .iter()
+    .all(|v| v.message() == "value is required"));
+}
+
+#[test]
+fn required_oneof_field() {
+  // The oneof must be set to the required field
+  let other_field = RequiredTests {
+    contact: Some(Contact::Phone("555".to_string())),
+    ..valid_message()
+  };
+
+  assert_eq!(
+    required_violations(&other_field),
+    [violation("email", "required")]
+  );
+
+  let invalid_email = RequiredTests {
+    contact: Some(Contact::Email("bob".to_string())),
+    ..valid_message()
+  };
+
+  assert_eq!(
+    required_violations(&invalid_email),
+    [violation("email", "string.email")]
+  );
+}
+
+#[test]
+fn required_with_other_rules() {
+  // The rules of the type are still applied to the values that are set
+  let invalid = RequiredTests {
+    address: Some(Address::default()),
+    age: Some(-1),
+    tags: vec!["a".to_string()],
+    name: "bo".to_string(),
+    ..valid_message()
+  };
+
+  assert_eq!(
+    required_violations(&invalid),
+    [
+      violation("address.city", "string.min_len"),
+      violation("age", "int32.gte"),
+      violation("name", "string.min_len"),
+      violation("tags[0]", "string.min_len"),
+    ]
+  );
+}
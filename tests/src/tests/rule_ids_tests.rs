@@ -0,0 +1,43 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::RuleIdsTests;
+
+#[test]
+fn rule_ids() {
+  let msg = RuleIdsTests {
+    tags: vec!["".to_string()],
+    labels: vec!["a".to_string(), "a".to_string()],
+    ..Default::default()
+  };
+
+  let Violations { violations } = msg.validate().unwrap_err();
+
+  let violations: Vec<(String, &str, String)> = violations
+    .iter()
+    .map(|v| {
+      (
+        v.field_path_str().unwrap(),
+        v.rule_id(),
+        v.rule_path_str().unwrap(),
+      )
+    })
+    .collect();
+
+  // The field, rule id and rule path of each violation
+  let expected = [
+    ("name", "string.min_len", "string.min_len"),
+    ("email", "string.email_empty", "string.email"),
+    ("payload", "bytes.pattern", "bytes.pattern"),
+    ("score", "int32.gt_lt", "int32.gt"),
+    ("code", "uint64.in", "uint64.in"),
+    ("active", "bool.const", "bool.const"),
+    ("tags[0]", "string.min_len", "repeated.items.string.min_len"),
+    ("labels", "repeated.unique", "repeated.unique"),
+    ("attributes", "map.min_pairs", "map.min_pairs"),
+    ("level", "level.positive", "cel"),
+    ("owner", "required", "required"),
+  ]
+  .map(|(field, rule_id, rule_path)| (field.to_string(), rule_id, rule_path.to_string()));
+
+  assert_eq!(violations, expected);
+}
@@ -1,6 +1,6 @@
 use protocheck::types::protovalidate::Violations;
 
-use crate::myapp::v1::StringTests;
+use crate::myapp::v1::{StringLengthTests, StringTests, SubstringTests};
 
 #[test]
 fn string_tests() {
@@ -45,3 +45,126 @@ fn string_tests() {
 
   assert!(valid_result.is_ok());
 }
+
+fn length_violations(value: &str) -> Vec<String> {
+  let message = StringLengthTests {
+    max_len: value.to_string(),
+    min_len: value.to_string(),
+    len: value.to_string(),
+    max_bytes: value.to_string(),
+    min_bytes: value.to_string(),
+    len_bytes: value.to_string(),
+  };
+
+  match message.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations.iter().map(|v| v.rule_id().to_string()).collect(),
+  }
+}
+
+#[test]
+fn string_lengths() {
+  assert!(length_violations("hello").is_empty());
+
+  // 5 code points, 6 bytes
+  assert_eq!(
+    length_violations("héllo"),
+    ["string.max_bytes", "string.len_bytes"]
+  );
+
+  // 5 code points (with a combining acute accent), 6 bytes
+  assert_eq!(
+    length_violations("he\u{301}ll"),
+    ["string.max_bytes", "string.len_bytes"]
+  );
+
+  // 2 code points, 8 bytes
+  assert_eq!(
+    length_violations("🦀🦀"),
+    [
+      "string.min_len",
+      "string.len",
+      "string.max_bytes",
+      "string.len_bytes"
+    ]
+  );
+
+  // 6 code points, 6 bytes
+  assert_eq!(
+    length_violations("hobbit"),
+    [
+      "string.max_len",
+      "string.len",
+      "string.max_bytes",
+      "string.len_bytes"
+    ]
+  );
+
+  // 2 code points, 5 bytes
+  assert_eq!(length_violations("🦀a"), ["string.min_len", "string.len"]);
+
+  assert_eq!(
+    length_violations(&"🦀".repeat(10_000)),
+    [
+      "string.max_len",
+      "string.len",
+      "string.max_bytes",
+      "string.len_bytes"
+    ]
+  );
+}
+
+#[test]
+fn substrings() {
+  let valid = SubstringTests {
+    empty_prefix: String::new(),
+    // Precomposed é
+    accented: "caf\u{e9}".to_string(),
+    combined: "abmnyz".to_string(),
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = SubstringTests {
+    empty_prefix: "anything".to_string(),
+    // e followed by a combining acute accent, which is not normalized
+    accented: "cafe\u{301}".to_string(),
+    combined: "xX".to_string(),
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  let accented: Vec<&str> = violations
+    .iter()
+    .filter(|v| v.field_path_str().as_deref() == Some("accented"))
+    .map(|v| v.rule_id())
+    .collect();
+
+  assert_eq!(accented, ["string.contains"]);
+
+  let combined: Vec<&str> = violations
+    .iter()
+    .filter(|v| v.field_path_str().as_deref() == Some("combined"))
+    .map(|v| v.rule_id())
+    .collect();
+
+  for rule_id in [
+    "string.min_len",
+    "string.pattern",
+    "string.prefix",
+    "string.suffix",
+    "string.contains",
+    "string.not_contains",
+  ] {
+    assert!(combined.contains(&rule_id), "missing {}", rule_id);
+  }
+
+  assert_eq!(violations.len(), 7);
+
+  let prefix = violations
+    .iter()
+    .find(|v| v.rule_id() == "string.prefix")
+    .unwrap();
+
+  assert_eq!(prefix.message(), "must start with 'ab'");
+}
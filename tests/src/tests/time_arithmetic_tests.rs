@@ -0,0 +1,163 @@
+use proptest::prelude::*;
+use protocheck::{
+  types::{Duration, Timestamp},
+  validators::timestamps::with_now,
+};
+
+use crate::myapp::v1::TimestampTests;
+
+// 0001-01-01T00:00:00Z and 9999-12-31T23:59:59Z
+const MIN_SECONDS: i64 = -62_135_596_800;
+const MAX_SECONDS: i64 = 253_402_300_799;
+const MAX_DURATION_SECONDS: i64 = 315_576_000_000;
+
+fn timestamps() -> impl Strategy<Value = Timestamp> {
+  (MIN_SECONDS..=MAX_SECONDS, 0..1_000_000_000i32)
+    .prop_map(|(seconds, nanos)| Timestamp { seconds, nanos })
+}
+
+// Not normalized, to check that the results are
+fn durations() -> impl Strategy<Value = Duration> {
+  (
+    -MAX_DURATION_SECONDS + 1..MAX_DURATION_SECONDS,
+    -999_999_999..1_000_000_000i32,
+  )
+    .prop_map(|(seconds, nanos)| Duration { seconds, nanos })
+}
+
+fn is_normalized_timestamp(timestamp: &Timestamp) -> bool {
+  (0..1_000_000_000).contains(&timestamp.nanos)
+}
+
+fn is_normalized_duration(duration: &Duration) -> bool {
+  duration.nanos.abs() < 1_000_000_000
+    && (duration.seconds == 0
+      || duration.nanos == 0
+      || (duration.seconds < 0) == (duration.nanos < 0))
+}
+
+proptest! {
+  #[test]
+  fn timestamp_and_duration(timestamp in timestamps(), duration in durations()) {
+    match timestamp.checked_add(&duration) {
+      Some(sum) => {
+        prop_assert!(is_normalized_timestamp(&sum) && sum.is_in_range());
+        prop_assert_eq!(sum.checked_sub(&duration), Some(timestamp));
+        prop_assert_eq!(sum - timestamp, Some(duration.normalized()));
+        prop_assert_eq!(timestamp + duration, sum);
+      }
+      None => {
+        let saturated = timestamp + duration;
+        prop_assert!(saturated == Timestamp::MIN || saturated == Timestamp::MAX);
+      }
+    }
+
+    match timestamp.checked_sub(&duration) {
+      Some(difference) => {
+        prop_assert!(is_normalized_timestamp(&difference) && difference.is_in_range());
+        prop_assert_eq!(difference.checked_add(&duration), Some(timestamp));
+        prop_assert_eq!(timestamp - duration, difference);
+      }
+      None => {
+        let saturated = timestamp - duration;
+        prop_assert!(saturated == Timestamp::MIN || saturated == Timestamp::MAX);
+      }
+    }
+  }
+
+  #[test]
+  fn between_timestamps(first in timestamps(), second in timestamps()) {
+    let duration = (second - first).unwrap();
+
+    prop_assert!(is_normalized_duration(&duration) && duration.is_in_range());
+    prop_assert_eq!(first + duration, second);
+    prop_assert_eq!(first - second, duration.checked_neg());
+  }
+
+  #[test]
+  fn between_durations(first in durations(), second in durations()) {
+    match first.checked_add(&second) {
+      Some(sum) => {
+        prop_assert!(is_normalized_duration(&sum) && sum.is_in_range());
+        prop_assert_eq!(sum.checked_sub(&second), Some(first));
+        prop_assert_eq!(first + second, sum);
+      }
+      None => {
+        let saturated = first + second;
+        prop_assert!(saturated == Duration::MIN || saturated == Duration::MAX);
+      }
+    }
+
+    let negated = first.checked_neg().unwrap();
+    prop_assert!(is_normalized_duration(&negated));
+    prop_assert_eq!(-first, negated);
+    prop_assert_eq!(negated.checked_add(&first), Some(Duration::default()));
+  }
+}
+
+#[test]
+fn limits() {
+  let nano = Duration::new(0, 1);
+
+  assert_eq!(Timestamp::MAX.checked_add(&nano), None);
+  assert_eq!(Timestamp::MIN.checked_sub(&nano), None);
+  assert_eq!(
+    Timestamp::MAX - nano,
+    Timestamp::new(MAX_SECONDS, 999_999_998)
+  );
+  assert_eq!(Timestamp::MAX + Duration::MAX, Timestamp::MAX);
+  assert_eq!(Timestamp::MIN - Duration::MAX, Timestamp::MIN);
+
+  // No overflow of the i64 seconds, even with the values that are out of range
+  let huge = Duration {
+    seconds: i64::MAX,
+    nanos: 999_999_999,
+  };
+  assert_eq!(Timestamp::MIN.checked_add(&huge), None);
+  assert_eq!(Timestamp::MIN + huge, Timestamp::MAX);
+  assert_eq!(Timestamp::MAX - huge, Timestamp::MIN);
+  assert_eq!(huge.checked_neg(), None);
+  assert_eq!(-huge, Duration::MIN);
+
+  assert_eq!(Duration::MAX.checked_add(&nano), None);
+  assert_eq!(Duration::MIN.checked_sub(&nano), None);
+  assert_eq!(Duration::MAX + nano, Duration::MAX);
+  assert_eq!(Duration::MIN - Duration::MAX, Duration::MIN);
+  assert_eq!(Duration::MAX.checked_neg(), Some(Duration::MIN));
+
+  let out_of_range = Timestamp::new(MAX_SECONDS + 1, 0);
+  assert_eq!(out_of_range - Timestamp::MIN, None);
+  assert_eq!(out_of_range + Duration::default(), Timestamp::MAX);
+}
+
+#[test]
+fn within_at_the_limits() {
+  // The bounds of the range past the limits of the valid timestamps are ignored
+  let test = |now: Timestamp, within: Timestamp| {
+    with_now(now, || {
+      TimestampTests {
+        within: Some(within),
+        ..Default::default()
+      }
+      .validate()
+      .is_ok()
+    })
+  };
+
+  assert!(test(
+    Timestamp::MAX,
+    Timestamp::MAX - Duration::new(3600, 0)
+  ));
+  assert!(!test(
+    Timestamp::MAX,
+    Timestamp::MAX - Duration::new(3601, 0)
+  ));
+  assert!(test(
+    Timestamp::MIN,
+    Timestamp::MIN + Duration::new(3600, 0)
+  ));
+  assert!(!test(
+    Timestamp::MIN,
+    Timestamp::MIN + Duration::new(3601, 0)
+  ));
+}
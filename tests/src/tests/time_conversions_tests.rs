@@ -0,0 +1,137 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use proptest::prelude::*;
+use protocheck::types::{duration::DurationError, timestamp::TimestampError, Duration, Timestamp};
+use time::OffsetDateTime;
+
+// 0001-01-01T00:00:00Z and 9999-12-31T23:59:59Z
+const MIN_SECONDS: i64 = -62_135_596_800;
+const MAX_SECONDS: i64 = 253_402_300_799;
+const MAX_DURATION_SECONDS: i64 = 315_576_000_000;
+
+fn timestamps() -> impl Strategy<Value = Timestamp> {
+  (MIN_SECONDS..=MAX_SECONDS, 0..1_000_000_000i32)
+    .prop_map(|(seconds, nanos)| Timestamp { seconds, nanos })
+}
+
+fn durations() -> impl Strategy<Value = Duration> {
+  (
+    -MAX_DURATION_SECONDS..=MAX_DURATION_SECONDS,
+    0..1_000_000_000i32,
+  )
+    .prop_map(|(seconds, nanos)| Duration {
+      seconds,
+      // The nanos have the same sign as the seconds
+      nanos: if seconds < 0 { -nanos } else { nanos },
+    })
+}
+
+proptest! {
+  #[test]
+  fn timestamps_round_trip(timestamp in timestamps()) {
+    let chrono_datetime = DateTime::<Utc>::try_from(timestamp).unwrap();
+    prop_assert_eq!(Timestamp::try_from(chrono_datetime).unwrap(), timestamp);
+    prop_assert_eq!(Timestamp::try_from(chrono_datetime.naive_utc()).unwrap(), timestamp);
+
+    let time_datetime = OffsetDateTime::try_from(timestamp).unwrap();
+    prop_assert_eq!(time_datetime.unix_timestamp(), timestamp.seconds);
+    prop_assert_eq!(Timestamp::try_from(time_datetime).unwrap(), timestamp);
+
+    let system_time = SystemTime::try_from(timestamp).unwrap();
+    prop_assert_eq!(Timestamp::from(system_time), timestamp);
+  }
+
+  #[test]
+  fn durations_round_trip(duration in durations()) {
+    let time_duration = time::Duration::try_from(duration).unwrap();
+    prop_assert_eq!(Duration::try_from(time_duration).unwrap(), duration);
+
+    let chrono_duration = chrono::TimeDelta::try_from(duration).unwrap();
+    prop_assert_eq!(Duration::from(chrono_duration), duration);
+
+    if duration.seconds >= 0 {
+      let std_duration = std::time::Duration::try_from(duration).unwrap();
+      prop_assert_eq!(Duration::try_from(std_duration).unwrap(), duration);
+    } else {
+      prop_assert!(matches!(
+        std::time::Duration::try_from(duration),
+        Err(DurationError::NegativeDuration(_))
+      ));
+    }
+  }
+}
+
+#[test]
+fn out_of_range_timestamps() {
+  let after_max = NaiveDate::from_ymd_opt(10000, 1, 1)
+    .unwrap()
+    .and_hms_opt(0, 0, 0)
+    .unwrap();
+
+  assert!(matches!(
+    Timestamp::try_from(after_max),
+    Err(TimestampError::OutOfRange(_))
+  ));
+  assert!(matches!(
+    Timestamp::try_from(after_max.and_utc()),
+    Err(TimestampError::OutOfRange(_))
+  ));
+
+  let before_min = OffsetDateTime::from_unix_timestamp(MIN_SECONDS - 1).unwrap();
+  assert!(matches!(
+    Timestamp::try_from(before_min),
+    Err(TimestampError::OutOfRange(_))
+  ));
+
+  // The nanos are normalized before the range is checked
+  let last = Timestamp {
+    seconds: MAX_SECONDS,
+    nanos: 999_999_999,
+  };
+  assert!(last.is_in_range());
+  assert!(!Timestamp {
+    nanos: 1_000_000_000,
+    ..last
+  }
+  .is_in_range());
+
+  // The time crate only supports the years up to 9999 by default
+  assert!(matches!(
+    OffsetDateTime::try_from(Timestamp {
+      seconds: MAX_SECONDS + 1,
+      nanos: 0,
+    }),
+    Err(TimestampError::OutOfRange(_))
+  ));
+}
+
+#[test]
+fn out_of_range_durations() {
+  let max = std::time::Duration::new(MAX_DURATION_SECONDS as u64, 999_999_999);
+
+  assert_eq!(
+    Duration::try_from(max).unwrap(),
+    Duration {
+      seconds: MAX_DURATION_SECONDS,
+      nanos: 999_999_999,
+    }
+  );
+  assert_eq!(
+    Duration::try_from(max + std::time::Duration::from_secs(1)),
+    Err(DurationError::OutOfRange)
+  );
+  assert_eq!(
+    Duration::try_from(time::Duration::seconds(-MAX_DURATION_SECONDS - 1)),
+    Err(DurationError::OutOfRange)
+  );
+
+  assert_eq!(
+    time::Duration::try_from(Duration {
+      seconds: i64::MAX,
+      nanos: 999_999_999,
+    })
+    .unwrap(),
+    time::Duration::new(i64::MAX, 999_999_999)
+  );
+}
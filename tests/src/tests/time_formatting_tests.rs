@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+use protocheck::types::{duration::DurationError, timestamp::TimestampError, Duration, Timestamp};
+
+// 0001-01-01T00:00:00Z and 9999-12-31T23:59:59Z
+const MIN_SECONDS: i64 = -62_135_596_800;
+const MAX_SECONDS: i64 = 253_402_300_799;
+const MAX_DURATION_SECONDS: i64 = 315_576_000_000;
+
+proptest! {
+  #[test]
+  fn timestamps_round_trip(seconds in MIN_SECONDS..=MAX_SECONDS, nanos in 0..1_000_000_000i32) {
+    let timestamp = Timestamp { seconds, nanos };
+    let formatted = timestamp.to_string();
+
+    prop_assert!(formatted.ends_with('Z'));
+    prop_assert_eq!(formatted.parse::<Timestamp>().unwrap(), timestamp);
+  }
+
+  #[test]
+  fn durations_round_trip(
+    seconds in -MAX_DURATION_SECONDS..=MAX_DURATION_SECONDS,
+    nanos in 0..1_000_000_000i32
+  ) {
+    let duration = Duration {
+      seconds,
+      nanos: if seconds < 0 { -nanos } else { nanos },
+    };
+    let formatted = duration.to_json_string();
+
+    prop_assert!(formatted.ends_with('s'));
+    prop_assert_eq!(formatted.parse::<Duration>().unwrap(), duration);
+  }
+}
+
+#[test]
+fn timestamps_formatting() {
+  let cases = [
+    (
+      Timestamp {
+        seconds: 0,
+        nanos: 0,
+      },
+      "1970-01-01T00:00:00Z",
+    ),
+    (
+      Timestamp {
+        seconds: 1_714_566_600,
+        nanos: 500_000_000,
+      },
+      "2024-05-01T12:30:00.500Z",
+    ),
+    (
+      Timestamp {
+        seconds: -1,
+        nanos: 1_000,
+      },
+      "1969-12-31T23:59:59.000001Z",
+    ),
+    // The nanos are normalized first
+    (
+      Timestamp {
+        seconds: 1,
+        nanos: -1,
+      },
+      "1970-01-01T00:00:00.999999999Z",
+    ),
+    (
+      Timestamp {
+        seconds: MIN_SECONDS,
+        nanos: 0,
+      },
+      "0001-01-01T00:00:00Z",
+    ),
+  ];
+
+  for (timestamp, expected) in cases {
+    assert_eq!(timestamp.to_string(), expected);
+  }
+}
+
+#[test]
+fn timestamps_parsing() {
+  let expected = Timestamp {
+    seconds: 1_714_566_600,
+    nanos: 500_000_000,
+  };
+
+  // The offsets are converted to UTC
+  for input in [
+    "2024-05-01T12:30:00.5Z",
+    "2024-05-01t12:30:00.500z",
+    "2024-05-01T14:30:00.5+02:00",
+    "2024-05-01T07:00:00.5-05:30",
+  ] {
+    assert_eq!(input.parse::<Timestamp>().unwrap(), expected, "{}", input);
+  }
+
+  assert_eq!(
+    "+10000-01-01T00:00:00Z".parse::<Timestamp>(),
+    Err(TimestampError::OutOfRange(Timestamp {
+      seconds: MAX_SECONDS + 1,
+      nanos: 0,
+    }))
+  );
+  // In range before the offset is applied
+  assert!(matches!(
+    "0001-01-01T00:30:00+01:00".parse::<Timestamp>(),
+    Err(TimestampError::OutOfRange(_))
+  ));
+  assert_eq!(
+    "2024-05-01 12:30".parse::<Timestamp>(),
+    Err(TimestampError::ParseFailure)
+  );
+}
+
+#[test]
+fn durations_formatting() {
+  let cases = [
+    (Duration::default(), "0s"),
+    (
+      Duration {
+        seconds: 3,
+        nanos: 500_000_000,
+      },
+      "3.500s",
+    ),
+    (
+      Duration {
+        seconds: 0,
+        nanos: -1_000,
+      },
+      "-0.000001s",
+    ),
+    (
+      Duration {
+        seconds: -1,
+        nanos: -5,
+      },
+      "-1.000000005s",
+    ),
+    // The nanos are normalized first
+    (
+      Duration {
+        seconds: 1,
+        nanos: -500_000_000,
+      },
+      "0.500s",
+    ),
+  ];
+
+  for (duration, expected) in cases {
+    assert_eq!(duration.to_json_string(), expected);
+  }
+
+  assert_eq!("1.5".parse::<Duration>(), Err(DurationError::ParseFailure));
+}
+
+#[test]
+fn normalized_comparisons() {
+  let timestamp = Timestamp {
+    seconds: 1,
+    nanos: -1,
+  };
+  let normalized = Timestamp {
+    seconds: 0,
+    nanos: 999_999_999,
+  };
+
+  assert_eq!(timestamp, normalized);
+  assert!(
+    timestamp
+      < Timestamp {
+        seconds: 1,
+        nanos: 0
+      }
+  );
+  assert_eq!(HashSet::from([timestamp, normalized]).len(), 1);
+
+  let duration = Duration {
+    seconds: 1,
+    nanos: -500_000_000,
+  };
+  let normalized = Duration {
+    seconds: 0,
+    nanos: 500_000_000,
+  };
+
+  assert_eq!(duration, normalized);
+  assert!(
+    duration
+      > Duration {
+        seconds: 0,
+        nanos: 499_999_999,
+      }
+  );
+  assert!(
+    Duration {
+      seconds: 0,
+      nanos: -1,
+    } < Duration::default()
+  );
+  assert_eq!(HashSet::from([duration, normalized]).len(), 1);
+}
@@ -1,6 +1,9 @@
-use protocheck::types::{protovalidate::Violations, Duration, Timestamp};
+use protocheck::{
+  types::{protovalidate::Violations, Duration, Timestamp},
+  validators::timestamps::with_now,
+};
 
-use crate::myapp::v1::TimestampTests;
+use crate::myapp::v1::{TimestampRulesTests, TimestampTests};
 
 #[test]
 fn timestamp() {
@@ -25,3 +28,77 @@ fn timestamp() {
 
   assert!(test.validate().is_ok())
 }
+
+fn timestamp_violations(test: &TimestampRulesTests) -> Vec<(String, String)> {
+  match test.validate() {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+      .collect(),
+  }
+}
+
+#[test]
+fn timestamp_rules() {
+  let now = Timestamp::new(1_000_000, 0);
+
+  let valid = TimestampRulesTests {
+    fixed: Some(Timestamp::new(100, 0)),
+    around_epoch: Some(Timestamp::new(-1, 600_000_000)),
+    soon: Some(Timestamp::new(1_000_030, 0)),
+    recent: Some(Timestamp::new(999_970, 0)),
+  };
+
+  assert!(with_now(now, || timestamp_violations(&valid)).is_empty());
+
+  let invalid = TimestampRulesTests {
+    fixed: Some(Timestamp::new(100, 1)),
+    // -1.5s, which is before -0.5s even though the nanos are greater
+    around_epoch: Some(Timestamp::new(-2, 500_000_000)),
+    // Both rules are evaluated
+    soon: Some(Timestamp::new(999_000, 0)),
+    recent: Some(Timestamp::new(1_000_100, 0)),
+  };
+
+  let rule = |path: &str, rule_id: &str| (path.to_string(), rule_id.to_string());
+
+  assert_eq!(
+    with_now(now, || timestamp_violations(&invalid)),
+    [
+      rule("fixed", "timestamp.const"),
+      rule("around_epoch", "timestamp.gt_lte"),
+      rule("soon", "timestamp.within"),
+      rule("soon", "timestamp.gt_now"),
+      rule("recent", "timestamp.within"),
+      rule("recent", "timestamp.lt_now"),
+    ]
+  );
+
+  // The same values are valid at a different time
+  let later = TimestampRulesTests {
+    soon: Some(Timestamp::new(999_000, 0)),
+    recent: None,
+    ..valid
+  };
+
+  assert!(with_now(Timestamp::new(998_990, 0), || timestamp_violations(&later)).is_empty());
+}
+
+#[test]
+fn invalid_timestamps() {
+  let test = TimestampRulesTests {
+    fixed: Some(Timestamp::new(100, 1_000_000_000)),
+    around_epoch: Some(Timestamp::new(0, -1)),
+    soon: None,
+    recent: None,
+  };
+
+  assert_eq!(
+    timestamp_violations(&test),
+    [
+      ("fixed".to_string(), "timestamp.valid".to_string()),
+      ("around_epoch".to_string(), "timestamp.valid".to_string()),
+    ]
+  );
+}
@@ -0,0 +1,116 @@
+use std::{
+  convert::Infallible,
+  future::Ready,
+  task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use protocheck::tonic::{call_with_validated_stream, CallError, IntoValidatedRequest};
+use tonic::{
+  body::Body,
+  client::GrpcService,
+  codegen::{http, Body as HttpBody, Service, StdError},
+  Code, Request, Status,
+};
+
+use super::tonic_service_tests::{greeting, name, received, Greeter};
+use crate::{
+  clients::v1::greeter_service_client::GreeterServiceClient,
+  myapp::v1::{greeter_service_server::GreeterServiceServer, GreetRequest},
+};
+
+// A transport that fails the test if a request is ever sent through it
+#[derive(Clone)]
+struct Unreachable;
+
+impl Service<http::Request<Body>> for Unreachable {
+  type Response = http::Response<Body>;
+  type Error = Infallible;
+  type Future = Ready<Result<Self::Response, Infallible>>;
+
+  fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+    panic!("the request was sent to {}", request.uri())
+  }
+}
+
+async fn greet<T>(
+  client: &mut GreeterServiceClient<T>,
+  request: impl IntoValidatedRequest<GreetRequest>,
+) -> Result<String, CallError>
+where
+  T: GrpcService<Body>,
+  T::Error: Into<StdError>,
+  T::ResponseBody: HttpBody<Data = Bytes> + Send + 'static,
+  <T::ResponseBody as HttpBody>::Error: Into<StdError> + Send,
+{
+  let request = request.into_validated_request()?;
+
+  Ok(client.greet(request).await?.into_inner().text)
+}
+
+#[tokio::test]
+async fn unary_requests() {
+  let mut unreachable = GreeterServiceClient::new(Unreachable);
+
+  // The invalid requests are never sent
+  let error = greet(&mut unreachable, name("")).await.unwrap_err();
+  assert_eq!(
+    error.violations().unwrap().violations[0].rule_id(),
+    "string.min_len"
+  );
+  assert_eq!(Status::from(error).code(), Code::InvalidArgument);
+
+  let mut request = Request::new(name(""));
+  request
+    .metadata_mut()
+    .insert("x-request-id", "1".parse().unwrap());
+  assert!(matches!(
+    greet(&mut unreachable, request).await,
+    Err(CallError::Invalid(_))
+  ));
+
+  // While the valid ones reach the service
+  let greeter = Greeter::default();
+  let mut client = GreeterServiceClient::new(GreeterServiceServer::new(greeter.clone()));
+
+  assert_eq!(
+    greet(&mut client, name("obiwan")).await.unwrap(),
+    greeting("obiwan").text
+  );
+  assert_eq!(received(&greeter), [Ok("obiwan".to_string())]);
+}
+
+#[tokio::test]
+async fn streaming_requests() {
+  let greeter = Greeter::default();
+  let mut client = GreeterServiceClient::new(GreeterServiceServer::new(greeter.clone()));
+
+  let stream = tokio_stream::iter([name("obiwan"), name("anakin")]);
+  let response = call_with_validated_stream(stream, |stream| client.stream_greetings(stream))
+    .await
+    .unwrap();
+  assert!(response.into_inner().message().await.unwrap().is_some());
+  assert_eq!(
+    received(&greeter),
+    [Ok("obiwan".to_string()), Ok("anakin".to_string())]
+  );
+
+  // The call is dropped at the first invalid message, so the following ones are never sent
+  let greeter = Greeter::default();
+  let mut client = GreeterServiceClient::new(GreeterServiceServer::new(greeter.clone()));
+
+  let stream = tokio_stream::iter([name("obiwan"), name(""), name("anakin")]);
+  let error = call_with_validated_stream(stream, |stream| client.stream_greetings(stream))
+    .await
+    .unwrap_err();
+
+  assert_eq!(
+    error.violations().unwrap().violations[0].rule_id(),
+    "string.min_len"
+  );
+  assert_eq!(received(&greeter), [Ok("obiwan".to_string())]);
+}
@@ -0,0 +1,224 @@
+use std::{
+  pin::Pin,
+  sync::{Arc, Mutex},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http_body_util::{BodyExt, Full};
+use prost::Message;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{
+  codegen::{http, Service},
+  Code, Request, Response, Status, Streaming,
+};
+
+use crate::myapp::v1::{
+  greeter_service_server::{GreeterService, GreeterServiceServer},
+  GreetRequest, Greeting, ListGreetingsRequest,
+};
+
+type GreetingsStream = Pin<Box<dyn Stream<Item = Result<Greeting, Status>> + Send>>;
+
+// Records the names of the requests that reach the handlers, and the errors of the streams
+#[derive(Clone, Default)]
+pub(super) struct Greeter {
+  received: Arc<Mutex<Vec<Result<String, Code>>>>,
+}
+
+pub(super) fn greeting(name: &str) -> Greeting {
+  Greeting {
+    text: format!("Hello, {name}!"),
+  }
+}
+
+#[tonic::async_trait]
+impl GreeterService for Greeter {
+  async fn greet(&self, request: Request<GreetRequest>) -> Result<Response<Greeting>, Status> {
+    let name = request.into_inner().name;
+    self.received.lock().unwrap().push(Ok(name.clone()));
+
+    Ok(Response::new(greeting(&name)))
+  }
+
+  type StreamGreetingsStream = GreetingsStream;
+
+  async fn stream_greetings(
+    &self,
+    request: Request<Streaming<GreetRequest>>,
+  ) -> Result<Response<Self::StreamGreetingsStream>, Status> {
+    let mut stream = request.into_inner();
+    let mut greetings = Vec::new();
+
+    loop {
+      match stream.next().await {
+        Some(Ok(request)) => {
+          self.received.lock().unwrap().push(Ok(request.name.clone()));
+          greetings.push(Ok(greeting(&request.name)));
+        }
+        Some(Err(status)) => {
+          // Records a few of the items that come after the error, before returning it
+          let following: Vec<_> = stream.take(3).collect().await;
+
+          let mut received = self.received.lock().unwrap();
+          received.push(Err(status.code()));
+          received.extend(
+            following
+              .into_iter()
+              .map(|item| item.map(|request| request.name).map_err(|e| e.code())),
+          );
+
+          return Err(status);
+        }
+        None => break,
+      }
+    }
+
+    Ok(Response::new(Box::pin(tokio_stream::iter(greetings))))
+  }
+
+  type ListGreetingsStream = GreetingsStream;
+
+  async fn list_greetings(
+    &self,
+    request: Request<ListGreetingsRequest>,
+  ) -> Result<Response<Self::ListGreetingsStream>, Status> {
+    let page_size = request.into_inner().page_size;
+    let greetings: Vec<_> = (0..page_size)
+      .map(|i| Ok(greeting(&i.to_string())))
+      .collect();
+
+    Ok(Response::new(Box::pin(tokio_stream::iter(greetings))))
+  }
+}
+
+pub(super) fn name(name: &str) -> GreetRequest {
+  GreetRequest {
+    name: name.to_string(),
+  }
+}
+
+fn error(status: Option<Status>) -> Option<Status> {
+  status.filter(|status| status.code() != Code::Ok)
+}
+
+// Sends the messages to the method with the gRPC framing, and decodes the messages of the response
+async fn call(
+  greeter: &Greeter,
+  method: &str,
+  messages: &[impl Message],
+) -> Result<Vec<Greeting>, Status> {
+  let mut body = BytesMut::new();
+  for message in messages {
+    body.put_u8(0);
+    body.put_u32(message.encoded_len() as u32);
+    message.encode(&mut body).unwrap();
+  }
+
+  let request = http::Request::builder()
+    .method("POST")
+    .uri(format!("/myapp.v1.GreeterService/{method}"))
+    .header("content-type", "application/grpc")
+    .body(Full::new(body.freeze()))
+    .unwrap();
+
+  let response = GreeterServiceServer::new(greeter.clone())
+    .call(request)
+    .await
+    .unwrap();
+
+  // The errors that happen before the response are sent in the headers
+  if let Some(status) = error(Status::from_header_map(response.headers())) {
+    return Err(status);
+  }
+
+  let collected = response.into_body().collect().await.unwrap();
+  if let Some(status) = error(collected.trailers().and_then(Status::from_header_map)) {
+    return Err(status);
+  }
+
+  let mut bytes: Bytes = collected.to_bytes();
+  let mut greetings = Vec::new();
+  while bytes.has_remaining() {
+    bytes.advance(1);
+    let len = bytes.get_u32() as usize;
+    greetings.push(Greeting::decode(bytes.split_to(len)).unwrap());
+  }
+
+  Ok(greetings)
+}
+
+pub(super) fn received(greeter: &Greeter) -> Vec<Result<String, Code>> {
+  greeter.received.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn unary_requests() {
+  let greeter = Greeter::default();
+
+  assert_eq!(
+    call(&greeter, "Greet", &[name("obiwan")]).await.unwrap(),
+    [greeting("obiwan")]
+  );
+
+  // The invalid requests do not reach the handler
+  let status = call(&greeter, "Greet", &[name("")]).await.unwrap_err();
+  assert_eq!(status.code(), Code::InvalidArgument);
+  assert_eq!(received(&greeter), [Ok("obiwan".to_string())]);
+
+  // And the status contains the violations in its details, like the one converted from them
+  let expected = Status::from(name("").validate().unwrap_err());
+  assert_eq!(status.message(), expected.message());
+  assert_eq!(status.details(), expected.details());
+
+  // The requests without rules are always valid
+  assert_eq!(
+    call(
+      &greeter,
+      "ListGreetings",
+      &[ListGreetingsRequest { page_size: 2 }]
+    )
+    .await
+    .unwrap(),
+    [greeting("0"), greeting("1")]
+  );
+}
+
+#[tokio::test]
+async fn streaming_requests() {
+  let greeter = Greeter::default();
+
+  assert_eq!(
+    call(
+      &greeter,
+      "StreamGreetings",
+      &[name("obiwan"), name("anakin")]
+    )
+    .await
+    .unwrap(),
+    [greeting("obiwan"), greeting("anakin")]
+  );
+
+  // The stream ends with the status of the first invalid message
+  let greeter = Greeter::default();
+  let status = call(
+    &greeter,
+    "StreamGreetings",
+    &[name("obiwan"), name(""), name("anakin"), name("luke")],
+  )
+  .await
+  .unwrap_err();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+  assert_eq!(
+    status.message(),
+    Status::from(name("").validate().unwrap_err()).message()
+  );
+
+  // The messages after the invalid one are never yielded
+  let received = received(&greeter);
+  assert_eq!(
+    received[..2],
+    [Ok("obiwan".to_string()), Err(Code::InvalidArgument)]
+  );
+  assert!(received[2..].iter().all(Result::is_err), "{received:?}");
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use base64::{
+  alphabet,
+  engine::{general_purpose::GeneralPurposeConfig, DecodePaddingMode, GeneralPurpose},
+  Engine,
+};
+use prost::Message;
+use protocheck::types::{protovalidate::Violations, BadRequest, Code, Status};
+
+use crate::myapp::v1::{
+  display_tests::{Customer, Item},
+  DisplayTests,
+};
+
+// The padding of the header is optional
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+  &alphabet::STANDARD,
+  GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+fn violations() -> Violations {
+  DisplayTests {
+    customer: Some(Customer {
+      email: "obiwan".to_string(),
+    }),
+    items: vec![
+      Item {
+        sku: "abc".to_string(),
+      },
+      Item {
+        sku: "x".to_string(),
+      },
+    ],
+    quantities: HashMap::from([("apple".to_string(), 1)]),
+    total: 10,
+    discount: 0,
+  }
+  .validate()
+  .unwrap_err()
+}
+
+#[test]
+fn status_details() {
+  let violations = violations();
+  let status = violations.clone().into_status();
+
+  assert_eq!(status.code(), tonic::Code::InvalidArgument);
+  assert_eq!(
+    status.message(),
+    "customer.email: must be a valid email address (string.email); items[1].sku: must contain at least 3 characters (string.min_len)"
+  );
+
+  // Decoded from the header that is sent to the clients, like the interceptors of the other languages do
+  let response = status.clone().into_http::<()>();
+  let details = BASE64
+    .decode(response.headers().get("grpc-status-details-bin").unwrap())
+    .unwrap();
+
+  let rpc_status = Status::decode(details.as_slice()).unwrap();
+  assert_eq!(rpc_status.code, Code::InvalidArgument as i32);
+  assert_eq!(rpc_status.message, status.message());
+
+  let [bad_request, raw_violations] = rpc_status.details.as_slice() else {
+    panic!("there are two details");
+  };
+
+  assert_eq!(
+    bad_request.type_url,
+    "type.googleapis.com/google.rpc.BadRequest"
+  );
+  let bad_request = BadRequest::decode(bad_request.value.as_slice()).unwrap();
+  let fields: Vec<(&str, &str)> = bad_request
+    .field_violations
+    .iter()
+    .map(|v| (v.field.as_str(), v.description.as_str()))
+    .collect();
+
+  assert_eq!(
+    fields,
+    [
+      ("customer.email", "must be a valid email address"),
+      ("items[1].sku", "must contain at least 3 characters"),
+    ]
+  );
+
+  assert_eq!(
+    raw_violations.type_url,
+    "type.googleapis.com/buf.validate.Violations"
+  );
+  assert_eq!(
+    Violations::decode(raw_violations.value.as_slice()).unwrap(),
+    violations
+  );
+}
+
+#[test]
+fn empty_violations() {
+  let status = tonic::Status::from(Violations::default());
+
+  assert_eq!(status.code(), tonic::Code::InvalidArgument);
+  assert_eq!(status.message(), "Validation Error");
+}
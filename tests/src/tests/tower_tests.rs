@@ -0,0 +1,111 @@
+use std::{
+  convert::Infallible,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+};
+
+use protocheck::tower::ValidateRequestLayer;
+use tower::{service_fn, Layer, ServiceBuilder, ServiceExt};
+
+use crate::myapp::v1::GreetRequest;
+
+// A request type that knows nothing about protocheck
+struct Envelope {
+  id: u32,
+  message: Option<GreetRequest>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Reply {
+  Handled(u32),
+  Rejected(String),
+}
+
+fn envelope(id: u32, name: Option<&str>) -> Envelope {
+  Envelope {
+    id,
+    message: name.map(|name| GreetRequest {
+      name: name.to_string(),
+    }),
+  }
+}
+
+#[tokio::test]
+async fn valid_requests() {
+  let calls = Arc::new(AtomicUsize::new(0));
+  let handler = {
+    let calls = calls.clone();
+    service_fn(move |request: Envelope| {
+      calls.fetch_add(1, Ordering::SeqCst);
+      async move { Ok::<_, Infallible>(Reply::Handled(request.id)) }
+    })
+  };
+
+  let service = ServiceBuilder::new()
+    .layer(ValidateRequestLayer::new(
+      |request: &Envelope| request.message.as_ref(),
+      |violations| Ok(Reply::Rejected(violations.to_string())),
+    ))
+    .service(handler);
+
+  let reply = service
+    .clone()
+    .oneshot(envelope(1, Some("obiwan")))
+    .await
+    .unwrap();
+  assert_eq!(reply, Reply::Handled(1));
+
+  // The requests without a message are not validated
+  let reply = service.oneshot(envelope(2, None)).await.unwrap();
+  assert_eq!(reply, Reply::Handled(2));
+
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn invalid_requests() {
+  let calls = Arc::new(AtomicUsize::new(0));
+  let handler = {
+    let calls = calls.clone();
+    service_fn(move |request: Envelope| {
+      calls.fetch_add(1, Ordering::SeqCst);
+      async move { Ok::<_, String>(Reply::Handled(request.id)) }
+    })
+  };
+
+  let layer = ValidateRequestLayer::new(
+    |request: &Envelope| request.message.as_ref(),
+    |violations| {
+      Ok(Reply::Rejected(
+        violations.violations[0].rule_id().to_string(),
+      ))
+    },
+  );
+
+  let reply = layer
+    .clone()
+    .layer(handler.clone())
+    .oneshot(envelope(1, Some("")))
+    .await
+    .unwrap();
+  assert_eq!(reply, Reply::Rejected("string.min_len".to_string()));
+
+  // The violations can also be mapped to the error of the service
+  let error = ValidateRequestLayer::new(
+    |request: &Envelope| request.message.as_ref(),
+    |violations| Err(violations.to_string()),
+  )
+  .layer(handler)
+  .oneshot(envelope(2, Some("")))
+  .await
+  .unwrap_err();
+  assert_eq!(
+    error,
+    "name: must contain at least 1 character (string.min_len)"
+  );
+
+  // The inner service is never called
+  assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
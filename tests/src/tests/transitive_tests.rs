@@ -0,0 +1,41 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::{myapp::v1::TransitiveTest, shared::v1::SharedInner};
+
+#[test]
+fn transitive_test() {
+  let valid = TransitiveTest {
+    inner: Some(SharedInner {
+      name: "abc".to_string(),
+      child: None,
+    }),
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = TransitiveTest {
+    inner: Some(SharedInner {
+      name: "abc".to_string(),
+      child: Some(Box::new(SharedInner {
+        name: "".to_string(),
+        child: None,
+      })),
+    }),
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "string.min_len");
+
+  let field_path: Vec<&str> = violations[0]
+    .field
+    .as_ref()
+    .unwrap()
+    .elements
+    .iter()
+    .map(|element| element.field_name())
+    .collect();
+
+  assert_eq!(field_path, ["inner", "child", "name"]);
+}
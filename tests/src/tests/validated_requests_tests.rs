@@ -0,0 +1,27 @@
+use protocheck::{types::protovalidate::Violations, ValidatedRequest};
+
+use crate::myapp::v1::{GreetRequest, ListGreetingsRequest};
+
+// Like a middleware that only accepts the requests which have validators
+fn validate_request<T: ValidatedRequest>(request: &T) -> Result<(), Violations> {
+  request.validate()
+}
+
+#[test]
+fn validated_requests() {
+  let Violations { violations } = validate_request(&GreetRequest {
+    name: String::new(),
+  })
+  .unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "string.min_len");
+
+  assert!(validate_request(&GreetRequest {
+    name: "abc".to_string(),
+  })
+  .is_ok());
+
+  // The requests without rules are validated too
+  assert!(validate_request(&ListGreetingsRequest { page_size: 10 }).is_ok());
+}
@@ -0,0 +1,92 @@
+use protocheck::{
+  types::protovalidate::Violations, validators::recursion::DEFAULT_MAX_DEPTH, ProtoValidator,
+  ValidationOptions,
+};
+
+use crate::myapp::v1::{FailFastTests, Node};
+
+fn node(name: &str) -> Node {
+  Node {
+    name: name.to_string(),
+    ..Default::default()
+  }
+}
+
+fn paths(result: Result<(), Violations>) -> Vec<String> {
+  match result {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => violations
+      .iter()
+      .map(|v| v.field_path_str().unwrap())
+      .collect(),
+  }
+}
+
+#[test]
+fn trait_objects() {
+  let messages: Vec<Box<dyn ProtoValidator>> = vec![
+    Box::new(node("a")),
+    Box::new(node("")),
+    Box::new(FailFastTests::default()),
+  ];
+
+  let results: Vec<bool> = messages.iter().map(|msg| msg.validate().is_ok()).collect();
+
+  assert_eq!(results, [true, false, true]);
+
+  let invalid: &dyn ProtoValidator = &node("");
+
+  assert_eq!(paths(invalid.validate()), ["name"]);
+}
+
+#[test]
+fn containers() {
+  assert!(None::<Node>.validate().is_ok());
+  assert_eq!(paths(Some(node("")).validate()), ["name"]);
+  assert_eq!(paths(Box::new(node("")).validate()), ["name"]);
+
+  let nodes = vec![node("a"), node(""), node("b"), node("")];
+
+  assert_eq!(paths(nodes.validate()), ["[1].name", "[3].name"]);
+  assert_eq!(paths(nodes.validate_fail_fast()), ["[1].name"]);
+}
+
+#[test]
+fn validation_options() {
+  let msg = FailFastTests {
+    tags: vec!["".to_string(); 3],
+    ..Default::default()
+  };
+
+  assert_eq!(ValidationOptions::default().max_depth, DEFAULT_MAX_DEPTH);
+  assert_eq!(
+    paths(msg.validate_with(&ValidationOptions::default())).len(),
+    3
+  );
+  assert_eq!(
+    paths(msg.validate_with(&ValidationOptions {
+      fail_fast: true,
+      ..Default::default()
+    })),
+    ["tags[0]"]
+  );
+
+  let nested = Node {
+    children: vec![Node {
+      children: vec![node("leaf")],
+      ..node("child")
+    }],
+    ..node("root")
+  };
+
+  assert!(nested.validate().is_ok());
+
+  let Violations { violations } = nested
+    .validate_with(&ValidationOptions {
+      max_depth: 1,
+      ..Default::default()
+    })
+    .unwrap_err();
+
+  assert_eq!(violations[0].rule_id(), "message.recursion_limit");
+}
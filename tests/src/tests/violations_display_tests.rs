@@ -0,0 +1,48 @@
+use std::{collections::HashMap, error::Error};
+
+use crate::myapp::v1::{
+  display_tests::{Customer, Item},
+  DisplayTests,
+};
+
+fn item(sku: &str) -> Item {
+  Item {
+    sku: sku.to_string(),
+  }
+}
+
+#[test]
+fn display() {
+  let msg = DisplayTests {
+    customer: Some(Customer {
+      email: "obiwan".to_string(),
+    }),
+    items: vec![item("abc"), item("def"), item("x")],
+    quantities: HashMap::from([("apple".to_string(), 0)]),
+    total: -5,
+    discount: 20,
+  };
+
+  let violations = msg.validate().unwrap_err();
+
+  assert_eq!(
+    violations.to_string(),
+    [
+      "the discount cannot be higher than the total (discount.max)",
+      "customer.email: must be a valid email address (string.email)",
+      "items[2].sku: must contain at least 3 characters (string.min_len)",
+      "quantities[\"apple\"]: must be greater than 0 (int32.gt)",
+      "total: must be greater than or equal to 0 (int32.gte)",
+    ]
+    .join("\n")
+  );
+
+  assert_eq!(
+    violations.violations[2].to_string(),
+    "items[2].sku: must contain at least 3 characters (string.min_len)"
+  );
+
+  // The violations can be used like any other error
+  let boxed: Box<dyn Error> = Box::new(violations.clone());
+  assert_eq!(boxed.to_string(), violations.to_string());
+}
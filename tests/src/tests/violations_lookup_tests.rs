@@ -0,0 +1,195 @@
+use protocheck::types::protovalidate::{
+  field_path_element::Subscript, FieldPathElement, Violation, Violations,
+};
+
+fn element(name: &str, subscript: Option<Subscript>) -> FieldPathElement {
+  FieldPathElement {
+    field_name: Some(name.to_string()),
+    subscript,
+    ..Default::default()
+  }
+}
+
+fn violations() -> Violations {
+  let sku = |index| {
+    Violation::new(
+      vec![
+        element("items", Some(Subscript::Index(index))),
+        element("sku", None),
+      ],
+      "sku.format",
+      "invalid sku",
+    )
+  };
+
+  Violations {
+    violations: vec![
+      Violation::new(vec![], "order.total", "invalid total"),
+      Violation::new(
+        vec![element("address", None), element("zip", None)],
+        "zip.format",
+        "invalid zip",
+      ),
+      Violation::new(
+        vec![element("address", None), element("zip_code", None)],
+        "zip_code.format",
+        "invalid zip code",
+      ),
+      sku(2),
+      sku(12),
+      Violation::new(vec![element("items", None)], "items.max", "too many items"),
+      Violation {
+        for_key: Some(true),
+        ..Violation::new(
+          vec![element(
+            "labels",
+            Some(Subscript::StringKey("app.kubernetes.io/name".to_string())),
+          )],
+          "labels.key",
+          "invalid label key",
+        )
+      },
+      Violation::new(
+        vec![element(
+          "labels",
+          Some(Subscript::StringKey("app.kubernetes.io/name".to_string())),
+        )],
+        "labels.value",
+        "invalid label value",
+      ),
+      Violation::new(
+        vec![element(
+          "labels",
+          Some(Subscript::StringKey("say \"hi\"\n".to_string())),
+        )],
+        "labels.quoted",
+        "invalid label",
+      ),
+      Violation::new(
+        vec![element("scores", Some(Subscript::IntKey(-3)))],
+        "scores.value",
+        "invalid score",
+      ),
+      Violation::new(
+        vec![element("flags", Some(Subscript::BoolKey(false)))],
+        "flags.value",
+        "invalid flag",
+      ),
+      Violation::new(
+        vec![
+          FieldPathElement {
+            subscript: Some(Subscript::Index(1)),
+            ..Default::default()
+          },
+          element("name", None),
+        ],
+        "name.required",
+        "the name is required",
+      ),
+    ],
+  }
+}
+
+fn ids<'a>(violations: impl Iterator<Item = &'a Violation>) -> Vec<&'a str> {
+  violations.map(Violation::rule_id).collect()
+}
+
+#[test]
+fn for_field() {
+  let violations = violations();
+
+  assert_eq!(ids(violations.for_field("address.zip")), ["zip.format"]);
+  assert_eq!(ids(violations.for_field("items[2].sku")), ["sku.format"]);
+  assert_eq!(
+    violations
+      .for_field("items[12].sku")
+      .map(|v| v.field_path_str().unwrap())
+      .collect::<Vec<_>>(),
+    ["items[12].sku"]
+  );
+  assert_eq!(ids(violations.for_field("items")), ["items.max"]);
+  assert_eq!(
+    ids(violations.for_field("items[3].sku")),
+    Vec::<&str>::new()
+  );
+
+  // The keys with dots, with the violations of both the key and the value
+  assert_eq!(
+    ids(violations.for_field(r#"labels["app.kubernetes.io/name"]"#)),
+    ["labels.key", "labels.value"]
+  );
+  assert_eq!(
+    violations
+      .for_field(r#"labels["app.kubernetes.io/name"]"#)
+      .filter(|v| v.for_key())
+      .count(),
+    1
+  );
+
+  // The same escapes as in the Display output
+  let quoted = violations
+    .iter()
+    .find(|v| v.rule_id() == "labels.quoted")
+    .unwrap()
+    .field_path_str()
+    .unwrap();
+  assert_eq!(quoted, r#"labels["say \"hi\"\n"]"#);
+  assert_eq!(ids(violations.for_field(&quoted)), ["labels.quoted"]);
+
+  assert_eq!(ids(violations.for_field("scores[-3]")), ["scores.value"]);
+  assert_eq!(ids(violations.for_field("flags[false]")), ["flags.value"]);
+  assert_eq!(ids(violations.for_field("[1].name")), ["name.required"]);
+}
+
+#[test]
+fn for_field_prefix() {
+  let violations = violations();
+
+  // Only the whole elements are compared
+  assert_eq!(
+    ids(violations.for_field_prefix("address.zip")),
+    ["zip.format"]
+  );
+  assert_eq!(
+    ids(violations.for_field_prefix("address")),
+    ["zip.format", "zip_code.format"]
+  );
+  assert_eq!(
+    ids(violations.for_field_prefix("items")),
+    ["sku.format", "sku.format", "items.max"]
+  );
+  assert_eq!(ids(violations.for_field_prefix("items[2]")), ["sku.format"]);
+  assert_eq!(
+    ids(violations.for_field_prefix("labels")),
+    ["labels.key", "labels.value", "labels.quoted"]
+  );
+}
+
+#[test]
+fn invalid_paths() {
+  let violations = violations();
+
+  for path in [
+    "",
+    "address.",
+    ".zip",
+    "items[2",
+    "items[two]",
+    r#"labels["app]"#,
+    "items[2]sku",
+  ] {
+    assert_eq!(violations.for_field(path).count(), 0, "{path}");
+    assert_eq!(violations.for_field_prefix(path).count(), 0, "{path}");
+  }
+}
+
+#[test]
+fn rule_ids() {
+  let violations = violations();
+
+  assert_eq!(violations.rule_ids().count(), violations.len());
+  assert_eq!(
+    violations.rule_ids().take(3).collect::<Vec<_>>(),
+    ["order.total", "zip.format", "zip_code.format"]
+  );
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use protocheck::types::{
+  field_descriptor_proto::Type,
+  protovalidate::{
+    field_path_element::Subscript, FieldPath, FieldPathElement, Violation, Violations,
+  },
+};
+use serde_json::json;
+
+use crate::myapp::v1::{
+  display_tests::{Customer, Item},
+  DisplayTests,
+};
+
+// The JSON encoding of the violations below, matching the output of protojson in protovalidate-go
+const FIXTURE: &str = include_str!("../../fixtures/violations.json");
+
+fn element(number: i32, name: &str, field_type: Type) -> FieldPathElement {
+  FieldPathElement {
+    field_number: Some(number),
+    field_name: Some(name.to_string()),
+    field_type: Some(field_type as i32),
+    ..Default::default()
+  }
+}
+
+fn map_element(
+  number: i32,
+  name: &str,
+  key_type: Type,
+  value_type: Type,
+  key: Subscript,
+) -> FieldPathElement {
+  FieldPathElement {
+    key_type: Some(key_type as i32),
+    value_type: Some(value_type as i32),
+    subscript: Some(key),
+    ..element(number, name, Type::Message)
+  }
+}
+
+fn path(elements: Vec<FieldPathElement>) -> Option<FieldPath> {
+  Some(FieldPath { elements })
+}
+
+fn violation(
+  field: Option<FieldPath>,
+  rule: Option<FieldPath>,
+  rule_id: &str,
+  message: &str,
+) -> Violation {
+  Violation {
+    field,
+    rule,
+    rule_id: Some(rule_id.to_string()),
+    message: Some(message.to_string()),
+    for_key: None,
+  }
+}
+
+fn fixture_violations() -> Violations {
+  let min_len = element(2, "min_len", Type::Uint64);
+
+  Violations {
+    violations: vec![
+      violation(
+        None,
+        path(vec![FieldPathElement {
+          subscript: Some(Subscript::Index(0)),
+          ..element(3, "cel", Type::Message)
+        }]),
+        "discount.max",
+        "the discount cannot be higher than the total",
+      ),
+      violation(
+        path(vec![
+          FieldPathElement {
+            subscript: Some(Subscript::Index(2)),
+            ..element(2, "items", Type::Message)
+          },
+          element(1, "sku", Type::String),
+        ]),
+        path(vec![element(14, "string", Type::Message), min_len.clone()]),
+        "string.min_len",
+        "must contain at least 3 characters",
+      ),
+      violation(
+        path(vec![map_element(
+          3,
+          "quantities",
+          Type::String,
+          Type::Int32,
+          Subscript::StringKey("apple".to_string()),
+        )]),
+        path(vec![
+          element(19, "map", Type::Message),
+          element(5, "values", Type::Message),
+          element(3, "int32", Type::Message),
+          element(4, "gt", Type::Int32),
+        ]),
+        "int32.gt",
+        "must be greater than 0",
+      ),
+      Violation {
+        for_key: Some(true),
+        ..violation(
+          // Larger than the biggest integer that can be represented exactly by a double
+          path(vec![map_element(
+            4,
+            "scores",
+            Type::Sint64,
+            Type::Uint64,
+            Subscript::IntKey(-9_007_199_254_740_993),
+          )]),
+          path(vec![
+            element(19, "map", Type::Message),
+            element(4, "keys", Type::Message),
+            element(12, "sint64", Type::Message),
+            element(5, "gte", Type::Sint64),
+          ]),
+          "sint64.gte",
+          "must be greater than or equal to 0",
+        )
+      },
+      violation(
+        path(vec![map_element(
+          5,
+          "flags",
+          Type::Bool,
+          Type::String,
+          Subscript::BoolKey(true),
+        )]),
+        path(vec![
+          element(19, "map", Type::Message),
+          element(5, "values", Type::Message),
+          element(14, "string", Type::Message),
+          min_len,
+        ]),
+        "string.min_len",
+        "must be at least 1 characters",
+      ),
+    ],
+  }
+}
+
+#[test]
+fn golden_json() {
+  let expected: serde_json::Value = serde_json::from_str(FIXTURE).unwrap();
+
+  assert_eq!(
+    serde_json::to_value(fixture_violations()).unwrap(),
+    expected
+  );
+  assert_eq!(
+    serde_json::from_str::<Violations>(FIXTURE).unwrap(),
+    fixture_violations()
+  );
+}
+
+#[test]
+fn round_trip() {
+  let msg = DisplayTests {
+    customer: Some(Customer {
+      email: "obiwan".to_string(),
+    }),
+    items: vec![
+      Item {
+        sku: "abc".to_string(),
+      },
+      Item {
+        sku: "x".to_string(),
+      },
+    ],
+    quantities: HashMap::from([("apple".to_string(), 0)]),
+    total: -5,
+    discount: 20,
+  };
+
+  let violations = msg.validate().unwrap_err();
+  let json = serde_json::to_string(&violations).unwrap();
+
+  assert_eq!(
+    serde_json::from_str::<Violations>(&json).unwrap(),
+    violations
+  );
+
+  for violation in &violations.violations {
+    let json = serde_json::to_string(violation).unwrap();
+    assert_eq!(
+      &serde_json::from_str::<Violation>(&json).unwrap(),
+      violation
+    );
+  }
+}
+
+#[test]
+fn lenient_parsing() {
+  // Like protojson, the numbers are also accepted for the enums and the 64 bit integers
+  let element: FieldPathElement = serde_json::from_value(json!({
+    "fieldNumber": 2,
+    "fieldName": "items",
+    "fieldType": 11,
+    "index": 3
+  }))
+  .unwrap();
+
+  assert_eq!(
+    element,
+    FieldPathElement {
+      subscript: Some(Subscript::Index(3)),
+      ..self::element(2, "items", Type::Message)
+    }
+  );
+
+  // The missing fields are left empty
+  assert_eq!(
+    serde_json::from_value::<Violation>(json!({ "ruleId": "required" })).unwrap(),
+    Violation {
+      rule_id: Some("required".to_string()),
+      ..Default::default()
+    }
+  );
+  assert_eq!(
+    serde_json::to_value(Violations::default()).unwrap(),
+    json!({})
+  );
+
+  assert!(
+    serde_json::from_value::<FieldPathElement>(json!({ "fieldType": "TYPE_UNKNOWN" })).is_err()
+  );
+  assert!(
+    serde_json::from_value::<FieldPathElement>(json!({ "index": "1", "stringKey": "a" })).is_err()
+  );
+}
@@ -34,6 +34,72 @@ fn well_known_strings() {
 
   assert_eq!(violations.len(), 21);
 
+  let email_violation = violations
+    .iter()
+    .find(|v| v.field_name() == Some("email"))
+    .unwrap();
+  assert_eq!(email_violation.rule_id(), "string.email");
+
+  let hostname_violation = violations
+    .iter()
+    .find(|v| v.field_name() == Some("hostname"))
+    .unwrap();
+  assert_eq!(hostname_violation.rule_id(), "string.hostname");
+
+  for (field, rule_id, message) in [
+    ("ip", "string.ip", "must be a valid IP address"),
+    ("ipv4", "string.ipv4", "must be a valid IPv4 address"),
+    ("ipv6", "string.ipv6", "must be a valid IPv6 address"),
+    ("ip_prefix", "string.ip_prefix", "must be a valid IP prefix"),
+    (
+      "ipv4_prefix",
+      "string.ipv4_prefix",
+      "must be a valid IPv4 prefix",
+    ),
+    (
+      "ipv6_prefix",
+      "string.ipv6_prefix",
+      "must be a valid IPv6 prefix",
+    ),
+    (
+      "ip_with_prefixlen",
+      "string.ip_with_prefixlen",
+      "must be a valid IP address with prefix length",
+    ),
+    (
+      "ipv4_with_prefixlen",
+      "string.ipv4_with_prefixlen",
+      "must be a valid IPv4 address with prefix length",
+    ),
+    (
+      "ipv6_with_prefixlen",
+      "string.ipv6_with_prefixlen",
+      "must be a valid IPv6 address with prefix length",
+    ),
+    ("uri", "string.uri", "must be a valid uri"),
+    ("uri_ref", "string.uri_ref", "must be a valid URI reference"),
+    ("uuid", "string.uuid", "must be a valid UUID"),
+    ("tuuid", "string.tuuid", "must be a valid trimmed UUID"),
+    (
+      "address",
+      "string.address",
+      "must be a valid hostname, or ip address",
+    ),
+    (
+      "host_and_port",
+      "string.host_and_port",
+      "must be a valid host (hostname or IP address) and port pair",
+    ),
+  ] {
+    let violation = violations
+      .iter()
+      .find(|v| v.field_name() == Some(field))
+      .unwrap();
+
+    assert_eq!(violation.rule_id(), rule_id);
+    assert_eq!(violation.message(), message);
+  }
+
   let ipv4 = "192.168.1.1";
   let ipv6 = "2a01:c23:7b6d:a900:1de7:5cbe:d8d2:f4a1";
   let uri = "https://middleeathtracker.com/hobbits?location=isengard";
@@ -64,3 +130,54 @@ fn well_known_strings() {
 
   assert!(test.validate().is_ok())
 }
+
+#[test]
+fn http_headers() {
+  let rule_id = |test: &WellKnownStrings, field: &str| {
+    let Violations { violations } = test.validate().unwrap_err();
+
+    violations
+      .iter()
+      .find(|v| v.field_name() == Some(field))
+      .map(|v| v.rule_id().to_string())
+  };
+
+  let test = WellKnownStrings {
+    header_name_strict: "x:header".to_string(),
+    header_value_strict: "with\ttab".to_string(),
+    header_name_loose: "x:header".to_string(),
+    header_value_loose: " leading space".to_string(),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    rule_id(&test, "header_name_strict").as_deref(),
+    Some("string.well_known_regex.header_name")
+  );
+  assert_eq!(rule_id(&test, "header_value_strict"), None);
+  assert_eq!(rule_id(&test, "header_name_loose"), None);
+  assert_eq!(rule_id(&test, "header_value_loose"), None);
+
+  let test = WellKnownStrings {
+    header_value_strict: "value\u{0007}with\u{0007}bell".to_string(),
+    header_value_loose: "value\r\n folded".to_string(),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    rule_id(&test, "header_name_strict").as_deref(),
+    Some("string.well_known_regex.header_name_empty")
+  );
+  assert_eq!(
+    rule_id(&test, "header_name_loose").as_deref(),
+    Some("string.well_known_regex.header_name_empty")
+  );
+  assert_eq!(
+    rule_id(&test, "header_value_strict").as_deref(),
+    Some("string.well_known_regex.header_value")
+  );
+  assert_eq!(
+    rule_id(&test, "header_value_loose").as_deref(),
+    Some("string.well_known_regex.header_value")
+  );
+}
@@ -0,0 +1,164 @@
+use bytes::Bytes;
+use protocheck::types::{
+  protovalidate::Violations, BytesValue, DoubleValue, FloatValue, Int32Value, Int64Value,
+  StringValue, UInt32Value, UInt64Value, WrapperExt,
+};
+
+use crate::{myapp::v1::WrapperTests, prosttypes::v1::WrappersTest};
+
+fn violations(result: Result<(), Violations>) -> Vec<(String, String)> {
+  match result {
+    Ok(()) => Vec::new(),
+    Err(Violations { violations }) => {
+      let mut violations: Vec<(String, String)> = violations
+        .iter()
+        .map(|v| (v.field_path_str().unwrap(), v.rule_id().to_string()))
+        .collect();
+      violations.sort();
+      violations
+    }
+  }
+}
+
+fn violation(path: &str, rule_id: &str) -> (String, String) {
+  (path.to_string(), rule_id.to_string())
+}
+
+#[test]
+fn wrappers() {
+  let valid = WrapperTests {
+    name: Some(StringValue {
+      value: "bob".to_string(),
+    }),
+    balance: Some(Int64Value { value: 0 }),
+    quantity: Some(UInt32Value { value: 2 }),
+    checksum: Some(BytesValue {
+      value: Bytes::from_static(b"\x01\x02\x03\x04"),
+    }),
+    ratio: Some(DoubleValue { value: 0.5 }),
+    weight: Some(FloatValue { value: 2.5 }),
+    offset: Some(Int32Value { value: -1 }),
+    serial: Some(UInt64Value { value: 7 }),
+    aliases: vec![StringValue {
+      value: "bb".to_string(),
+    }],
+  };
+
+  assert!(violations(valid.validate()).is_empty());
+
+  // The rules are skipped for the unset wrappers, unless they are required
+  let unset = WrapperTests::default();
+
+  assert_eq!(
+    violations(unset.validate()),
+    [violation("quantity", "required")]
+  );
+
+  // The rules are applied to the wrapped values, and the violations point at the wrapper fields
+  let invalid = WrapperTests {
+    name: Some(StringValue::default()),
+    balance: Some(Int64Value { value: -1 }),
+    quantity: Some(UInt32Value { value: 0 }),
+    checksum: Some(BytesValue {
+      value: Bytes::from_static(b"\x01"),
+    }),
+    ratio: Some(DoubleValue { value: 1.5 }),
+    weight: Some(FloatValue { value: f32::NAN }),
+    offset: Some(Int32Value { value: 0 }),
+    serial: Some(UInt64Value { value: 0 }),
+    aliases: vec![
+      StringValue {
+        value: "bb".to_string(),
+      },
+      StringValue {
+        value: "bobby".to_string(),
+      },
+    ],
+  };
+
+  assert_eq!(
+    violations(invalid.validate()),
+    [
+      violation("aliases[1]", "string.max_len"),
+      violation("balance", "int64.gte"),
+      violation("checksum", "bytes.len"),
+      violation("name", "string.min_len"),
+      violation("offset", "int32.in"),
+      violation("quantity", "uint32.gt"),
+      violation("ratio", "double.lte"),
+      violation("serial", "uint64.not_in"),
+      violation("weight", "float.finite"),
+    ]
+  );
+}
+
+#[test]
+fn prost_types_wrappers() {
+  // prost turns the wrappers into the wrapped type when the well known types are not compiled
+  let valid = WrappersTest {
+    name: Some("bob".to_string()),
+    balance: Some(3),
+    checksum: Some(vec![1, 2, 3, 4]),
+  };
+
+  assert!(violations(valid.validate()).is_empty());
+  assert!(violations(WrappersTest::default().validate()).is_empty());
+
+  let invalid = WrappersTest {
+    name: Some(String::new()),
+    balance: Some(-3),
+    checksum: Some(vec![1]),
+  };
+
+  assert_eq!(
+    violations(invalid.validate()),
+    [
+      violation("balance", "int64.gte"),
+      violation("checksum", "bytes.len"),
+      violation("name", "string.min_len"),
+    ]
+  );
+}
+
+#[test]
+fn conversions() {
+  let message = WrapperTests {
+    name: Some("bob".into()),
+    balance: Some(Int64Value::new(3)),
+    quantity: Some(2.into()),
+    checksum: Some(vec![1, 2, 3, 4].into()),
+    ratio: Some(0.5.into()),
+    weight: Some(FloatValue::new(2.5)),
+    offset: Some((-1).into()),
+    serial: Some(UInt64Value::from(7)),
+    aliases: vec!["bb".into()],
+  };
+
+  // The wrappers can be compared with the wrapped values
+  assert_eq!(message.name.clone().unwrap(), "bob");
+  assert_eq!("bob", message.name.clone().unwrap());
+  assert_eq!(message.balance.unwrap(), 3);
+  assert_eq!(message.checksum.clone().unwrap(), [1u8, 2, 3, 4].as_slice());
+  assert_eq!(message.ratio.unwrap(), 0.5);
+  assert_eq!(message.aliases, ["bb"]);
+
+  // And they can be unwrapped
+  assert_eq!(message.name.as_deref(), Some("bob"));
+  assert_eq!(message.checksum.as_deref(), Some([1, 2, 3, 4].as_slice()));
+  assert_eq!(message.weight.as_inner(), Some(&2.5));
+  assert_eq!(message.serial.into_inner(), Some(7));
+  assert_eq!(i32::from(message.offset.unwrap()), -1);
+  assert_eq!(
+    Vec::<u8>::from(message.checksum.clone().unwrap()),
+    [1, 2, 3, 4]
+  );
+  assert_eq!(String::from(StringValue::new("bob")), "bob");
+  assert_eq!(message.quantity.unwrap_or_default_inner(), 2);
+  assert!(message.validate().is_ok());
+
+  let unset = WrapperTests::default();
+  assert_eq!(unset.name.unwrap_or_default_inner(), "");
+  assert_eq!(unset.checksum.unwrap_or_default_inner(), Bytes::new());
+  assert_eq!(unset.ratio.unwrap_or_default_inner(), 0.0);
+  assert_eq!(unset.balance.into_inner(), None);
+}